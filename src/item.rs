@@ -2,23 +2,29 @@
 
 A [Sequence] is the fundamental data type in XPath. It is a series of zero or more [Item]s.
 
-An [Item] is a [Node], Function or atomic [Value].
+An [Item] is a [Node], Function, [Map], [Array] or atomic [Value].
 
 [Node]s are defined as a trait.
 */
 
+use chrono::FixedOffset;
 use qualname::{NamespacePrefix, NamespaceUri, QName};
 
 use crate::item;
 use crate::output::OutputDefinition;
+use crate::transform::Transform;
+use crate::transform::types::SequenceType;
 use crate::validators::{Schema, ValidationError};
-use crate::value::{Operator, Value};
+use crate::value::{Operator, Value, ValueData};
 use crate::xdmerror::{Error, ErrorKind};
 use crate::xmldecl::{DTD, XMLDecl};
+use rust_decimal::Decimal;
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Formatter;
 use std::rc::Rc;
+use std::str::FromStr;
 
 /// In XPath, the Sequence is the fundamental data structure.
 /// It is an ordered collection of [Item]s.
@@ -180,16 +186,20 @@ impl fmt::Display for NodeType {
     }
 }
 
-/// An Item in a [Sequence]. Can be a node, function or [Value].
-///
-/// Functions are not yet implemented.
+/// An Item in a [Sequence]. Can be a node, function, map, array or [Value].
 #[derive(Clone)]
 pub enum Item<N: Node> {
     /// A [Node] in the source document.
     Node(N),
 
-    /// Functions are not yet supported
-    Function,
+    /// A function item. See [Function].
+    Function(Rc<Function<N>>),
+
+    /// A map from atomic keys to sequences.
+    Map(Rc<Map<N>>),
+
+    /// An array of members, where each member is a sequence.
+    Array(Rc<Array<N>>),
 
     /// A scalar value. These are in an Rc since they are frequently shared.
     Value(Rc<Value>),
@@ -200,7 +210,8 @@ impl<N: item::Node> fmt::Display for Item<N> {
         // Gives the string value of an item. All items have a string value.
         let result = match self {
             Item::Node(n) => n.to_string(),
-            Item::Function => "".to_string(),
+            Item::Function(_) | Item::Map(_) => "".to_string(),
+            Item::Array(a) => a.iter().map(|m| m.to_string()).collect(),
             Item::Value(v) => v.to_string(),
         };
         f.write_str(result.as_str())
@@ -212,7 +223,8 @@ impl<N: Node> Item<N> {
     pub fn to_xml(&self) -> String {
         match self {
             Item::Node(n) => n.to_xml(),
-            Item::Function => "".to_string(),
+            Item::Function(_) | Item::Map(_) => "".to_string(),
+            Item::Array(a) => a.iter().map(|m| m.to_xml()).collect(),
             Item::Value(v) => v.to_string(),
         }
     }
@@ -220,7 +232,8 @@ impl<N: Node> Item<N> {
     pub fn to_xml_with_options(&self, od: &OutputDefinition) -> String {
         match self {
             Item::Node(n) => n.to_xml_with_options(od),
-            Item::Function => "".to_string(),
+            Item::Function(_) | Item::Map(_) => "".to_string(),
+            Item::Array(a) => a.iter().map(|m| m.to_xml_with_options(od)).collect(),
             Item::Value(v) => v.to_string(),
         }
     }
@@ -228,7 +241,18 @@ impl<N: Node> Item<N> {
     pub fn to_json(&self) -> String {
         match self {
             Item::Node(n) => n.to_json(),
-            Item::Function => "".to_string(),
+            Item::Function(_) => "".to_string(),
+            Item::Map(m) => format!(
+                "{{{}}}",
+                m.iter()
+                    .map(|(k, v)| format!("{}:{}", json_string(&k.to_string()), json_member(v)))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+            Item::Array(a) => format!(
+                "[{}]",
+                a.iter().map(json_member).collect::<Vec<String>>().join(",")
+            ),
             Item::Value(v) => v.to_string(),
         }
     }
@@ -237,8 +261,8 @@ impl<N: Node> Item<N> {
     /// See XPath 2.4.3.
     pub fn to_bool(&self) -> bool {
         match self {
-            Item::Node(..) => true,
-            Item::Function => false,
+            Item::Node(..) | Item::Map(_) | Item::Array(_) => true,
+            Item::Function(_) => false,
             Item::Value(v) => v.to_bool(),
         }
     }
@@ -250,10 +274,18 @@ impl<N: Node> Item<N> {
                 ErrorKind::TypeError,
                 String::from("type error: item is a node"),
             )),
-            Item::Function => Result::Err(Error::new(
+            Item::Function(_) => Result::Err(Error::new(
                 ErrorKind::TypeError,
                 String::from("type error: item is a function"),
             )),
+            Item::Map(_) => Result::Err(Error::new(
+                ErrorKind::TypeError,
+                String::from("type error: item is a map"),
+            )),
+            Item::Array(_) => Result::Err(Error::new(
+                ErrorKind::TypeError,
+                String::from("type error: item is an array"),
+            )),
             Item::Value(v) => match v.to_int() {
                 Ok(i) => Ok(i),
                 Err(e) => Result::Err(e),
@@ -265,7 +297,7 @@ impl<N: Node> Item<N> {
     pub fn to_double(&self) -> f64 {
         match self {
             Item::Node(..) => f64::NAN,
-            Item::Function => f64::NAN,
+            Item::Function(_) | Item::Map(_) | Item::Array(_) => f64::NAN,
             Item::Value(v) => v.to_double(),
         }
     }
@@ -295,6 +327,26 @@ impl<N: Node> Item<N> {
             _ => Result::Err(Error::new(ErrorKind::TypeError, String::from("type error"))),
         }
     }
+    /// Compare two items. Dates and times without a timezone are taken to be in the given implicit timezone.
+    pub fn compare_with_timezone(
+        &self,
+        other: &Item<N>,
+        op: Operator,
+        implicit: FixedOffset,
+    ) -> Result<bool, Error> {
+        match (self, other) {
+            (Item::Value(v), Item::Value(w)) => v.compare_with_timezone(w, op, implicit),
+            (Item::Value(v), Item::Node(..)) => {
+                v.compare_with_timezone(&Value::from(other.to_string()), op, implicit)
+            }
+            (Item::Node(..), _) => other.compare_with_timezone(
+                &Item::Value(Rc::new(Value::from(self.to_string()))),
+                op,
+                implicit,
+            ),
+            _ => Result::Err(Error::new(ErrorKind::TypeError, String::from("type error"))),
+        }
+    }
 
     /// Is this item a node?
     pub fn is_node(&self) -> bool {
@@ -325,7 +377,9 @@ impl<N: Node> Item<N> {
     pub fn item_type(&self) -> &'static str {
         match self {
             Item::Node(..) => "Node",
-            Item::Function => "Function",
+            Item::Function(_) => "Function",
+            Item::Map(_) => "Map",
+            Item::Array(_) => "Array",
             Item::Value(v) => v.value_type(),
         }
     }
@@ -334,22 +388,20 @@ impl<N: Node> Item<N> {
     pub fn shallow_copy(&self) -> Result<Self, Error> {
         match self {
             Item::Value(v) => Ok(Item::Value(v.clone())),
+            Item::Map(m) => Ok(Item::Map(m.clone())),
+            Item::Array(a) => Ok(Item::Array(a.clone())),
+            Item::Function(f) => Ok(Item::Function(f.clone())),
             Item::Node(n) => Ok(Item::Node(n.shallow_copy()?)),
-            _ => Result::Err(Error::new(
-                ErrorKind::NotImplemented,
-                "not implemented".to_string(),
-            )),
         }
     }
     /// Make a deep copy of an item.
     pub fn deep_copy(&self) -> Result<Self, Error> {
         match self {
             Item::Value(v) => Ok(Item::Value(v.clone())),
+            Item::Map(m) => Ok(Item::Map(m.clone())),
+            Item::Array(a) => Ok(Item::Array(a.clone())),
+            Item::Function(f) => Ok(Item::Function(f.clone())),
             Item::Node(n) => Ok(Item::Node(n.deep_copy()?)),
-            _ => Result::Err(Error::new(
-                ErrorKind::NotImplemented,
-                "not implemented".to_string(),
-            )),
         }
     }
 }
@@ -366,8 +418,15 @@ impl<N: Node> fmt::Debug for Item<N> {
                       //                    n.name()
                 )
             }
-            Item::Function => {
-                write!(f, "function type item")
+            Item::Function(func) => match &func.name {
+                Some(qn) => write!(f, "function type item ({}#{})", qn, func.arity()),
+                None => write!(f, "function type item (anonymous#{})", func.arity()),
+            },
+            Item::Map(m) => {
+                write!(f, "map type item ({} entries)", m.len())
+            }
+            Item::Array(a) => {
+                write!(f, "array type item ({} members)", a.len())
             }
             Item::Value(v) => {
                 write!(f, "value type item ({})", v)
@@ -376,6 +435,262 @@ impl<N: Node> fmt::Debug for Item<N> {
     }
 }
 
+/// A function item. Named function references, inline functions and partial function applications all produce function items.
+///
+/// When the function is called, the arguments are bound to the parameters as variables and then the body is evaluated.
+/// An inline function also has the variables that were in scope where it was created, i.e. its closure.
+#[derive(Clone)]
+pub struct Function<N: Node> {
+    pub(crate) name: Option<QName>,
+    pub(crate) parameters: Vec<(String, Option<SequenceType>)>,
+    pub(crate) body: Rc<Transform<N>>,
+    pub(crate) closure: HashMap<String, Vec<Sequence<N>>>,
+}
+
+impl<N: Node> Function<N> {
+    /// Create a function item. Each parameter has a name and, optionally, a type.
+    pub fn new(
+        name: Option<QName>,
+        parameters: Vec<(String, Option<SequenceType>)>,
+        body: Rc<Transform<N>>,
+        closure: HashMap<String, Vec<Sequence<N>>>,
+    ) -> Self {
+        Function {
+            name,
+            parameters,
+            body,
+            closure,
+        }
+    }
+    /// The name of the function. Inline functions are anonymous.
+    pub fn name(&self) -> Option<&QName> {
+        self.name.as_ref()
+    }
+    /// The number of parameters of the function.
+    pub fn arity(&self) -> usize {
+        self.parameters.len()
+    }
+}
+
+/// An XDM array. Each member of the array is a [Sequence].
+pub type Array<N> = Vec<Sequence<N>>;
+
+/// An XDM map. Each entry in the map has an atomic key and a [Sequence] for its value.
+///
+/// Two keys are the same if they are equal according to op:same-key (see [same_key]), so the integer 1 and the double 1.0 are the same key.
+/// Entries are kept in the order in which they were added.
+#[derive(Clone)]
+pub struct Map<N: Node> {
+    entries: Vec<(Rc<Value>, Sequence<N>)>,
+    index: HashMap<String, usize>,
+}
+
+impl<N: Node> Map<N> {
+    /// Create an empty map.
+    pub fn new() -> Self {
+        Map {
+            entries: vec![],
+            index: HashMap::new(),
+        }
+    }
+    /// The number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    /// Does the map have no entries?
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    /// Find the value for a key.
+    pub fn get(&self, k: &Value) -> Option<&Sequence<N>> {
+        self.index.get(&key_form(k)).map(|i| &self.entries[*i].1)
+    }
+    /// Does the map have an entry for the key?
+    pub fn contains_key(&self, k: &Value) -> bool {
+        self.index.contains_key(&key_form(k))
+    }
+    /// Add an entry to the map. If there is already an entry with the same key then it is replaced, and the previous value is returned.
+    pub fn insert(&mut self, k: Rc<Value>, v: Sequence<N>) -> Option<Sequence<N>> {
+        let sk = key_form(&k);
+        if let Some(i) = self.index.get(&sk) {
+            let (_, old) = std::mem::replace(&mut self.entries[*i], (k, v));
+            Some(old)
+        } else {
+            self.index.insert(sk, self.entries.len());
+            self.entries.push((k, v));
+            None
+        }
+    }
+    /// Remove the entry for a key, returning its value.
+    pub fn remove(&mut self, k: &Value) -> Option<Sequence<N>> {
+        let i = self.index.remove(&key_form(k))?;
+        let (_, v) = self.entries.remove(i);
+        for j in self.index.values_mut() {
+            if *j > i {
+                *j -= 1
+            }
+        }
+        Some(v)
+    }
+    /// Iterate over the keys of the map.
+    pub fn keys(&self) -> impl Iterator<Item = &Rc<Value>> {
+        self.entries.iter().map(|(k, _)| k)
+    }
+    /// Iterate over the entries of the map.
+    pub fn iter(&self) -> impl Iterator<Item = (&Rc<Value>, &Sequence<N>)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+}
+
+impl<N: Node> Default for Map<N> {
+    fn default() -> Self {
+        Map::new()
+    }
+}
+
+/// Are two keys the same, according to op:same-key?
+pub fn same_key(a: &Value, b: &Value) -> bool {
+    key_form(a) == key_form(b)
+}
+
+// A normalised form of a key.
+// Keys that are the same according to op:same-key have the same normalised form.
+fn key_form(k: &Value) -> String {
+    match &k.value {
+        ValueData::Double(d) => numeric_key(*d),
+        ValueData::Float(f) => numeric_key(f64::from(*f)),
+        ValueData::Decimal(d) => format!("n{}", d.normalize()),
+        ValueData::Integer(_)
+        | ValueData::NonPositiveInteger(_)
+        | ValueData::NegativeInteger(_)
+        | ValueData::Long(_)
+        | ValueData::Int(_)
+        | ValueData::Short(_)
+        | ValueData::Byte(_)
+        | ValueData::NonNegativeInteger(_)
+        | ValueData::UnsignedLong(_)
+        | ValueData::UnsignedInt(_)
+        | ValueData::UnsignedShort(_)
+        | ValueData::UnsignedByte(_)
+        | ValueData::PositiveInteger(_) => format!("n{}", k),
+        ValueData::String(_)
+        | ValueData::NormalizedString(_)
+        | ValueData::Token
+        | ValueData::Language
+        | ValueData::NMTOKEN(_)
+        | ValueData::Name(_)
+        | ValueData::NCName(_)
+        | ValueData::ID(_)
+        | ValueData::IDREF(_)
+        | ValueData::ENTITY(_)
+        | ValueData::NamespaceUri(_)
+        | ValueData::UntypedAtomic => format!("s{}", k),
+        ValueData::Boolean(b) => format!("b{}", b),
+        // The prefix of a QName is not significant
+        ValueData::QName(q, _) => format!(
+            "QName:Q{{{}}}{}",
+            q.namespace_uri().map_or(String::new(), |u| u.to_string()),
+            q.local_name()
+        ),
+        ValueData::Duration(d)
+        | ValueData::YearMonthDuration(d)
+        | ValueData::DayTimeDuration(d) => {
+            format!("d{}:{}", d.months(), d.seconds().normalize())
+        }
+        // Values with a timezone are keyed by their time in UTC; values without a timezone by their local time.
+        // A value with a timezone is never the same key as a value without one.
+        ValueData::DateTime(..) | ValueData::DateTimeStamp(..) => temporal_key("DateTime", k),
+        ValueData::Date(..)
+        | ValueData::Time(..)
+        | ValueData::GYearMonth(..)
+        | ValueData::GYear(..)
+        | ValueData::GMonthDay(..)
+        | ValueData::GMonth(..)
+        | ValueData::GDay(..) => temporal_key(k.value_type(), k),
+        _ => format!("{}:{}", k.value_type(), k),
+    }
+}
+
+fn temporal_key(t: &str, k: &Value) -> String {
+    let tz = match &k.value {
+        ValueData::DateTime(_, tz)
+        | ValueData::Date(_, tz)
+        | ValueData::Time(_, tz)
+        | ValueData::GYearMonth(_, _, tz)
+        | ValueData::GYear(_, tz)
+        | ValueData::GMonthDay(_, _, tz)
+        | ValueData::GMonth(_, tz)
+        | ValueData::GDay(_, tz) => tz.is_some(),
+        _ => true,
+    };
+    format!(
+        "{}:{}:{:?}",
+        t,
+        if tz { "Z" } else { "L" },
+        FixedOffset::east_opt(0).and_then(|z| k.to_utc(z))
+    )
+}
+
+fn numeric_key(d: f64) -> String {
+    if d.is_nan() {
+        String::from("nNaN")
+    } else if d.is_infinite() {
+        String::from(if d > 0.0 { "nINF" } else { "n-INF" })
+    } else {
+        Decimal::from_str(&d.to_string())
+            .map_or_else(|_| format!("n{}", d), |e| format!("n{}", e.normalize()))
+    }
+}
+
+// Serialise a member of an array, or the value of a map entry, as JSON.
+fn json_member<N: Node>(s: &Sequence<N>) -> String {
+    let items: Vec<String> = s
+        .iter()
+        .map(|i| match i {
+            Item::Value(v) => match v.value {
+                ValueData::Boolean(_)
+                | ValueData::Decimal(_)
+                | ValueData::Integer(_)
+                | ValueData::Long(_)
+                | ValueData::Int(_)
+                | ValueData::Short(_)
+                | ValueData::Byte(_)
+                | ValueData::UnsignedLong(_)
+                | ValueData::UnsignedInt(_)
+                | ValueData::UnsignedShort(_)
+                | ValueData::UnsignedByte(_) => v.to_string(),
+                ValueData::Double(d) if d.is_finite() => v.to_string(),
+                ValueData::Float(f) if f.is_finite() => v.to_string(),
+                _ => json_string(&v.to_string()),
+            },
+            Item::Node(n) => json_string(&n.to_xml()),
+            _ => i.to_json(),
+        })
+        .collect();
+    match items.len() {
+        0 => String::from("null"),
+        1 => items[0].clone(),
+        _ => format!("[{}]", items.join(",")),
+    }
+}
+
+pub(crate) fn json_string(s: &str) -> String {
+    let mut r = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => r.push_str("\\\""),
+            '\\' => r.push_str("\\\\"),
+            '\n' => r.push_str("\\n"),
+            '\r' => r.push_str("\\r"),
+            '\t' => r.push_str("\\t"),
+            c if (c as u32) < 0x20 => r.push_str(format!("\\u{:04x}", c as u32).as_str()),
+            c => r.push(c),
+        }
+    }
+    r.push('"');
+    r
+}
+
 /// Nodes make up a document tree. Nodes must be fully navigable. The tree must be mutable but also stable (i.e. removing a node from the tree does not invalidate the remaining nodes).
 ///
 /// Some nodes have names, such as elements. Some nodes have values, such as text or comments. Some have both a name and a value, such as attributes and processing instructions.
@@ -637,6 +952,17 @@ pub trait Node: Clone + PartialEq + fmt::Debug {
     /// Store an internal representation of the DTD. Does not keep a copy of the original text
     fn set_dtd(&self, dtd: DTD) -> Result<(), Error>;
 
+    /// Get the typed value that schema validation has given to an element or attribute, if any.
+    /// The default implementation has no typed values.
+    fn typed_value(&self) -> Option<Rc<Value>> {
+        None
+    }
+    /// Give an element or attribute a typed value. The content of the node is not changed.
+    /// The default implementation discards the typed value.
+    fn set_typed_value(&self, _v: Rc<Value>) -> Result<(), Error> {
+        Ok(())
+    }
+
     fn validate(&self, schema: Schema) -> Result<(), ValidationError>;
 
     /// Return a list of nodes that are associated with this document, but are not attached.
@@ -1,24 +1,27 @@
 //! General productions for XPath expressions.
 
 use crate::item::Node;
-use crate::parser::combinators::alt::{alt2, alt5};
+use crate::parser::combinators::alt::{alt2, alt3, alt5};
 //use crate::parser::combinators::debug::inspect;
 use crate::parser::combinators::delimited::delimited;
+use crate::parser::combinators::many::many0;
 use crate::parser::combinators::map::map;
 use crate::parser::combinators::pair::pair;
 use crate::parser::combinators::tag::tag;
 use crate::parser::xpath::context::context_item;
 use crate::parser::xpath::expr_wrapper;
-use crate::parser::xpath::functions::function_call;
+use crate::parser::xpath::functions::{
+    argumentlist, dynamic_call, function_call, inline_function_expr, named_function_ref,
+};
 use crate::parser::xpath::literals::literal;
-use crate::parser::xpath::predicates::predicate_list;
+use crate::parser::xpath::maps::{array_constructor, lookup, map_constructor, unary_lookup};
+use crate::parser::xpath::predicates::predicate;
 use crate::parser::xpath::variables::variable_reference;
 use crate::parser::{ParseError, ParseInput, StaticState};
-use crate::transform::Transform;
+use crate::transform::{KeySpecifier, Transform};
 use qualname::{NamespacePrefix, NamespaceUri};
 
 // PostfixExpr ::= PrimaryExpr (Predicate | ArgumentList | Lookup)*
-// TODO: arg list, lookup
 pub(crate) fn postfix_expr<'a, N: Node + 'a, L>() -> Box<
     dyn Fn(
             ParseInput<'a, N>,
@@ -30,13 +33,55 @@ where
     L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
 {
     Box::new(map(
-        pair(primary_expr::<N, L>(), predicate_list()),
-        |(pr, pl)| Transform::Compose(vec![pr, pl]),
+        pair(
+            primary_expr::<N, L>(),
+            many0(alt3(
+                map(predicate::<N, L>(), Postfix::Predicate),
+                map(argumentlist::<N, L>(), Postfix::Arguments),
+                map(lookup::<N, L>(), Postfix::Lookup),
+            )),
+        ),
+        |(pr, pl)| {
+            // Consecutive predicates filter the same sequence.
+            // A lookup or a dynamic function call applies to the filtered sequence.
+            let mut result = pr;
+            let mut predicates = vec![];
+            for p in pl {
+                match p {
+                    Postfix::Predicate(f) => predicates.push(f),
+                    Postfix::Lookup(k) => {
+                        result = Transform::Lookup(
+                            Box::new(Transform::Compose(vec![
+                                result,
+                                Transform::Compose(std::mem::take(&mut predicates)),
+                            ])),
+                            k,
+                        )
+                    }
+                    Postfix::Arguments(a) => {
+                        result = dynamic_call(
+                            Transform::Compose(vec![
+                                result,
+                                Transform::Compose(std::mem::take(&mut predicates)),
+                            ]),
+                            a,
+                        )
+                    }
+                }
+            }
+            Transform::Compose(vec![result, Transform::Compose(predicates)])
+        },
     ))
 }
 
+enum Postfix<N: Node> {
+    Predicate(Transform<N>),
+    Arguments(Vec<Option<Transform<N>>>),
+    Lookup(KeySpecifier<N>),
+}
+
 // PrimaryExpr ::= Literal | VarRef | ParenthesizedExpr | ContextItemExpr | FunctionCall | FunctionItemExpr | MapConstructor | ArrayConstructor | UnaryLookup
-// TODO: finish this parser
+// FunctionItemExpr ::= NamedFunctionRef | InlineFunctionExpr
 fn primary_expr<'a, N: Node + 'a, L>() -> Box<
     dyn Fn(
             ParseInput<'a, N>,
@@ -47,12 +92,21 @@ fn primary_expr<'a, N: Node + 'a, L>() -> Box<
 where
     L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
 {
-    Box::new(alt5(
-        literal::<N, L>(),
-        parenthesized_expr::<N, L>(),
-        function_call::<N, L>(),
-        variable_reference::<N, L>(),
-        context_item::<N, L>(),
+    Box::new(alt2(
+        alt5(
+            map_constructor::<N, L>(),
+            array_constructor::<N, L>(),
+            unary_lookup::<N, L>(),
+            inline_function_expr::<N, L>(),
+            named_function_ref::<N, L>(),
+        ),
+        alt5(
+            literal::<N, L>(),
+            parenthesized_expr::<N, L>(),
+            function_call::<N, L>(),
+            variable_reference::<N, L>(),
+            context_item::<N, L>(),
+        ),
     ))
 }
 
@@ -1,6 +1,7 @@
 //! XPath FLWR expressions.
 
 use crate::item::Node;
+use crate::parser::combinators::alt::alt2;
 use crate::parser::combinators::list::separated_list1;
 use crate::parser::combinators::map::{map, map_with_state};
 use crate::parser::combinators::pair::pair;
@@ -12,7 +13,7 @@ use crate::parser::xpath::nodetests::qualname_test;
 use crate::parser::xpath::support::get_nt_localname;
 use crate::parser::xpath::{expr_single_wrapper, expr_wrapper};
 use crate::parser::{ParseError, ParseInput, StaticState};
-use crate::transform::{Transform, in_scope_namespaces};
+use crate::transform::{Quantifier, Transform, in_scope_namespaces};
 use qualname::{NamespacePrefix, NamespaceUri};
 
 // IfExpr ::= 'if' '(' Expr ')' 'then' ExprSingle 'else' ExprSingle
@@ -111,6 +112,45 @@ where
     ))
 }
 
+// QuantifiedExpr ::= ('some' | 'every') '$' VarName 'in' ExprSingle (',' '$' VarName 'in' ExprSingle)* 'satisfies' ExprSingle
+pub(crate) fn quantified_expr<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, Transform<N>), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(map(
+        tuple5(
+            alt2(
+                map(tag("some"), |_| Quantifier::Some),
+                map(tag("every"), |_| Quantifier::Every),
+            ),
+            xpwhitespace(),
+            separated_list1(
+                map(tuple3(xpwhitespace(), tag(","), xpwhitespace()), |_| ()),
+                map(
+                    tuple6(
+                        tag("$"),
+                        qualname_test(),
+                        xpwhitespace(),
+                        tag("in"),
+                        xpwhitespace(),
+                        expr_single_wrapper::<N, L>(true),
+                    ),
+                    |(_, qn, _, _, _, e)| (get_nt_localname(&qn), e),
+                ),
+            ),
+            tuple3(xpwhitespace(), tag("satisfies"), xpwhitespace()),
+            expr_single_wrapper::<N, L>(true),
+        ),
+        |(q, _, v, _, e)| Transform::Quantified(q, v, Box::new(e)),
+    ))
+}
+
 // LetExpr ::= SimpleLetClause 'return' ExprSingle
 pub(crate) fn let_expr<'a, N: Node + 'a, L>() -> Box<
     dyn Fn(
@@ -1,28 +1,38 @@
 //! Functions for functions.
 
 use crate::item::Node;
-use crate::parser::combinators::alt::alt2;
+use crate::parser::combinators::alt::{alt2, alt3};
 //use crate::parser::combinators::debug::inspect;
 use crate::parser::combinators::list::separated_list0;
-use crate::parser::combinators::map::{map, map_with_state};
+use crate::parser::combinators::many::many0;
+use crate::parser::combinators::map::{map, map_with_state, map_with_state_and_result};
 use crate::parser::combinators::opt::opt;
 use crate::parser::combinators::pair::pair;
+use crate::parser::combinators::support::digit1;
 use crate::parser::combinators::tag::tag;
-use crate::parser::combinators::tuple::{tuple3, tuple6};
+use crate::parser::combinators::tuple::{tuple2, tuple3, tuple6};
 use crate::parser::combinators::whitespace::xpwhitespace;
-use crate::parser::xml::qname::qualname_to_qname;
-use crate::parser::xpath::expr_single_wrapper;
 use crate::parser::xpath::expressions::parenthesized_expr;
 use crate::parser::xpath::nodetests::qualname_test;
 use crate::parser::xpath::numbers::unary_expr;
-use crate::parser::{ParseError, ParseInput, StaticState};
+use crate::parser::xpath::support::get_nt_localname;
+use crate::parser::xpath::types::sequencetype_expr;
+use crate::parser::xpath::variables::variable_reference;
+use crate::parser::xpath::{expr_single_wrapper, expr_wrapper};
+use crate::parser::{ParseError, ParseInput, ParserState, StaticState};
+use crate::transform::arrays::ARRAYNS;
+use crate::transform::binary::BINNS;
 use crate::transform::callable::ActualParameters;
-use crate::transform::{NameTest, NodeTest, Transform, in_scope_namespaces};
+use crate::transform::maps::MAPNS;
+use crate::transform::strings::FNNS;
+use crate::transform::types::{SequenceType, XSDNS};
+use crate::transform::{DateTimeComponent, NameTest, NodeTest, Transform, in_scope_namespaces};
 use crate::xdmerror::ErrorKind;
-use qualname::{NamespacePrefix, NamespaceUri};
+use qualname::{NamespacePrefix, NamespaceUri, QName};
 use std::rc::Rc;
 
 // ArrowExpr ::= UnaryExpr ( '=>' ArrowFunctionSpecifier ArgumentList)*
+// The value of the left-hand expression becomes the first argument of the function call.
 pub(crate) fn arrow_expr<'a, N: Node + 'a, L>() -> Box<
     dyn Fn(
             ParseInput<'a, N>,
@@ -33,50 +43,55 @@ pub(crate) fn arrow_expr<'a, N: Node + 'a, L>() -> Box<
 where
     L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
 {
-    Box::new(map(
+    Box::new(map_with_state(
         pair(
             unary_expr::<N, L>(),
-            opt(tuple6(
+            many0(tuple6(
                 xpwhitespace(),
                 tag("=>"),
                 xpwhitespace(),
                 arrowfunctionspecifier::<N, L>(),
                 xpwhitespace(),
-                opt(argumentlist::<N, L>()),
+                argumentlist::<N, L>(),
             )),
         ),
-        |(v, o)| {
-            if o.is_none() {
-                v
-            } else {
-                Transform::NotImplemented("arrow_expr".to_string())
-            }
+        |(v, o), state, _ss| {
+            o.into_iter().fold(v, |acc, (_, _, _, f, _, mut a)| {
+                a.insert(0, Some(acc));
+                match f {
+                    ArrowTarget::Named(qn) => static_call(qn, a, &state),
+                    ArrowTarget::Dynamic(t) => dynamic_call(t, a),
+                }
+            })
         },
     ))
 }
 
+enum ArrowTarget<N: Node> {
+    Named(NodeTest),
+    Dynamic(Transform<N>),
+}
+
 // ArrowFunctionSpecifier ::= EQName | VarRef | ParenthesizedExpr
-// TODO: finish this parser with EQName and VarRef
 fn arrowfunctionspecifier<'a, N: Node + 'a, L>() -> Box<
     dyn Fn(
             ParseInput<'a, N>,
             &mut StaticState<L>,
-        ) -> Result<(ParseInput<'a, N>, Transform<N>), ParseError>
+        ) -> Result<(ParseInput<'a, N>, ArrowTarget<N>), ParseError>
         + 'a,
 >
 where
     L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
 {
-    Box::new(map(
-        alt2(
-            map(qualname_to_qname(), |_| ()),
-            map(parenthesized_expr::<N, L>(), |_| ()),
-        ),
-        |_| Transform::NotImplemented("arrowfunctionspecifier".to_string()),
+    Box::new(alt3(
+        map(qualname_test(), ArrowTarget::Named),
+        map(variable_reference::<N, L>(), ArrowTarget::Dynamic),
+        map(parenthesized_expr::<N, L>(), ArrowTarget::Dynamic),
     ))
 }
 
 // FunctionCall ::= EQName ArgumentList
+// A function call with argument placeholders is a partial function application.
 pub(crate) fn function_call<'a, N: Node + 'a, L>() -> Box<
     dyn Fn(
             ParseInput<'a, N>,
@@ -89,562 +104,951 @@ where
 {
     Box::new(map_with_state(
         pair(qualname_test(), argumentlist::<N, L>()),
-        |(qn, mut a), state, _ss| match qn {
-            NodeTest::Name(NameTest::Name(ref ntqn)) => {
-                match ntqn.local_name().to_string().as_str() {
-                    "current" => Transform::CurrentItem,
-                    "position" => Transform::Position,
-                    "last" => Transform::Last,
-                    "count" => {
-                        if a.is_empty() {
-                            Transform::Count(Box::new(Transform::Empty))
-                        } else if a.len() == 1 {
-                            Transform::Count(Box::new(a.pop().unwrap()))
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "local-name" => {
-                        if a.is_empty() {
-                            Transform::LocalName(None)
-                        } else if a.len() == 1 {
-                            Transform::LocalName(Some(Box::new(a.pop().unwrap())))
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "name" => {
-                        if a.is_empty() {
-                            Transform::Name(None)
-                        } else if a.len() == 1 {
-                            Transform::Name(Some(Box::new(a.pop().unwrap())))
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "string" => {
-                        if a.len() == 1 {
-                            Transform::String(Box::new(a.pop().unwrap()))
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "concat" => Transform::Concat(a),
-                    "starts-with" => {
-                        if a.len() == 2 {
-                            let b = a.pop().unwrap();
-                            let c = a.pop().unwrap();
-                            Transform::StartsWith(Box::new(c), Box::new(b))
-                        } else {
-                            // Incorrect arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("incorrect arguments"),
-                            )
-                        }
-                    }
-                    "contains" => {
-                        if a.len() == 2 {
-                            let b = a.pop().unwrap();
-                            let c = a.pop().unwrap();
-                            Transform::Contains(Box::new(c), Box::new(b))
-                        } else {
-                            // Incorrect arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("incorrect arguments"),
-                            )
-                        }
-                    }
-                    "substring" => {
-                        if a.len() == 2 {
-                            let b = a.pop().unwrap();
-                            let c = a.pop().unwrap();
-                            Transform::Substring(Box::new(c), Box::new(b), None)
-                        } else if a.len() == 3 {
-                            let b = a.pop().unwrap();
-                            let c = a.pop().unwrap();
-                            let d = a.pop().unwrap();
-                            Transform::Substring(Box::new(d), Box::new(c), Some(Box::new(b)))
-                        } else {
-                            // Wrong number of arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("wrong number of arguments"),
-                            )
-                        }
-                    }
-                    "substring-before" => {
-                        if a.len() == 2 {
-                            let b = a.pop().unwrap();
-                            let c = a.pop().unwrap();
-                            Transform::SubstringBefore(Box::new(c), Box::new(b))
-                        } else {
-                            // Incorrect arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("incorrect arguments"),
-                            )
-                        }
-                    }
-                    "substring-after" => {
-                        if a.len() == 2 {
-                            let b = a.pop().unwrap();
-                            let c = a.pop().unwrap();
-                            Transform::SubstringAfter(Box::new(c), Box::new(b))
-                        } else {
-                            // Incorrect arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("incorrect arguments"),
-                            )
-                        }
-                    }
-                    "normalize-space" => {
-                        if a.is_empty() {
-                            Transform::NormalizeSpace(None)
-                        } else if a.len() == 1 {
-                            Transform::NormalizeSpace(Some(Box::new(a.pop().unwrap())))
-                        } else {
-                            // Wrong number of arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("wrong number of arguments"),
-                            )
-                        }
-                    }
-                    "translate" => {
-                        if a.len() == 3 {
-                            let b = a.pop().unwrap();
-                            let c = a.pop().unwrap();
-                            let d = a.pop().unwrap();
-                            Transform::Translate(Box::new(d), Box::new(c), Box::new(b))
-                        } else {
-                            // Wrong number of arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("wrong number of arguments"),
-                            )
-                        }
-                    }
-                    "generate-id" => {
-                        if a.is_empty() {
-                            Transform::GenerateId(None)
-                        } else if a.len() == 1 {
-                            Transform::GenerateId(Some(Box::new(a.pop().unwrap())))
-                        } else {
-                            // Wrong number of arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("wrong number of arguments"),
-                            )
-                        }
-                    }
-                    "boolean" => {
-                        if a.len() == 1 {
-                            Transform::Boolean(Box::new(a.pop().unwrap()))
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "not" => {
-                        if a.len() == 1 {
-                            Transform::Not(Box::new(a.pop().unwrap()))
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "true" => {
-                        if a.is_empty() {
-                            Transform::True
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "false" => {
-                        if a.is_empty() {
-                            Transform::False
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "number" => {
-                        if a.len() == 1 {
-                            Transform::Number(Box::new(a.pop().unwrap()))
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "sum" => {
-                        if a.len() == 1 {
-                            Transform::Sum(Box::new(a.pop().unwrap()))
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "avg" => {
-                        if a.is_empty() {
-                            Transform::Empty
-                        } else if a.len() == 1 {
-                            Transform::Avg(Box::new(a.pop().unwrap()))
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "min" => {
-                        if a.is_empty() {
-                            Transform::Empty
-                        } else if a.len() == 1 {
-                            Transform::Min(Box::new(a.pop().unwrap()))
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "max" => {
-                        if a.is_empty() {
-                            Transform::Empty
-                        } else if a.len() == 1 {
-                            Transform::Max(Box::new(a.pop().unwrap()))
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "floor" => {
-                        if a.len() == 1 {
-                            Transform::Floor(Box::new(a.pop().unwrap()))
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "ceiling" => {
-                        if a.len() == 1 {
-                            Transform::Ceiling(Box::new(a.pop().unwrap()))
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "round" => {
-                        if a.len() == 1 {
-                            let b = a.pop().unwrap();
-                            Transform::Round(Box::new(b), None)
-                        } else if a.len() == 2 {
-                            let b = a.pop().unwrap();
-                            let c = a.pop().unwrap();
-                            Transform::Round(Box::new(c), Some(Box::new(b)))
-                        } else {
-                            // Wrong number of arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("wrong number of arguments"),
-                            )
-                        }
-                    }
-                    "current-date-time" => {
-                        if a.is_empty() {
-                            Transform::CurrentDateTime
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "current-date" => {
-                        if a.is_empty() {
-                            Transform::CurrentDate
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "current-time" => {
-                        if a.is_empty() {
-                            Transform::CurrentTime
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "format-date-time" => {
-                        if a.len() == 2 {
-                            let b = a.pop().unwrap();
-                            let c = a.pop().unwrap();
-                            Transform::FormatDateTime(Box::new(c), Box::new(b), None, None, None)
-                        } else if a.len() == 5 {
-                            let b = a.pop().unwrap();
-                            let c = a.pop().unwrap();
-                            let d = a.pop().unwrap();
-                            let e = a.pop().unwrap();
-                            let f = a.pop().unwrap();
-                            Transform::FormatDateTime(
-                                Box::new(f),
-                                Box::new(e),
-                                Some(Box::new(d)),
-                                Some(Box::new(c)),
-                                Some(Box::new(b)),
-                            )
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "format-date" => {
-                        if a.len() == 2 {
-                            let b = a.pop().unwrap();
-                            let c = a.pop().unwrap();
-                            Transform::FormatDate(Box::new(c), Box::new(b), None, None, None)
-                        } else if a.len() == 5 {
-                            let b = a.pop().unwrap();
-                            let c = a.pop().unwrap();
-                            let d = a.pop().unwrap();
-                            let e = a.pop().unwrap();
-                            let f = a.pop().unwrap();
-                            Transform::FormatDate(
-                                Box::new(f),
-                                Box::new(e),
-                                Some(Box::new(d)),
-                                Some(Box::new(c)),
-                                Some(Box::new(b)),
-                            )
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "format-time" => {
-                        if a.len() == 2 {
-                            let b = a.pop().unwrap();
-                            let c = a.pop().unwrap();
-                            Transform::FormatTime(Box::new(c), Box::new(b), None, None, None)
-                        } else if a.len() == 5 {
-                            let b = a.pop().unwrap();
-                            let c = a.pop().unwrap();
-                            let d = a.pop().unwrap();
-                            let e = a.pop().unwrap();
-                            let f = a.pop().unwrap();
-                            Transform::FormatTime(
-                                Box::new(f),
-                                Box::new(e),
-                                Some(Box::new(d)),
-                                Some(Box::new(c)),
-                                Some(Box::new(b)),
-                            )
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "format-number" => {
-                        if a.is_empty() || a.len() == 1 {
-                            // Too few arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too few arguments"),
-                            )
-                        } else if a.len() == 2 {
-                            let b = a.pop().unwrap();
-                            let c = a.pop().unwrap();
-                            Transform::FormatNumber(Box::new(c), Box::new(b), None)
-                        } else if a.len() == 3 {
-                            let b = a.pop().unwrap();
-                            let c = a.pop().unwrap();
-                            let d = a.pop().unwrap();
-                            Transform::FormatNumber(Box::new(d), Box::new(c), Some(Box::new(b)))
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "current-group" => {
-                        if a.is_empty() {
-                            Transform::CurrentGroup
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "current-grouping-key" => {
-                        if a.is_empty() {
-                            Transform::CurrentGroupingKey
-                        } else {
-                            // Too many arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("too many arguments"),
-                            )
-                        }
-                    }
-                    "key" => {
-                        if a.len() == 2 {
-                            let m = a.pop().unwrap();
-                            let name = a.pop().unwrap();
-                            Transform::Key(
-                                Box::new(name),
-                                Box::new(m),
-                                None,
-                                in_scope_namespaces(state.cur.clone()),
-                            )
-                        } else if a.len() == 3 {
-                            let u = a.pop().unwrap();
-                            let m = a.pop().unwrap();
-                            let name = a.pop().unwrap();
-                            Transform::Key(
-                                Box::new(name),
-                                Box::new(m),
-                                Some(Box::new(u)),
-                                in_scope_namespaces(state.cur.clone()),
-                            )
-                        } else {
-                            // Wrong # arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("wrong number of arguments"),
-                            )
-                        }
-                    }
-                    "system-property" => {
-                        if a.len() == 1 {
-                            let p = a.pop().unwrap();
-                            Transform::SystemProperty(
-                                Box::new(p),
-                                in_scope_namespaces(state.cur.clone()),
-                            )
-                        } else {
-                            // Wrong # arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("wrong number of arguments"),
-                            )
-                        }
-                    }
-                    "available-system-properties" => {
-                        if a.is_empty() {
-                            Transform::AvailableSystemProperties
-                        } else {
-                            // Wrong # arguments
-                            Transform::Error(
-                                ErrorKind::ParseError,
-                                String::from("wrong number of arguments"),
-                            )
-                        }
-                    }
-                    "document" => match a.len() {
-                        0 => Transform::Document(Box::new(Transform::Empty), None),
-                        1 => {
-                            let u = a.pop().unwrap();
-                            Transform::Document(Box::new(u), None)
-                        }
-                        2 => {
-                            let b = a.pop().unwrap();
-                            let u = a.pop().unwrap();
-                            Transform::Document(Box::new(u), Some(Box::new(b)))
-                        }
-                        _ => Transform::Error(
+        |(qn, a), state, _ss| static_call(qn, a, &state),
+    ))
+}
+
+// NamedFunctionRef ::= EQName '#' IntegerLiteral
+pub(crate) fn named_function_ref<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, Transform<N>), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(map_with_state_and_result(
+        tuple3(qualname_test(), tag("#"), digit1()),
+        |(qn, _, n), state, _ss| {
+            n.parse::<usize>()
+                .map(|arity| function_reference(qn, arity, &state))
+                .map_err(|_| ParseError::Combinator(String::from("not a valid arity")))
+        },
+    ))
+}
+
+// InlineFunctionExpr ::= 'function' '(' ParamList? ')' ('as' SequenceType)? FunctionBody
+// ParamList ::= Param (',' Param)*
+// FunctionBody ::= EnclosedExpr
+pub(crate) fn inline_function_expr<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, Transform<N>), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(map(
+        tuple6(
+            tuple3(tag("function"), xpwhitespace(), tag("(")),
+            xpwhitespace(),
+            separated_list0(
+                map(tuple3(xpwhitespace(), tag(","), xpwhitespace()), |_| ()),
+                param::<N, L>(),
+            ),
+            tuple2(xpwhitespace(), tag(")")),
+            opt(tuple2(
+                tuple3(xpwhitespace(), tag("as"), xpwhitespace()),
+                sequencetype_expr::<N, L>(),
+            )),
+            tuple3(
+                tuple2(xpwhitespace(), tag("{")),
+                opt(tuple2(xpwhitespace(), expr_wrapper::<N, L>(true))),
+                tuple2(xpwhitespace(), tag("}")),
+            ),
+        ),
+        |(_, _, p, _, r, (_, b, _))| {
+            let body = b.map_or(Transform::Empty, |(_, e)| e);
+            // The result of the function must match the declared type
+            let body = match r {
+                Some((_, st)) => Transform::Treat(Box::new(body), st),
+                None => body,
+            };
+            Transform::FunctionItem(None, p, Rc::new(body))
+        },
+    ))
+}
+
+// Param ::= '$' EQName TypeDeclaration?
+// TypeDeclaration ::= 'as' SequenceType
+fn param<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, (String, Option<SequenceType>)), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(map(
+        tuple3(
+            tag("$"),
+            qualname_test(),
+            opt(tuple2(
+                tuple3(xpwhitespace(), tag("as"), xpwhitespace()),
+                sequencetype_expr::<N, L>(),
+            )),
+        ),
+        |(_, qn, t)| (get_nt_localname(&qn), t.map(|(_, st)| st)),
+    ))
+}
+
+// A call to a named function. If there are argument placeholders then the function is partially applied.
+fn static_call<N: Node>(
+    qn: NodeTest,
+    a: Vec<Option<Transform<N>>>,
+    state: &ParserState<N>,
+) -> Transform<N> {
+    if a.iter().all(Option::is_some) {
+        function(qn, a.into_iter().flatten().collect(), state)
+    } else {
+        Transform::PartialApply(Box::new(function_reference(qn, a.len(), state)), a)
+    }
+}
+
+// A call to a function item. If there are argument placeholders then the function is partially applied.
+pub(crate) fn dynamic_call<N: Node>(f: Transform<N>, a: Vec<Option<Transform<N>>>) -> Transform<N> {
+    if a.iter().all(Option::is_some) {
+        Transform::DynamicCall(Box::new(f), a.into_iter().flatten().collect())
+    } else {
+        Transform::PartialApply(Box::new(f), a)
+    }
+}
+
+// A function item for a named function with the given arity.
+// The body of the function item is a static call, with the parameters as its arguments.
+fn function_reference<N: Node>(qn: NodeTest, arity: usize, state: &ParserState<N>) -> Transform<N> {
+    let NodeTest::Name(NameTest::Name(ref name)) = qn else {
+        return Transform::Error(ErrorKind::Unknown, format!("unknown function \"{}\"", qn));
+    };
+    let name = name.clone();
+    let parameters: Vec<(String, Option<SequenceType>)> =
+        (1..=arity).map(|i| (format!("#{}", i), None)).collect();
+    let args = parameters
+        .iter()
+        .map(|(p, _)| {
+            Transform::VariableReference(p.clone(), in_scope_namespaces(state.cur.clone()))
+        })
+        .collect();
+    match function(qn, args, state) {
+        e @ Transform::Error(_, _) => e,
+        body => Transform::FunctionItem(Some(name), parameters, Rc::new(body)),
+    }
+}
+
+// Produce the transformation for a call to a named function.
+fn function<N: Node>(
+    qn: NodeTest,
+    mut a: Vec<Transform<N>>,
+    state: &ParserState<N>,
+) -> Transform<N> {
+    match qn {
+        NodeTest::Name(NameTest::Name(ref ntqn)) if in_namespace(ntqn, MAPNS) => {
+            map_function(ntqn.local_name().to_string().as_str(), a)
+        }
+        NodeTest::Name(NameTest::Name(ref ntqn)) if in_namespace(ntqn, ARRAYNS) => {
+            array_function(ntqn.local_name().to_string().as_str(), a)
+        }
+        NodeTest::Name(NameTest::Name(ref ntqn)) if in_namespace(ntqn, BINNS) => {
+            binary_function(ntqn.local_name().to_string().as_str(), a)
+        }
+        NodeTest::Name(NameTest::Name(ref ntqn)) if in_namespace(ntqn, XSDNS) => {
+            // A constructor function is equivalent to a cast that allows the empty sequence
+            if a.len() == 1 {
+                Transform::Cast(Box::new(a.pop().unwrap()), ntqn.clone(), true)
+            } else {
+                Transform::Error(
+                    ErrorKind::ParseError,
+                    String::from("wrong number of arguments"),
+                )
+            }
+        }
+        // Built-in functions are in the standard function namespace, or have no namespace
+        NodeTest::Name(NameTest::Name(ref ntqn))
+            if ntqn.namespace_uri().is_none() || in_namespace(ntqn, FNNS) =>
+        {
+            match ntqn.local_name().to_string().as_str() {
+                "current" => Transform::CurrentItem,
+                "position" => Transform::Position,
+                "last" => Transform::Last,
+                "count" => {
+                    if a.is_empty() {
+                        Transform::Count(Box::new(Transform::Empty))
+                    } else if a.len() == 1 {
+                        Transform::Count(Box::new(a.pop().unwrap()))
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "local-name" => {
+                    if a.is_empty() {
+                        Transform::LocalName(None)
+                    } else if a.len() == 1 {
+                        Transform::LocalName(Some(Box::new(a.pop().unwrap())))
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "name" => {
+                    if a.is_empty() {
+                        Transform::Name(None)
+                    } else if a.len() == 1 {
+                        Transform::Name(Some(Box::new(a.pop().unwrap())))
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "string" => {
+                    if a.len() == 1 {
+                        Transform::String(Box::new(a.pop().unwrap()))
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "concat" => Transform::Concat(a),
+                "substring" => {
+                    if a.len() == 2 {
+                        let b = a.pop().unwrap();
+                        let c = a.pop().unwrap();
+                        Transform::Substring(Box::new(c), Box::new(b), None)
+                    } else if a.len() == 3 {
+                        let b = a.pop().unwrap();
+                        let c = a.pop().unwrap();
+                        let d = a.pop().unwrap();
+                        Transform::Substring(Box::new(d), Box::new(c), Some(Box::new(b)))
+                    } else {
+                        // Wrong number of arguments
+                        Transform::Error(
                             ErrorKind::ParseError,
                             String::from("wrong number of arguments"),
-                        ),
-                    },
-                    _ => Transform::Invoke(
-                        ntqn.clone(),
-                        ActualParameters::Positional(a),
-                        Rc::new(state.in_scope_namespaces.clone()),
-                    ),
+                        )
+                    }
+                }
+                "normalize-space" => {
+                    if a.is_empty() {
+                        Transform::NormalizeSpace(None)
+                    } else if a.len() == 1 {
+                        Transform::NormalizeSpace(Some(Box::new(a.pop().unwrap())))
+                    } else {
+                        // Wrong number of arguments
+                        Transform::Error(
+                            ErrorKind::ParseError,
+                            String::from("wrong number of arguments"),
+                        )
+                    }
+                }
+                "translate" => {
+                    if a.len() == 3 {
+                        let b = a.pop().unwrap();
+                        let c = a.pop().unwrap();
+                        let d = a.pop().unwrap();
+                        Transform::Translate(Box::new(d), Box::new(c), Box::new(b))
+                    } else {
+                        // Wrong number of arguments
+                        Transform::Error(
+                            ErrorKind::ParseError,
+                            String::from("wrong number of arguments"),
+                        )
+                    }
+                }
+                "generate-id" => {
+                    if a.is_empty() {
+                        Transform::GenerateId(None)
+                    } else if a.len() == 1 {
+                        Transform::GenerateId(Some(Box::new(a.pop().unwrap())))
+                    } else {
+                        // Wrong number of arguments
+                        Transform::Error(
+                            ErrorKind::ParseError,
+                            String::from("wrong number of arguments"),
+                        )
+                    }
+                }
+                "boolean" => {
+                    if a.len() == 1 {
+                        Transform::Boolean(Box::new(a.pop().unwrap()))
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "not" => {
+                    if a.len() == 1 {
+                        Transform::Not(Box::new(a.pop().unwrap()))
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
                 }
+                "true" => {
+                    if a.is_empty() {
+                        Transform::True
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "false" => {
+                    if a.is_empty() {
+                        Transform::False
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "number" => {
+                    if a.len() == 1 {
+                        Transform::Number(Box::new(a.pop().unwrap()))
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "sum" => {
+                    if a.len() == 1 {
+                        Transform::Sum(Box::new(a.pop().unwrap()))
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "avg" => {
+                    if a.is_empty() {
+                        Transform::Empty
+                    } else if a.len() == 1 {
+                        Transform::Avg(Box::new(a.pop().unwrap()))
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "min" => {
+                    if a.is_empty() {
+                        Transform::Empty
+                    } else if a.len() == 1 {
+                        Transform::Min(Box::new(a.pop().unwrap()))
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "max" => {
+                    if a.is_empty() {
+                        Transform::Empty
+                    } else if a.len() == 1 {
+                        Transform::Max(Box::new(a.pop().unwrap()))
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "floor" => {
+                    if a.len() == 1 {
+                        Transform::Floor(Box::new(a.pop().unwrap()))
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "ceiling" => {
+                    if a.len() == 1 {
+                        Transform::Ceiling(Box::new(a.pop().unwrap()))
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "round" => {
+                    if a.len() == 1 {
+                        let b = a.pop().unwrap();
+                        Transform::Round(Box::new(b), None)
+                    } else if a.len() == 2 {
+                        let b = a.pop().unwrap();
+                        let c = a.pop().unwrap();
+                        Transform::Round(Box::new(c), Some(Box::new(b)))
+                    } else {
+                        // Wrong number of arguments
+                        Transform::Error(
+                            ErrorKind::ParseError,
+                            String::from("wrong number of arguments"),
+                        )
+                    }
+                }
+                "current-date-time" | "current-dateTime" => {
+                    if a.is_empty() {
+                        Transform::CurrentDateTime
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "current-date" => {
+                    if a.is_empty() {
+                        Transform::CurrentDate
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "current-time" => {
+                    if a.is_empty() {
+                        Transform::CurrentTime
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "format-date-time" | "format-dateTime" => {
+                    if a.len() == 2 {
+                        let b = a.pop().unwrap();
+                        let c = a.pop().unwrap();
+                        Transform::FormatDateTime(Box::new(c), Box::new(b), None, None, None)
+                    } else if a.len() == 5 {
+                        let b = a.pop().unwrap();
+                        let c = a.pop().unwrap();
+                        let d = a.pop().unwrap();
+                        let e = a.pop().unwrap();
+                        let f = a.pop().unwrap();
+                        Transform::FormatDateTime(
+                            Box::new(f),
+                            Box::new(e),
+                            Some(Box::new(d)),
+                            Some(Box::new(c)),
+                            Some(Box::new(b)),
+                        )
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "format-date" => {
+                    if a.len() == 2 {
+                        let b = a.pop().unwrap();
+                        let c = a.pop().unwrap();
+                        Transform::FormatDate(Box::new(c), Box::new(b), None, None, None)
+                    } else if a.len() == 5 {
+                        let b = a.pop().unwrap();
+                        let c = a.pop().unwrap();
+                        let d = a.pop().unwrap();
+                        let e = a.pop().unwrap();
+                        let f = a.pop().unwrap();
+                        Transform::FormatDate(
+                            Box::new(f),
+                            Box::new(e),
+                            Some(Box::new(d)),
+                            Some(Box::new(c)),
+                            Some(Box::new(b)),
+                        )
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "format-time" => {
+                    if a.len() == 2 {
+                        let b = a.pop().unwrap();
+                        let c = a.pop().unwrap();
+                        Transform::FormatTime(Box::new(c), Box::new(b), None, None, None)
+                    } else if a.len() == 5 {
+                        let b = a.pop().unwrap();
+                        let c = a.pop().unwrap();
+                        let d = a.pop().unwrap();
+                        let e = a.pop().unwrap();
+                        let f = a.pop().unwrap();
+                        Transform::FormatTime(
+                            Box::new(f),
+                            Box::new(e),
+                            Some(Box::new(d)),
+                            Some(Box::new(c)),
+                            Some(Box::new(b)),
+                        )
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "format-number" => {
+                    if a.is_empty() || a.len() == 1 {
+                        // Too few arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too few arguments"))
+                    } else if a.len() == 2 {
+                        let b = a.pop().unwrap();
+                        let c = a.pop().unwrap();
+                        Transform::FormatNumber(Box::new(c), Box::new(b), None)
+                    } else if a.len() == 3 {
+                        let b = a.pop().unwrap();
+                        let c = a.pop().unwrap();
+                        let d = a.pop().unwrap();
+                        Transform::FormatNumber(Box::new(d), Box::new(c), Some(Box::new(b)))
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "current-group" => {
+                    if a.is_empty() {
+                        Transform::CurrentGroup
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "current-grouping-key" => {
+                    if a.is_empty() {
+                        Transform::CurrentGroupingKey
+                    } else {
+                        // Too many arguments
+                        Transform::Error(ErrorKind::ParseError, String::from("too many arguments"))
+                    }
+                }
+                "key" => {
+                    if a.len() == 2 {
+                        let m = a.pop().unwrap();
+                        let name = a.pop().unwrap();
+                        Transform::Key(
+                            Box::new(name),
+                            Box::new(m),
+                            None,
+                            in_scope_namespaces(state.cur.clone()),
+                        )
+                    } else if a.len() == 3 {
+                        let u = a.pop().unwrap();
+                        let m = a.pop().unwrap();
+                        let name = a.pop().unwrap();
+                        Transform::Key(
+                            Box::new(name),
+                            Box::new(m),
+                            Some(Box::new(u)),
+                            in_scope_namespaces(state.cur.clone()),
+                        )
+                    } else {
+                        // Wrong # arguments
+                        Transform::Error(
+                            ErrorKind::ParseError,
+                            String::from("wrong number of arguments"),
+                        )
+                    }
+                }
+                "system-property" => {
+                    if a.len() == 1 {
+                        let p = a.pop().unwrap();
+                        Transform::SystemProperty(
+                            Box::new(p),
+                            in_scope_namespaces(state.cur.clone()),
+                        )
+                    } else {
+                        // Wrong # arguments
+                        Transform::Error(
+                            ErrorKind::ParseError,
+                            String::from("wrong number of arguments"),
+                        )
+                    }
+                }
+                "available-system-properties" => {
+                    if a.is_empty() {
+                        Transform::AvailableSystemProperties
+                    } else {
+                        // Wrong # arguments
+                        Transform::Error(
+                            ErrorKind::ParseError,
+                            String::from("wrong number of arguments"),
+                        )
+                    }
+                }
+                name @ ("for-each" | "filter" | "fold-left" | "fold-right" | "for-each-pair"
+                | "sort" | "apply") => higher_order_function(name, a),
+                name @ ("starts-with"
+                | "ends-with"
+                | "contains"
+                | "substring-before"
+                | "substring-after"
+                | "upper-case"
+                | "lower-case"
+                | "string-join"
+                | "string-length"
+                | "string-to-codepoints"
+                | "codepoints-to-string"
+                | "compare"
+                | "codepoint-equal"
+                | "encode-for-uri"
+                | "iri-to-uri"
+                | "escape-html-uri"
+                | "normalize-unicode"
+                | "contains-token") => string_function(name, a),
+                name @ ("empty" | "exists" | "head" | "tail" | "insert-before" | "remove"
+                | "reverse" | "subsequence" | "unordered" | "distinct-values"
+                | "index-of" | "deep-equal" | "zero-or-one" | "one-or-more"
+                | "exactly-one") => sequence_function(name, a),
+                name @ ("QName"
+                | "resolve-QName"
+                | "prefix-from-QName"
+                | "local-name-from-QName"
+                | "namespace-uri-from-QName"
+                | "namespace-uri-for-prefix"
+                | "in-scope-prefixes"
+                | "node-name"
+                | "namespace-uri") => qname_function(name, a),
+                name @ ("parse-json" | "json-doc" | "json-to-xml" | "xml-to-json") => {
+                    json_function(name, a)
+                }
+                name @ ("matches" | "replace" | "tokenize" | "analyze-string") => {
+                    regex_function(name, a)
+                }
+                name @ ("year-from-dateTime"
+                | "month-from-dateTime"
+                | "day-from-dateTime"
+                | "hours-from-dateTime"
+                | "minutes-from-dateTime"
+                | "seconds-from-dateTime"
+                | "timezone-from-dateTime"
+                | "year-from-date"
+                | "month-from-date"
+                | "day-from-date"
+                | "timezone-from-date"
+                | "hours-from-time"
+                | "minutes-from-time"
+                | "seconds-from-time"
+                | "timezone-from-time"
+                | "years-from-duration"
+                | "months-from-duration"
+                | "days-from-duration"
+                | "hours-from-duration"
+                | "minutes-from-duration"
+                | "seconds-from-duration"
+                | "adjust-dateTime-to-timezone") => datetime_function(name, a),
+                "document" => match a.len() {
+                    0 => Transform::Document(Box::new(Transform::Empty), None),
+                    1 => {
+                        let u = a.pop().unwrap();
+                        Transform::Document(Box::new(u), None)
+                    }
+                    2 => {
+                        let b = a.pop().unwrap();
+                        let u = a.pop().unwrap();
+                        Transform::Document(Box::new(u), Some(Box::new(b)))
+                    }
+                    _ => Transform::Error(
+                        ErrorKind::ParseError,
+                        String::from("wrong number of arguments"),
+                    ),
+                },
+                _ => Transform::Invoke(
+                    ntqn.clone(),
+                    ActualParameters::Positional(a),
+                    Rc::new(state.in_scope_namespaces.clone()),
+                ),
             }
-            _ => Transform::Error(ErrorKind::Unknown, format!("unknown function \"{}\"", qn)),
-        },
-    ))
+        }
+        // A function in any other namespace is a user-defined function
+        NodeTest::Name(NameTest::Name(ref ntqn)) => Transform::Invoke(
+            ntqn.clone(),
+            ActualParameters::Positional(a),
+            Rc::new(state.in_scope_namespaces.clone()),
+        ),
+        _ => Transform::Error(ErrorKind::Unknown, format!("unknown function \"{}\"", qn)),
+    }
+}
+
+fn in_namespace(qn: &QName, ns: &str) -> bool {
+    qn.namespace_uri().is_some_and(|u| u.to_string() == ns)
+}
+
+// Higher-order functions in the default function namespace.
+fn higher_order_function<N: Node>(name: &str, a: Vec<Transform<N>>) -> Transform<N> {
+    let n = a.len();
+    let mut a = a.into_iter().map(Box::new);
+    let mut arg = || a.next().unwrap();
+    match (name, n) {
+        ("for-each", 2) => Transform::ForEachItem(arg(), arg()),
+        ("filter", 2) => Transform::FilterItems(arg(), arg()),
+        ("fold-left", 3) => Transform::FoldLeft(arg(), arg(), arg()),
+        ("fold-right", 3) => Transform::FoldRight(arg(), arg(), arg()),
+        ("for-each-pair", 3) => Transform::ForEachPair(arg(), arg(), arg()),
+        ("sort", 1) => Transform::Sort(arg(), None, None),
+        ("sort", 2) => Transform::Sort(arg(), Some(arg()), None),
+        ("sort", 3) => Transform::Sort(arg(), Some(arg()), Some(arg())),
+        ("apply", 2) => Transform::Apply(arg(), arg()),
+        _ => Transform::Error(
+            ErrorKind::ParseError,
+            String::from("wrong number of arguments"),
+        ),
+    }
+}
+
+// String functions in the default function namespace.
+fn string_function<N: Node>(name: &str, a: Vec<Transform<N>>) -> Transform<N> {
+    let n = a.len();
+    let mut a = a.into_iter().map(Box::new);
+    let mut arg = || a.next().unwrap();
+    match (name, n) {
+        ("starts-with", 2) => Transform::StartsWith(arg(), arg(), None),
+        ("starts-with", 3) => Transform::StartsWith(arg(), arg(), Some(arg())),
+        ("ends-with", 2) => Transform::EndsWith(arg(), arg(), None),
+        ("ends-with", 3) => Transform::EndsWith(arg(), arg(), Some(arg())),
+        ("contains", 2) => Transform::Contains(arg(), arg(), None),
+        ("contains", 3) => Transform::Contains(arg(), arg(), Some(arg())),
+        ("substring-before", 2) => Transform::SubstringBefore(arg(), arg(), None),
+        ("substring-before", 3) => Transform::SubstringBefore(arg(), arg(), Some(arg())),
+        ("substring-after", 2) => Transform::SubstringAfter(arg(), arg(), None),
+        ("substring-after", 3) => Transform::SubstringAfter(arg(), arg(), Some(arg())),
+        ("upper-case", 1) => Transform::UpperCase(arg()),
+        ("lower-case", 1) => Transform::LowerCase(arg()),
+        ("string-join", 1) => Transform::StringJoin(arg(), None),
+        ("string-join", 2) => Transform::StringJoin(arg(), Some(arg())),
+        ("string-length", 0) => Transform::StringLength(None),
+        ("string-length", 1) => Transform::StringLength(Some(arg())),
+        ("string-to-codepoints", 1) => Transform::StringToCodepoints(arg()),
+        ("codepoints-to-string", 1) => Transform::CodepointsToString(arg()),
+        ("compare", 2) => Transform::Compare(arg(), arg(), None),
+        ("compare", 3) => Transform::Compare(arg(), arg(), Some(arg())),
+        ("codepoint-equal", 2) => Transform::CodepointEqual(arg(), arg()),
+        ("encode-for-uri", 1) => Transform::EncodeForUri(arg()),
+        ("iri-to-uri", 1) => Transform::IriToUri(arg()),
+        ("escape-html-uri", 1) => Transform::EscapeHtmlUri(arg()),
+        ("normalize-unicode", 1) => Transform::NormalizeUnicode(arg(), None),
+        ("normalize-unicode", 2) => Transform::NormalizeUnicode(arg(), Some(arg())),
+        ("contains-token", 2) => Transform::ContainsToken(arg(), arg(), None),
+        ("contains-token", 3) => Transform::ContainsToken(arg(), arg(), Some(arg())),
+        _ => Transform::Error(
+            ErrorKind::ParseError,
+            String::from("wrong number of arguments"),
+        ),
+    }
+}
+
+// General sequence functions in the default function namespace.
+fn sequence_function<N: Node>(name: &str, a: Vec<Transform<N>>) -> Transform<N> {
+    let n = a.len();
+    let mut a = a.into_iter().map(Box::new);
+    let mut arg = || a.next().unwrap();
+    match (name, n) {
+        ("empty", 1) => Transform::IsEmpty(arg()),
+        ("exists", 1) => Transform::Exists(arg()),
+        ("head", 1) => Transform::Head(arg()),
+        ("tail", 1) => Transform::Tail(arg()),
+        ("insert-before", 3) => Transform::InsertBefore(arg(), arg(), arg()),
+        ("remove", 2) => Transform::Remove(arg(), arg()),
+        ("reverse", 1) => Transform::Reverse(arg()),
+        ("subsequence", 2) => Transform::Subsequence(arg(), arg(), None),
+        ("subsequence", 3) => Transform::Subsequence(arg(), arg(), Some(arg())),
+        ("unordered", 1) => Transform::Unordered(arg()),
+        ("distinct-values", 1) => Transform::DistinctValues(arg(), None),
+        ("distinct-values", 2) => Transform::DistinctValues(arg(), Some(arg())),
+        ("index-of", 2) => Transform::IndexOf(arg(), arg(), None),
+        ("index-of", 3) => Transform::IndexOf(arg(), arg(), Some(arg())),
+        ("deep-equal", 2) => Transform::DeepEqual(arg(), arg(), None),
+        ("deep-equal", 3) => Transform::DeepEqual(arg(), arg(), Some(arg())),
+        ("zero-or-one", 1) => Transform::ZeroOrOne(arg()),
+        ("one-or-more", 1) => Transform::OneOrMore(arg()),
+        ("exactly-one", 1) => Transform::ExactlyOne(arg()),
+        _ => Transform::Error(
+            ErrorKind::ParseError,
+            String::from("wrong number of arguments"),
+        ),
+    }
+}
+
+// Date, time, and duration functions in the default function namespace.
+fn datetime_function<N: Node>(name: &str, a: Vec<Transform<N>>) -> Transform<N> {
+    let n = a.len();
+    let mut a = a.into_iter().map(Box::new);
+    let mut arg = || a.next().unwrap();
+    match (name, n) {
+        ("adjust-dateTime-to-timezone", 1) => Transform::AdjustDateTimeToTimezone(arg(), None),
+        ("adjust-dateTime-to-timezone", 2) => {
+            Transform::AdjustDateTimeToTimezone(arg(), Some(arg()))
+        }
+        (_, 1) => {
+            // The name of a component function is "component-from-type"
+            let (c, t) = name.split_once("-from-").unwrap();
+            let c = match c {
+                "year" | "years" => DateTimeComponent::Year,
+                "month" | "months" => DateTimeComponent::Month,
+                "day" | "days" => DateTimeComponent::Day,
+                "hours" => DateTimeComponent::Hours,
+                "minutes" => DateTimeComponent::Minutes,
+                "seconds" => DateTimeComponent::Seconds,
+                _ => DateTimeComponent::Timezone,
+            };
+            match t {
+                "dateTime" => Transform::ComponentFromDateTime(c, arg()),
+                "date" => Transform::ComponentFromDate(c, arg()),
+                "time" => Transform::ComponentFromTime(c, arg()),
+                _ => Transform::ComponentFromDuration(c, arg()),
+            }
+        }
+        _ => Transform::Error(
+            ErrorKind::ParseError,
+            String::from("wrong number of arguments"),
+        ),
+    }
+}
+
+// Functions in the default function namespace that construct and take apart qualified names.
+fn qname_function<N: Node>(name: &str, a: Vec<Transform<N>>) -> Transform<N> {
+    let n = a.len();
+    let mut a = a.into_iter().map(Box::new);
+    let mut arg = || a.next().unwrap();
+    match (name, n) {
+        ("QName", 2) => Transform::QName(arg(), arg()),
+        ("resolve-QName", 2) => Transform::ResolveQName(arg(), arg()),
+        ("prefix-from-QName", 1) => Transform::PrefixFromQName(arg()),
+        ("local-name-from-QName", 1) => Transform::LocalNameFromQName(arg()),
+        ("namespace-uri-from-QName", 1) => Transform::NamespaceUriFromQName(arg()),
+        ("namespace-uri-for-prefix", 2) => Transform::NamespaceUriForPrefix(arg(), arg()),
+        ("in-scope-prefixes", 1) => Transform::InScopePrefixes(arg()),
+        ("node-name", 0) => Transform::NodeName(None),
+        ("node-name", 1) => Transform::NodeName(Some(arg())),
+        ("namespace-uri", 0) => Transform::NamespaceUri(None),
+        ("namespace-uri", 1) => Transform::NamespaceUri(Some(arg())),
+        _ => Transform::Error(
+            ErrorKind::ParseError,
+            String::from("wrong number of arguments"),
+        ),
+    }
+}
+
+// Functions in the default function namespace that parse and serialise JSON.
+fn json_function<N: Node>(name: &str, a: Vec<Transform<N>>) -> Transform<N> {
+    let n = a.len();
+    let mut a = a.into_iter().map(Box::new);
+    let mut arg = || a.next().unwrap();
+    match (name, n) {
+        ("parse-json", 1) => Transform::ParseJson(arg(), None),
+        ("parse-json", 2) => Transform::ParseJson(arg(), Some(arg())),
+        ("json-doc", 1) => Transform::JsonDoc(arg(), None),
+        ("json-doc", 2) => Transform::JsonDoc(arg(), Some(arg())),
+        ("json-to-xml", 1) => Transform::JsonToXml(arg(), None),
+        ("json-to-xml", 2) => Transform::JsonToXml(arg(), Some(arg())),
+        ("xml-to-json", 1) => Transform::XmlToJson(arg(), None),
+        ("xml-to-json", 2) => Transform::XmlToJson(arg(), Some(arg())),
+        _ => Transform::Error(
+            ErrorKind::ParseError,
+            String::from("wrong number of arguments"),
+        ),
+    }
+}
+
+// Regular expression functions in the default function namespace.
+fn regex_function<N: Node>(name: &str, a: Vec<Transform<N>>) -> Transform<N> {
+    let n = a.len();
+    let mut a = a.into_iter().map(Box::new);
+    let mut arg = || a.next().unwrap();
+    match (name, n) {
+        ("matches", 2) => Transform::Matches(arg(), arg(), None),
+        ("matches", 3) => Transform::Matches(arg(), arg(), Some(arg())),
+        ("replace", 3) => Transform::Replace(arg(), arg(), arg(), None),
+        ("replace", 4) => Transform::Replace(arg(), arg(), arg(), Some(arg())),
+        ("tokenize", 1) => Transform::Tokenize(arg(), None, None),
+        ("tokenize", 2) => Transform::Tokenize(arg(), Some(arg()), None),
+        ("tokenize", 3) => Transform::Tokenize(arg(), Some(arg()), Some(arg())),
+        ("analyze-string", 2) => Transform::AnalyzeString(arg(), arg(), None),
+        ("analyze-string", 3) => Transform::AnalyzeString(arg(), arg(), Some(arg())),
+        _ => Transform::Error(
+            ErrorKind::ParseError,
+            String::from("wrong number of arguments"),
+        ),
+    }
+}
+
+// Functions in the map namespace. The name is the local part of the function name.
+fn map_function<N: Node>(name: &str, a: Vec<Transform<N>>) -> Transform<N> {
+    let n = a.len();
+    let mut a = a.into_iter().map(Box::new);
+    let mut arg = || a.next().unwrap();
+    match (name, n) {
+        ("merge", 1) => Transform::MapMerge(arg(), None),
+        ("merge", 2) => Transform::MapMerge(arg(), Some(arg())),
+        ("size", 1) => Transform::MapSize(arg()),
+        ("keys", 1) => Transform::MapKeys(arg()),
+        ("contains", 2) => Transform::MapContains(arg(), arg()),
+        ("get", 2) => Transform::MapGet(arg(), arg()),
+        ("find", 2) => Transform::MapFind(arg(), arg()),
+        ("put", 3) => Transform::MapPut(arg(), arg(), arg()),
+        ("entry", 2) => Transform::MapEntry(arg(), arg()),
+        ("remove", 2) => Transform::MapRemove(arg(), arg()),
+        ("for-each", 2) => Transform::MapForEach(arg(), arg()),
+        _ => Transform::Error(
+            ErrorKind::StaticBadFunction,
+            format!("unknown function map:{}#{}", name, n),
+        ),
+    }
+}
+
+// Functions in the array namespace. The name is the local part of the function name.
+fn array_function<N: Node>(name: &str, a: Vec<Transform<N>>) -> Transform<N> {
+    let n = a.len();
+    let mut a = a.into_iter().map(Box::new);
+    let mut arg = || a.next().unwrap();
+    match (name, n) {
+        ("size", 1) => Transform::ArraySize(arg()),
+        ("get", 2) => Transform::ArrayGet(arg(), arg()),
+        ("put", 3) => Transform::ArrayPut(arg(), arg(), arg()),
+        ("append", 2) => Transform::ArrayAppend(arg(), arg()),
+        ("subarray", 2) => Transform::ArraySubarray(arg(), arg(), None),
+        ("subarray", 3) => Transform::ArraySubarray(arg(), arg(), Some(arg())),
+        ("remove", 2) => Transform::ArrayRemove(arg(), arg()),
+        ("insert-before", 3) => Transform::ArrayInsertBefore(arg(), arg(), arg()),
+        ("head", 1) => Transform::ArrayHead(arg()),
+        ("tail", 1) => Transform::ArrayTail(arg()),
+        ("reverse", 1) => Transform::ArrayReverse(arg()),
+        ("join", 1) => Transform::ArrayJoin(arg()),
+        ("flatten", 1) => Transform::ArrayFlatten(arg()),
+        ("for-each", 2) => Transform::ArrayForEach(arg(), arg()),
+        ("filter", 2) => Transform::ArrayFilter(arg(), arg()),
+        ("fold-left", 3) => Transform::ArrayFoldLeft(arg(), arg(), arg()),
+        ("fold-right", 3) => Transform::ArrayFoldRight(arg(), arg(), arg()),
+        ("for-each-pair", 3) => Transform::ArrayForEachPair(arg(), arg(), arg()),
+        ("sort", 1) => Transform::ArraySort(arg(), None, None),
+        ("sort", 2) => Transform::ArraySort(arg(), Some(arg()), None),
+        ("sort", 3) => Transform::ArraySort(arg(), Some(arg()), Some(arg())),
+        _ => Transform::Error(
+            ErrorKind::StaticBadFunction,
+            format!("unknown function array:{}#{}", name, n),
+        ),
+    }
+}
+
+// Functions in the EXPath binary namespace. The name is the local part of the function name.
+fn binary_function<N: Node>(name: &str, a: Vec<Transform<N>>) -> Transform<N> {
+    let n = a.len();
+    let mut a = a.into_iter().map(Box::new);
+    let mut arg = || a.next().unwrap();
+    match (name, n) {
+        ("length", 1) => Transform::BinaryLength(arg()),
+        ("part", 2) => Transform::BinaryPart(arg(), arg(), None),
+        ("part", 3) => Transform::BinaryPart(arg(), arg(), Some(arg())),
+        ("decode-string", 1) => Transform::BinaryDecodeString(arg(), None, None, None),
+        ("decode-string", 2) => Transform::BinaryDecodeString(arg(), Some(arg()), None, None),
+        ("decode-string", 3) => {
+            Transform::BinaryDecodeString(arg(), Some(arg()), Some(arg()), None)
+        }
+        ("decode-string", 4) => {
+            Transform::BinaryDecodeString(arg(), Some(arg()), Some(arg()), Some(arg()))
+        }
+        _ => Transform::Error(
+            ErrorKind::StaticBadFunction,
+            format!("unknown function bin:{}#{}", name, n),
+        ),
+    }
 }
 
 // ArgumentList ::= '(' (Argument (',' Argument)*)? ')'
-// TODO: finish this parser with actual arguments
-fn argumentlist<'a, N: Node + 'a, L>() -> Box<
+// An argument placeholder is None.
+pub(crate) fn argumentlist<'a, N: Node + 'a, L>() -> Box<
     dyn Fn(
             ParseInput<'a, N>,
             &mut StaticState<L>,
-        ) -> Result<(ParseInput<'a, N>, Vec<Transform<N>>), ParseError>
+        ) -> Result<(ParseInput<'a, N>, Vec<Option<Transform<N>>>), ParseError>
         + 'a,
 >
 where
@@ -664,16 +1068,20 @@ where
 }
 
 // Argument ::= ExprSingle | ArgumentPlaceHolder
-// TODO: ArgumentPlaceHolder
+// ArgumentPlaceHolder ::= '?'
+// An unary lookup also begins with '?', so try an expression first.
 fn argument<'a, N: Node + 'a, L>() -> Box<
     dyn Fn(
             ParseInput<'a, N>,
             &mut StaticState<L>,
-        ) -> Result<(ParseInput<'a, N>, Transform<N>), ParseError>
+        ) -> Result<(ParseInput<'a, N>, Option<Transform<N>>), ParseError>
         + 'a,
 >
 where
     L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
 {
-    Box::new(expr_single_wrapper::<N, L>(true))
+    Box::new(alt2(
+        map(expr_single_wrapper::<N, L>(true), Some),
+        map(tuple2(xpwhitespace(), tag("?")), |_| None),
+    ))
 }
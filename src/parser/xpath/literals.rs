@@ -19,6 +19,7 @@ use crate::value::Value;
 use crate::xdmerror::ErrorKind;
 use qualname::{NamespacePrefix, NamespaceUri};
 
+use num_bigint::BigInt;
 use rust_decimal::Decimal;
 
 // Literal ::= NumericLiteral | StringLiteral
@@ -64,14 +65,14 @@ where
     L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
 {
     Box::new(map(digit1(), |s: String| {
-        s.parse::<i64>().map_or(
+        s.parse::<BigInt>().map_or(
             Transform::Error(ErrorKind::ParseError, String::from("not a valid integer")),
             |n| Transform::Literal(Item::Value(Rc::new(Value::from(n)))),
         )
     }))
 }
 // DecimalLiteral ::= ('.' Digits) | (Digits '.' [0-9]*)
-// Construct a double, but if that fails fall back to decimal
+// Construct a decimal
 fn decimal_literal<'a, N: Node + 'a, L>() -> Box<
     dyn Fn(
             ParseInput<'a, N>,
@@ -97,17 +98,8 @@ fn decimal_literal_frac<'a, N: Node + 'a, L>() -> Box<
 where
     L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
 {
-    Box::new(map(pair(tag("."), digit1()), |(_, mut f)| {
-        f.insert(0, '.');
-        let n = f.parse::<f64>();
-        let i = match n {
-            Ok(m) => Value::from(m),
-            Err(_) => {
-                f.insert(0, '0');
-                Value::from(Decimal::from_str(&f).unwrap())
-            }
-        };
-        Transform::Literal(Item::Value(Rc::new(i)))
+    Box::new(map(pair(tag("."), digit1()), |(_, f)| {
+        decimal_value(format!("0.{}", f))
     }))
 }
 fn decimal_literal_comp<'a, N: Node + 'a, L>() -> Box<
@@ -121,15 +113,18 @@ where
     L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
 {
     Box::new(map(tuple3(digit1(), tag("."), digit0()), |(w, _, f)| {
-        let s = format!("{}.{}", w, f);
-        let n = s.parse::<f64>();
-        let i = match n {
-            Ok(m) => Value::from(m),
-            Err(_) => Value::from(Decimal::from_str(&s).unwrap()),
-        };
-        Transform::Literal(Item::Value(Rc::new(i)))
+        decimal_value(format!("{}.{}", w, f))
     }))
 }
+fn decimal_value<N: Node>(s: String) -> Transform<N> {
+    Decimal::from_str(&s).map_or(
+        Transform::Error(
+            ErrorKind::ParseError,
+            String::from("decimal value is out of range"),
+        ),
+        |d| Transform::Literal(Item::Value(Rc::new(Value::from(d.normalize())))),
+    )
+}
 
 // DoubleLiteral ::= (('.' Digits) | (Digits ('.' [0-9]*)?)) [eE] [+-]? Digits
 // Construct a double
@@ -0,0 +1,189 @@
+//! Map and array constructors, and the lookup operator.
+
+use crate::item::Node;
+use crate::parser::combinators::alt::{alt2, alt4};
+use crate::parser::combinators::list::separated_list0;
+use crate::parser::combinators::map::{map, map_with_state_and_result};
+use crate::parser::combinators::opt::opt;
+use crate::parser::combinators::support::digit1;
+use crate::parser::combinators::tag::tag;
+use crate::parser::combinators::tuple::{tuple2, tuple3};
+use crate::parser::combinators::whitespace::xpwhitespace;
+use crate::parser::xml::qname::ncname;
+use crate::parser::xpath::expressions::parenthesized_expr;
+use crate::parser::xpath::{expr_single_wrapper, expr_wrapper};
+use crate::parser::{ParseError, ParseInput, StaticState};
+use crate::transform::{KeySpecifier, Transform};
+use qualname::{NamespacePrefix, NamespaceUri};
+
+// MapConstructor ::= 'map' '{' (MapConstructorEntry (',' MapConstructorEntry)*)? '}'
+pub(crate) fn map_constructor<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, Transform<N>), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(map(
+        tuple3(
+            tuple3(tag("map"), xpwhitespace(), tag("{")),
+            separated_list0(
+                map(tuple3(xpwhitespace(), tag(","), xpwhitespace()), |_| ()),
+                map_constructor_entry::<N, L>(),
+            ),
+            tuple2(xpwhitespace(), tag("}")),
+        ),
+        |(_, v, _)| Transform::MapConstructor(v),
+    ))
+}
+
+// MapConstructorEntry ::= MapKeyExpr ':' MapValueExpr
+fn map_constructor_entry<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, (Transform<N>, Transform<N>)), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(map(
+        tuple3(
+            tuple2(xpwhitespace(), expr_single_wrapper::<N, L>(true)),
+            tuple3(xpwhitespace(), tag(":"), xpwhitespace()),
+            expr_single_wrapper::<N, L>(true),
+        ),
+        |((_, k), _, v)| (k, v),
+    ))
+}
+
+// ArrayConstructor ::= SquareArrayConstructor | CurlyArrayConstructor
+pub(crate) fn array_constructor<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, Transform<N>), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(alt2(
+        square_array_constructor::<N, L>(),
+        curly_array_constructor::<N, L>(),
+    ))
+}
+
+// SquareArrayConstructor ::= '[' (ExprSingle (',' ExprSingle)*)? ']'
+fn square_array_constructor<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, Transform<N>), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(map(
+        tuple3(
+            tuple2(tag("["), xpwhitespace()),
+            separated_list0(
+                map(tuple3(xpwhitespace(), tag(","), xpwhitespace()), |_| ()),
+                expr_single_wrapper::<N, L>(true),
+            ),
+            tuple2(xpwhitespace(), tag("]")),
+        ),
+        |(_, v, _)| Transform::ArrayConstructor(v),
+    ))
+}
+
+// CurlyArrayConstructor ::= 'array' EnclosedExpr
+fn curly_array_constructor<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, Transform<N>), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(map(
+        tuple3(
+            tuple3(tag("array"), xpwhitespace(), tag("{")),
+            opt(tuple2(xpwhitespace(), expr_wrapper::<N, L>(true))),
+            tuple2(xpwhitespace(), tag("}")),
+        ),
+        |(_, e, _)| {
+            Transform::CurlyArrayConstructor(Box::new(e.map_or(Transform::Empty, |(_, t)| t)))
+        },
+    ))
+}
+
+// Lookup ::= '?' KeySpecifier
+pub(crate) fn lookup<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, KeySpecifier<N>), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(map(
+        tuple2(
+            tuple3(xpwhitespace(), tag("?"), xpwhitespace()),
+            key_specifier::<N, L>(),
+        ),
+        |(_, k)| k,
+    ))
+}
+
+// UnaryLookup ::= '?' KeySpecifier
+// The context item is the map or array to look up.
+pub(crate) fn unary_lookup<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, Transform<N>), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(map(
+        tuple3(tag("?"), xpwhitespace(), key_specifier::<N, L>()),
+        |(_, _, k)| Transform::Lookup(Box::new(Transform::ContextItem), k),
+    ))
+}
+
+// KeySpecifier ::= NCName | IntegerLiteral | ParenthesizedExpr | '*'
+fn key_specifier<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, KeySpecifier<N>), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(alt4(
+        map(ncname(), KeySpecifier::Name),
+        map_with_state_and_result(digit1(), |s: String, _, _| {
+            s.parse::<i64>()
+                .map(KeySpecifier::Integer)
+                .map_err(|_| ParseError::Combinator(String::from("not a valid integer")))
+        }),
+        map(parenthesized_expr::<N, L>(), |e| {
+            KeySpecifier::Expr(Box::new(e))
+        }),
+        map(tag("*"), |_| KeySpecifier::Wildcard),
+    ))
+}
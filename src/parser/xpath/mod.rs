@@ -52,6 +52,7 @@ mod flwr;
 mod functions;
 pub(crate) mod literals;
 mod logic;
+mod maps;
 mod nodes;
 pub(crate) mod nodetests;
 mod numbers;
@@ -61,14 +62,14 @@ pub(crate) mod support;
 mod types;
 pub(crate) mod variables;
 
-use crate::parser::combinators::alt::alt4;
+use crate::parser::combinators::alt::{alt2, alt4};
 //use crate::parser::combinators::debug::inspect;
 use crate::parser::combinators::list::separated_list1;
 use crate::parser::combinators::map::map;
 use crate::parser::combinators::tag::tag;
 use crate::parser::combinators::tuple::tuple3;
 use crate::parser::combinators::whitespace::xpwhitespace;
-use crate::parser::xpath::flwr::{for_expr, if_expr, let_expr};
+use crate::parser::xpath::flwr::{for_expr, if_expr, let_expr, quantified_expr};
 use crate::parser::xpath::logic::or_expr;
 use crate::parser::xpath::support::noop;
 use crate::parser::{
@@ -228,7 +229,10 @@ fn expr_single<'a, N: Node + 'a, L>() -> Box<
 where
     L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
 {
-    Box::new(alt4(let_expr(), for_expr(), if_expr(), or_expr()))
+    Box::new(alt2(
+        alt4(let_expr(), for_expr(), quantified_expr(), if_expr()),
+        or_expr(),
+    ))
 }
 
 pub(crate) fn expr_single_wrapper<'a, N: Node + 'a, L>(
@@ -68,18 +68,23 @@ where
             many0(tuple2(
                 tuple3(
                     xpwhitespace(),
-                    alt2(tag("intersect"), tag("except")),
+                    alt2(
+                        map(tag("intersect"), |_| true),
+                        map(tag("except"), |_| false),
+                    ),
                     xpwhitespace(),
                 ),
                 instanceof_expr::<N, L>(),
             )),
         ),
         |(v, o)| {
-            if o.is_empty() {
-                v
-            } else {
-                Transform::NotImplemented("intersectexcept_expr".to_string())
-            }
+            o.into_iter().fold(v, |acc, ((_, is_intersect, _), t)| {
+                if is_intersect {
+                    Transform::Intersect(Box::new(acc), Box::new(t))
+                } else {
+                    Transform::Except(Box::new(acc), Box::new(t))
+                }
+            })
         },
     ))
 }
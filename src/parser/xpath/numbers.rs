@@ -153,18 +153,29 @@ where
     L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
 {
     Box::new(map(
-        pair(many0(alt2(tag("-"), tag("+"))), value_expr::<N, L>()),
+        pair(
+            many0(map(
+                tuple2(
+                    alt2(map(tag("-"), |_| true), map(tag("+"), |_| false)),
+                    xpwhitespace(),
+                ),
+                |(m, _)| m,
+            )),
+            value_expr::<N, L>(),
+        ),
         |(u, v)| {
             if u.is_empty() {
                 v
+            } else if u.iter().filter(|m| **m).count() % 2 == 1 {
+                Transform::Unary(ArithmeticOperator::Subtract, Box::new(v))
             } else {
-                Transform::NotImplemented("unary_expr".to_string())
+                Transform::Unary(ArithmeticOperator::Add, Box::new(v))
             }
         },
     ))
 }
 
-// ValueExpr (SBox<dyneMapExpr) ::= PathExpr ('!' PathExpr)*
+// ValueExpr (SimpleMapExpr) ::= PathExpr ('!' PathExpr)*
 fn value_expr<'a, N: Node + 'a, L>() -> Box<
     dyn Fn(
             ParseInput<'a, N>,
@@ -178,13 +189,18 @@ where
     Box::new(map(
         pair(
             path_expr::<N, L>(),
-            many0(tuple2(tag("!"), path_expr::<N, L>())),
+            many0(tuple2(
+                tuple3(xpwhitespace(), tag("!"), xpwhitespace()),
+                path_expr::<N, L>(),
+            )),
         ),
         |(u, v)| {
             if v.is_empty() {
                 u
             } else {
-                Transform::NotImplemented("value_expr".to_string())
+                let mut s = vec![u];
+                v.into_iter().for_each(|(_, p)| s.push(p));
+                Transform::SimpleMap(s)
             }
         },
     ))
@@ -1,17 +1,19 @@
 //! Functions that manipulate type information
 
 use crate::item::Node;
-use crate::parser::combinators::map::map;
+use crate::parser::combinators::alt::{alt2, alt3, alt4};
+use crate::parser::combinators::map::{map, map_with_state_and_result};
 use crate::parser::combinators::opt::opt;
 use crate::parser::combinators::pair::pair;
 use crate::parser::combinators::tag::tag;
 use crate::parser::combinators::tuple::tuple6;
 use crate::parser::combinators::whitespace::xpwhitespace;
 use crate::parser::xpath::functions::arrow_expr;
-use crate::parser::xpath::nodetests::qualname_test;
+use crate::parser::xpath::nodetests::{kindtest, qualname_test};
 use crate::parser::{ParseError, ParseInput, StaticState};
-use crate::transform::Transform;
-use qualname::{NamespacePrefix, NamespaceUri};
+use crate::transform::types::{ItemType, Occurrence, SequenceType};
+use crate::transform::{NameTest, NodeTest, Transform};
+use qualname::{NamespacePrefix, NamespaceUri, QName};
 
 // InstanceOfExpr ::= TreatExpr ( 'instance' 'of' SequenceType)?
 pub(crate) fn instanceof_expr<'a, N: Node + 'a, L>() -> Box<
@@ -36,31 +38,75 @@ where
                 sequencetype_expr::<N, L>(),
             )),
         ),
-        |(v, o)| {
-            if o.is_none() {
-                v
-            } else {
-                Transform::NotImplemented("instanceof_expr".to_string())
-            }
+        |(v, o)| match o {
+            None => v,
+            Some((_, _, _, _, _, st)) => Transform::InstanceOf(Box::new(v), st),
         },
     ))
 }
 
 // SequenceType ::= ( 'empty-sequence' '(' ')' | (ItemType OccurrenceIndicator?)
-// TODO: implement this parser fully
-fn sequencetype_expr<'a, N: Node + 'a, L>() -> Box<
+// OccurrenceIndicator ::= '?' | '*' | '+'
+pub(crate) fn sequencetype_expr<'a, N: Node + 'a, L>() -> Box<
     dyn Fn(
             ParseInput<'a, N>,
             &mut StaticState<L>,
-        ) -> Result<(ParseInput<'a, N>, Transform<N>), ParseError>
+        ) -> Result<(ParseInput<'a, N>, SequenceType), ParseError>
         + 'a,
 >
 where
     L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
 {
-    Box::new(map(tag("empty-sequence()"), |_| {
-        Transform::NotImplemented("sequencetype_expr".to_string())
-    }))
+    Box::new(alt2(
+        map(tag("empty-sequence()"), |_| SequenceType::Empty),
+        map(
+            pair(
+                itemtype::<N, L>(),
+                opt(alt3(
+                    map(tag("?"), |_| Occurrence::Optional),
+                    map(tag("*"), |_| Occurrence::ZeroOrMore),
+                    map(tag("+"), |_| Occurrence::OneOrMore),
+                )),
+            ),
+            |(t, o)| SequenceType::Item(t, o.unwrap_or(Occurrence::One)),
+        ),
+    ))
+}
+
+// ItemType ::= KindTest | ('item' '(' ')') | FunctionTest | MapTest | ArrayTest | AtomicOrUnionType
+// TODO: typed function, map and array tests, parenthesized item types
+fn itemtype<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, ItemType), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(alt4(
+        map_with_state_and_result(kindtest::<N, L>(), |nt, _, _| match nt {
+            NodeTest::Kind(k) => Ok(ItemType::Kind(k)),
+            _ => Err(ParseError::Combinator(String::from("not a kind test"))),
+        }),
+        map(tag("item()"), |_| ItemType::Item),
+        alt3(
+            map(tag("function(*)"), |_| ItemType::Function),
+            map(tag("map(*)"), |_| ItemType::Map),
+            map(tag("array(*)"), |_| ItemType::Array),
+        ),
+        map_with_state_and_result(qualname_test::<N, L>(), |nt, _, _| {
+            type_name(nt).map(ItemType::Atomic)
+        }),
+    ))
+}
+
+fn type_name(nt: NodeTest) -> Result<QName, ParseError> {
+    match nt {
+        NodeTest::Name(NameTest::Name(qn)) => Ok(qn),
+        _ => Err(ParseError::Combinator(String::from("not a type name"))),
+    }
 }
 
 // TreatExpr ::= CastableExpr ( 'treat' 'as' SequenceType)?
@@ -86,12 +132,9 @@ where
                 sequencetype_expr::<N, L>(),
             )),
         ),
-        |(v, o)| {
-            if o.is_none() {
-                v
-            } else {
-                Transform::NotImplemented("treat_expr".to_string())
-            }
+        |(v, o)| match o {
+            None => v,
+            Some((_, _, _, _, _, st)) => Transform::Treat(Box::new(v), st),
         },
     ))
 }
@@ -119,12 +162,9 @@ where
                 singletype_expr::<N, L>(),
             )),
         ),
-        |(v, o)| {
-            if o.is_none() {
-                v
-            } else {
-                Transform::NotImplemented("castable_expr".to_string())
-            }
+        |(v, o)| match o {
+            None => v,
+            Some((_, _, _, _, _, (qn, e))) => Transform::Castable(Box::new(v), qn, e),
         },
     ))
 }
@@ -141,20 +181,20 @@ where
 // LocalPart ::= NCName
 // NCName ::= Name - (Char* ':' Char*)
 // Char ::= #x9 | #xA |#xD | [#x20-#xD7FF] | [#xE000-#xFFFD | [#x10000-#x10FFFF]
-// TODO: implement this parser fully
 fn singletype_expr<'a, N: Node + 'a, L>() -> Box<
     dyn Fn(
             ParseInput<'a, N>,
             &mut StaticState<L>,
-        ) -> Result<(ParseInput<'a, N>, Transform<N>), ParseError>
+        ) -> Result<(ParseInput<'a, N>, (QName, bool)), ParseError>
         + 'a,
 >
 where
     L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
 {
-    Box::new(map(pair(qualname_test(), tag("?")), |_| {
-        Transform::NotImplemented("singletype_expr".to_string())
-    }))
+    Box::new(map_with_state_and_result(
+        pair(qualname_test::<N, L>(), opt(tag("?"))),
+        |(nt, o), _, _| type_name(nt).map(|qn| (qn, o.is_some())),
+    ))
 }
 
 // CastExpr ::= ArrowExpr ( 'cast' 'as' SingleType)?
@@ -180,12 +220,9 @@ where
                 singletype_expr::<N, L>(),
             )),
         ),
-        |(v, o)| {
-            if o.is_none() {
-                v
-            } else {
-                Transform::NotImplemented("cast_expr".to_string())
-            }
+        |(v, o)| match o {
+            None => v,
+            Some((_, _, _, _, _, (qn, e))) => Transform::Cast(Box::new(v), qn, e),
         },
     ))
 }
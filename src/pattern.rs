@@ -170,6 +170,7 @@ fn branch_terminal_node_test(b: &Branch) -> (Axis, Axis, NodeTest) {
         Branch::SingleStep(t) => (t.terminal, t.non_terminal, t.nt.clone()),
         Branch::RelPath(r) => branch_terminal_node_test(&r[0]),
         Branch::Union(u) => branch_terminal_node_test(&u[0]), // TODO: should be all of the alternatives
+        Branch::Intersect(a, _) | Branch::Except(a, _) => branch_terminal_node_test(a),
         Branch::Error(_) => (
             Axis::SelfDocument,
             Axis::SelfDocument,
@@ -217,6 +218,23 @@ fn branch_match<N: Node>(p: &Path, s: Sequence<N>) -> Sequence<N> {
                 })
                 .collect()
         }
+        Branch::Intersect(a, b) => {
+            // An item matches if it matches both operands.
+            // The context continues from the left operand.
+            s.iter()
+                .cloned()
+                .filter(|i| path_match(b, i))
+                .flat_map(|i| branch_match(a, vec![i]))
+                .collect()
+        }
+        Branch::Except(a, b) => {
+            // An item matches if it matches the left operand but not the right.
+            s.iter()
+                .cloned()
+                .filter(|i| !path_match(b, i))
+                .flat_map(|i| branch_match(a, vec![i]))
+                .collect()
+        }
         Branch::Error(_) => vec![],
     }
 }
@@ -288,6 +306,16 @@ fn is_match<N: Node>(a: &Axis, nt: &NodeTest, i: &Item<N>) -> bool {
             }
             _ => false,
         },
+        Axis::SelfNamespace => match i {
+            Item::Node(n) => {
+                if n.node_type() == NodeType::Namespace {
+                    nt.matches_namespace(n)
+                } else {
+                    false
+                }
+            }
+            _ => false,
+        },
         _ => false, // todo
     }
 }
@@ -308,6 +336,8 @@ impl<N: Node> Debug for Pattern<N> {
 // A Path is a Branch.
 // A Branch::Union is caused by a union ("|") operator -
 // if any of the union branches match then the Path matches.
+// A Branch::Intersect matches if both operands match,
+// a Branch::Except matches if the first operand matches but not the second.
 // If the vector is empty then there is no match.
 // A Rel(ative)Path is caused by the "/" character.
 // The terminal case is a single Step.
@@ -317,6 +347,8 @@ pub enum Branch {
     SingleStep(Step),
     RelPath(Vec<Branch>),
     Union(Vec<Branch>),
+    Intersect(Box<Branch>, Box<Branch>),
+    Except(Box<Branch>, Box<Branch>),
     Error(Error),
 }
 
@@ -331,6 +363,7 @@ impl Branch {
             Branch::SingleStep(_) => false,
             Branch::RelPath(r) => r.iter().any(|f| f.is_err()),
             Branch::Union(u) => u.iter().any(|f| f.is_err()),
+            Branch::Intersect(a, b) | Branch::Except(a, b) => a.is_err() || b.is_err(),
         }
     }
     /// Get any error in the Branch
@@ -340,6 +373,7 @@ impl Branch {
             Branch::SingleStep(_) => None,
             Branch::RelPath(r) => r.iter().fold(None, |v, f| v.or_else(|| f.get_err())),
             Branch::Union(u) => u.iter().fold(None, |v, f| v.or_else(|| f.get_err())),
+            Branch::Intersect(a, b) | Branch::Except(a, b) => a.get_err().or_else(|| b.get_err()),
         }
     }
 }
@@ -608,7 +642,7 @@ where
 }
 
 // IntersectExceptExprP ::= PathExprP (("intersect" | "except") PathExprP)*
-// intersect and except not yet supported
+// The operators are left-associative.
 fn intersect_except_expr_pattern<'a, N: Node + 'a, L>() -> Box<
     dyn Fn(ParseInput<'a, N>, &mut StaticState<L>) -> Result<(ParseInput<'a, N>, Path), ParseError>
         + 'a,
@@ -617,27 +651,29 @@ where
     L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
 {
     Box::new(map(
-        separated_list1(
-            map(
+        pair(
+            path_expr_pattern::<N, L>(),
+            many0(tuple2(
                 tuple3(
                     xpwhitespace(),
-                    alt2(tag("intersect"), tag("except")),
+                    alt2(
+                        map(tag("intersect"), |_| true),
+                        map(tag("except"), |_| false),
+                    ),
                     xpwhitespace(),
                 ),
-                |_| (),
-            ),
-            path_expr_pattern::<N, L>(),
+                path_expr_pattern::<N, L>(),
+            )),
         ),
-        |mut v| {
-            if v.len() == 1 {
-                v.pop().unwrap()
-            } else {
-                // intersect/except not implemented
-                Branch::Error(Error::new(
-                    ErrorKind::NotImplemented,
-                    String::from("intersect or except in a pattern has not been implemented"),
-                ))
-            }
+        |(first, rest)| {
+            rest.into_iter()
+                .fold(first, |acc, ((_, is_intersect, _), b)| {
+                    if is_intersect {
+                        Branch::Intersect(Box::new(acc), Box::new(b))
+                    } else {
+                        Branch::Except(Box::new(acc), Box::new(b))
+                    }
+                })
         },
     ))
 }
@@ -0,0 +1,363 @@
+//! Arrays, and the functions in the array namespace.
+
+use num_bigint::Sign;
+use rust_decimal::prelude::ToPrimitive;
+use std::rc::Rc;
+use url::Url;
+
+use crate::item::{Array, Item, Node, Sequence};
+use crate::transform::Transform;
+use crate::transform::context::{Context, StaticContext};
+use crate::transform::maps::{atomize, atomize_one};
+use crate::transform::types::error;
+use crate::value::{Value, ValueData};
+use crate::xdmerror::{Error, ErrorKind};
+
+/// The namespace for the array functions.
+pub(crate) const ARRAYNS: &str = "http://www.w3.org/2005/xpath-functions/array";
+
+// The single array in a sequence.
+pub(crate) fn array_of<N: Node>(s: Sequence<N>) -> Result<Rc<Array<N>>, Error> {
+    match s.as_slice() {
+        [Item::Array(a)] => Ok(a.clone()),
+        _ => Err(error(
+            ErrorKind::TypeError,
+            "XPTY0004",
+            String::from("expected a single array"),
+        )),
+    }
+}
+
+// The value of an integer. Any other type of value is a type error.
+// An integer beyond the 64-bit range is clamped, so that it fails the caller's bounds checks.
+pub(crate) fn integer(v: &Value) -> Result<i64, Error> {
+    match &v.value {
+        ValueData::Integer(i) => Ok(i.to_i64().unwrap_or(if i.sign() == Sign::Minus {
+            i64::MIN
+        } else {
+            i64::MAX
+        })),
+        ValueData::Long(i) => Ok(*i),
+        ValueData::Int(i) => Ok(i64::from(*i)),
+        ValueData::Short(i) => Ok(i64::from(*i)),
+        ValueData::Byte(i) => Ok(i64::from(*i)),
+        ValueData::UnsignedInt(i) => Ok(i64::from(*i)),
+        ValueData::UnsignedShort(i) => Ok(i64::from(*i)),
+        ValueData::UnsignedByte(i) => Ok(i64::from(*i)),
+        _ => Err(error(
+            ErrorKind::TypeError,
+            "XPTY0004",
+            format!("expected an integer, got a {}", v.value_type()),
+        )),
+    }
+}
+
+/// Convert a 1-based position in an array to an index.
+/// The position must be an integer that is within the bounds of the array.
+pub(crate) fn position(k: &Value, size: usize) -> Result<usize, Error> {
+    let i = integer(k)?;
+    if i < 1 || i as usize > size {
+        Err(out_of_bounds(i))
+    } else {
+        Ok(i as usize - 1)
+    }
+}
+
+fn out_of_bounds(i: i64) -> Error {
+    error(
+        ErrorKind::Unknown,
+        "FOAY0001",
+        format!("array index {} is out of bounds", i),
+    )
+}
+
+/// Construct an array with the square array constructor. Each transformation produces a member of the array.
+pub(crate) fn array_constructor<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    v: &[Transform<N>],
+) -> Result<Sequence<N>, Error> {
+    let a = v
+        .iter()
+        .map(|t| ctxt.dispatch(stctxt, t))
+        .collect::<Result<Array<N>, Error>>()?;
+    Ok(vec![Item::Array(Rc::new(a))])
+}
+
+/// Construct an array with the curly array constructor. Each item in the sequence is a member of the array.
+pub(crate) fn curly_array_constructor<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    t: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let a = ctxt
+        .dispatch(stctxt, t)?
+        .into_iter()
+        .map(|i| vec![i])
+        .collect();
+    Ok(vec![Item::Array(Rc::new(a))])
+}
+
+/// array:size()
+pub(crate) fn size<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    a: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let a = array_of(ctxt.dispatch(stctxt, a)?)?;
+    Ok(vec![Item::Value(Rc::new(Value::from(a.len() as i64)))])
+}
+
+/// array:get()
+pub(crate) fn get<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    a: &Transform<N>,
+    p: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let a = array_of(ctxt.dispatch(stctxt, a)?)?;
+    let p = atomize_one(&ctxt.dispatch(stctxt, p)?)?;
+    Ok(a[position(&p, a.len())?].clone())
+}
+
+/// array:put()
+pub(crate) fn put<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    a: &Transform<N>,
+    p: &Transform<N>,
+    m: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let mut a = (*array_of(ctxt.dispatch(stctxt, a)?)?).clone();
+    let p = atomize_one(&ctxt.dispatch(stctxt, p)?)?;
+    let i = position(&p, a.len())?;
+    a[i] = ctxt.dispatch(stctxt, m)?;
+    Ok(vec![Item::Array(Rc::new(a))])
+}
+
+/// array:append()
+pub(crate) fn append<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    a: &Transform<N>,
+    m: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let mut a = (*array_of(ctxt.dispatch(stctxt, a)?)?).clone();
+    a.push(ctxt.dispatch(stctxt, m)?);
+    Ok(vec![Item::Array(Rc::new(a))])
+}
+
+/// array:subarray(). The arguments are the array, the start position, and optionally the number of members.
+pub(crate) fn subarray<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    a: &Transform<N>,
+    s: &Transform<N>,
+    l: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let a = array_of(ctxt.dispatch(stctxt, a)?)?;
+    let start = integer(&atomize_one(&ctxt.dispatch(stctxt, s)?)?)?;
+    if start < 1 || start as usize > a.len() + 1 {
+        return Err(out_of_bounds(start));
+    }
+    let length = match l {
+        Some(t) => integer(&atomize_one(&ctxt.dispatch(stctxt, t)?)?)?,
+        None => (a.len() + 1) as i64 - start,
+    };
+    if length < 0 {
+        return Err(error(
+            ErrorKind::Unknown,
+            "FOAY0002",
+            format!("negative array length {}", length),
+        ));
+    }
+    let begin = start as usize - 1;
+    let end = begin + length as usize;
+    if end > a.len() {
+        return Err(out_of_bounds(start + length - 1));
+    }
+    Ok(vec![Item::Array(Rc::new(a[begin..end].to_vec()))])
+}
+
+/// array:remove(). The second argument is a sequence of positions to remove.
+pub(crate) fn remove<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    a: &Transform<N>,
+    p: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let a = array_of(ctxt.dispatch(stctxt, a)?)?;
+    let positions = atomize(&ctxt.dispatch(stctxt, p)?)?
+        .iter()
+        .map(|p| position(p, a.len()))
+        .collect::<Result<Vec<usize>, Error>>()?;
+    let result = a
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !positions.contains(i))
+        .map(|(_, m)| m.clone())
+        .collect();
+    Ok(vec![Item::Array(Rc::new(result))])
+}
+
+/// array:insert-before()
+pub(crate) fn insert_before<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    a: &Transform<N>,
+    p: &Transform<N>,
+    m: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let mut a = (*array_of(ctxt.dispatch(stctxt, a)?)?).clone();
+    // The position may be one past the end of the array, in which case the member is appended
+    let i = position(&atomize_one(&ctxt.dispatch(stctxt, p)?)?, a.len() + 1)?;
+    a.insert(i, ctxt.dispatch(stctxt, m)?);
+    Ok(vec![Item::Array(Rc::new(a))])
+}
+
+/// array:head()
+pub(crate) fn head<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    a: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let a = array_of(ctxt.dispatch(stctxt, a)?)?;
+    a.first().cloned().ok_or_else(|| out_of_bounds(1))
+}
+
+/// array:tail()
+pub(crate) fn tail<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    a: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let a = array_of(ctxt.dispatch(stctxt, a)?)?;
+    match a.split_first() {
+        Some((_, rest)) => Ok(vec![Item::Array(Rc::new(rest.to_vec()))]),
+        None => Err(out_of_bounds(1)),
+    }
+}
+
+/// array:reverse()
+pub(crate) fn reverse<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    a: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let a = array_of(ctxt.dispatch(stctxt, a)?)?;
+    Ok(vec![Item::Array(Rc::new(
+        a.iter().rev().cloned().collect(),
+    ))])
+}
+
+/// array:join(). Concatenates the members of a sequence of arrays.
+pub(crate) fn join<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    a: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let mut result = vec![];
+    for i in ctxt.dispatch(stctxt, a)? {
+        match i {
+            Item::Array(b) => result.extend(b.iter().cloned()),
+            _ => {
+                return Err(error(
+                    ErrorKind::TypeError,
+                    "XPTY0004",
+                    String::from("array:join requires a sequence of arrays"),
+                ));
+            }
+        }
+    }
+    Ok(vec![Item::Array(Rc::new(result))])
+}
+
+/// array:flatten(). Replaces any arrays in the sequence with their members, recursively.
+pub(crate) fn flatten<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let mut result = vec![];
+    flatten_sequence(ctxt.dispatch(stctxt, s)?, &mut result);
+    Ok(result)
+}
+
+fn flatten_sequence<N: Node>(s: Sequence<N>, result: &mut Sequence<N>) {
+    for i in s {
+        match i {
+            Item::Array(a) => a.iter().for_each(|m| flatten_sequence(m.clone(), result)),
+            _ => result.push(i),
+        }
+    }
+}
@@ -0,0 +1,259 @@
+//! Binary data, and the functions in the EXPath binary module.
+
+use std::rc::Rc;
+use url::Url;
+
+use crate::item::{Item, Node, Sequence, SequenceTrait};
+use crate::transform::Transform;
+use crate::transform::arrays::integer;
+use crate::transform::context::{Context, StaticContext};
+use crate::transform::maps::{atomize, atomize_one};
+use crate::transform::types::error;
+use crate::validators::datatypes;
+use crate::value::{Value, ValueBuilder, ValueData};
+use crate::xdmerror::{Error, ErrorKind};
+
+/// The namespace for the EXPath binary functions.
+pub(crate) const BINNS: &str = "http://expath.org/ns/binary";
+
+// The octets of a binary argument, or None if the argument is the empty sequence.
+// Strings, including the string value of a node, are cast to xs:base64Binary.
+fn binary_of<N: Node>(s: &Sequence<N>) -> Result<Option<Vec<u8>>, Error> {
+    let mut v = atomize(s)?;
+    if v.len() > 1 {
+        return Err(error(
+            ErrorKind::TypeError,
+            "XPTY0004",
+            format!("expected a single binary value, got {} values", v.len()),
+        ));
+    }
+    let Some(v) = v.pop() else {
+        return Ok(None);
+    };
+    match &v.value {
+        ValueData::Base64Binary(b) | ValueData::HexBinary(b) => Ok(Some(b.clone())),
+        ValueData::String(s) => match datatypes::parse("base64Binary", s) {
+            Ok(ValueData::Base64Binary(b)) => Ok(Some(b)),
+            _ => Err(error(
+                ErrorKind::TypeError,
+                "FORG0001",
+                format!("cannot cast \"{}\" to xs:base64Binary", s),
+            )),
+        },
+        _ => Err(error(
+            ErrorKind::TypeError,
+            "XPTY0004",
+            format!("expected a binary value, got a {}", v.value_type()),
+        )),
+    }
+}
+
+// An optional integer argument. The empty sequence is the same as no argument.
+fn optional_integer<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    t: &Option<Box<Transform<N>>>,
+) -> Result<Option<i64>, Error> {
+    match t {
+        Some(t) => {
+            let s = ctxt.dispatch(stctxt, t)?;
+            if s.is_empty() {
+                Ok(None)
+            } else {
+                integer(&atomize_one(&s)?).map(Some)
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+// The range of octets selected by an offset and optional size.
+fn range(len: usize, offset: i64, size: Option<i64>) -> Result<(usize, usize), Error> {
+    if let Some(n) = size
+        && n < 0
+    {
+        return Err(error(
+            ErrorKind::Unknown,
+            "negative-size",
+            format!("size {} is negative", n),
+        ));
+    }
+    let end = size.map_or(len as i64, |n| offset.saturating_add(n));
+    if offset < 0 || offset as usize > len || end as usize > len {
+        return Err(error(
+            ErrorKind::Unknown,
+            "index-out-of-range",
+            format!(
+                "offset {} and size {} are outside the binary data of length {}",
+                offset,
+                end - offset,
+                len
+            ),
+        ));
+    }
+    Ok((offset as usize, end as usize))
+}
+
+/// bin:length(). The number of octets in the binary data.
+pub(crate) fn length<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    b: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    Ok(binary_of(&ctxt.dispatch(stctxt, b)?)?
+        .map(|b| vec![Item::Value(Rc::new(Value::from(b.len() as i64)))])
+        .unwrap_or_default())
+}
+
+/// bin:part(). The octets starting at the given (zero-based) offset, to the end of the data or for the given size.
+pub(crate) fn part<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    b: &Transform<N>,
+    o: &Transform<N>,
+    s: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let Some(b) = binary_of(&ctxt.dispatch(stctxt, b)?)? else {
+        return Ok(vec![]);
+    };
+    let offset = integer(&atomize_one(&ctxt.dispatch(stctxt, o)?)?)?;
+    let (start, end) = range(b.len(), offset, optional_integer(ctxt, stctxt, s)?)?;
+    Ok(vec![Item::Value(Rc::new(
+        ValueBuilder::new()
+            .value(ValueData::Base64Binary(b[start..end].to_vec()))
+            .build(),
+    ))])
+}
+
+/// bin:decode-string(). Decode binary data as a string in the given encoding, which defaults to UTF-8.
+/// The supported encodings are UTF-8, US-ASCII and ISO-8859-1.
+pub(crate) fn decode_string<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    b: &Transform<N>,
+    e: &Option<Box<Transform<N>>>,
+    o: &Option<Box<Transform<N>>>,
+    s: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let Some(b) = binary_of(&ctxt.dispatch(stctxt, b)?)? else {
+        return Ok(vec![]);
+    };
+    let encoding = match e {
+        Some(t) => ctxt.dispatch(stctxt, t)?.to_string(),
+        None => String::new(),
+    };
+    let offset = optional_integer(ctxt, stctxt, o)?.unwrap_or(0);
+    let (start, end) = range(b.len(), offset, optional_integer(ctxt, stctxt, s)?)?;
+    let octets = &b[start..end];
+    let conversion = || {
+        error(
+            ErrorKind::Unknown,
+            "conversion-error",
+            format!("binary data is not valid {}", encoding),
+        )
+    };
+    let result = match encoding.to_ascii_lowercase().as_str() {
+        "" | "utf-8" | "utf8" => String::from_utf8(octets.to_vec()).map_err(|_| conversion())?,
+        "us-ascii" | "ascii" => {
+            if !octets.is_ascii() {
+                return Err(conversion());
+            }
+            octets.iter().map(|o| char::from(*o)).collect()
+        }
+        "iso-8859-1" | "latin1" => octets.iter().map(|o| char::from(*o)).collect(),
+        _ => {
+            return Err(error(
+                ErrorKind::Unknown,
+                "unknown-encoding",
+                format!("encoding \"{}\" is not supported", encoding),
+            ));
+        }
+    };
+    Ok(vec![Item::Value(Rc::new(Value::from(result)))])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trees::smite::RNode;
+
+    fn code(e: Error) -> String {
+        e.code
+            .map_or_else(String::new, |c| c.local_name().to_string())
+    }
+
+    #[test]
+    fn part_range() {
+        assert_eq!(range(5, 0, None).ok(), Some((0, 5)));
+        assert_eq!(range(5, 1, Some(3)).ok(), Some((1, 4)));
+        assert_eq!(range(5, 5, None).ok(), Some((5, 5)));
+        assert_eq!(range(5, 5, Some(0)).ok(), Some((5, 5)));
+        assert_eq!(range(0, 0, None).ok(), Some((0, 0)));
+        assert_eq!(
+            range(5, 6, None).map_err(code),
+            Err(String::from("index-out-of-range"))
+        );
+        assert_eq!(
+            range(5, 4, Some(2)).map_err(code),
+            Err(String::from("index-out-of-range"))
+        );
+        assert_eq!(
+            range(5, -1, Some(1)).map_err(code),
+            Err(String::from("index-out-of-range"))
+        );
+        assert_eq!(
+            range(5, i64::MAX, Some(i64::MAX)).map_err(code),
+            Err(String::from("index-out-of-range"))
+        );
+        assert_eq!(
+            range(5, 0, Some(-1)).map_err(code),
+            Err(String::from("negative-size"))
+        );
+    }
+
+    #[test]
+    fn binary_arguments() {
+        let arg = |v: Value| -> Sequence<RNode> { vec![Item::Value(Rc::new(v))] };
+        assert_eq!(
+            binary_of(&arg(Value::from("SGVsbG8="))).ok(),
+            Some(Some(b"Hello".to_vec()))
+        );
+        assert_eq!(binary_of::<RNode>(&vec![]).ok(), Some(None));
+        for s in ["SGVsbG8", "SGVsbG8==", "SGVs*G8=", "48656c6c6f!"] {
+            assert_eq!(
+                binary_of(&arg(Value::from(s))).map_err(code),
+                Err(String::from("FORG0001")),
+                "{}",
+                s
+            );
+        }
+        assert_eq!(
+            binary_of(&arg(Value::from(1))).map_err(code),
+            Err(String::from("XPTY0004"))
+        );
+        assert_eq!(
+            binary_of(&[arg(Value::from("AA==")), arg(Value::from("AA=="))].concat()).map_err(code),
+            Err(String::from("XPTY0004"))
+        );
+    }
+}
@@ -5,12 +5,16 @@
 
 // TODO: tunneling parameters
 
-use crate::item::Node;
-use crate::transform::context::StaticContext;
+use crate::item::{Function, Node};
+use crate::transform::arrays::position;
+use crate::transform::context::{ContextBuilder, StaticContext};
+use crate::transform::maps::atomize_one;
+use crate::transform::types::SequenceType;
 use crate::transform::{NamespaceMap, Transform};
-use crate::{Context, Error, ErrorKind, Sequence};
-use qualname::QName;
+use crate::{Context, Error, ErrorKind, Item, Sequence};
+use qualname::{NcName, QName};
 use std::collections::HashMap;
+use std::rc::Rc;
 use url::Url;
 
 #[derive(Clone, Debug)]
@@ -38,6 +42,156 @@ pub enum ActualParameters<N: Node> {
     Positional(Vec<Transform<N>>),
 }
 
+/// Create a function item. An anonymous function captures the variables that are currently in scope.
+pub(crate) fn function_item<N: Node>(
+    ctxt: &Context<N>,
+    name: &Option<QName>,
+    parameters: &[(String, Option<SequenceType>)],
+    body: &Rc<Transform<N>>,
+) -> Result<Sequence<N>, Error> {
+    let closure = if name.is_none() {
+        ctxt.vars.clone()
+    } else {
+        HashMap::new()
+    };
+    Ok(vec![Item::Function(Rc::new(Function::new(
+        name.clone(),
+        parameters.to_vec(),
+        body.clone(),
+        closure,
+    )))])
+}
+
+/// Call a function item. The first transform produces the function item, the remainder are the actual arguments.
+/// Maps and arrays are also functions, with a single argument that is the key or the position of the member.
+pub(crate) fn dynamic_call<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    f: &Transform<N>,
+    a: &[Transform<N>],
+) -> Result<Sequence<N>, Error> {
+    let fi = ctxt.dispatch(stctxt, f)?;
+    let args = a
+        .iter()
+        .map(|t| ctxt.dispatch(stctxt, t))
+        .collect::<Result<Vec<Sequence<N>>, Error>>()?;
+    match fi.as_slice() {
+        [Item::Function(func)] => call_function(ctxt, stctxt, func, args),
+        [Item::Map(m)] if args.len() == 1 => {
+            Ok(m.get(&atomize_one(&args[0])?).cloned().unwrap_or_default())
+        }
+        [Item::Array(v)] if args.len() == 1 => {
+            Ok(v[position(&atomize_one(&args[0])?, v.len())?].clone())
+        }
+        _ => Err(type_error(
+            "the target of a dynamic function call must be a single function item",
+        )),
+    }
+}
+
+/// Call a function item with the given arguments.
+pub(crate) fn call_function<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    f: &Function<N>,
+    args: Vec<Sequence<N>>,
+) -> Result<Sequence<N>, Error> {
+    if args.len() != f.arity() {
+        return Err(type_error(format!(
+            "function expects {} arguments, but {} were supplied",
+            f.arity(),
+            args.len()
+        )));
+    }
+    // The focus is absent within the body of the function
+    let mut newctxt = ContextBuilder::from(ctxt)
+        .context(vec![])
+        .context_item(None)
+        .build();
+    f.closure.iter().for_each(|(name, v)| {
+        if let Some(s) = v.last() {
+            newctxt.var_push(name.clone(), s.clone())
+        }
+    });
+    // TODO: function conversion rules, i.e. atomization and type promotion of arguments
+    for ((name, t), arg) in f.parameters.iter().zip(args) {
+        if let Some(st) = t
+            && !st.matches(&arg)?
+        {
+            return Err(type_error(format!(
+                "argument for parameter ${} does not match {}",
+                name, st
+            )));
+        }
+        newctxt.var_push(name.clone(), arg)
+    }
+    newctxt.dispatch(stctxt, &f.body)
+}
+
+/// Partially apply a function. Arguments that are None are placeholders, and become the parameters of the new function.
+pub(crate) fn partial_apply<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    f: &Transform<N>,
+    a: &[Option<Transform<N>>],
+) -> Result<Sequence<N>, Error> {
+    let fi = ctxt.dispatch(stctxt, f)?;
+    let [Item::Function(func)] = fi.as_slice() else {
+        return Err(type_error(
+            "only a single function item can be partially applied",
+        ));
+    };
+    if a.len() != func.arity() {
+        return Err(type_error(format!(
+            "function expects {} arguments, but {} were supplied",
+            func.arity(),
+            a.len()
+        )));
+    }
+    let mut closure = func.closure.clone();
+    let mut parameters = vec![];
+    for (p, t) in func.parameters.iter().zip(a) {
+        match t {
+            Some(u) => {
+                let arg = ctxt.dispatch(stctxt, u)?;
+                closure.entry(p.0.clone()).or_default().push(arg);
+            }
+            None => parameters.push(p.clone()),
+        }
+    }
+    Ok(vec![Item::Function(Rc::new(Function::new(
+        None,
+        parameters,
+        func.body.clone(),
+        closure,
+    )))])
+}
+
+fn type_error(msg: impl Into<String>) -> Error {
+    Error::new_with_code(
+        ErrorKind::TypeError,
+        msg,
+        Some(QName::from_local_name(
+            NcName::try_from("XPTY0004").unwrap(),
+        )),
+    )
+}
+
 /// Invoke a callable component
 pub(crate) fn invoke<
     N: Node,
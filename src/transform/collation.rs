@@ -0,0 +1,283 @@
+/*! Collations, used to compare and sort strings.
+
+A collation is identified by a URI. The [StaticContext] holds a registry of collations, which always includes:
+
+* the Unicode codepoint collation, <http://www.w3.org/2005/xpath-functions/collation/codepoint>. This is the default collation.
+* the HTML ASCII case-insensitive collation, <http://www.w3.org/2005/xpath-functions/collation/html-ascii-case-insensitive>.
+* the Unicode Collation Algorithm family of collations, <http://www.w3.org/2013/collation/UCA>. Parameters are given in the query part of the URI, separated by semicolons, for example `http://www.w3.org/2013/collation/UCA?lang=de;strength=secondary`. Language-specific tailorings are taken from the CLDR.
+
+Further collations may be registered using [StaticContextBuilder::collation](crate::transform::context::StaticContextBuilder::collation).
+*/
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use icu_collator::{
+    AlternateHandling, BackwardSecondLevel, CaseFirst, CaseLevel, Collator, CollatorOptions,
+    MaxVariable, Numeric, Strength,
+};
+use icu_provider::DataLocale;
+use url::Url;
+
+use crate::item::{Node, Sequence, SequenceTrait};
+use crate::transform::Transform;
+use crate::transform::context::{Context, StaticContext};
+use crate::transform::types::error;
+use crate::xdmerror::{Error, ErrorKind};
+
+/// The Unicode codepoint collation.
+pub const CODEPOINT: &str = "http://www.w3.org/2005/xpath-functions/collation/codepoint";
+/// The HTML ASCII case-insensitive collation.
+pub const HTML_ASCII_CASE_INSENSITIVE: &str =
+    "http://www.w3.org/2005/xpath-functions/collation/html-ascii-case-insensitive";
+/// The base URI of the Unicode Collation Algorithm collations.
+pub const UCA: &str = "http://www.w3.org/2013/collation/UCA";
+
+/// A collation.
+#[derive(Clone)]
+pub enum Collation {
+    /// Strings are compared codepoint by codepoint.
+    Codepoint,
+    /// Strings are compared codepoint by codepoint, after folding ASCII letters to lower case.
+    HtmlAsciiCaseInsensitive,
+    /// Strings are compared using the Unicode Collation Algorithm.
+    Uca(Rc<Collator>),
+    /// An application-defined collation.
+    Custom(Rc<dyn Fn(&str, &str) -> Ordering>),
+}
+
+impl Collation {
+    /// Compare two strings.
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        match self {
+            Collation::Codepoint => a.cmp(b),
+            Collation::HtmlAsciiCaseInsensitive => a
+                .chars()
+                .map(|c| c.to_ascii_lowercase())
+                .cmp(b.chars().map(|c| c.to_ascii_lowercase())),
+            Collation::Uca(c) => c.compare(a, b),
+            Collation::Custom(f) => f(a, b),
+        }
+    }
+    /// Are two strings equal according to this collation?
+    pub fn equal(&self, a: &str, b: &str) -> bool {
+        self.compare(a, b) == Ordering::Equal
+    }
+    /// Is this the codepoint collation?
+    pub fn is_codepoint(&self) -> bool {
+        matches!(self, Collation::Codepoint)
+    }
+}
+
+/// The collations known to a transformation. UCA collations are created when they are first used.
+#[derive(Clone, Default)]
+pub struct Collations(HashMap<String, Collation>);
+
+impl Collations {
+    pub fn new() -> Self {
+        Collations(HashMap::new())
+    }
+    /// Register a collation for a URI. This replaces any collation previously registered for that URI.
+    pub fn register(&mut self, uri: &str, c: Collation) {
+        self.0.insert(uri.to_string(), c);
+    }
+    /// Find the collation for a URI. Returns error FOCH0002 if the collation is not supported.
+    pub fn get(&mut self, uri: &str) -> Result<Collation, Error> {
+        if let Some(c) = self.0.get(uri) {
+            return Ok(c.clone());
+        }
+        let c = match uri {
+            CODEPOINT => Collation::Codepoint,
+            HTML_ASCII_CASE_INSENSITIVE => Collation::HtmlAsciiCaseInsensitive,
+            _ => match uri.strip_prefix(UCA) {
+                Some("") => uca(uri, "")?,
+                Some(q) if q.starts_with('?') => uca(uri, &q[1..])?,
+                _ => return Err(unsupported(uri)),
+            },
+        };
+        self.0.insert(uri.to_string(), c.clone());
+        Ok(c)
+    }
+}
+
+// Create a UCA collation from the parameters in the query part of its URI.
+// Parameters that are not recognised, or that have an unsupported value, are ignored unless fallback=no.
+fn uca(uri: &str, query: &str) -> Result<Collation, Error> {
+    let params: Vec<(&str, &str)> = query
+        .split(';')
+        .filter(|p| !p.is_empty())
+        .map(|p| p.split_once('=').unwrap_or((p, "")))
+        .collect();
+    let fallback = !params.contains(&("fallback", "no"));
+    let mut locale = DataLocale::default();
+    let mut options = CollatorOptions::new();
+    for (k, v) in &params {
+        let ok = match *k {
+            "fallback" => matches!(*v, "yes" | "no"),
+            "lang" => v.parse().map(|l| locale = l).is_ok(),
+            "strength" => set_option(&mut options.strength, v, |v| match v {
+                "primary" | "1" => Some(Strength::Primary),
+                "secondary" | "2" => Some(Strength::Secondary),
+                "tertiary" | "3" => Some(Strength::Tertiary),
+                "quaternary" | "4" => Some(Strength::Quaternary),
+                "identical" | "5" => Some(Strength::Identical),
+                _ => None,
+            }),
+            "alternate" => set_option(&mut options.alternate_handling, v, |v| match v {
+                "non-ignorable" => Some(AlternateHandling::NonIgnorable),
+                "shifted" => Some(AlternateHandling::Shifted),
+                _ => None,
+            }),
+            "maxVariable" => set_option(&mut options.max_variable, v, |v| match v {
+                "space" => Some(MaxVariable::Space),
+                "punct" => Some(MaxVariable::Punctuation),
+                "symbol" => Some(MaxVariable::Symbol),
+                "currency" => Some(MaxVariable::Currency),
+                _ => None,
+            }),
+            "caseFirst" => set_option(&mut options.case_first, v, |v| match v {
+                "upper" => Some(CaseFirst::UpperFirst),
+                "lower" => Some(CaseFirst::LowerFirst),
+                "off" => Some(CaseFirst::Off),
+                _ => None,
+            }),
+            "caseLevel" => set_option(&mut options.case_level, v, |v| match v {
+                "yes" => Some(CaseLevel::On),
+                "no" => Some(CaseLevel::Off),
+                _ => None,
+            }),
+            "numeric" => set_option(&mut options.numeric, v, |v| match v {
+                "yes" => Some(Numeric::On),
+                "no" => Some(Numeric::Off),
+                _ => None,
+            }),
+            "backwards" => set_option(&mut options.backward_second_level, v, |v| match v {
+                "yes" => Some(BackwardSecondLevel::On),
+                "no" => Some(BackwardSecondLevel::Off),
+                _ => None,
+            }),
+            // The collator always normalizes its input
+            "normalization" => matches!(*v, "yes" | "no"),
+            _ => false,
+        };
+        if !ok && !fallback {
+            return Err(unsupported(uri));
+        }
+    }
+    Collator::try_new(&locale, options)
+        .map(|c| Collation::Uca(Rc::new(c)))
+        .map_err(|_| unsupported(uri))
+}
+
+// Set an option if the value is recognised.
+fn set_option<T>(opt: &mut Option<T>, v: &str, f: impl Fn(&str) -> Option<T>) -> bool {
+    f(v).map(|o| *opt = Some(o)).is_some()
+}
+
+fn unsupported(uri: &str) -> Error {
+    error(
+        ErrorKind::Unknown,
+        "FOCH0002",
+        format!("collation \"{}\" is not supported", uri),
+    )
+}
+
+/// Evaluate an optional collation argument and find the collation.
+/// If there is no argument, or it is the empty sequence, then the codepoint collation is used.
+pub(crate) fn collation_argument<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    c: &Option<Box<Transform<N>>>,
+) -> Result<Collation, Error> {
+    match c {
+        Some(t) => {
+            let uri: Sequence<N> = ctxt.dispatch(stctxt, t)?;
+            if uri.is_empty() {
+                Ok(Collation::Codepoint)
+            } else {
+                stctxt.collations.get(&uri.to_string())
+            }
+        }
+        None => Ok(Collation::Codepoint),
+    }
+}
+
+/// The URI of the collation for a static collation or language specification, such as the collation and lang attributes of xsl:sort.
+/// A language with no collation selects the UCA collation for that language.
+pub(crate) fn collation_uri(collation: &str, lang: &str) -> Option<String> {
+    if !collation.is_empty() {
+        Some(collation.to_string())
+    } else if !lang.is_empty() {
+        Some(format!("{}?lang={}", UCA, lang))
+    } else {
+        None
+    }
+}
+
+/// Find the collation for an optional URI. If there is no URI then the codepoint collation is used.
+pub(crate) fn find_collation<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    stctxt: &mut StaticContext<N, F, G, H>,
+    uri: &Option<String>,
+) -> Result<Collation, Error> {
+    match uri {
+        Some(u) => stctxt.collations.get(u),
+        None => Ok(Collation::Codepoint),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uca_german() {
+        let c = Collations::new()
+            .get("http://www.w3.org/2013/collation/UCA?lang=de;strength=secondary")
+            .expect("unable to create collation");
+        assert_eq!(c.compare("Äpfel", "Birnen"), Ordering::Less);
+        assert_eq!(c.compare("apfel", "Apfel"), Ordering::Equal);
+        assert_eq!(c.compare("apfel", "äpfel"), Ordering::Less);
+    }
+
+    #[test]
+    fn uca_swedish() {
+        let c = Collations::new()
+            .get("http://www.w3.org/2013/collation/UCA?lang=sv")
+            .expect("unable to create collation");
+        assert_eq!(c.compare("öl", "zebra"), Ordering::Greater);
+        let d = Collations::new()
+            .get("http://www.w3.org/2013/collation/UCA?lang=de")
+            .expect("unable to create collation");
+        assert_eq!(d.compare("öl", "zebra"), Ordering::Less);
+    }
+
+    #[test]
+    fn uca_fallback() {
+        assert!(
+            Collations::new()
+                .get("http://www.w3.org/2013/collation/UCA?lang=de;colour=blue")
+                .is_ok()
+        );
+        assert!(
+            Collations::new()
+                .get("http://www.w3.org/2013/collation/UCA?fallback=no;colour=blue")
+                .is_err()
+        );
+        assert!(
+            Collations::new()
+                .get("http://example.org/collation")
+                .is_err()
+        );
+    }
+}
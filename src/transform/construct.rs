@@ -114,6 +114,11 @@ pub(crate) fn element<
         match i {
             Item::Node(t) => match t.node_type() {
                 NodeType::Attribute => e.add_attribute(t.clone()),
+                NodeType::Namespace => e.add_namespace(r.new_namespace(
+                    t.as_namespace_uri()?.clone(),
+                    t.as_namespace_prefix()?.cloned(),
+                    t.is_in_scope(),
+                )?),
                 _ => e.push(t.deep_copy()?),
             },
             _ => {
@@ -389,6 +394,11 @@ pub(crate) fn copy<
                     Item::Value(v) => im.push(im.new_text(v.clone())?)?,
                     Item::Node(n) => match n.node_type() {
                         NodeType::Attribute => im.add_attribute(n.clone())?,
+                        NodeType::Namespace => im.add_namespace(im.new_namespace(
+                            n.as_namespace_uri()?.clone(),
+                            n.as_namespace_prefix()?.cloned(),
+                            n.is_in_scope(),
+                        )?)?,
                         _ => im.push(n.clone())?,
                     },
                     _ => {
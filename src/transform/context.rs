@@ -16,7 +16,8 @@ use crate::output::OutputDefinition;
 use crate::pattern::Pattern;
 use crate::transform::Transform;
 use crate::transform::booleans::*;
-use crate::transform::callable::{Callable, invoke};
+use crate::transform::callable::{Callable, dynamic_call, function_item, invoke, partial_apply};
+use crate::transform::collation::{Collation, Collations};
 use crate::transform::construct::*;
 use crate::transform::controlflow::*;
 use crate::transform::datetime::*;
@@ -27,11 +28,16 @@ use crate::transform::logic::*;
 use crate::transform::misc::*;
 use crate::transform::navigate::*;
 use crate::transform::numbers::*;
+use crate::transform::sequences::*;
 use crate::transform::strings::*;
 use crate::transform::template::{Template, apply_imports, apply_templates, next_match};
+use crate::transform::types::{cast_as, castable_as, instance_of, treat_as};
 use crate::transform::variables::{declare_variable, reference_variable};
+use crate::transform::{arrays, binary, higherorder, json, maps, qnames};
+use crate::value::local_timezone;
 use crate::xdmerror::Error;
 use crate::{ErrorKind, Item, SequenceTrait, Value};
+use chrono::{DateTime, FixedOffset, Utc};
 use qualname::{NamespaceMap, QName};
 use std::cmp::Ordering;
 use std::collections::HashMap;
@@ -71,6 +77,8 @@ pub struct Context<N: Node> {
     pub(crate) keys: HashMap<String, Vec<(Pattern<N>, Transform<N>)>>,
     // The calculated values of keys.
     pub(crate) key_values: HashMap<String, HashMap<String, Vec<N>>>,
+    // The URI of the collation used to compare the values of a key.
+    pub(crate) key_collations: HashMap<String, String>,
     // Output control
     pub(crate) od: OutputDefinition,
     pub(crate) base_url: Option<Url>,
@@ -78,6 +86,10 @@ pub struct Context<N: Node> {
     // then these URI -> prefix mappings may be used. These are usually derived from the stylesheet document.
     // The search order is: Namespace declarations in the source document; namespace declarations in the result document; this NamespaceMap.
     pub(crate) namespaces: Option<Rc<NamespaceMap>>,
+    // The implicit timezone, for dates and times that do not have a timezone. If not set then the local timezone is used.
+    pub(crate) implicit_timezone: Option<FixedOffset>,
+    // The value of current-dateTime. If not set then the system clock is used.
+    pub(crate) current_date_time: Option<DateTime<FixedOffset>>,
 }
 impl<N: Node> Default for Context<N> {
     fn default() -> Self {
@@ -104,11 +116,23 @@ impl<N: Node> Context<N> {
             current_group: Sequence::new(),
             keys: HashMap::new(),
             key_values: HashMap::new(),
+            key_collations: HashMap::new(),
             od: OutputDefinition::new(),
             base_url: None,
             namespaces: None,
+            implicit_timezone: None,
+            current_date_time: None,
         }
     }
+    /// The implicit timezone. Unless one has been set, this is the offset of the local timezone.
+    pub fn implicit_timezone(&self) -> FixedOffset {
+        self.implicit_timezone.unwrap_or_else(local_timezone)
+    }
+    /// The current date and time, in the implicit timezone. Unless it has been set, this is read from the system clock.
+    pub fn current_date_time(&self) -> DateTime<FixedOffset> {
+        self.current_date_time
+            .unwrap_or_else(|| Utc::now().with_timezone(&self.implicit_timezone()))
+    }
     /// Sets the outer context and the context item.
     pub fn context(&mut self, s: Sequence<N>, i: usize) {
         self.context_item = Some(s[i].clone());
@@ -145,6 +169,10 @@ impl<N: Node> Context<N> {
             self.key_values.insert(name, HashMap::new());
         }
     }
+    /// Set the collation for a key. Values of the key are compared using the collation.
+    pub fn key_collation(&mut self, name: String, uri: String) {
+        self.key_collations.insert(name, uri);
+    }
     /// Calculate the key values for a source document
     pub fn populate_key_values<
         F: FnMut(&str) -> Result<(), Error>,
@@ -492,13 +520,27 @@ impl<N: Node> Context<N> {
             Transform::Or(v) => tr_or(self, stctxt, v),
             Transform::And(v) => tr_and(self, stctxt, v),
             Transform::Union(b) => union(self, stctxt, b),
+            Transform::Intersect(a, b) => intersect(self, stctxt, a, b),
+            Transform::Except(a, b) => except(self, stctxt, a, b),
+            Transform::SimpleMap(v) => simple_map(self, stctxt, v),
+            Transform::InstanceOf(t, st) => instance_of(self, stctxt, t, st),
+            Transform::Treat(t, st) => treat_as(self, stctxt, t, st),
+            Transform::Cast(t, qn, e) => cast_as(self, stctxt, t, qn, *e),
+            Transform::Castable(t, qn, e) => castable_as(self, stctxt, t, qn, *e),
+            Transform::MapConstructor(v) => maps::map_constructor(self, stctxt, v),
+            Transform::ArrayConstructor(v) => arrays::array_constructor(self, stctxt, v),
+            Transform::CurlyArrayConstructor(t) => arrays::curly_array_constructor(self, stctxt, t),
+            Transform::Lookup(t, k) => maps::lookup(self, stctxt, t, k),
             Transform::GeneralComparison(o, l, r) => general_comparison(self, stctxt, o, l, r),
             Transform::ValueComparison(o, l, r) => value_comparison(self, stctxt, o, l, r),
+            Transform::NodeComparison(o, l, r) => node_comparison(self, stctxt, o, l, r),
             Transform::Concat(v) => tr_concat(self, stctxt, v),
             Transform::Range(s, e) => tr_range(self, stctxt, s, e),
             Transform::Arithmetic(v) => arithmetic(self, stctxt, v),
+            Transform::Unary(o, t) => unary(self, stctxt, o, t),
             Transform::Loop(v, b) => tr_loop(self, stctxt, v, b),
             Transform::Switch(c, o) => switch(self, stctxt, c, o),
+            Transform::Quantified(q, v, s) => quantified(self, stctxt, q, v, s),
             Transform::ForEach(g, s, b, o) => for_each(self, stctxt, g, s, b, o),
             Transform::ApplyTemplates(s, m, o) => apply_templates(self, stctxt, s, m, o),
             Transform::ApplyImports => apply_imports(self, stctxt),
@@ -512,15 +554,64 @@ impl<N: Node> Context<N> {
             Transform::Count(s) => tr_count(self, stctxt, s),
             Transform::LocalName(s) => local_name(self, stctxt, s),
             Transform::Name(s) => name(self, stctxt, s),
+            Transform::QName(u, n) => qnames::qname(self, stctxt, u, n),
+            Transform::ResolveQName(n, e) => qnames::resolve_qname(self, stctxt, n, e),
+            Transform::PrefixFromQName(q) => qnames::prefix_from_qname(self, stctxt, q),
+            Transform::LocalNameFromQName(q) => qnames::local_name_from_qname(self, stctxt, q),
+            Transform::NamespaceUriFromQName(q) => {
+                qnames::namespace_uri_from_qname(self, stctxt, q)
+            }
+            Transform::NamespaceUriForPrefix(p, e) => {
+                qnames::namespace_uri_for_prefix(self, stctxt, p, e)
+            }
+            Transform::InScopePrefixes(e) => qnames::in_scope_prefixes(self, stctxt, e),
+            Transform::NodeName(s) => qnames::node_name(self, stctxt, s),
+            Transform::NamespaceUri(s) => qnames::namespace_uri(self, stctxt, s),
+            Transform::ParseJson(t, o) => json::parse_json(self, stctxt, t, o),
+            Transform::JsonDoc(h, o) => json::json_doc(self, stctxt, h, o),
+            Transform::JsonToXml(t, o) => json::json_to_xml(self, stctxt, t, o),
+            Transform::XmlToJson(n, o) => json::xml_to_json(self, stctxt, n, o),
             Transform::String(s) => string(self, stctxt, s),
-            Transform::StartsWith(s, t) => starts_with(self, stctxt, s, t),
-            Transform::EndsWith(s, t) => ends_with(self, stctxt, s, t),
-            Transform::Contains(s, t) => contains(self, stctxt, s, t),
+            Transform::StartsWith(s, t, c) => starts_with(self, stctxt, s, t, c),
+            Transform::EndsWith(s, t, c) => ends_with(self, stctxt, s, t, c),
+            Transform::Contains(s, t, c) => contains(self, stctxt, s, t, c),
             Transform::Substring(s, t, l) => substring(self, stctxt, s, t, l),
-            Transform::SubstringBefore(s, t) => substring_before(self, stctxt, s, t),
-            Transform::SubstringAfter(s, t) => substring_after(self, stctxt, s, t),
+            Transform::SubstringBefore(s, t, c) => substring_before(self, stctxt, s, t, c),
+            Transform::SubstringAfter(s, t, c) => substring_after(self, stctxt, s, t, c),
             Transform::NormalizeSpace(s) => normalize_space(self, stctxt, s),
             Transform::Translate(s, m, t) => translate(self, stctxt, s, m, t),
+            Transform::UpperCase(s) => upper_case(self, stctxt, s),
+            Transform::LowerCase(s) => lower_case(self, stctxt, s),
+            Transform::StringJoin(s, t) => string_join(self, stctxt, s, t),
+            Transform::StringLength(s) => string_length(self, stctxt, s),
+            Transform::StringToCodepoints(s) => string_to_codepoints(self, stctxt, s),
+            Transform::CodepointsToString(s) => codepoints_to_string(self, stctxt, s),
+            Transform::Compare(s, t, c) => compare(self, stctxt, s, t, c),
+            Transform::CodepointEqual(s, t) => codepoint_equal(self, stctxt, s, t),
+            Transform::EncodeForUri(s) => encode_for_uri(self, stctxt, s),
+            Transform::IriToUri(s) => iri_to_uri(self, stctxt, s),
+            Transform::EscapeHtmlUri(s) => escape_html_uri(self, stctxt, s),
+            Transform::NormalizeUnicode(s, t) => normalize_unicode(self, stctxt, s, t),
+            Transform::ContainsToken(s, t, c) => contains_token(self, stctxt, s, t, c),
+            Transform::Matches(s, p, f) => matches(self, stctxt, s, p, f),
+            Transform::Replace(s, p, r, f) => replace(self, stctxt, s, p, r, f),
+            Transform::Tokenize(s, p, f) => tokenize(self, stctxt, s, p, f),
+            Transform::AnalyzeString(s, p, f) => analyze_string(self, stctxt, s, p, f),
+            Transform::IsEmpty(s) => tr_empty(self, stctxt, s),
+            Transform::Exists(s) => exists(self, stctxt, s),
+            Transform::Head(s) => head(self, stctxt, s),
+            Transform::Tail(s) => tail(self, stctxt, s),
+            Transform::InsertBefore(s, p, i) => insert_before(self, stctxt, s, p, i),
+            Transform::Remove(s, p) => remove(self, stctxt, s, p),
+            Transform::Reverse(s) => reverse(self, stctxt, s),
+            Transform::Subsequence(s, p, l) => subsequence(self, stctxt, s, p, l),
+            Transform::Unordered(s) => unordered(self, stctxt, s),
+            Transform::DistinctValues(s, c) => distinct_values(self, stctxt, s, c),
+            Transform::IndexOf(s, t, c) => index_of(self, stctxt, s, t, c),
+            Transform::DeepEqual(s, t, c) => deep_equal(self, stctxt, s, t, c),
+            Transform::ZeroOrOne(s) => zero_or_one(self, stctxt, s),
+            Transform::OneOrMore(s) => one_or_more(self, stctxt, s),
+            Transform::ExactlyOne(s) => exactly_one(self, stctxt, s),
             Transform::GenerateId(s) => generate_id(self, stctxt, s),
             Transform::Boolean(b) => boolean(self, stctxt, b),
             Transform::Not(b) => not(self, stctxt, b),
@@ -544,6 +635,13 @@ impl<N: Node> Context<N> {
             }
             Transform::FormatDate(t, p, l, c, q) => format_date(self, stctxt, t, p, l, c, q),
             Transform::FormatTime(t, p, l, c, q) => format_time(self, stctxt, t, p, l, c, q),
+            Transform::ComponentFromDateTime(c, t) => component(self, stctxt, "dateTime", c, t),
+            Transform::ComponentFromDate(c, t) => component(self, stctxt, "date", c, t),
+            Transform::ComponentFromTime(c, t) => component(self, stctxt, "time", c, t),
+            Transform::ComponentFromDuration(c, t) => component(self, stctxt, "duration", c, t),
+            Transform::AdjustDateTimeToTimezone(t, z) => {
+                adjust_date_time_to_timezone(self, stctxt, t, z)
+            }
             Transform::FormatNumber(v, p, d) => format_number(self, stctxt, v, p, d),
             Transform::FormatInteger(i, s) => format_integer(self, stctxt, i, s),
             Transform::GenerateIntegers(start_at, select, n) => {
@@ -553,6 +651,57 @@ impl<N: Node> Context<N> {
             Transform::SystemProperty(p, ns) => system_property(self, stctxt, p, ns),
             Transform::AvailableSystemProperties => available_system_properties(),
             Transform::Document(uris, base) => document(self, stctxt, uris, base),
+            Transform::ForEachItem(s, f) => higherorder::for_each(self, stctxt, s, f),
+            Transform::FilterItems(s, f) => higherorder::filter(self, stctxt, s, f),
+            Transform::FoldLeft(s, z, f) => higherorder::fold_left(self, stctxt, s, z, f),
+            Transform::FoldRight(s, z, f) => higherorder::fold_right(self, stctxt, s, z, f),
+            Transform::ForEachPair(s1, s2, f) => {
+                higherorder::for_each_pair(self, stctxt, s1, s2, f)
+            }
+            Transform::Sort(s, c, k) => higherorder::sort(self, stctxt, s, c, k),
+            Transform::Apply(f, a) => higherorder::apply(self, stctxt, f, a),
+            Transform::MapMerge(m, o) => maps::merge(self, stctxt, m, o),
+            Transform::MapSize(m) => maps::size(self, stctxt, m),
+            Transform::MapKeys(m) => maps::keys(self, stctxt, m),
+            Transform::MapContains(m, k) => maps::contains(self, stctxt, m, k),
+            Transform::MapGet(m, k) => maps::get(self, stctxt, m, k),
+            Transform::MapFind(i, k) => maps::find(self, stctxt, i, k),
+            Transform::MapPut(m, k, v) => maps::put(self, stctxt, m, k, v),
+            Transform::MapEntry(k, v) => maps::entry(self, stctxt, k, v),
+            Transform::MapRemove(m, k) => maps::remove(self, stctxt, m, k),
+            Transform::MapForEach(m, f) => higherorder::map_for_each(self, stctxt, m, f),
+            Transform::ArraySize(a) => arrays::size(self, stctxt, a),
+            Transform::ArrayGet(a, p) => arrays::get(self, stctxt, a, p),
+            Transform::ArrayPut(a, p, m) => arrays::put(self, stctxt, a, p, m),
+            Transform::ArrayAppend(a, m) => arrays::append(self, stctxt, a, m),
+            Transform::ArraySubarray(a, s, l) => arrays::subarray(self, stctxt, a, s, l),
+            Transform::ArrayRemove(a, p) => arrays::remove(self, stctxt, a, p),
+            Transform::ArrayInsertBefore(a, p, m) => arrays::insert_before(self, stctxt, a, p, m),
+            Transform::ArrayHead(a) => arrays::head(self, stctxt, a),
+            Transform::ArrayTail(a) => arrays::tail(self, stctxt, a),
+            Transform::ArrayReverse(a) => arrays::reverse(self, stctxt, a),
+            Transform::ArrayJoin(a) => arrays::join(self, stctxt, a),
+            Transform::ArrayFlatten(s) => arrays::flatten(self, stctxt, s),
+            Transform::ArrayForEach(a, f) => higherorder::array_for_each(self, stctxt, a, f),
+            Transform::ArrayFilter(a, f) => higherorder::array_filter(self, stctxt, a, f),
+            Transform::ArrayFoldLeft(a, z, f) => {
+                higherorder::array_fold_left(self, stctxt, a, z, f)
+            }
+            Transform::ArrayFoldRight(a, z, f) => {
+                higherorder::array_fold_right(self, stctxt, a, z, f)
+            }
+            Transform::ArrayForEachPair(a1, a2, f) => {
+                higherorder::array_for_each_pair(self, stctxt, a1, a2, f)
+            }
+            Transform::ArraySort(a, c, k) => higherorder::array_sort(self, stctxt, a, c, k),
+            Transform::BinaryLength(b) => binary::length(self, stctxt, b),
+            Transform::BinaryPart(b, o, s) => binary::part(self, stctxt, b, o, s),
+            Transform::BinaryDecodeString(b, e, o, s) => {
+                binary::decode_string(self, stctxt, b, e, o, s)
+            }
+            Transform::FunctionItem(n, p, b) => function_item(self, n, p, b),
+            Transform::DynamicCall(f, a) => dynamic_call(self, stctxt, f, a),
+            Transform::PartialApply(f, a) => partial_apply(self, stctxt, f, a),
             Transform::Invoke(qn, a, ns) => invoke(self, stctxt, qn, a, ns),
             Transform::Message(b, s, e, t) => message(self, stctxt, b, s, e, t),
             Transform::Error(k, m) => tr_error(self, k, m),
@@ -587,11 +736,14 @@ impl<N: Node> From<Sequence<N>> for Context<N> {
             pre_vars: Vec::new(),
             keys: HashMap::new(),
             key_values: HashMap::new(),
+            key_collations: HashMap::new(),
             current_grouping_key: None,
             current_group: Sequence::new(),
             od: OutputDefinition::new(),
             base_url: None,
             namespaces: None,
+            implicit_timezone: None,
+            current_date_time: None,
         }
     }
 }
@@ -692,6 +844,17 @@ impl<N: Node> ContextBuilder<N> {
         self.0.namespaces = Some(Rc::new(nm));
         self
     }
+    /// Set the implicit timezone, used for dates and times that do not have a timezone.
+    pub fn implicit_timezone(mut self, tz: FixedOffset) -> Self {
+        self.0.implicit_timezone = Some(tz);
+        self
+    }
+    /// Fix the value of current-dateTime, current-date, and current-time.
+    /// This makes evaluation deterministic, e.g. for testing.
+    pub fn current_date_time(mut self, dt: DateTime<FixedOffset>) -> Self {
+        self.0.current_date_time = Some(dt);
+        self
+    }
     pub fn build(self) -> Context<N> {
         self.0
     }
@@ -723,6 +886,7 @@ where
     pub(crate) message: Option<F>,
     pub(crate) parser: Option<G>,
     pub(crate) fetcher: Option<H>,
+    pub(crate) collations: Collations,
 }
 impl<N: Node, F, G, H> Default for StaticContext<N, F, G, H>
 where
@@ -746,6 +910,7 @@ where
             message: None,
             parser: None,
             fetcher: None,
+            collations: Collations::new(),
         }
     }
 }
@@ -827,6 +992,11 @@ where
         self.0.fetcher = Some(f);
         self
     }
+    /// Register a collation. The collation is identified by its URI.
+    pub fn collation(mut self, uri: &str, c: Collation) -> Self {
+        self.0.collations.register(uri, c);
+        self
+    }
     pub fn build(self) -> StaticContext<N, F, G, H> {
         self.0
     }
@@ -1,14 +1,16 @@
 //! These functions are for features that control program flow.
 
+use chrono::FixedOffset;
 use std::collections::HashMap;
 use std::rc::Rc;
 use url::Url;
 
-use crate::item::{Node, Sequence, SequenceTrait};
+use crate::item::{Item, Node, Sequence, SequenceTrait};
 use crate::pattern::Pattern;
+use crate::transform::collation::{Collation, find_collation};
 use crate::transform::context::{Context, ContextBuilder, StaticContext};
-use crate::transform::{Grouping, Order, Transform, do_sort};
-use crate::value::{Operator, Value};
+use crate::transform::{Grouping, Order, Quantifier, Transform, do_sort, sort_by_keys};
+use crate::value::{Operator, Value, ValueData};
 use crate::xdmerror::{Error, ErrorKind};
 
 /// Iterate over the items in a sequence.
@@ -65,6 +67,54 @@ pub(crate) fn switch<
     Ok(candidate)
 }
 
+/// Test whether some, or every, binding of the variables satisfies the test expression.
+/// Evaluation stops as soon as the result is known.
+pub(crate) fn quantified<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    q: &Quantifier,
+    v: &[(String, Transform<N>)],
+    s: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let b = quantify(ctxt, stctxt, q, v, s)?;
+    Ok(vec![Item::Value(Rc::new(Value::from(b)))])
+}
+
+// Bind the first variable to each item in turn, then quantify over the remaining variables.
+// Each binding expression may refer to the variables bound before it.
+fn quantify<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    q: &Quantifier,
+    v: &[(String, Transform<N>)],
+    s: &Transform<N>,
+) -> Result<bool, Error> {
+    let Some(((name, t), rest)) = v.split_first() else {
+        return Ok(ctxt.dispatch(stctxt, s)?.to_bool());
+    };
+    for i in ctxt.dispatch(stctxt, t)? {
+        let lctxt = ContextBuilder::from(ctxt)
+            .variable(name.clone(), vec![i])
+            .build();
+        match (q, quantify(&lctxt, stctxt, q, rest, s)?) {
+            (Quantifier::Some, true) => return Ok(true),
+            (Quantifier::Every, false) => return Ok(false),
+            _ => {}
+        }
+    }
+    Ok(*q == Quantifier::Every)
+}
+
 /// Evaluate a combinator for each item.
 pub fn for_each<
     N: Node,
@@ -77,7 +127,7 @@ pub fn for_each<
     g: &Option<Grouping<N>>,
     s: &Transform<N>,
     body: &Transform<N>,
-    o: &Vec<(Order, Transform<N>)>,
+    o: &Vec<(Order, Transform<N>, Option<String>)>,
 ) -> Result<Sequence<N>, Error> {
     match g {
         None => {
@@ -94,8 +144,8 @@ pub fn for_each<
             }
             Ok(result)
         }
-        Some(Grouping::By(b)) => group_by(ctxt, stctxt, b, s, body, o),
-        Some(Grouping::Adjacent(a)) => group_adjacent(ctxt, stctxt, a, s, body, o),
+        Some(Grouping::By(b, c)) => group_by(ctxt, stctxt, b, c, s, body, o),
+        Some(Grouping::Adjacent(a, c)) => group_adjacent(ctxt, stctxt, a, c, s, body, o),
         Some(Grouping::StartingWith(v)) => group_starting_with(ctxt, stctxt, v, s, body, o),
         Some(Grouping::EndingWith(v)) => group_ending_with(ctxt, stctxt, v, s, body, o),
     }
@@ -111,14 +161,16 @@ fn group_by<
     ctxt: &Context<N>,
     stctxt: &mut StaticContext<N, F, G, H>,
     by: &Vec<Transform<N>>,
+    c: &Option<String>,
     s: &Transform<N>,
     body: &Transform<N>,
-    o: &Vec<(Order, Transform<N>)>,
+    o: &Vec<(Order, Transform<N>, Option<String>)>,
 ) -> Result<Sequence<N>, Error> {
     // Each 'by' expression is evaluated to a string key and stored in the hashmap
     // TODO: this implementation is only supporting a single key
     let t = by[0].clone();
-    let mut groups = HashMap::new();
+    let collation = find_collation(stctxt, c)?;
+    let mut groups: HashMap<String, Sequence<N>> = HashMap::new();
     ctxt.dispatch(stctxt, s)?.iter().try_for_each(|i| {
         // There may be multiple keys returned.
         // For each one, add this item into the group for that key
@@ -129,7 +181,14 @@ fn group_by<
             .dispatch(stctxt, &t)?
             .iter()
             .for_each(|k| {
-                let e: &mut Sequence<N> = groups.entry(k.to_string()).or_default();
+                // Keys that are equal according to the collation belong to the same group
+                let mut key = k.to_string();
+                if !collation.is_codepoint()
+                    && let Some(g) = groups.keys().find(|g| collation.equal(g, &key))
+                {
+                    key = g.clone()
+                }
+                let e: &mut Sequence<N> = groups.entry(key).or_default();
                 e.push(i.clone());
             });
         Ok(())
@@ -137,26 +196,19 @@ fn group_by<
 
     if !o.is_empty() {
         // Build a vector of the groups, and then sort the vector
-        // TODO: support multiple sort keys
-        let mut gr_vec: Vec<(String, Sequence<N>)> =
-            groups.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-        gr_vec.sort_by_cached_key(|(k, v)| {
-            // TODO: Don't panic
-            let key_seq = ContextBuilder::from(ctxt)
-                .context(v.clone())
-                .current_grouping_key(Rc::new(Value::from(k.clone())))
-                .current_group(v.clone())
-                .build()
-                .dispatch(stctxt, &o[0].1)
-                .expect("unable to determine key value");
-            // Assume string data type for now
-            // TODO: support number data type
-            // TODO: support all data types
-            key_seq.to_string()
-        });
-        if o[0].0 == Order::Descending {
-            gr_vec.reverse();
-        }
+        let gr_vec: Vec<(String, Sequence<N>)> = sort_by_keys(
+            groups.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+            o,
+            stctxt,
+            |(k, v), t, stctxt| {
+                ContextBuilder::from(ctxt)
+                    .context(v.clone())
+                    .current_grouping_key(Rc::new(Value::from(k.clone())))
+                    .current_group(v.clone())
+                    .build()
+                    .dispatch(stctxt, t)
+            },
+        )?;
         // Now evaluate the body for each group
         gr_vec.iter().try_fold(vec![], |mut result, (k, v)| {
             // Set current-group and current-grouping-key
@@ -193,12 +245,14 @@ fn group_adjacent<
     ctxt: &Context<N>,
     stctxt: &mut StaticContext<N, F, G, H>,
     adj: &Vec<Transform<N>>,
+    c: &Option<String>,
     s: &Transform<N>,
     body: &Transform<N>,
-    o: &Vec<(Order, Transform<N>)>,
+    o: &Vec<(Order, Transform<N>, Option<String>)>,
 ) -> Result<Sequence<N>, Error> {
     // TODO: this implementation is only supporting a single key
     let t = adj[0].clone();
+    let collation = find_collation(stctxt, c)?;
     let mut groups = Vec::new();
     let sel = ctxt.dispatch(stctxt, s)?;
     if sel.is_empty() {
@@ -222,7 +276,12 @@ fn group_adjacent<
                 .build()
                 .dispatch(stctxt, &t)?;
             if thiskey.len() == 1 {
-                if curkey[0].compare(&thiskey[0], Operator::Equal)? {
+                if adjacent_keys_equal(
+                    &curkey[0],
+                    &thiskey[0],
+                    &collation,
+                    ctxt.implicit_timezone(),
+                )? {
                     // Append to the current group
                     curgrp.push(i.clone())
                 } else {
@@ -245,26 +304,19 @@ fn group_adjacent<
 
     if !o.is_empty() {
         // Build a vector of the groups, and then sort the vector
-        // TODO: support multiple sort keys
-        let mut gr_vec: Vec<(String, Sequence<N>)> =
-            groups.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-        gr_vec.sort_by_cached_key(|(k, v)| {
-            // TODO: Don't panic
-            let key_seq = ContextBuilder::from(ctxt)
-                .context(v.clone())
-                .current_grouping_key(Rc::new(Value::from(k.clone())))
-                .current_group(v.clone())
-                .build()
-                .dispatch(stctxt, &o[0].1)
-                .expect("unable to determine key value");
-            // Assume string data type for now
-            // TODO: support number data type
-            // TODO: support all data types
-            key_seq.to_string()
-        });
-        if o[0].0 == Order::Descending {
-            gr_vec.reverse();
-        }
+        let gr_vec: Vec<(String, Sequence<N>)> = sort_by_keys(
+            groups.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+            o,
+            stctxt,
+            |(k, v), t, stctxt| {
+                ContextBuilder::from(ctxt)
+                    .context(v.clone())
+                    .current_grouping_key(Rc::new(Value::from(k.clone())))
+                    .current_group(v.clone())
+                    .build()
+                    .dispatch(stctxt, t)
+            },
+        )?;
         // Now evaluate the body for each group
         gr_vec.iter().try_fold(vec![], |mut result, (k, v)| {
             // Set current-group and current-grouping-key
@@ -291,6 +343,26 @@ fn group_adjacent<
     }
 }
 
+// Compare grouping keys for group-adjacent. Strings, and the string values of nodes, are compared using the collation.
+// Dates and times without a timezone are in the implicit timezone.
+fn adjacent_keys_equal<N: Node>(
+    a: &Item<N>,
+    b: &Item<N>,
+    collation: &Collation,
+    implicit: FixedOffset,
+) -> Result<bool, Error> {
+    let is_string = |i: &Item<N>| match i {
+        Item::Node(_) => true,
+        Item::Value(v) => matches!(v.value, ValueData::String(_)),
+        _ => false,
+    };
+    if is_string(a) && is_string(b) {
+        Ok(collation.equal(&a.to_string(), &b.to_string()))
+    } else {
+        a.compare_with_timezone(b, Operator::Equal, implicit)
+    }
+}
+
 /// Evaluate a combinator for each group of items.
 fn group_starting_with<
     N: Node,
@@ -303,7 +375,7 @@ fn group_starting_with<
     pat: &Pattern<N>,
     s: &Transform<N>,
     body: &Transform<N>,
-    o: &Vec<(Order, Transform<N>)>,
+    o: &Vec<(Order, Transform<N>, Option<String>)>,
 ) -> Result<Sequence<N>, Error> {
     // The first item starts the first group.
     // Each item selected is tested against the pattern.
@@ -332,23 +404,13 @@ fn group_starting_with<
     })?;
 
     if !o.is_empty() {
-        // TODO: support multiple sort keys
-        groups.sort_by_cached_key(|v| {
-            // TODO: Don't panic
-            let key_seq = ContextBuilder::from(ctxt)
+        groups = sort_by_keys(groups, o, stctxt, |v, t, stctxt| {
+            ContextBuilder::from(ctxt)
                 .context(v.clone())
                 .current_group(v.clone())
                 .build()
-                .dispatch(stctxt, &o[0].1)
-                .expect("unable to determine key value");
-            // Assume string data type for now
-            // TODO: support number data type
-            // TODO: support all data types
-            key_seq.to_string()
-        });
-        if o[0].0 == Order::Descending {
-            groups.reverse();
-        }
+                .dispatch(stctxt, t)
+        })?;
         // Now evaluate the body for each group
         groups.iter().try_fold(vec![], |mut result, v| {
             // Set current-group, but not current-grouping-key
@@ -385,7 +447,7 @@ pub fn group_ending_with<
     _pat: &Pattern<N>,
     _s: &Transform<N>,
     _body: &Transform<N>,
-    _o: &Vec<(Order, Transform<N>)>,
+    _o: &Vec<(Order, Transform<N>, Option<String>)>,
 ) -> Result<Sequence<N>, Error> {
     Err(Error::new(
         ErrorKind::NotImplemented,
@@ -2,32 +2,57 @@
 
 use std::rc::Rc;
 
-#[allow(unused_imports)]
-use chrono::{DateTime, Datelike, FixedOffset, Local, Timelike};
+use chrono::{
+    DateTime, Datelike, FixedOffset, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta,
+    TimeZone, Timelike,
+};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use url::Url;
 
 use crate::item::{Item, Node, Sequence, SequenceTrait};
 use crate::parser::datetime::parse as picture_parse;
-use crate::transform::Transform;
 use crate::transform::context::{Context, StaticContext};
-use crate::value::{Value, ValueData};
+use crate::transform::types::{cast_value, error};
+use crate::transform::{ArithmeticOperator, DateTimeComponent, Transform};
+use crate::value::{Duration, Value, ValueBuilder, ValueData};
 use crate::xdmerror::{Error, ErrorKind};
 
-/// XPath current-date-time function.
-pub fn current_date_time<N: Node>(_ctxt: &Context<N>) -> Result<Sequence<N>, Error> {
-    Ok(vec![Item::Value(Rc::new(Value::from(Local::now())))])
+/// XPath current-dateTime function. The result is in the implicit timezone.
+pub fn current_date_time<N: Node>(ctxt: &Context<N>) -> Result<Sequence<N>, Error> {
+    Ok(vec![Item::Value(Rc::new(Value::from(
+        ctxt.current_date_time(),
+    )))])
 }
 
-/// XPath current-date function.
-pub fn current_date<N: Node>(_ctxt: &Context<N>) -> Result<Sequence<N>, Error> {
+/// XPath current-date function. The result is in the implicit timezone.
+pub fn current_date<N: Node>(ctxt: &Context<N>) -> Result<Sequence<N>, Error> {
+    let now = ctxt.current_date_time();
     Ok(vec![Item::Value(Rc::new(Value::new_date(
-        Local::now().date_naive(),
+        now.date_naive(),
+        Some(*now.offset()),
     )))])
 }
 
-/// XPath current-time function.
-pub fn current_time<N: Node>(_ctxt: &Context<N>) -> Result<Sequence<N>, Error> {
-    Ok(vec![Item::Value(Rc::new(Value::new_time(Local::now())))])
+/// XPath current-time function. The result is in the implicit timezone.
+pub fn current_time<N: Node>(ctxt: &Context<N>) -> Result<Sequence<N>, Error> {
+    let now = ctxt.current_date_time();
+    Ok(vec![Item::Value(Rc::new(Value::new_time(
+        now.time(),
+        Some(*now.offset()),
+    )))])
+}
+
+// A date and time in its own timezone, or in the implicit timezone if it does not have one.
+fn zoned<N: Node>(
+    ctxt: &Context<N>,
+    dt: NaiveDateTime,
+    tz: Option<FixedOffset>,
+) -> Result<DateTime<FixedOffset>, Error> {
+    tz.unwrap_or_else(|| ctxt.implicit_timezone())
+        .from_local_datetime(&dt)
+        .single()
+        .ok_or_else(date_overflow)
 }
 
 /// XPath format-date-time function.
@@ -53,16 +78,19 @@ pub fn format_date_time<
         0 => Ok(vec![]), // Empty value returns empty sequence
         1 => {
             match &dt[0] {
-                Item::Value(d) => match d.value {
-                    ValueData::DateTime(i) => Ok(vec![Item::Value(Rc::new(Value::from(
-                        i.format(&pic).to_string(),
+                Item::Value(d) => match &d.value {
+                    ValueData::DateTime(i, tz) => Ok(vec![Item::Value(Rc::new(Value::from(
+                        zoned(ctxt, *i, *tz)?.format(&pic).to_string(),
+                    )))]),
+                    ValueData::DateTimeStamp(i, tz) => Ok(vec![Item::Value(Rc::new(Value::from(
+                        zoned(ctxt, *i, Some(*tz))?.format(&pic).to_string(),
                     )))]),
-                    ValueData::String(ref s) => {
+                    ValueData::String(_) => {
                         // Try and coerce into a DateTime value
-                        match DateTime::<FixedOffset>::parse_from_rfc3339(s.as_str()) {
-                            Ok(j) => Ok(vec![Item::Value(Rc::new(Value::from(
-                                j.format(&pic).to_string(),
-                            )))]),
+                        match cast_value(&d.value, "dateTime") {
+                            Ok(ValueData::DateTime(j, tz)) => Ok(vec![Item::Value(Rc::new(
+                                Value::from(zoned(ctxt, j, tz)?.format(&pic).to_string()),
+                            ))]),
                             _ => Err(Error::new(
                                 ErrorKind::TypeError,
                                 String::from("unable to determine date value"),
@@ -110,17 +138,22 @@ pub fn format_date<
         0 => Ok(vec![]), // Empty value returns empty sequence
         1 => {
             match &dt[0] {
-                Item::Value(d) => match d.value {
-                    ValueData::Date(i) => Ok(vec![Item::Value(Rc::new(Value::from(
-                        i.format(&pic).to_string(),
+                Item::Value(d) => match &d.value {
+                    ValueData::Date(i, tz) => Ok(vec![Item::Value(Rc::new(Value::from(
+                        zoned(ctxt, i.and_time(NaiveTime::MIN), *tz)?
+                            .format(&pic)
+                            .to_string(),
                     )))]),
-                    ValueData::String(ref s) => {
-                        // Try and coerce into a DateTime value
-                        let a = format!("{}T00:00:00Z", s);
-                        match DateTime::<FixedOffset>::parse_from_rfc3339(a.as_str()) {
-                            Ok(j) => Ok(vec![Item::Value(Rc::new(Value::from(
-                                j.date_naive().format(&pic).to_string(),
-                            )))]),
+                    ValueData::String(_) => {
+                        // Try and coerce into a Date value
+                        match cast_value(&d.value, "date") {
+                            Ok(ValueData::Date(j, tz)) => {
+                                Ok(vec![Item::Value(Rc::new(Value::from(
+                                    zoned(ctxt, j.and_time(NaiveTime::MIN), tz)?
+                                        .format(&pic)
+                                        .to_string(),
+                                )))])
+                            }
                             _ => Err(Error::new(
                                 ErrorKind::TypeError,
                                 String::from("unable to determine date value"),
@@ -168,17 +201,22 @@ pub fn format_time<
         0 => Ok(vec![]), // Empty value returns empty sequence
         1 => {
             match &dt[0] {
-                Item::Value(d) => match d.value {
-                    ValueData::Time(i) => Ok(vec![Item::Value(Rc::new(Value::from(
-                        i.format(&pic).to_string(),
+                Item::Value(d) => match &d.value {
+                    ValueData::Time(i, tz) => Ok(vec![Item::Value(Rc::new(Value::from(
+                        zoned(ctxt, reference_date().and_time(*i), *tz)?
+                            .format(&pic)
+                            .to_string(),
                     )))]),
-                    ValueData::String(ref s) => {
-                        // Try and coerce into a DateTime value
-                        let a = format!("1900-01-01T{}Z", s);
-                        match DateTime::<FixedOffset>::parse_from_rfc3339(a.as_str()) {
-                            Ok(j) => Ok(vec![Item::Value(Rc::new(Value::from(
-                                j.format(&pic).to_string(),
-                            )))]),
+                    ValueData::String(_) => {
+                        // Try and coerce into a Time value
+                        match cast_value(&d.value, "time") {
+                            Ok(ValueData::Time(j, tz)) => {
+                                Ok(vec![Item::Value(Rc::new(Value::from(
+                                    zoned(ctxt, reference_date().and_time(j), tz)?
+                                        .format(&pic)
+                                        .to_string(),
+                                )))])
+                            }
                             _ => Err(Error::new(
                                 ErrorKind::TypeError,
                                 String::from("unable to determine time value"),
@@ -202,3 +240,517 @@ pub fn format_time<
         )),
     }
 }
+
+/// Is the value a date, time, or duration, i.e. a value that takes part in date/time arithmetic?
+pub(crate) fn is_temporal(v: &Value) -> bool {
+    matches!(
+        v.value,
+        ValueData::Duration(_)
+            | ValueData::YearMonthDuration(_)
+            | ValueData::DayTimeDuration(_)
+            | ValueData::DateTime(..)
+            | ValueData::DateTimeStamp(..)
+            | ValueData::Date(..)
+            | ValueData::Time(..)
+            | ValueData::GYearMonth(..)
+            | ValueData::GYear(..)
+            | ValueData::GMonthDay(..)
+            | ValueData::GMonth(..)
+            | ValueData::GDay(..)
+    )
+}
+
+// The date that a time is placed on when it is treated as a dateTime
+fn reference_date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1972, 12, 31).unwrap_or_default()
+}
+
+/// Arithmetic on dates, times, and durations. See XPath F&O 3.1 sections 8.2 and 9.7.
+/// At least one of the operands must be a date, time, or duration value; the other may be numeric.
+/// Dates and times without a timezone are taken to be in the implicit timezone when they are subtracted.
+pub(crate) fn temporal_arithmetic(
+    a: &Value,
+    op: ArithmeticOperator,
+    b: &Value,
+    implicit: FixedOffset,
+) -> Result<Value, Error> {
+    // A dateTimeStamp is a dateTime that has a timezone, and the result is a dateTime
+    if let ValueData::DateTimeStamp(dt, tz) = a.value {
+        let x = ValueBuilder::new()
+            .value(ValueData::DateTime(dt, Some(tz)))
+            .build();
+        return temporal_arithmetic(&x, op, b, implicit);
+    }
+    if let ValueData::DateTimeStamp(dt, tz) = b.value {
+        let y = ValueBuilder::new()
+            .value(ValueData::DateTime(dt, Some(tz)))
+            .build();
+        return temporal_arithmetic(a, op, &y, implicit);
+    }
+    let result = match (&a.value, op, &b.value) {
+        (
+            ValueData::YearMonthDuration(x),
+            ArithmeticOperator::Add,
+            ValueData::YearMonthDuration(y),
+        ) => ValueData::YearMonthDuration(Duration::from_months(
+            x.months().checked_add(y.months()).ok_or_else(overflow)?,
+        )),
+        (
+            ValueData::YearMonthDuration(x),
+            ArithmeticOperator::Subtract,
+            ValueData::YearMonthDuration(y),
+        ) => ValueData::YearMonthDuration(Duration::from_months(
+            x.months().checked_sub(y.months()).ok_or_else(overflow)?,
+        )),
+        (ValueData::DayTimeDuration(x), ArithmeticOperator::Add, ValueData::DayTimeDuration(y)) => {
+            ValueData::DayTimeDuration(Duration::from_seconds(
+                x.seconds().checked_add(y.seconds()).ok_or_else(overflow)?,
+            ))
+        }
+        (
+            ValueData::DayTimeDuration(x),
+            ArithmeticOperator::Subtract,
+            ValueData::DayTimeDuration(y),
+        ) => ValueData::DayTimeDuration(Duration::from_seconds(
+            x.seconds().checked_sub(y.seconds()).ok_or_else(overflow)?,
+        )),
+        (ValueData::YearMonthDuration(x), ArithmeticOperator::Multiply, n)
+        | (n, ArithmeticOperator::Multiply, ValueData::YearMonthDuration(x))
+            if numeric(n).is_some() =>
+        {
+            ValueData::YearMonthDuration(Duration::from_months(scale_months(
+                x.months(),
+                numeric(n).unwrap(),
+            )?))
+        }
+        (ValueData::YearMonthDuration(x), ArithmeticOperator::Divide, n)
+            if numeric(n).is_some() =>
+        {
+            ValueData::YearMonthDuration(Duration::from_months(scale_months(
+                x.months(),
+                1.0 / numeric(n).unwrap(),
+            )?))
+        }
+        (
+            ValueData::YearMonthDuration(x),
+            ArithmeticOperator::Divide,
+            ValueData::YearMonthDuration(y),
+        ) => {
+            if y.months() == 0 {
+                return Err(error(
+                    ErrorKind::Unknown,
+                    "FOAR0001",
+                    String::from("division by zero"),
+                ));
+            }
+            ValueData::Decimal(Decimal::from(x.months()) / Decimal::from(y.months()))
+        }
+        (ValueData::DayTimeDuration(x), ArithmeticOperator::Multiply, n)
+        | (n, ArithmeticOperator::Multiply, ValueData::DayTimeDuration(x))
+            if numeric(n).is_some() =>
+        {
+            ValueData::DayTimeDuration(Duration::from_seconds(scale_seconds(
+                x.seconds(),
+                numeric(n).unwrap(),
+            )?))
+        }
+        (ValueData::DayTimeDuration(x), ArithmeticOperator::Divide, n) if numeric(n).is_some() => {
+            ValueData::DayTimeDuration(Duration::from_seconds(scale_seconds(
+                x.seconds(),
+                1.0 / numeric(n).unwrap(),
+            )?))
+        }
+        (
+            ValueData::DayTimeDuration(x),
+            ArithmeticOperator::Divide,
+            ValueData::DayTimeDuration(y),
+        ) => ValueData::Decimal(x.seconds().checked_div(y.seconds()).ok_or_else(|| {
+            error(
+                ErrorKind::Unknown,
+                "FOAR0001",
+                String::from("division by zero"),
+            )
+        })?),
+        (ValueData::DateTime(..), ArithmeticOperator::Subtract, ValueData::DateTime(..))
+        | (ValueData::Time(..), ArithmeticOperator::Subtract, ValueData::Time(..))
+        | (ValueData::Date(..), ArithmeticOperator::Subtract, ValueData::Date(..)) => {
+            let x = a.to_utc(implicit).ok_or_else(date_overflow)?;
+            let y = b.to_utc(implicit).ok_or_else(date_overflow)?;
+            ValueData::DayTimeDuration(Duration::from_seconds(delta_seconds(
+                x.signed_duration_since(y),
+            )))
+        }
+        (
+            ValueData::DateTime(x, tz),
+            ArithmeticOperator::Add | ArithmeticOperator::Subtract,
+            ValueData::YearMonthDuration(d),
+        )
+        | (ValueData::YearMonthDuration(d), ArithmeticOperator::Add, ValueData::DateTime(x, tz)) => {
+            ValueData::DateTime(
+                months_delta(d.months(), op)
+                    .and_then(|m| {
+                        if m < 0 {
+                            x.checked_sub_months(Months::new(m.unsigned_abs() as u32))
+                        } else {
+                            x.checked_add_months(Months::new(m as u32))
+                        }
+                    })
+                    .ok_or_else(date_overflow)?,
+                *tz,
+            )
+        }
+        (
+            ValueData::Date(x, tz),
+            ArithmeticOperator::Add | ArithmeticOperator::Subtract,
+            ValueData::YearMonthDuration(d),
+        )
+        | (ValueData::YearMonthDuration(d), ArithmeticOperator::Add, ValueData::Date(x, tz)) => {
+            ValueData::Date(
+                months_delta(d.months(), op)
+                    .and_then(|m| {
+                        if m < 0 {
+                            x.checked_sub_months(Months::new(m.unsigned_abs() as u32))
+                        } else {
+                            x.checked_add_months(Months::new(m as u32))
+                        }
+                    })
+                    .ok_or_else(date_overflow)?,
+                *tz,
+            )
+        }
+        (
+            ValueData::DateTime(x, tz),
+            ArithmeticOperator::Add | ArithmeticOperator::Subtract,
+            ValueData::DayTimeDuration(d),
+        )
+        | (ValueData::DayTimeDuration(d), ArithmeticOperator::Add, ValueData::DateTime(x, tz)) => {
+            ValueData::DateTime(
+                x.checked_add_signed(time_delta(d.seconds(), op)?)
+                    .ok_or_else(date_overflow)?,
+                *tz,
+            )
+        }
+        (
+            ValueData::Date(x, tz),
+            ArithmeticOperator::Add | ArithmeticOperator::Subtract,
+            ValueData::DayTimeDuration(d),
+        )
+        | (ValueData::DayTimeDuration(d), ArithmeticOperator::Add, ValueData::Date(x, tz)) => {
+            // The date is treated as midnight at the start of the day, and the time is then discarded
+            ValueData::Date(
+                x.and_time(NaiveTime::MIN)
+                    .checked_add_signed(time_delta(d.seconds(), op)?)
+                    .ok_or_else(date_overflow)?
+                    .date(),
+                *tz,
+            )
+        }
+        (
+            ValueData::Time(x, tz),
+            ArithmeticOperator::Add | ArithmeticOperator::Subtract,
+            ValueData::DayTimeDuration(d),
+        )
+        | (ValueData::DayTimeDuration(d), ArithmeticOperator::Add, ValueData::Time(x, tz)) => {
+            // Times wrap around at midnight
+            let (t, _) = x.overflowing_add_signed(time_delta(d.seconds(), op)?);
+            ValueData::Time(t, *tz)
+        }
+        _ => {
+            return Err(error(
+                ErrorKind::TypeError,
+                "XPTY0004",
+                format!(
+                    "operator \"{}\" cannot be applied to {} and {}",
+                    op,
+                    a.value_type(),
+                    b.value_type()
+                ),
+            ));
+        }
+    };
+    Ok(ValueBuilder::new().value(result).build())
+}
+
+// The value of a numeric operand
+fn numeric(v: &ValueData) -> Option<f64> {
+    match v {
+        ValueData::Double(d) => Some(*d),
+        ValueData::Float(f) => Some(f64::from(*f)),
+        ValueData::Decimal(d) => d.to_f64(),
+        ValueData::Integer(_)
+        | ValueData::NonPositiveInteger(_)
+        | ValueData::NegativeInteger(_)
+        | ValueData::Long(_)
+        | ValueData::Int(_)
+        | ValueData::Short(_)
+        | ValueData::Byte(_)
+        | ValueData::NonNegativeInteger(_)
+        | ValueData::UnsignedLong(_)
+        | ValueData::UnsignedInt(_)
+        | ValueData::UnsignedShort(_)
+        | ValueData::UnsignedByte(_)
+        | ValueData::PositiveInteger(_) => v.to_string().parse().ok(),
+        _ => None,
+    }
+}
+
+// Multiply a number of months, rounding the result half up to a whole number of months
+fn scale_months(months: i64, n: f64) -> Result<i64, Error> {
+    if n.is_nan() {
+        return Err(nan());
+    }
+    let m = (months as f64 * n + 0.5).floor();
+    if m.is_finite() && m.abs() < i64::MAX as f64 {
+        Ok(m as i64)
+    } else {
+        Err(overflow())
+    }
+}
+
+// Multiply a number of seconds
+fn scale_seconds(seconds: Decimal, n: f64) -> Result<Decimal, Error> {
+    if n.is_nan() {
+        return Err(nan());
+    }
+    Decimal::from_f64(n)
+        .and_then(|d| seconds.checked_mul(d))
+        .map(|s| s.round_dp(9))
+        .ok_or_else(overflow)
+}
+
+// Adding a duration uses its months, subtracting uses their negation
+fn months_delta(months: i64, op: ArithmeticOperator) -> Option<i64> {
+    let m = if op == ArithmeticOperator::Subtract {
+        months.checked_neg()?
+    } else {
+        months
+    };
+    u32::try_from(m.unsigned_abs()).ok().map(|_| m)
+}
+
+// The chrono equivalent of a number of seconds, negated for subtraction
+fn time_delta(seconds: Decimal, op: ArithmeticOperator) -> Result<TimeDelta, Error> {
+    let s = if op == ArithmeticOperator::Subtract {
+        -seconds
+    } else {
+        seconds
+    };
+    let whole = s.trunc();
+    let nanos = ((s - whole) * Decimal::from(1_000_000_000)).trunc();
+    whole
+        .to_i64()
+        .and_then(TimeDelta::try_seconds)
+        .zip(nanos.to_i64())
+        .and_then(|(d, n)| d.checked_add(&TimeDelta::nanoseconds(n)))
+        .ok_or_else(date_overflow)
+}
+
+// The number of seconds in a chrono duration
+fn delta_seconds(d: TimeDelta) -> Decimal {
+    (Decimal::from(d.num_seconds()) + Decimal::new(i64::from(d.subsec_nanos()), 9)).normalize()
+}
+
+fn overflow() -> Error {
+    error(
+        ErrorKind::Unknown,
+        "FODT0002",
+        String::from("overflow in duration arithmetic"),
+    )
+}
+
+fn date_overflow() -> Error {
+    error(
+        ErrorKind::Unknown,
+        "FODT0001",
+        String::from("overflow in date/time arithmetic"),
+    )
+}
+
+fn nan() -> Error {
+    error(
+        ErrorKind::Unknown,
+        "FOCA0005",
+        String::from("a duration cannot be multiplied or divided by NaN"),
+    )
+}
+
+// Evaluate the argument of a function that expects a single date, time, or duration value of the given type.
+// Nodes are untyped, so their string value is cast to the expected type.
+fn temporal_argument<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    t: &Transform<N>,
+    datatype: &str,
+) -> Result<Option<ValueData>, Error> {
+    let s = ctxt.dispatch(stctxt, t)?;
+    let v = match s.as_slice() {
+        [] => return Ok(None),
+        [Item::Value(v)] => match v.value {
+            ValueData::DateTimeStamp(dt, tz) => ValueData::DateTime(dt, Some(tz)),
+            _ => v.value.clone(),
+        },
+        [Item::Node(n)] => {
+            return cast_value(&ValueData::String(n.to_string()), datatype).map(Some);
+        }
+        _ => {
+            return Err(error(
+                ErrorKind::TypeError,
+                "XPTY0004",
+                format!("expected a single xs:{} value", datatype),
+            ));
+        }
+    };
+    if matches!(
+        (datatype, &v),
+        ("dateTime", ValueData::DateTime(..))
+            | ("date", ValueData::Date(..))
+            | ("time", ValueData::Time(..))
+            | ("dayTimeDuration", ValueData::DayTimeDuration(_))
+            | (
+                "duration",
+                ValueData::Duration(_)
+                    | ValueData::YearMonthDuration(_)
+                    | ValueData::DayTimeDuration(_)
+            )
+    ) {
+        Ok(Some(v))
+    } else {
+        Err(error(
+            ErrorKind::TypeError,
+            "XPTY0004",
+            format!("expected an xs:{} value, not {}", datatype, v),
+        ))
+    }
+}
+
+/// XPath functions that extract a component of a date, time, or duration value, such as year-from-date and hours-from-duration.
+/// The datatype of the argument is "dateTime", "date", "time", or "duration".
+pub(crate) fn component<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    datatype: &str,
+    c: &DateTimeComponent,
+    t: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let Some(v) = temporal_argument(ctxt, stctxt, t, datatype)? else {
+        return Ok(vec![]);
+    };
+    let (date, time, tz) = match &v {
+        ValueData::DateTime(dt, tz) => (Some(dt.date()), Some(dt.time()), tz),
+        ValueData::Date(d, tz) => (Some(*d), None, tz),
+        ValueData::Time(t, tz) => (None, Some(*t), tz),
+        ValueData::Duration(d)
+        | ValueData::YearMonthDuration(d)
+        | ValueData::DayTimeDuration(d) => {
+            return Ok(vec![Item::Value(Rc::new(duration_component(d, c)))]);
+        }
+        _ => return Ok(vec![]),
+    };
+    let result = match (c, date, time, tz) {
+        (DateTimeComponent::Year, Some(d), _, _) => Value::from(i64::from(d.year())),
+        (DateTimeComponent::Month, Some(d), _, _) => Value::from(i64::from(d.month())),
+        (DateTimeComponent::Day, Some(d), _, _) => Value::from(i64::from(d.day())),
+        (DateTimeComponent::Hours, _, Some(t), _) => Value::from(i64::from(t.hour())),
+        (DateTimeComponent::Minutes, _, Some(t), _) => Value::from(i64::from(t.minute())),
+        (DateTimeComponent::Seconds, _, Some(t), _) => Value::from(
+            (Decimal::from(t.second()) + Decimal::new(i64::from(t.nanosecond()), 9)).normalize(),
+        ),
+        (DateTimeComponent::Timezone, _, _, Some(z)) => ValueBuilder::new()
+            .value(ValueData::DayTimeDuration(Duration::from_seconds(
+                Decimal::from(z.local_minus_utc()),
+            )))
+            .build(),
+        // A value without a timezone has no timezone component
+        _ => return Ok(vec![]),
+    };
+    Ok(vec![Item::Value(Rc::new(result))])
+}
+
+// A component of a duration. The seconds component includes any fractional seconds.
+fn duration_component(d: &Duration, c: &DateTimeComponent) -> Value {
+    let s = d.seconds();
+    match c {
+        DateTimeComponent::Year => Value::from(d.months() / 12),
+        DateTimeComponent::Month => Value::from(d.months() % 12),
+        DateTimeComponent::Day => Value::from(whole(s / Decimal::from(86400))),
+        DateTimeComponent::Hours => {
+            Value::from(whole(s % Decimal::from(86400) / Decimal::from(3600)))
+        }
+        DateTimeComponent::Minutes => {
+            Value::from(whole(s % Decimal::from(3600) / Decimal::from(60)))
+        }
+        DateTimeComponent::Seconds | DateTimeComponent::Timezone => {
+            Value::from((s % Decimal::from(60)).normalize())
+        }
+    }
+}
+
+// The integer part of a decimal
+fn whole(d: Decimal) -> i64 {
+    d.trunc().to_i64().unwrap_or(0)
+}
+
+/// XPath adjust-dateTime-to-timezone function.
+/// Without a timezone argument the value is adjusted to the implicit timezone; if the timezone is the empty sequence then the timezone is removed.
+/// A value without a timezone keeps its local time, otherwise the result is the same instant in the new timezone.
+/// The timezone must be between -PT14H and PT14H, and a whole number of minutes (FODT0003).
+pub(crate) fn adjust_date_time_to_timezone<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    t: &Transform<N>,
+    z: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let Some(ValueData::DateTime(dt, tz)) = temporal_argument(ctxt, stctxt, t, "dateTime")? else {
+        return Ok(vec![]);
+    };
+    let target = match z {
+        None => Some(ctxt.implicit_timezone()),
+        Some(u) => match temporal_argument(ctxt, stctxt, u, "dayTimeDuration")? {
+            Some(ValueData::DayTimeDuration(d)) => Some(offset(&d)?),
+            _ => None,
+        },
+    };
+    let result = match (tz, target) {
+        (Some(from), Some(to)) => dt
+            .checked_add_signed(TimeDelta::seconds(i64::from(
+                to.local_minus_utc() - from.local_minus_utc(),
+            )))
+            .ok_or_else(date_overflow)?,
+        _ => dt,
+    };
+    Ok(vec![Item::Value(Rc::new(
+        ValueBuilder::new()
+            .value(ValueData::DateTime(result, target))
+            .build(),
+    ))])
+}
+
+// The timezone given by a duration
+fn offset(d: &Duration) -> Result<FixedOffset, Error> {
+    d.seconds()
+        .to_i32()
+        .filter(|s| s.abs() <= 14 * 3600 && s % 60 == 0 && d.seconds().fract().is_zero())
+        .and_then(FixedOffset::east_opt)
+        .ok_or_else(|| {
+            error(
+                ErrorKind::Unknown,
+                "FODT0003",
+                format!("invalid timezone {}", d),
+            )
+        })
+}
@@ -0,0 +1,421 @@
+//! Higher-order functions, i.e. functions that take a function item as an argument.
+//! This includes the higher-order functions in the map and array namespaces.
+
+use chrono::FixedOffset;
+use std::cmp::Ordering;
+use std::rc::Rc;
+use url::Url;
+
+use crate::item::{Function, Item, Node, Sequence, SequenceTrait};
+use crate::transform::Transform;
+use crate::transform::arrays::array_of;
+use crate::transform::callable::call_function;
+use crate::transform::collation::{Collation, collation_argument};
+use crate::transform::context::{Context, StaticContext};
+use crate::transform::maps::{atomize, map_of};
+use crate::transform::types::error;
+use crate::value::{Operator, Value, ValueData};
+use crate::xdmerror::{Error, ErrorKind};
+
+// The single function item in a sequence.
+fn function_of<N: Node>(s: Sequence<N>) -> Result<Rc<Function<N>>, Error> {
+    match s.as_slice() {
+        [Item::Function(f)] => Ok(f.clone()),
+        _ => Err(error(
+            ErrorKind::TypeError,
+            "XPTY0004",
+            String::from("expected a single function item"),
+        )),
+    }
+}
+
+// The result of a predicate function must be a single boolean value.
+fn predicate_result<N: Node>(s: Sequence<N>) -> Result<bool, Error> {
+    if let [Item::Value(v)] = s.as_slice()
+        && let ValueData::Boolean(b) = v.value
+    {
+        Ok(b)
+    } else {
+        Err(error(
+            ErrorKind::TypeError,
+            "XPTY0004",
+            String::from("the result of a predicate function must be a single boolean value"),
+        ))
+    }
+}
+
+/// fn:for-each()
+pub(crate) fn for_each<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    f: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let seq = ctxt.dispatch(stctxt, s)?;
+    let func = function_of(ctxt.dispatch(stctxt, f)?)?;
+    let mut result = vec![];
+    for i in seq {
+        result.append(&mut call_function(ctxt, stctxt, &func, vec![vec![i]])?)
+    }
+    Ok(result)
+}
+
+/// fn:filter()
+pub(crate) fn filter<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    f: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let seq = ctxt.dispatch(stctxt, s)?;
+    let func = function_of(ctxt.dispatch(stctxt, f)?)?;
+    let mut result = vec![];
+    for i in seq {
+        if predicate_result(call_function(ctxt, stctxt, &func, vec![vec![i.clone()]])?)? {
+            result.push(i)
+        }
+    }
+    Ok(result)
+}
+
+/// fn:fold-left()
+pub(crate) fn fold_left<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    z: &Transform<N>,
+    f: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let seq = ctxt.dispatch(stctxt, s)?;
+    let zero = ctxt.dispatch(stctxt, z)?;
+    let func = function_of(ctxt.dispatch(stctxt, f)?)?;
+    seq.into_iter().try_fold(zero, |acc, i| {
+        call_function(ctxt, stctxt, &func, vec![acc, vec![i]])
+    })
+}
+
+/// fn:fold-right()
+pub(crate) fn fold_right<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    z: &Transform<N>,
+    f: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let seq = ctxt.dispatch(stctxt, s)?;
+    let zero = ctxt.dispatch(stctxt, z)?;
+    let func = function_of(ctxt.dispatch(stctxt, f)?)?;
+    seq.into_iter().rev().try_fold(zero, |acc, i| {
+        call_function(ctxt, stctxt, &func, vec![vec![i], acc])
+    })
+}
+
+/// fn:for-each-pair()
+pub(crate) fn for_each_pair<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s1: &Transform<N>,
+    s2: &Transform<N>,
+    f: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let seq1 = ctxt.dispatch(stctxt, s1)?;
+    let seq2 = ctxt.dispatch(stctxt, s2)?;
+    let func = function_of(ctxt.dispatch(stctxt, f)?)?;
+    let mut result = vec![];
+    for (i, j) in seq1.into_iter().zip(seq2) {
+        result.append(&mut call_function(
+            ctxt,
+            stctxt,
+            &func,
+            vec![vec![i], vec![j]],
+        )?)
+    }
+    Ok(result)
+}
+
+/// fn:sort()
+pub(crate) fn sort<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    c: &Option<Box<Transform<N>>>,
+    k: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let seq = ctxt.dispatch(stctxt, s)?;
+    let sorted = sort_sequences(
+        ctxt,
+        stctxt,
+        seq.into_iter().map(|i| vec![i]).collect(),
+        c,
+        k,
+    )?;
+    Ok(sorted.into_iter().flatten().collect())
+}
+
+/// fn:apply()
+pub(crate) fn apply<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    f: &Transform<N>,
+    a: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let func = function_of(ctxt.dispatch(stctxt, f)?)?;
+    let args = array_of(ctxt.dispatch(stctxt, a)?)?;
+    call_function(ctxt, stctxt, &func, args.to_vec())
+}
+
+/// map:for-each()
+pub(crate) fn map_for_each<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    m: &Transform<N>,
+    f: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let m = map_of(ctxt.dispatch(stctxt, m)?)?;
+    let func = function_of(ctxt.dispatch(stctxt, f)?)?;
+    let mut result = vec![];
+    for (k, v) in m.iter() {
+        result.append(&mut call_function(
+            ctxt,
+            stctxt,
+            &func,
+            vec![vec![Item::Value(k.clone())], v.clone()],
+        )?)
+    }
+    Ok(result)
+}
+
+/// array:for-each()
+pub(crate) fn array_for_each<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    a: &Transform<N>,
+    f: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let a = array_of(ctxt.dispatch(stctxt, a)?)?;
+    let func = function_of(ctxt.dispatch(stctxt, f)?)?;
+    let result = a
+        .iter()
+        .map(|m| call_function(ctxt, stctxt, &func, vec![m.clone()]))
+        .collect::<Result<Vec<Sequence<N>>, Error>>()?;
+    Ok(vec![Item::Array(Rc::new(result))])
+}
+
+/// array:filter()
+pub(crate) fn array_filter<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    a: &Transform<N>,
+    f: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let a = array_of(ctxt.dispatch(stctxt, a)?)?;
+    let func = function_of(ctxt.dispatch(stctxt, f)?)?;
+    let mut result = vec![];
+    for m in a.iter() {
+        if predicate_result(call_function(ctxt, stctxt, &func, vec![m.clone()])?)? {
+            result.push(m.clone())
+        }
+    }
+    Ok(vec![Item::Array(Rc::new(result))])
+}
+
+/// array:fold-left()
+pub(crate) fn array_fold_left<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    a: &Transform<N>,
+    z: &Transform<N>,
+    f: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let a = array_of(ctxt.dispatch(stctxt, a)?)?;
+    let zero = ctxt.dispatch(stctxt, z)?;
+    let func = function_of(ctxt.dispatch(stctxt, f)?)?;
+    a.iter().try_fold(zero, |acc, m| {
+        call_function(ctxt, stctxt, &func, vec![acc, m.clone()])
+    })
+}
+
+/// array:fold-right()
+pub(crate) fn array_fold_right<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    a: &Transform<N>,
+    z: &Transform<N>,
+    f: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let a = array_of(ctxt.dispatch(stctxt, a)?)?;
+    let zero = ctxt.dispatch(stctxt, z)?;
+    let func = function_of(ctxt.dispatch(stctxt, f)?)?;
+    a.iter().rev().try_fold(zero, |acc, m| {
+        call_function(ctxt, stctxt, &func, vec![m.clone(), acc])
+    })
+}
+
+/// array:for-each-pair()
+pub(crate) fn array_for_each_pair<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    a1: &Transform<N>,
+    a2: &Transform<N>,
+    f: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let a1 = array_of(ctxt.dispatch(stctxt, a1)?)?;
+    let a2 = array_of(ctxt.dispatch(stctxt, a2)?)?;
+    let func = function_of(ctxt.dispatch(stctxt, f)?)?;
+    let result = a1
+        .iter()
+        .zip(a2.iter())
+        .map(|(m1, m2)| call_function(ctxt, stctxt, &func, vec![m1.clone(), m2.clone()]))
+        .collect::<Result<Vec<Sequence<N>>, Error>>()?;
+    Ok(vec![Item::Array(Rc::new(result))])
+}
+
+/// array:sort()
+pub(crate) fn array_sort<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    a: &Transform<N>,
+    c: &Option<Box<Transform<N>>>,
+    k: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let a = array_of(ctxt.dispatch(stctxt, a)?)?;
+    let sorted = sort_sequences(ctxt, stctxt, a.to_vec(), c, k)?;
+    Ok(vec![Item::Array(Rc::new(sorted))])
+}
+
+// Sort a vector of sequences, using the atomized value of each sequence as the sort key.
+// If a key function is given then it is applied to each sequence to compute its sort key.
+// The sort is stable.
+fn sort_sequences<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    v: Vec<Sequence<N>>,
+    c: &Option<Box<Transform<N>>>,
+    k: &Option<Box<Transform<N>>>,
+) -> Result<Vec<Sequence<N>>, Error> {
+    let collation = collation_argument(ctxt, stctxt, c)?;
+    let func = match k {
+        Some(t) => Some(function_of(ctxt.dispatch(stctxt, t)?)?),
+        None => None,
+    };
+    let mut keyed = vec![];
+    for s in v {
+        let key = match &func {
+            Some(f) => atomize(&call_function(ctxt, stctxt, f, vec![s.clone()])?)?,
+            None => atomize(&s)?,
+        };
+        keyed.push((key, s))
+    }
+    let tz = ctxt.implicit_timezone();
+    let mut err = None;
+    keyed.sort_by(|(a, _), (b, _)| {
+        compare_keys(a, b, &collation, tz).unwrap_or_else(|e| {
+            err.get_or_insert(e);
+            Ordering::Equal
+        })
+    });
+    match err {
+        Some(e) => Err(e),
+        None => Ok(keyed.into_iter().map(|(_, s)| s).collect()),
+    }
+}
+
+// Compare two sort keys. Keys are compared item by item; a shorter key sorts before a longer key.
+// Strings are compared using the collation. Dates and times without a timezone are in the implicit timezone.
+fn compare_keys(
+    a: &[Rc<Value>],
+    b: &[Rc<Value>],
+    collation: &Collation,
+    implicit: FixedOffset,
+) -> Result<Ordering, Error> {
+    for (x, y) in a.iter().zip(b) {
+        if let (ValueData::String(s), ValueData::String(t)) = (&x.value, &y.value) {
+            match collation.compare(s, t) {
+                Ordering::Equal => continue,
+                o => return Ok(o),
+            }
+        }
+        if x.compare_with_timezone(y, Operator::LessThan, implicit)? {
+            return Ok(Ordering::Less);
+        }
+        if y.compare_with_timezone(x, Operator::LessThan, implicit)? {
+            return Ok(Ordering::Greater);
+        }
+    }
+    Ok(a.len().cmp(&b.len()))
+}
@@ -0,0 +1,773 @@
+//! JSON: parsing JSON text into maps and arrays, and converting between JSON and its XML representation.
+
+use std::rc::Rc;
+
+use qualname::{NamespaceUri, NcName, QName};
+use url::Url;
+
+use crate::item::{Item, Map, Node, NodeType, Sequence, json_string};
+use crate::parser::common::is_char10;
+use crate::transform::Transform;
+use crate::transform::context::{Context, StaticContext};
+use crate::transform::maps::{atomize_one, map_of};
+use crate::transform::types::error;
+use crate::validators::datatypes::double;
+use crate::value::{Value, ValueData};
+use crate::xdmerror::{Error, ErrorKind};
+
+/// The namespace of the XML representation of JSON.
+pub(crate) const JSONNS: &str = "http://www.w3.org/2005/xpath-functions";
+
+/// A JSON value, as parsed from JSON text.
+enum Json {
+    Null,
+    Boolean(bool),
+    /// The lexical form of the number.
+    Number(String),
+    /// The string, and whether it contains JSON escape sequences.
+    String(String, bool),
+    Array(Vec<Json>),
+    /// The members of an object: the key, whether the key contains JSON escape sequences, and the value.
+    Object(Vec<(String, bool, Json)>),
+}
+
+/// The options that control JSON parsing.
+struct Options {
+    liberal: bool,
+    duplicates: String,
+    escape: bool,
+}
+
+// Read the options map. The duplicates option must be one of the given values.
+fn options<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    o: &Option<Box<Transform<N>>>,
+    duplicates: &[&str],
+) -> Result<Options, Error> {
+    let mut result = Options {
+        liberal: false,
+        duplicates: String::from(duplicates[0]),
+        escape: false,
+    };
+    let Some(o) = o else {
+        return Ok(result);
+    };
+    let m = map_of(ctxt.dispatch(stctxt, o)?)?;
+    let boolean = |name: &str| -> Result<Option<bool>, Error> {
+        match m.get(&Value::from(name)) {
+            Some(s) => match atomize_one(s)?.value {
+                ValueData::Boolean(b) => Ok(Some(b)),
+                _ => Err(error(
+                    ErrorKind::TypeError,
+                    "XPTY0004",
+                    format!("the {} option must be a boolean", name),
+                )),
+            },
+            None => Ok(None),
+        }
+    };
+    if let Some(b) = boolean("liberal")? {
+        result.liberal = b
+    }
+    if let Some(b) = boolean("escape")? {
+        result.escape = b
+    }
+    if let Some(d) = m.get(&Value::from("duplicates")) {
+        result.duplicates = atomize_one(d)?.to_string()
+    }
+    if !duplicates.contains(&result.duplicates.as_str()) {
+        return Err(error(
+            ErrorKind::TypeError,
+            "FOJS0005",
+            format!(
+                "invalid value \"{}\" for the duplicates option",
+                result.duplicates
+            ),
+        ));
+    }
+    Ok(result)
+}
+
+/// A recursive-descent parser for JSON text.
+struct JsonParser {
+    input: Vec<char>,
+    pos: usize,
+    liberal: bool,
+    escape: bool,
+}
+
+impl JsonParser {
+    fn new(text: &str, o: &Options) -> Self {
+        JsonParser {
+            input: text.chars().collect(),
+            pos: 0,
+            liberal: o.liberal,
+            escape: o.escape,
+        }
+    }
+    fn fail(&self, msg: &str) -> Error {
+        error(
+            ErrorKind::Unknown,
+            "FOJS0001",
+            format!("invalid JSON at offset {}: {}", self.pos, msg),
+        )
+    }
+    fn peek(&self) -> Option<char> {
+        self.input.get(self.pos).copied()
+    }
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.pos += 1
+        }
+    }
+    fn expect(&mut self, c: char) -> Result<(), Error> {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.fail(format!("expected \"{}\"", c).as_str()))
+        }
+    }
+    // The whole of the input must be a single JSON value.
+    fn document(&mut self) -> Result<Json, Error> {
+        if self.peek() == Some('\u{FEFF}') {
+            self.pos += 1
+        }
+        let v = self.value()?;
+        self.skip_whitespace();
+        if self.pos < self.input.len() {
+            Err(self.fail("unexpected text after the JSON value"))
+        } else {
+            Ok(v)
+        }
+    }
+    fn value(&mut self) -> Result<Json, Error> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => self.string().map(|(s, e)| Json::String(s, e)),
+            Some('-' | '0'..='9') => self.number(),
+            Some('+') if self.liberal => self.number(),
+            Some(_) => {
+                for (word, v) in [
+                    ("true", Json::Boolean(true)),
+                    ("false", Json::Boolean(false)),
+                    ("null", Json::Null),
+                ] {
+                    if self.input[self.pos..].starts_with(&word.chars().collect::<Vec<_>>()) {
+                        self.pos += word.len();
+                        return Ok(v);
+                    }
+                }
+                Err(self.fail("expected a JSON value"))
+            }
+            None => Err(self.fail("unexpected end of input")),
+        }
+    }
+    // Parse the members or elements of an object or array, up to the closing character.
+    fn members<T>(
+        &mut self,
+        close: char,
+        mut member: impl FnMut(&mut Self) -> Result<T, Error>,
+    ) -> Result<Vec<T>, Error> {
+        self.pos += 1;
+        let mut result = vec![];
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(close) && (result.is_empty() || self.liberal) {
+                self.pos += 1;
+                return Ok(result);
+            }
+            result.push(member(self)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(c) if c == close => {
+                    self.pos += 1;
+                    return Ok(result);
+                }
+                _ => return Err(self.fail(format!("expected \",\" or \"{}\"", close).as_str())),
+            }
+        }
+    }
+    fn array(&mut self) -> Result<Json, Error> {
+        self.members(']', |p| p.value()).map(Json::Array)
+    }
+    fn object(&mut self) -> Result<Json, Error> {
+        self.members('}', |p| {
+            p.skip_whitespace();
+            if p.peek() != Some('"') {
+                return Err(p.fail("expected a string key"));
+            }
+            let (k, e) = p.string()?;
+            p.expect(':')?;
+            Ok((k, e, p.value()?))
+        })
+        .map(Json::Object)
+    }
+    fn number(&mut self) -> Result<Json, Error> {
+        let start = self.pos;
+        let digits = |p: &mut Self| {
+            let s = p.pos;
+            while matches!(p.peek(), Some('0'..='9')) {
+                p.pos += 1
+            }
+            p.pos - s
+        };
+        if matches!(self.peek(), Some('-' | '+')) {
+            self.pos += 1
+        }
+        let int_start = self.pos;
+        let n = digits(self);
+        if n == 0 || (n > 1 && self.input[int_start] == '0' && !self.liberal) {
+            return Err(self.fail("invalid number"));
+        }
+        if self.peek() == Some('.') {
+            self.pos += 1;
+            if digits(self) == 0 {
+                return Err(self.fail("invalid number"));
+            }
+        }
+        if matches!(self.peek(), Some('e' | 'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some('-' | '+')) {
+                self.pos += 1
+            }
+            if digits(self) == 0 {
+                return Err(self.fail("invalid number"));
+            }
+        }
+        Ok(Json::Number(self.input[start..self.pos].iter().collect()))
+    }
+    // Parse a string, returning its value and whether escape sequences were retained.
+    fn string(&mut self) -> Result<(String, bool), Error> {
+        self.pos += 1;
+        // Characters, or UTF-16 code units from \u escapes
+        let mut units: Vec<Result<char, u16>> = vec![];
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(self.fail("unterminated string"));
+            };
+            self.pos += 1;
+            match c {
+                '"' => break,
+                '\\' => {
+                    let Some(e) = self.peek() else {
+                        return Err(self.fail("unterminated string"));
+                    };
+                    self.pos += 1;
+                    let unit = match e {
+                        '"' => Ok('"'),
+                        '\\' => Ok('\\'),
+                        '/' => Ok('/'),
+                        'b' => Ok('\u{8}'),
+                        'f' => Ok('\u{C}'),
+                        'n' => Ok('\n'),
+                        'r' => Ok('\r'),
+                        't' => Ok('\t'),
+                        'u' => {
+                            let hex: String = self.input.iter().skip(self.pos).take(4).collect();
+                            let u = (hex.len() == 4 && hex.chars().all(|h| h.is_ascii_hexdigit()))
+                                .then(|| u16::from_str_radix(&hex, 16).ok())
+                                .flatten()
+                                .ok_or_else(|| self.fail("invalid \\u escape"))?;
+                            self.pos += 4;
+                            Err(u)
+                        }
+                        _ => return Err(self.fail("invalid escape sequence")),
+                    };
+                    units.push(unit)
+                }
+                c if (c as u32) < 0x20 && !self.liberal => {
+                    return Err(self.fail("unescaped control character in string"));
+                }
+                c => units.push(Ok(c)),
+            }
+        }
+        // Combine surrogate pairs, then represent special characters
+        let mut result = String::new();
+        let mut escaped = false;
+        let mut i = 0;
+        while i < units.len() {
+            let c = match units[i] {
+                Ok(c) => Ok(c),
+                Err(hi @ 0xD800..=0xDBFF) => match units.get(i + 1) {
+                    Some(Err(lo @ 0xDC00..=0xDFFF)) => {
+                        i += 1;
+                        Ok(char::from_u32(
+                            0x10000 + ((hi as u32 - 0xD800) << 10) + (*lo as u32 - 0xDC00),
+                        )
+                        .unwrap())
+                    }
+                    _ => Err(hi),
+                },
+                Err(u) => char::from_u32(u as u32).ok_or(u),
+            };
+            i += 1;
+            match (c, self.escape) {
+                (Ok(c), true) if c == '\\' || special(c) => {
+                    escaped = true;
+                    result.push_str(escape_char(c).as_str())
+                }
+                (Ok(c), false) if !is_char10(&c) => result.push('\u{FFFD}'),
+                (Ok(c), _) => result.push(c),
+                (Err(u), true) => {
+                    escaped = true;
+                    result.push_str(format!("\\u{:04X}", u).as_str())
+                }
+                (Err(_), false) => result.push('\u{FFFD}'),
+            }
+        }
+        Ok((result, escaped))
+    }
+}
+
+// Characters that are represented by escape sequences when the escape option is used.
+fn special(c: char) -> bool {
+    (c as u32) < 0x20 || ('\u{7F}'..='\u{9F}').contains(&c) || !is_char10(&c)
+}
+
+// The JSON escape sequence for a character.
+fn escape_char(c: char) -> String {
+    match c {
+        '\\' => String::from("\\\\"),
+        '"' => String::from("\\\""),
+        '\u{8}' => String::from("\\b"),
+        '\u{C}' => String::from("\\f"),
+        '\n' => String::from("\\n"),
+        '\r' => String::from("\\r"),
+        '\t' => String::from("\\t"),
+        c => format!("\\u{:04X}", c as u32),
+    }
+}
+
+// Convert a parsed JSON value to an XDM value. A JSON null is the empty sequence.
+fn to_xdm<N: Node>(j: Json, duplicates: &str) -> Result<Sequence<N>, Error> {
+    Ok(match j {
+        Json::Null => vec![],
+        Json::Boolean(b) => vec![Item::Value(Rc::new(Value::from(b)))],
+        Json::Number(n) => vec![Item::Value(Rc::new(Value::from(
+            n.trim_start_matches('+').parse::<f64>().unwrap_or(f64::NAN),
+        )))],
+        Json::String(s, _) => vec![Item::Value(Rc::new(Value::from(s)))],
+        Json::Array(a) => vec![Item::Array(Rc::new(
+            a.into_iter()
+                .map(|m| to_xdm(m, duplicates))
+                .collect::<Result<Vec<_>, Error>>()?,
+        ))],
+        Json::Object(o) => {
+            let mut m = Map::new();
+            for (k, _, v) in o {
+                let key = Value::from(k);
+                if m.get(&key).is_some() {
+                    match duplicates {
+                        "reject" => return Err(duplicate_key(&key.to_string())),
+                        "use-first" => continue,
+                        _ => {}
+                    }
+                }
+                m.insert(Rc::new(key), to_xdm(v, duplicates)?);
+            }
+            vec![Item::Map(Rc::new(m))]
+        }
+    })
+}
+
+fn duplicate_key(k: &str) -> Error {
+    error(
+        ErrorKind::Unknown,
+        "FOJS0003",
+        format!("duplicate key \"{}\"", k),
+    )
+}
+
+// The JSON text that is the first argument of parse-json or json-to-xml.
+fn text_argument<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    t: &Transform<N>,
+) -> Result<Option<String>, Error> {
+    let s = ctxt.dispatch(stctxt, t)?;
+    if s.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(atomize_one(&s)?.to_string()))
+    }
+}
+
+/// fn:parse-json()
+pub(crate) fn parse_json<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    t: &Transform<N>,
+    o: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let o = options(ctxt, stctxt, o, &["use-first", "use-last", "reject"])?;
+    match text_argument(ctxt, stctxt, t)? {
+        Some(text) => to_xdm(JsonParser::new(&text, &o).document()?, &o.duplicates),
+        None => Ok(vec![]),
+    }
+}
+
+/// fn:json-doc(). A relative URI is resolved against the base URI of the context.
+pub(crate) fn json_doc<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    href: &Transform<N>,
+    o: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let o = options(ctxt, stctxt, o, &["use-first", "use-last", "reject"])?;
+    let Some(href) = text_argument(ctxt, stctxt, href)? else {
+        return Ok(vec![]);
+    };
+    let Some(h) = &mut stctxt.fetcher else {
+        return Err(Error::new(
+            ErrorKind::StaticAbsent,
+            "function to resolve URI not supplied",
+        ));
+    };
+    // A relative URI is resolved against the base URI of the context
+    let text = ctxt
+        .base_url
+        .as_ref()
+        .map_or_else(|| Url::parse(href.as_str()), |b| b.join(href.as_str()))
+        .map_err(|e| e.to_string())
+        .and_then(|url| h(&url).map_err(|e| e.to_string()))
+        .map_err(|e| {
+            error(
+                ErrorKind::Unknown,
+                "FOUT1170",
+                format!("unable to retrieve \"{}\": {}", href, e),
+            )
+        })?;
+    to_xdm(JsonParser::new(&text, &o).document()?, &o.duplicates)
+}
+
+// A name in the namespace of the XML representation of JSON.
+fn json_name(local: &str) -> QName {
+    QName::new_from_parts(
+        NcName::try_from(local).unwrap(),
+        Some(NamespaceUri::try_from(JSONNS).unwrap()),
+    )
+}
+
+// A name in no namespace.
+fn attribute_name(local: &str) -> QName {
+    QName::from_local_name(NcName::try_from(local).unwrap())
+}
+
+// Construct the XML representation of a JSON value.
+// key is the key of the value if it is a member of an object, and whether the key contains escape sequences.
+fn to_xml<N: Node>(
+    doc: &N,
+    j: &Json,
+    key: Option<(&str, bool)>,
+    duplicates: &str,
+) -> Result<N, Error> {
+    let (name, text, escaped) = match j {
+        Json::Null => ("null", None, false),
+        Json::Boolean(b) => ("boolean", Some(b.to_string()), false),
+        Json::Number(n) => ("number", Some(n.clone()), false),
+        Json::String(s, e) => ("string", Some(s.clone()), *e),
+        Json::Array(_) => ("array", None, false),
+        Json::Object(_) => ("map", None, false),
+    };
+    let mut e = doc.new_element(json_name(name))?;
+    if let Some((k, ke)) = key {
+        e.add_attribute(doc.new_attribute(attribute_name("key"), Rc::new(Value::from(k)))?)?;
+        if ke {
+            e.add_attribute(
+                doc.new_attribute(attribute_name("escaped-key"), Rc::new(Value::from(true)))?,
+            )?;
+        }
+    }
+    if escaped {
+        e.add_attribute(doc.new_attribute(attribute_name("escaped"), Rc::new(Value::from(true)))?)?;
+    }
+    if let Some(t) = text
+        && !t.is_empty()
+    {
+        e.push(doc.new_text(Rc::new(Value::from(t)))?)?;
+    }
+    match j {
+        Json::Array(a) => {
+            for m in a {
+                e.push(to_xml(doc, m, None, duplicates)?)?
+            }
+        }
+        Json::Object(o) => {
+            let mut seen: Vec<&str> = vec![];
+            for (k, ke, v) in o {
+                if seen.contains(&k.as_str()) {
+                    match duplicates {
+                        "reject" => return Err(duplicate_key(k)),
+                        "use-first" => continue,
+                        _ => {}
+                    }
+                }
+                seen.push(k.as_str());
+                e.push(to_xml(doc, v, Some((k.as_str(), *ke)), duplicates)?)?
+            }
+        }
+        _ => {}
+    }
+    Ok(e)
+}
+
+/// fn:json-to-xml()
+pub(crate) fn json_to_xml<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    t: &Transform<N>,
+    o: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let o = options(ctxt, stctxt, o, &["retain", "use-first", "reject"])?;
+    let Some(text) = text_argument(ctxt, stctxt, t)? else {
+        return Ok(vec![]);
+    };
+    let j = JsonParser::new(&text, &o).document()?;
+    let mut doc = N::new_document();
+    let root = to_xml(&doc, &j, None, &o.duplicates)?;
+    root.add_namespace(doc.new_namespace(NamespaceUri::try_from(JSONNS).unwrap(), None, true)?)?;
+    doc.push(root)?;
+    Ok(vec![Item::Node(doc)])
+}
+
+fn invalid_xml(msg: String) -> Error {
+    error(ErrorKind::Unknown, "FOJS0006", msg)
+}
+
+// The element children of an element. Text content other than whitespace is not allowed.
+fn element_children<N: Node>(n: &N) -> Result<Vec<N>, Error> {
+    n.child_iter().try_fold(vec![], |mut acc, c| {
+        match c.node_type() {
+            NodeType::Element => acc.push(c),
+            NodeType::Text if !c.to_string().trim().is_empty() => {
+                return Err(invalid_xml(String::from(
+                    "unexpected text content in the XML representation of JSON",
+                )));
+            }
+            _ => {}
+        }
+        Ok(acc)
+    })
+}
+
+// Whether an attribute in no namespace has the value true.
+fn flag<N: Node>(n: &N, name: &str) -> Result<bool, Error> {
+    match n.get_attribute_node(&attribute_name(name)) {
+        None => Ok(false),
+        Some(a) => match a.to_string().trim() {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            v => Err(invalid_xml(format!(
+                "invalid value \"{}\" for the {} attribute",
+                v, name
+            ))),
+        },
+    }
+}
+
+// Serialise a string that may contain escape sequences.
+fn escaped_string(s: &str) -> Result<String, Error> {
+    let invalid = || {
+        error(
+            ErrorKind::Unknown,
+            "FOJS0007",
+            format!("invalid escape sequence in \"{}\"", s),
+        )
+    };
+    let mut result = String::from("\"");
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                result.push('\\');
+                match chars.next() {
+                    Some(e @ ('"' | '\\' | '/' | 'b' | 'f' | 'n' | 'r' | 't')) => result.push(e),
+                    Some('u') => {
+                        let hex: String = chars.by_ref().take(4).collect();
+                        if hex.len() != 4 || !hex.chars().all(|h| h.is_ascii_hexdigit()) {
+                            return Err(invalid());
+                        }
+                        result.push('u');
+                        result.push_str(&hex)
+                    }
+                    _ => return Err(invalid()),
+                }
+            }
+            c if c == '"' || (c as u32) < 0x20 => result.push_str(escape_char(c).as_str()),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    Ok(result)
+}
+
+// The value of a string, with any escape sequences resolved, for detecting duplicate keys.
+fn unescaped(s: &str) -> String {
+    let mut p = JsonParser::new(
+        format!("\"{}\"", s).as_str(),
+        &Options {
+            liberal: true,
+            duplicates: String::new(),
+            escape: false,
+        },
+    );
+    p.string().map_or_else(|_| s.to_string(), |(u, _)| u)
+}
+
+// Serialise an element of the XML representation of JSON.
+fn to_json<N: Node>(n: &N) -> Result<String, Error> {
+    let name = n
+        .name()
+        .ok_or_else(|| invalid_xml(String::from("expected an element")))?;
+    if name.namespace_uri().map(|u| u.to_string()) != Some(String::from(JSONNS)) {
+        return Err(invalid_xml(format!(
+            "element \"{}\" is not in the namespace {}",
+            name, JSONNS
+        )));
+    }
+    let leaf = |n: &N| -> Result<String, Error> {
+        if element_children(n)?.is_empty() {
+            Ok(n.to_string())
+        } else {
+            Err(invalid_xml(format!(
+                "element \"{}\" must not have element children",
+                name
+            )))
+        }
+    };
+    match name.local_name().to_string().as_str() {
+        "null" => {
+            if leaf(n)?.trim().is_empty() {
+                Ok(String::from("null"))
+            } else {
+                Err(invalid_xml(String::from("null must be empty")))
+            }
+        }
+        "boolean" => match leaf(n)?.trim() {
+            "true" | "1" => Ok(String::from("true")),
+            "false" | "0" => Ok(String::from("false")),
+            v => Err(invalid_xml(format!("invalid boolean \"{}\"", v))),
+        },
+        "number" => match double(&leaf(n)?) {
+            Some(d) if d.is_finite() => Ok(Value::from(d).to_string()),
+            _ => Err(invalid_xml(format!("invalid number \"{}\"", n.to_string()))),
+        },
+        "string" => {
+            let s = leaf(n)?;
+            if flag(n, "escaped")? {
+                escaped_string(&s)
+            } else {
+                Ok(json_string(&s))
+            }
+        }
+        "array" => element_children(n)?
+            .iter()
+            .map(to_json)
+            .collect::<Result<Vec<_>, Error>>()
+            .map(|m| format!("[{}]", m.join(","))),
+        "map" => {
+            let mut keys: Vec<String> = vec![];
+            let mut members = vec![];
+            for c in element_children(n)? {
+                let Some(k) = c.get_attribute_node(&attribute_name("key")) else {
+                    return Err(invalid_xml(String::from(
+                        "a member of a map must have a key attribute",
+                    )));
+                };
+                let k = k.to_string();
+                let (key, value) = if flag(&c, "escaped-key")? {
+                    (unescaped(&k), escaped_string(&k)?)
+                } else {
+                    (k.clone(), json_string(&k))
+                };
+                if keys.contains(&key) {
+                    return Err(invalid_xml(format!("duplicate key \"{}\"", key)));
+                }
+                keys.push(key);
+                members.push(format!("{}:{}", value, to_json(&c)?));
+            }
+            Ok(format!("{{{}}}", members.join(",")))
+        }
+        _ => Err(invalid_xml(format!(
+            "element \"{}\" is not part of the XML representation of JSON",
+            name
+        ))),
+    }
+}
+
+/// fn:xml-to-json()
+pub(crate) fn xml_to_json<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    t: &Transform<N>,
+    o: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    // The only option is indent, and indentation is not performed
+    if let Some(o) = o {
+        map_of(ctxt.dispatch(stctxt, o)?)?;
+    }
+    let n = match ctxt.dispatch(stctxt, t)?.as_slice() {
+        [] => return Ok(vec![]),
+        [Item::Node(n)] => n.clone(),
+        _ => {
+            return Err(error(
+                ErrorKind::TypeError,
+                "XPTY0004",
+                String::from("expected a single node"),
+            ));
+        }
+    };
+    let e = match n.node_type() {
+        NodeType::Element => n,
+        NodeType::Document => match element_children(&n)?.as_slice() {
+            [e] => e.clone(),
+            _ => {
+                return Err(invalid_xml(String::from(
+                    "the document must have a single element child",
+                )));
+            }
+        },
+        _ => {
+            return Err(invalid_xml(String::from(
+                "the argument must be a document or element",
+            )));
+        }
+    };
+    Ok(vec![Item::Value(Rc::new(Value::from(to_json(&e)?)))])
+}
@@ -2,7 +2,9 @@
 
 use crate::item::{Node, Sequence};
 use crate::transform::Transform;
+use crate::transform::collation::find_collation;
 use crate::transform::context::{Context, ContextBuilder, StaticContext};
+use crate::transform::logic::document_order;
 use crate::xdmerror::Error;
 use crate::{Item, SequenceTrait};
 use std::collections::HashMap;
@@ -62,6 +64,7 @@ pub(crate) fn populate_key_values<
 }
 
 /// Look up the value of a key. The value is evaluated to a Sequence. The interpretation of the sequence depends on the key's composite setting.
+/// If the key was declared with a collation then key values are compared using that collation.
 /// TODO: support composite keys
 pub fn key<
     N: Node,
@@ -75,17 +78,34 @@ pub fn key<
     v: &Box<Transform<N>>,
 ) -> Result<Sequence<N>, Error> {
     let keyname = ctxt.dispatch(stctxt, name)?.to_string();
-    Ok(ctxt.dispatch(stctxt, v)?.iter().fold(vec![], |mut acc, s| {
-        if let Some(u) = ctxt.key_values.get(&keyname) {
-            if let Some(a) = u.get(&s.to_string()) {
-                let mut b: Sequence<N> = a.iter().map(|n| Item::Node(n.clone())).collect();
-                acc.append(&mut b);
-                acc
+    let collation = find_collation(stctxt, &ctxt.key_collations.get(&keyname).cloned())?;
+    let values = ctxt.dispatch(stctxt, v)?;
+    if collation.is_codepoint() {
+        Ok(values.iter().fold(vec![], |mut acc, s| {
+            if let Some(u) = ctxt.key_values.get(&keyname) {
+                if let Some(a) = u.get(&s.to_string()) {
+                    let mut b: Sequence<N> = a.iter().map(|n| Item::Node(n.clone())).collect();
+                    acc.append(&mut b);
+                    acc
+                } else {
+                    acc
+                }
             } else {
                 acc
             }
-        } else {
-            acc
+        }))
+    } else {
+        // Several key values may be equal according to the collation,
+        // so the nodes are gathered from each of them and then put into document order.
+        let mut nodes = vec![];
+        if let Some(u) = ctxt.key_values.get(&keyname) {
+            for s in values {
+                let s = s.to_string();
+                u.iter()
+                    .filter(|(k, _)| collation.equal(k, &s))
+                    .for_each(|(_, a)| nodes.extend(a.iter().cloned()));
+            }
         }
-    }))
+        Ok(document_order(nodes))
+    }
 }
@@ -1,11 +1,13 @@
 //! These functions are for features defined in XPath Functions 1.0 and 2.0.
 
+use std::cmp::Ordering;
 use std::rc::Rc;
 use url::Url;
 
 use crate::item::{Item, Node, Sequence, SequenceTrait};
 use crate::transform::Transform;
 use crate::transform::context::{Context, StaticContext};
+use crate::transform::types::error;
 use crate::value::{Operator, Value};
 use crate::xdmerror::{Error, ErrorKind};
 
@@ -73,10 +75,11 @@ pub(crate) fn general_comparison<
     let left = ctxt.dispatch(stctxt, l)?;
     let right = ctxt.dispatch(stctxt, r)?;
 
+    let tz = ctxt.implicit_timezone();
     let mut b = false;
     for i in left {
         for j in &right {
-            b = i.compare(j, *o)?;
+            b = i.compare_with_timezone(j, *o, tz)?;
             if b {
                 break;
             }
@@ -118,10 +121,53 @@ pub(crate) fn value_comparison<
     }
 
     Ok(vec![Item::Value(Rc::new(Value::from(
-        left[0].compare(&right[0], *o)?,
+        left[0].compare_with_timezone(&right[0], *o, ctxt.implicit_timezone())?,
     )))])
 }
 
+/// Node comparison of two operands, each of which must be a single node or the empty sequence.
+/// If either operand is the empty sequence then the result is the empty sequence.
+pub(crate) fn node_comparison<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    o: &Operator,
+    l: &Transform<N>,
+    r: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let left = ctxt.dispatch(stctxt, l)?;
+    let right = ctxt.dispatch(stctxt, r)?;
+    if left.is_empty() || right.is_empty() {
+        return Ok(vec![]);
+    }
+    let (a, b) = match (left.as_slice(), right.as_slice()) {
+        ([Item::Node(a)], [Item::Node(b)]) => (a, b),
+        _ => {
+            return Err(error(
+                ErrorKind::TypeError,
+                "XPTY0004",
+                String::from("operands of a node comparison must be single nodes"),
+            ));
+        }
+    };
+    let result = match o {
+        Operator::Is => a.is_same(b),
+        Operator::Before => a.cmp_document_order(b) == Ordering::Less,
+        Operator::After => a.cmp_document_order(b) == Ordering::Greater,
+        _ => {
+            return Err(Error::new(
+                ErrorKind::TypeError,
+                format!("\"{}\" is not a node comparison operator", o),
+            ));
+        }
+    };
+    Ok(vec![Item::Value(Rc::new(Value::from(result)))])
+}
+
 /// Each function in the supplied vector is evaluated, and the resulting sequences are combined into a single sequence.
 /// All items must be nodes.
 /// TODO: eliminate duplicates, sort by document order (XPath 3.1 3.4.2).
@@ -149,3 +195,79 @@ pub(crate) fn union<
     // TODO: eliminate duplicates and sort into document order
     Ok(result)
 }
+
+/// The nodes selected by both operands, in document order with duplicates removed.
+pub(crate) fn intersect<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    left: &Transform<N>,
+    right: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let (l, r) = node_operands(ctxt, stctxt, left, right)?;
+    Ok(document_order(
+        l.into_iter()
+            .filter(|n| r.iter().any(|m| n.is_same(m)))
+            .collect(),
+    ))
+}
+
+/// The nodes selected by the first operand but not the second, in document order with duplicates removed.
+pub(crate) fn except<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    left: &Transform<N>,
+    right: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let (l, r) = node_operands(ctxt, stctxt, left, right)?;
+    Ok(document_order(
+        l.into_iter()
+            .filter(|n| !r.iter().any(|m| n.is_same(m)))
+            .collect(),
+    ))
+}
+
+// Evaluate both operands of a node set operator. All items must be nodes.
+fn node_operands<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    left: &Transform<N>,
+    right: &Transform<N>,
+) -> Result<(Vec<N>, Vec<N>), Error> {
+    let mut nodes = |t: &Transform<N>| -> Result<Vec<N>, Error> {
+        ctxt.dispatch(stctxt, t)?
+            .into_iter()
+            .map(|i| match i {
+                Item::Node(n) => Ok(n),
+                _ => Err(Error::new(
+                    ErrorKind::TypeError,
+                    "all operands must be nodes",
+                )),
+            })
+            .collect()
+    };
+    let l = nodes(left)?;
+    let r = nodes(right)?;
+    Ok((l, r))
+}
+
+// Sort nodes into document order, removing duplicates.
+pub(crate) fn document_order<N: Node>(mut v: Vec<N>) -> Sequence<N> {
+    v.sort_unstable_by(|a, b| a.cmp_document_order(b));
+    v.dedup_by(|a, b| a.is_same(b));
+    v.into_iter().map(Item::Node).collect()
+}
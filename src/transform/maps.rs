@@ -0,0 +1,354 @@
+//! Maps, and the functions in the map namespace.
+//! Also the lookup operator, which applies to both maps and arrays.
+
+use std::rc::Rc;
+use url::Url;
+
+use crate::item::{Item, Map, Node, Sequence, same_key};
+use crate::transform::arrays::position;
+use crate::transform::context::{Context, StaticContext};
+use crate::transform::types::error;
+use crate::transform::{KeySpecifier, Transform};
+use crate::value::Value;
+use crate::xdmerror::{Error, ErrorKind};
+
+/// The namespace for the map functions.
+pub(crate) const MAPNS: &str = "http://www.w3.org/2005/xpath-functions/map";
+
+// Atomize a sequence. Arrays are flattened.
+pub(crate) fn atomize<N: Node>(s: &Sequence<N>) -> Result<Vec<Rc<Value>>, Error> {
+    s.iter().try_fold(vec![], |mut acc, i| {
+        match i {
+            Item::Value(v) => acc.push(v.clone()),
+            Item::Node(n) => acc.push(Rc::new(Value::from(n.to_string()))),
+            Item::Array(a) => {
+                for m in a.iter() {
+                    acc.append(&mut atomize(m)?)
+                }
+            }
+            Item::Function(_) | Item::Map(_) => {
+                return Err(error(
+                    ErrorKind::TypeError,
+                    "FOTY0013",
+                    String::from("a function item cannot be atomized"),
+                ));
+            }
+        }
+        Ok(acc)
+    })
+}
+
+// Atomize a sequence that must produce a single value, such as a key.
+pub(crate) fn atomize_one<N: Node>(s: &Sequence<N>) -> Result<Rc<Value>, Error> {
+    let mut v = atomize(s)?;
+    if v.len() == 1 {
+        Ok(v.pop().unwrap())
+    } else {
+        Err(error(
+            ErrorKind::TypeError,
+            "XPTY0004",
+            format!("expected a single atomic value, got {} values", v.len()),
+        ))
+    }
+}
+
+// The single map in a sequence.
+pub(crate) fn map_of<N: Node>(s: Sequence<N>) -> Result<Rc<Map<N>>, Error> {
+    match s.as_slice() {
+        [Item::Map(m)] => Ok(m.clone()),
+        _ => Err(error(
+            ErrorKind::TypeError,
+            "XPTY0004",
+            String::from("expected a single map"),
+        )),
+    }
+}
+
+/// Construct a map. Keys must be unique.
+pub(crate) fn map_constructor<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    v: &[(Transform<N>, Transform<N>)],
+) -> Result<Sequence<N>, Error> {
+    let mut m = Map::new();
+    for (k, e) in v {
+        let key = atomize_one(&ctxt.dispatch(stctxt, k)?)?;
+        if m.contains_key(&key) {
+            return Err(error(
+                ErrorKind::Unknown,
+                "XQDY0137",
+                format!("duplicate key \"{}\" in map constructor", key),
+            ));
+        }
+        m.insert(key, ctxt.dispatch(stctxt, e)?);
+    }
+    Ok(vec![Item::Map(Rc::new(m))])
+}
+
+/// The lookup operator. Selects values from maps, or members from arrays.
+pub(crate) fn lookup<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    t: &Transform<N>,
+    k: &KeySpecifier<N>,
+) -> Result<Sequence<N>, Error> {
+    let s = ctxt.dispatch(stctxt, t)?;
+    let keys = match k {
+        KeySpecifier::Name(n) => Some(vec![Rc::new(Value::from(n.clone()))]),
+        KeySpecifier::Integer(i) => Some(vec![Rc::new(Value::from(*i))]),
+        KeySpecifier::Expr(e) => Some(atomize(&ctxt.dispatch(stctxt, e)?)?),
+        KeySpecifier::Wildcard => None,
+    };
+    let mut result = vec![];
+    for i in s {
+        match (&i, &keys) {
+            (Item::Map(m), Some(kv)) => kv
+                .iter()
+                .filter_map(|k| m.get(k))
+                .for_each(|v| result.extend(v.iter().cloned())),
+            (Item::Map(m), None) => m.iter().for_each(|(_, v)| result.extend(v.iter().cloned())),
+            (Item::Array(a), Some(kv)) => {
+                for k in kv {
+                    result.extend(a[position(k, a.len())?].iter().cloned())
+                }
+            }
+            (Item::Array(a), None) => a.iter().for_each(|m| result.extend(m.iter().cloned())),
+            _ => {
+                return Err(error(
+                    ErrorKind::TypeError,
+                    "XPTY0004",
+                    format!("cannot look up a key in a {} item", i.item_type()),
+                ));
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// map:merge(). The first argument is the maps to merge, the second argument is an options map.
+/// The "duplicates" option controls what happens when two maps have the same key.
+pub(crate) fn merge<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    maps: &Transform<N>,
+    options: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let duplicates = match options {
+        Some(o) => map_of(ctxt.dispatch(stctxt, o)?)?
+            .get(&Value::from("duplicates"))
+            .map_or(Ok(String::from("use-first")), |d| {
+                atomize_one(d).map(|v| v.to_string())
+            })?,
+        None => String::from("use-first"),
+    };
+    if !["reject", "use-first", "use-last", "use-any", "combine"].contains(&duplicates.as_str()) {
+        return Err(error(
+            ErrorKind::TypeError,
+            "FOJS0005",
+            format!("invalid value \"{}\" for the duplicates option", duplicates),
+        ));
+    }
+    let mut result: Map<N> = Map::new();
+    for i in ctxt.dispatch(stctxt, maps)? {
+        let Item::Map(m) = i else {
+            return Err(error(
+                ErrorKind::TypeError,
+                "XPTY0004",
+                String::from("map:merge requires a sequence of maps"),
+            ));
+        };
+        for (k, v) in m.iter() {
+            let existing = result.get(k).cloned();
+            match (existing, duplicates.as_str()) {
+                (None, _) | (Some(_), "use-last") => {
+                    result.insert(k.clone(), v.clone());
+                }
+                (Some(_), "reject") => {
+                    return Err(error(
+                        ErrorKind::Unknown,
+                        "FOJS0003",
+                        format!("duplicate key \"{}\"", k),
+                    ));
+                }
+                (Some(mut c), "combine") => {
+                    c.extend(v.iter().cloned());
+                    result.insert(k.clone(), c);
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(vec![Item::Map(Rc::new(result))])
+}
+
+/// map:size()
+pub(crate) fn size<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    m: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let m = map_of(ctxt.dispatch(stctxt, m)?)?;
+    Ok(vec![Item::Value(Rc::new(Value::from(m.len() as i64)))])
+}
+
+/// map:keys()
+pub(crate) fn keys<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    m: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let m = map_of(ctxt.dispatch(stctxt, m)?)?;
+    Ok(m.keys().map(|k| Item::Value(k.clone())).collect())
+}
+
+/// map:contains()
+pub(crate) fn contains<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    m: &Transform<N>,
+    k: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let m = map_of(ctxt.dispatch(stctxt, m)?)?;
+    let k = atomize_one(&ctxt.dispatch(stctxt, k)?)?;
+    Ok(vec![Item::Value(Rc::new(Value::from(m.contains_key(&k))))])
+}
+
+/// map:get()
+pub(crate) fn get<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    m: &Transform<N>,
+    k: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let m = map_of(ctxt.dispatch(stctxt, m)?)?;
+    let k = atomize_one(&ctxt.dispatch(stctxt, k)?)?;
+    Ok(m.get(&k).cloned().unwrap_or_default())
+}
+
+/// map:find(). Searches the input, including maps and arrays nested within it, for entries with the given key.
+/// The result is an array of the values found.
+pub(crate) fn find<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    input: &Transform<N>,
+    k: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let s = ctxt.dispatch(stctxt, input)?;
+    let k = atomize_one(&ctxt.dispatch(stctxt, k)?)?;
+    let mut result = vec![];
+    find_key(&s, &k, &mut result);
+    Ok(vec![Item::Array(Rc::new(result))])
+}
+
+fn find_key<N: Node>(s: &Sequence<N>, k: &Value, result: &mut Vec<Sequence<N>>) {
+    for i in s {
+        match i {
+            Item::Map(m) => {
+                for (l, v) in m.iter() {
+                    if same_key(l, k) {
+                        result.push(v.clone())
+                    }
+                    find_key(v, k, result)
+                }
+            }
+            Item::Array(a) => a.iter().for_each(|m| find_key(m, k, result)),
+            _ => {}
+        }
+    }
+}
+
+/// map:put()
+pub(crate) fn put<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    m: &Transform<N>,
+    k: &Transform<N>,
+    v: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let mut m = (*map_of(ctxt.dispatch(stctxt, m)?)?).clone();
+    let k = atomize_one(&ctxt.dispatch(stctxt, k)?)?;
+    m.insert(k, ctxt.dispatch(stctxt, v)?);
+    Ok(vec![Item::Map(Rc::new(m))])
+}
+
+/// map:entry()
+pub(crate) fn entry<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    k: &Transform<N>,
+    v: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let mut m = Map::new();
+    let k = atomize_one(&ctxt.dispatch(stctxt, k)?)?;
+    m.insert(k, ctxt.dispatch(stctxt, v)?);
+    Ok(vec![Item::Map(Rc::new(m))])
+}
+
+/// map:remove(). The second argument is a sequence of keys to remove.
+pub(crate) fn remove<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    m: &Transform<N>,
+    k: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let mut m = (*map_of(ctxt.dispatch(stctxt, m)?)?).clone();
+    for k in atomize(&ctxt.dispatch(stctxt, k)?)? {
+        m.remove(&k);
+    }
+    Ok(vec![Item::Map(Rc::new(m))])
+}
@@ -37,21 +37,30 @@ assert_eq!(sequence.to_string(), "2")
 ```
 */
 
+pub(crate) mod arrays;
+pub(crate) mod binary;
 pub(crate) mod booleans;
 pub mod callable;
+pub mod collation;
 pub(crate) mod construct;
 pub mod context;
 pub(crate) mod controlflow;
 pub(crate) mod datetime;
 pub(crate) mod functions;
 pub(crate) mod grouping;
+pub(crate) mod higherorder;
+pub(crate) mod json;
 mod keys;
 pub(crate) mod logic;
+pub(crate) mod maps;
 pub(crate) mod misc;
 pub(crate) mod navigate;
 pub mod numbers;
+pub(crate) mod qnames;
+pub(crate) mod sequences;
 pub(crate) mod strings;
 pub mod template;
+pub mod types;
 pub(crate) mod variables;
 
 #[allow(unused_imports)]
@@ -60,13 +69,16 @@ use crate::item::{Item, Node, NodeType, SequenceTrait};
 use crate::output::OutputSpec;
 use crate::pattern::Pattern;
 use crate::transform::callable::ActualParameters;
+use crate::transform::collation::{Collation, find_collation};
 use crate::transform::context::{Context, ContextBuilder, StaticContext};
 use crate::transform::numbers::Numbering;
+use crate::transform::types::SequenceType;
 use crate::value::Operator;
 #[allow(unused_imports)]
 use crate::value::Value;
 use crate::xdmerror::{Error, ErrorKind};
 use qualname::{NamespaceDeclaration, NamespaceMap, NamespacePrefix, NamespaceUri, NcName, QName};
+use std::cmp::Ordering;
 use std::convert::TryFrom;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
@@ -138,6 +150,9 @@ pub enum Transform<N: Node> {
     /// XPath value comparison.
     /// The first singleton sequence is compared against the second singleton sequence.
     ValueComparison(Operator, Box<Transform<N>>, Box<Transform<N>>),
+    /// XPath node comparison: is, << or >>.
+    /// Each operand must be a single node or the empty sequence.
+    NodeComparison(Operator, Box<Transform<N>>, Box<Transform<N>>),
 
     /// Concatenate string values
     Concat(Vec<Transform<N>>),
@@ -146,28 +161,64 @@ pub enum Transform<N: Node> {
     Range(Box<Transform<N>>, Box<Transform<N>>),
     /// Perform arithmetic operations
     Arithmetic(Vec<ArithmeticOperand<N>>),
+    /// Unary plus (Add) or minus (Subtract)
+    Unary(ArithmeticOperator, Box<Transform<N>>),
 
     /// A repeating transformation. Consists of variable declarations and the loop body.
     Loop(Vec<(String, Transform<N>)>, Box<Transform<N>>),
     /// A branching transformation. Consists of (test, body) clauses and an otherwise clause.
     Switch(Vec<(Transform<N>, Transform<N>)>, Box<Transform<N>>),
+    /// A quantified expression. Consists of the quantifier, variable declarations, and the test expression.
+    /// The result is a single boolean value.
+    Quantified(Quantifier, Vec<(String, Transform<N>)>, Box<Transform<N>>),
 
     /// Evaluate a transformation for each selected item, with possible grouping and sorting.
+    /// Consists of the grouping, the selector, the body, and sort keys.
+    /// Each sort key consists of the order, the expression for the key value, and the URI of the collation.
     ForEach(
         Option<Grouping<N>>,
         Box<Transform<N>>,
         Box<Transform<N>>,
-        Vec<(Order, Transform<N>)>,
+        Vec<(Order, Transform<N>, Option<String>)>,
     ),
     /// Find a template that matches an item and evaluate its body with the item as the context.
     /// Consists of the selector for items to be matched, the mode, and sort keys.
-    ApplyTemplates(Box<Transform<N>>, Option<QName>, Vec<(Order, Transform<N>)>),
+    ApplyTemplates(
+        Box<Transform<N>>,
+        Option<QName>,
+        Vec<(Order, Transform<N>, Option<String>)>,
+    ),
     /// Find templates at the next import level and evaluate its body.
     ApplyImports,
     NextMatch,
 
     /// Set union
     Union(Vec<Transform<N>>),
+    /// Set intersection. The nodes that are in both operands.
+    Intersect(Box<Transform<N>>, Box<Transform<N>>),
+    /// Set difference. The nodes in the first operand that are not in the second.
+    Except(Box<Transform<N>>, Box<Transform<N>>),
+    /// The simple map operator. Each element of the vector is an operand.
+    /// Each operand is evaluated with each item produced by the previous operand as the context item.
+    SimpleMap(Vec<Transform<N>>),
+
+    /// Test whether a sequence matches a [SequenceType]. The result is a single boolean value.
+    InstanceOf(Box<Transform<N>>, SequenceType),
+    /// Return a sequence unchanged, provided it matches a [SequenceType]. Otherwise it is a dynamic error.
+    Treat(Box<Transform<N>>, SequenceType),
+    /// Cast a value to an atomic type. Consists of the value, the name of the type, and whether the empty sequence is allowed.
+    Cast(Box<Transform<N>>, QName, bool),
+    /// Test whether a value may be cast to an atomic type. Consists of the value, the name of the type, and whether the empty sequence is allowed.
+    Castable(Box<Transform<N>>, QName, bool),
+
+    /// Construct a map. Each element of the vector is the key and the value of an entry.
+    MapConstructor(Vec<(Transform<N>, Transform<N>)>),
+    /// Construct an array. Each element of the vector produces a member of the array.
+    ArrayConstructor(Vec<Transform<N>>),
+    /// Construct an array. Each item produced by the transformation is a member of the array.
+    CurlyArrayConstructor(Box<Transform<N>>),
+    /// The lookup operator. Consists of the maps or arrays to look up and the [KeySpecifier].
+    Lookup(Box<Transform<N>>, KeySpecifier<N>),
 
     /// Evaluate a named template or function, with arguments.
     /// Consists of the body of the template/function, the actual arguments (variable declarations), and in-scope namespace declarations.
@@ -195,19 +246,148 @@ pub enum Transform<N: Node> {
     Count(Box<Transform<N>>),
     LocalName(Option<Box<Transform<N>>>),
     Name(Option<Box<Transform<N>>>),
+    /// XPath functions that construct and take apart qualified names
+    QName(Box<Transform<N>>, Box<Transform<N>>),
+    ResolveQName(Box<Transform<N>>, Box<Transform<N>>),
+    PrefixFromQName(Box<Transform<N>>),
+    LocalNameFromQName(Box<Transform<N>>),
+    NamespaceUriFromQName(Box<Transform<N>>),
+    NamespaceUriForPrefix(Box<Transform<N>>, Box<Transform<N>>),
+    InScopePrefixes(Box<Transform<N>>),
+    NodeName(Option<Box<Transform<N>>>),
+    NamespaceUri(Option<Box<Transform<N>>>),
+    /// XPath functions that parse and serialise JSON
+    ParseJson(Box<Transform<N>>, Option<Box<Transform<N>>>),
+    JsonDoc(Box<Transform<N>>, Option<Box<Transform<N>>>),
+    JsonToXml(Box<Transform<N>>, Option<Box<Transform<N>>>),
+    XmlToJson(Box<Transform<N>>, Option<Box<Transform<N>>>),
     String(Box<Transform<N>>),
-    StartsWith(Box<Transform<N>>, Box<Transform<N>>),
-    EndsWith(Box<Transform<N>>, Box<Transform<N>>),
-    Contains(Box<Transform<N>>, Box<Transform<N>>),
+    /// XPath starts-with, ends-with and contains functions.
+    /// Consists of the string to search, the string to find, and the collation.
+    StartsWith(
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+    ),
+    EndsWith(
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+    ),
+    Contains(
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+    ),
     Substring(
         Box<Transform<N>>,
         Box<Transform<N>>,
         Option<Box<Transform<N>>>,
     ),
-    SubstringBefore(Box<Transform<N>>, Box<Transform<N>>),
-    SubstringAfter(Box<Transform<N>>, Box<Transform<N>>),
+    /// XPath substring-before and substring-after functions.
+    /// Consists of the string to search, the string to find, and the collation.
+    SubstringBefore(
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+    ),
+    SubstringAfter(
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+    ),
     NormalizeSpace(Option<Box<Transform<N>>>),
     Translate(Box<Transform<N>>, Box<Transform<N>>, Box<Transform<N>>),
+    UpperCase(Box<Transform<N>>),
+    LowerCase(Box<Transform<N>>),
+    /// XPath string-join function. Consists of the sequence and the separator.
+    StringJoin(Box<Transform<N>>, Option<Box<Transform<N>>>),
+    /// XPath string-length function. If there is no argument then the context item is used.
+    StringLength(Option<Box<Transform<N>>>),
+    StringToCodepoints(Box<Transform<N>>),
+    CodepointsToString(Box<Transform<N>>),
+    /// XPath compare function. Consists of the two strings, and the collation.
+    Compare(
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+    ),
+    CodepointEqual(Box<Transform<N>>, Box<Transform<N>>),
+    EncodeForUri(Box<Transform<N>>),
+    IriToUri(Box<Transform<N>>),
+    EscapeHtmlUri(Box<Transform<N>>),
+    /// XPath normalize-unicode function. Consists of the string and the normalization form.
+    NormalizeUnicode(Box<Transform<N>>, Option<Box<Transform<N>>>),
+    /// XPath contains-token function. Consists of the input strings, the token, and the collation.
+    ContainsToken(
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+    ),
+    /// XPath matches function. Consists of the input, pattern and flags.
+    Matches(
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+    ),
+    /// XPath replace function. Consists of the input, pattern, replacement and flags.
+    Replace(
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+    ),
+    /// XPath tokenize function. Consists of the input, pattern and flags.
+    Tokenize(
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+        Option<Box<Transform<N>>>,
+    ),
+    /// XPath analyze-string function. Consists of the input, pattern and flags.
+    AnalyzeString(
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+    ),
+    /// XPath empty function. True if the sequence is empty.
+    IsEmpty(Box<Transform<N>>),
+    /// XPath exists function. True if the sequence is not empty.
+    Exists(Box<Transform<N>>),
+    /// XPath head function. The first item of the sequence.
+    Head(Box<Transform<N>>),
+    /// XPath tail function. All but the first item of the sequence.
+    Tail(Box<Transform<N>>),
+    /// XPath insert-before function. Consists of the target sequence, the position, and the items to insert.
+    InsertBefore(Box<Transform<N>>, Box<Transform<N>>, Box<Transform<N>>),
+    /// XPath remove function. Consists of the target sequence and the position.
+    Remove(Box<Transform<N>>, Box<Transform<N>>),
+    /// XPath reverse function. The items of the sequence in reverse order.
+    Reverse(Box<Transform<N>>),
+    /// XPath subsequence function. Consists of the sequence, the starting position, and the length.
+    Subsequence(
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+    ),
+    /// XPath unordered function. The items of the sequence in an implementation-dependent order.
+    Unordered(Box<Transform<N>>),
+    /// XPath distinct-values function. Consists of the sequence and the collation.
+    DistinctValues(Box<Transform<N>>, Option<Box<Transform<N>>>),
+    /// XPath index-of function. Consists of the sequence, the search value, and the collation.
+    IndexOf(
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+    ),
+    /// XPath deep-equal function. Consists of the two sequences and the collation.
+    DeepEqual(
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+    ),
+    ZeroOrOne(Box<Transform<N>>),
+    OneOrMore(Box<Transform<N>>),
+    ExactlyOne(Box<Transform<N>>),
     GenerateId(Option<Box<Transform<N>>>),
     Boolean(Box<Transform<N>>),
     Not(Box<Transform<N>>),
@@ -245,6 +425,16 @@ pub enum Transform<N: Node> {
         Option<Box<Transform<N>>>,
         Option<Box<Transform<N>>>,
     ),
+    /// Extract a component of a dateTime value, as in year-from-dateTime.
+    ComponentFromDateTime(DateTimeComponent, Box<Transform<N>>),
+    /// Extract a component of a date value, as in year-from-date.
+    ComponentFromDate(DateTimeComponent, Box<Transform<N>>),
+    /// Extract a component of a time value, as in hours-from-time.
+    ComponentFromTime(DateTimeComponent, Box<Transform<N>>),
+    /// Extract a component of a duration value, as in hours-from-duration.
+    ComponentFromDuration(DateTimeComponent, Box<Transform<N>>),
+    /// Adjust a dateTime value to a timezone. If there is no timezone argument then the implicit timezone is used.
+    AdjustDateTimeToTimezone(Box<Transform<N>>, Option<Box<Transform<N>>>),
     FormatNumber(
         Box<Transform<N>>,
         Box<Transform<N>>,
@@ -280,6 +470,84 @@ pub enum Transform<N: Node> {
     /// Read an external document
     Document(Box<Transform<N>>, Option<Box<Transform<N>>>),
 
+    /// Higher-order functions
+    ForEachItem(Box<Transform<N>>, Box<Transform<N>>),
+    FilterItems(Box<Transform<N>>, Box<Transform<N>>),
+    FoldLeft(Box<Transform<N>>, Box<Transform<N>>, Box<Transform<N>>),
+    FoldRight(Box<Transform<N>>, Box<Transform<N>>, Box<Transform<N>>),
+    ForEachPair(Box<Transform<N>>, Box<Transform<N>>, Box<Transform<N>>),
+    /// Sort a sequence. Consists of the sequence, the collation, and the function that computes the sort key.
+    Sort(
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+        Option<Box<Transform<N>>>,
+    ),
+    Apply(Box<Transform<N>>, Box<Transform<N>>),
+
+    /// Functions in the map namespace
+    MapMerge(Box<Transform<N>>, Option<Box<Transform<N>>>),
+    MapSize(Box<Transform<N>>),
+    MapKeys(Box<Transform<N>>),
+    MapContains(Box<Transform<N>>, Box<Transform<N>>),
+    MapGet(Box<Transform<N>>, Box<Transform<N>>),
+    MapFind(Box<Transform<N>>, Box<Transform<N>>),
+    MapPut(Box<Transform<N>>, Box<Transform<N>>, Box<Transform<N>>),
+    MapEntry(Box<Transform<N>>, Box<Transform<N>>),
+    MapRemove(Box<Transform<N>>, Box<Transform<N>>),
+    MapForEach(Box<Transform<N>>, Box<Transform<N>>),
+    /// Functions in the array namespace
+    ArraySize(Box<Transform<N>>),
+    ArrayGet(Box<Transform<N>>, Box<Transform<N>>),
+    ArrayPut(Box<Transform<N>>, Box<Transform<N>>, Box<Transform<N>>),
+    ArrayAppend(Box<Transform<N>>, Box<Transform<N>>),
+    ArraySubarray(
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+    ),
+    ArrayRemove(Box<Transform<N>>, Box<Transform<N>>),
+    ArrayInsertBefore(Box<Transform<N>>, Box<Transform<N>>, Box<Transform<N>>),
+    ArrayHead(Box<Transform<N>>),
+    ArrayTail(Box<Transform<N>>),
+    ArrayReverse(Box<Transform<N>>),
+    ArrayJoin(Box<Transform<N>>),
+    ArrayFlatten(Box<Transform<N>>),
+    ArrayForEach(Box<Transform<N>>, Box<Transform<N>>),
+    ArrayFilter(Box<Transform<N>>, Box<Transform<N>>),
+    ArrayFoldLeft(Box<Transform<N>>, Box<Transform<N>>, Box<Transform<N>>),
+    ArrayFoldRight(Box<Transform<N>>, Box<Transform<N>>, Box<Transform<N>>),
+    ArrayForEachPair(Box<Transform<N>>, Box<Transform<N>>, Box<Transform<N>>),
+    ArraySort(
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+        Option<Box<Transform<N>>>,
+    ),
+    /// Functions in the EXPath binary namespace
+    BinaryLength(Box<Transform<N>>),
+    BinaryPart(
+        Box<Transform<N>>,
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+    ),
+    BinaryDecodeString(
+        Box<Transform<N>>,
+        Option<Box<Transform<N>>>,
+        Option<Box<Transform<N>>>,
+        Option<Box<Transform<N>>>,
+    ),
+
+    /// Create a function item. Consists of the name of the function, its parameters, and its body.
+    /// Named function references have a name; inline functions are anonymous and capture the variables that are in scope.
+    FunctionItem(
+        Option<QName>,
+        Vec<(String, Option<SequenceType>)>,
+        Rc<Transform<N>>,
+    ),
+    /// Call a function item. Consists of the expression that produces the function item, and the actual arguments.
+    DynamicCall(Box<Transform<N>>, Vec<Transform<N>>),
+    /// Partially apply a function item. Consists of the expression that produces the function item, and the actual arguments.
+    /// An argument that is None is a placeholder.
+    PartialApply(Box<Transform<N>>, Vec<Option<Transform<N>>>),
     /// Invoke a callable component. Consists of a name, an actual argument list, and in-scope namespace declarations.
     Invoke(QName, ActualParameters<N>, Rc<NamespaceMap>),
 
@@ -292,7 +560,7 @@ pub enum Transform<N: Node> {
     ),
 
     /// For things that are not yet implemented, such as:
-    /// Union, IntersectExcept, InstanceOf, Treat, Castable, Cast, Arrow, Unary, SimpleMap, Is, Before, After.
+    /// Union.
     NotImplemented(String),
 
     /// Error condition.
@@ -335,13 +603,20 @@ impl<N: Node> Debug for Transform<N> {
             Transform::ValueComparison(o, v, u) => {
                 write!(f, "value comparison {} of {:?} and {:?}", o, v, u)
             }
+            Transform::NodeComparison(o, v, u) => {
+                write!(f, "node comparison {} of {:?} and {:?}", o, v, u)
+            }
             Transform::Concat(o) => write!(f, "Concatenate {} operands", o.len()),
             Transform::Range(_, _) => write!(f, "range"),
             Transform::Arithmetic(o) => write!(f, "Arithmetic {} operands", o.len()),
+            Transform::Unary(o, t) => write!(f, "unary {} ({:?})", o, t),
             Transform::And(o) => write!(f, "AND {} operands", o.len()),
             Transform::Or(o) => write!(f, "OR {} operands", o.len()),
             Transform::Loop(_, _) => write!(f, "loop"),
             Transform::Switch(c, _) => write!(f, "switch {} clauses", c.len()),
+            Transform::Quantified(q, v, _) => {
+                write!(f, "quantified ({:?}) {} variables", q, v.len())
+            }
             Transform::ForEach(_g, _, _, o) => write!(f, "for-each ({} sort keys)", o.len()),
             Transform::Union(v) => {
                 write!(f, "union of {} operands", v.len()).ok();
@@ -350,6 +625,17 @@ impl<N: Node> Debug for Transform<N> {
                 });
                 Ok(())
             }
+            Transform::Intersect(a, b) => write!(f, "intersect ({:?}) ({:?})", a, b),
+            Transform::Except(a, b) => write!(f, "except ({:?}) ({:?})", a, b),
+            Transform::SimpleMap(v) => write!(f, "simple map of {} operands", v.len()),
+            Transform::InstanceOf(_, t) => write!(f, "instance of {}", t),
+            Transform::Treat(_, t) => write!(f, "treat as {}", t),
+            Transform::Cast(_, t, _) => write!(f, "cast as {}", t),
+            Transform::Castable(_, t, _) => write!(f, "castable as {}", t),
+            Transform::MapConstructor(v) => write!(f, "map constructor of {} entries", v.len()),
+            Transform::ArrayConstructor(v) => write!(f, "array constructor of {} members", v.len()),
+            Transform::CurlyArrayConstructor(_) => write!(f, "curly array constructor"),
+            Transform::Lookup(_, k) => write!(f, "lookup {:?}", k),
             Transform::ApplyTemplates(_, m, o) => {
                 write!(f, "Apply templates (mode {:?}, {} sort keys)", m, o.len())
             }
@@ -364,15 +650,66 @@ impl<N: Node> Debug for Transform<N> {
             Transform::Count(_s) => write!(f, "count()"),
             Transform::Name(_n) => write!(f, "name()"),
             Transform::LocalName(_n) => write!(f, "local-name()"),
+            Transform::QName(_, _) => write!(f, "QName()"),
+            Transform::ResolveQName(_, _) => write!(f, "resolve-QName()"),
+            Transform::PrefixFromQName(_) => write!(f, "prefix-from-QName()"),
+            Transform::LocalNameFromQName(_) => write!(f, "local-name-from-QName()"),
+            Transform::NamespaceUriFromQName(_) => write!(f, "namespace-uri-from-QName()"),
+            Transform::NamespaceUriForPrefix(_, _) => write!(f, "namespace-uri-for-prefix()"),
+            Transform::InScopePrefixes(_) => write!(f, "in-scope-prefixes()"),
+            Transform::NodeName(_) => write!(f, "node-name()"),
+            Transform::NamespaceUri(_) => write!(f, "namespace-uri()"),
+            Transform::ParseJson(_, _) => write!(f, "parse-json()"),
+            Transform::JsonDoc(_, _) => write!(f, "json-doc()"),
+            Transform::JsonToXml(_, _) => write!(f, "json-to-xml()"),
+            Transform::XmlToJson(_, _) => write!(f, "xml-to-json()"),
             Transform::String(s) => write!(f, "string({:?})", s),
-            Transform::StartsWith(s, t) => write!(f, "starts-with({:?}, {:?})", s, t),
-            Transform::EndsWith(s, t) => write!(f, "ends-with({:?}, {:?})", s, t),
-            Transform::Contains(s, t) => write!(f, "contains({:?}, {:?})", s, t),
+            Transform::StartsWith(s, t, _) => write!(f, "starts-with({:?}, {:?})", s, t),
+            Transform::EndsWith(s, t, _) => write!(f, "ends-with({:?}, {:?})", s, t),
+            Transform::Contains(s, t, _) => write!(f, "contains({:?}, {:?})", s, t),
             Transform::Substring(s, t, _l) => write!(f, "substring({:?}, {:?}, ...)", s, t),
-            Transform::SubstringBefore(s, t) => write!(f, "substring-before({:?}, {:?})", s, t),
-            Transform::SubstringAfter(s, t) => write!(f, "substring-after({:?}, {:?})", s, t),
+            Transform::SubstringBefore(s, t, _) => {
+                write!(f, "substring-before({:?}, {:?})", s, t)
+            }
+            Transform::SubstringAfter(s, t, _) => write!(f, "substring-after({:?}, {:?})", s, t),
             Transform::NormalizeSpace(_s) => write!(f, "normalize-space()"),
             Transform::Translate(s, t, u) => write!(f, "translate({:?}, {:?}, {:?})", s, t, u),
+            Transform::UpperCase(s) => write!(f, "upper-case({:?})", s),
+            Transform::LowerCase(s) => write!(f, "lower-case({:?})", s),
+            Transform::StringJoin(s, _) => write!(f, "string-join({:?})", s),
+            Transform::StringLength(_) => write!(f, "string-length()"),
+            Transform::StringToCodepoints(s) => write!(f, "string-to-codepoints({:?})", s),
+            Transform::CodepointsToString(s) => write!(f, "codepoints-to-string({:?})", s),
+            Transform::Compare(s, t, _) => write!(f, "compare({:?}, {:?})", s, t),
+            Transform::CodepointEqual(s, t) => write!(f, "codepoint-equal({:?}, {:?})", s, t),
+            Transform::EncodeForUri(s) => write!(f, "encode-for-uri({:?})", s),
+            Transform::IriToUri(s) => write!(f, "iri-to-uri({:?})", s),
+            Transform::EscapeHtmlUri(s) => write!(f, "escape-html-uri({:?})", s),
+            Transform::NormalizeUnicode(s, _) => write!(f, "normalize-unicode({:?})", s),
+            Transform::ContainsToken(s, t, _) => write!(f, "contains-token({:?}, {:?})", s, t),
+            Transform::Matches(s, p, _) => write!(f, "matches({:?}, {:?})", s, p),
+            Transform::Replace(s, p, r, _) => write!(f, "replace({:?}, {:?}, {:?})", s, p, r),
+            Transform::Tokenize(s, _, _) => write!(f, "tokenize({:?})", s),
+            Transform::AnalyzeString(s, p, _) => write!(f, "analyze-string({:?}, {:?})", s, p),
+            Transform::IsEmpty(s) => write!(f, "empty({:?})", s),
+            Transform::Exists(s) => write!(f, "exists({:?})", s),
+            Transform::Head(s) => write!(f, "head({:?})", s),
+            Transform::Tail(s) => write!(f, "tail({:?})", s),
+            Transform::InsertBefore(s, p, i) => {
+                write!(f, "insert-before({:?}, {:?}, {:?})", s, p, i)
+            }
+            Transform::Remove(s, p) => write!(f, "remove({:?}, {:?})", s, p),
+            Transform::Reverse(s) => write!(f, "reverse({:?})", s),
+            Transform::Subsequence(s, p, l) => {
+                write!(f, "subsequence({:?}, {:?}, {:?})", s, p, l)
+            }
+            Transform::Unordered(s) => write!(f, "unordered({:?})", s),
+            Transform::DistinctValues(s, _) => write!(f, "distinct-values({:?})", s),
+            Transform::IndexOf(s, t, _) => write!(f, "index-of({:?}, {:?})", s, t),
+            Transform::DeepEqual(s, t, _) => write!(f, "deep-equal({:?}, {:?})", s, t),
+            Transform::ZeroOrOne(s) => write!(f, "zero-or-one({:?})", s),
+            Transform::OneOrMore(s) => write!(f, "one-or-more({:?})", s),
+            Transform::ExactlyOne(s) => write!(f, "exactly-one({:?})", s),
             Transform::GenerateId(_) => write!(f, "generate-id()"),
             Transform::Boolean(b) => write!(f, "boolean({:?})", b),
             Transform::Not(b) => write!(f, "not({:?})", b),
@@ -394,6 +731,13 @@ impl<N: Node> Debug for Transform<N> {
             }
             Transform::FormatDate(p, q, _, _, _) => write!(f, "format-date({:?}, {:?}, ...)", p, q),
             Transform::FormatTime(p, q, _, _, _) => write!(f, "format-time({:?}, {:?}, ...)", p, q),
+            Transform::ComponentFromDateTime(c, t) => write!(f, "{:?}-from-dateTime({:?})", c, t),
+            Transform::ComponentFromDate(c, t) => write!(f, "{:?}-from-date({:?})", c, t),
+            Transform::ComponentFromTime(c, t) => write!(f, "{:?}-from-time({:?})", c, t),
+            Transform::ComponentFromDuration(c, t) => write!(f, "{:?}-from-duration({:?})", c, t),
+            Transform::AdjustDateTimeToTimezone(t, z) => {
+                write!(f, "adjust-dateTime-to-timezone({:?}, {:?})", t, z)
+            }
             Transform::FormatNumber(v, p, _) => write!(f, "format-number({:?}, {:?})", v, p),
             Transform::FormatInteger(i, s) => write!(f, "format-integer({:?}, {:?})", i, s),
             Transform::GenerateIntegers(_start_at, _select, _n) => write!(f, "generate-integers"),
@@ -403,6 +747,52 @@ impl<N: Node> Debug for Transform<N> {
             Transform::SystemProperty(p, _) => write!(f, "system-properties({:?})", p),
             Transform::AvailableSystemProperties => write!(f, "available-system-properties"),
             Transform::Document(uris, _) => write!(f, "document({:?})", uris),
+            Transform::ForEachItem(_, _) => write!(f, "for-each()"),
+            Transform::FilterItems(_, _) => write!(f, "filter()"),
+            Transform::FoldLeft(_, _, _) => write!(f, "fold-left()"),
+            Transform::FoldRight(_, _, _) => write!(f, "fold-right()"),
+            Transform::ForEachPair(_, _, _) => write!(f, "for-each-pair()"),
+            Transform::Sort(_, _, _) => write!(f, "sort()"),
+            Transform::Apply(_, _) => write!(f, "apply()"),
+            Transform::MapMerge(_, _) => write!(f, "map:merge()"),
+            Transform::MapSize(_) => write!(f, "map:size()"),
+            Transform::MapKeys(_) => write!(f, "map:keys()"),
+            Transform::MapContains(_, _) => write!(f, "map:contains()"),
+            Transform::MapGet(_, _) => write!(f, "map:get()"),
+            Transform::MapFind(_, _) => write!(f, "map:find()"),
+            Transform::MapPut(_, _, _) => write!(f, "map:put()"),
+            Transform::MapEntry(_, _) => write!(f, "map:entry()"),
+            Transform::MapRemove(_, _) => write!(f, "map:remove()"),
+            Transform::MapForEach(_, _) => write!(f, "map:for-each()"),
+            Transform::ArraySize(_) => write!(f, "array:size()"),
+            Transform::ArrayGet(_, _) => write!(f, "array:get()"),
+            Transform::ArrayPut(_, _, _) => write!(f, "array:put()"),
+            Transform::ArrayAppend(_, _) => write!(f, "array:append()"),
+            Transform::ArraySubarray(_, _, _) => write!(f, "array:subarray()"),
+            Transform::ArrayRemove(_, _) => write!(f, "array:remove()"),
+            Transform::ArrayInsertBefore(_, _, _) => write!(f, "array:insert-before()"),
+            Transform::ArrayHead(_) => write!(f, "array:head()"),
+            Transform::ArrayTail(_) => write!(f, "array:tail()"),
+            Transform::ArrayReverse(_) => write!(f, "array:reverse()"),
+            Transform::ArrayJoin(_) => write!(f, "array:join()"),
+            Transform::ArrayFlatten(_) => write!(f, "array:flatten()"),
+            Transform::ArrayForEach(_, _) => write!(f, "array:for-each()"),
+            Transform::ArrayFilter(_, _) => write!(f, "array:filter()"),
+            Transform::ArrayFoldLeft(_, _, _) => write!(f, "array:fold-left()"),
+            Transform::ArrayFoldRight(_, _, _) => write!(f, "array:fold-right()"),
+            Transform::ArrayForEachPair(_, _, _) => write!(f, "array:for-each-pair()"),
+            Transform::ArraySort(_, _, _) => write!(f, "array:sort()"),
+            Transform::BinaryLength(_) => write!(f, "bin:length()"),
+            Transform::BinaryPart(_, _, _) => write!(f, "bin:part()"),
+            Transform::BinaryDecodeString(_, _, _, _) => write!(f, "bin:decode-string()"),
+            Transform::FunctionItem(n, p, _) => match n {
+                Some(qn) => write!(f, "function reference {}#{}", qn, p.len()),
+                None => write!(f, "inline function with {} parameters", p.len()),
+            },
+            Transform::DynamicCall(_, a) => write!(f, "dynamic call with {} arguments", a.len()),
+            Transform::PartialApply(_, a) => {
+                write!(f, "partial application with {} arguments", a.len())
+            }
             Transform::Invoke(qn, _a, _) => write!(f, "invoke \"{}\"", qn),
             Transform::Message(_, _, _, _) => write!(f, "message"),
             Transform::NotImplemented(s) => write!(f, "Not implemented: \"{}\"", s),
@@ -433,6 +823,28 @@ pub fn in_scope_namespaces<N: Node>(n: Option<N>) -> Rc<NamespaceMap> {
     }
 }
 
+/// The quantifier of a quantified expression
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Quantifier {
+    /// At least one binding of the variables satisfies the test
+    Some,
+    /// Every binding of the variables satisfies the test
+    Every,
+}
+
+/// The key specifier of a lookup
+#[derive(Clone, Debug)]
+pub enum KeySpecifier<N: Node> {
+    /// An NCName, which is used as a string key
+    Name(String),
+    /// An integer, which is used as a key or as the position of an array member
+    Integer(i64),
+    /// A parenthesized expression that produces the keys
+    Expr(Box<Transform<N>>),
+    /// All of the entries of a map, or members of an array
+    Wildcard,
+}
+
 /// The sort order
 #[derive(Clone, PartialEq, Debug)]
 pub enum Order {
@@ -440,6 +852,18 @@ pub enum Order {
     Descending,
 }
 
+/// A component of a date, time, or duration value
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DateTimeComponent {
+    Year,
+    Month,
+    Day,
+    Hours,
+    Minutes,
+    Seconds,
+    Timezone,
+}
+
 /// Performing sorting of a [Sequence] using the given sort keys.
 pub(crate) fn do_sort<
     N: Node,
@@ -448,48 +872,87 @@ pub(crate) fn do_sort<
     H: FnMut(&Url) -> Result<String, Error>,
 >(
     seq: &mut Sequence<N>,
-    o: &Vec<(Order, Transform<N>)>,
+    o: &Vec<(Order, Transform<N>, Option<String>)>,
     ctxt: &Context<N>,
     stctxt: &mut StaticContext<N, F, G, H>,
 ) -> Result<(), Error> {
     // Optionally sort the select sequence
-    // TODO: multiple sort keys
     if !o.is_empty() {
-        seq.sort_by_cached_key(|k| {
-            // TODO: Don't panic
-            let key_seq = ContextBuilder::from(ctxt)
-                .context(vec![k.clone()])
+        let items = std::mem::take(seq);
+        *seq = sort_by_keys(items, o, stctxt, |i, k, stctxt| {
+            ContextBuilder::from(ctxt)
+                .context(vec![i.clone()])
                 .build()
-                .dispatch(stctxt, &o[0].1)
-                .expect("unable to determine key value");
-            // Assume string data type for now
-            // TODO: support number data type
-            // TODO: support all data types
-            key_seq.to_string()
-        });
-        if o[0].0 == Order::Descending {
-            seq.reverse();
-        }
+                .dispatch(stctxt, k)
+        })?;
     }
     Ok(())
 }
 
+/// Sort a vector using the given sort keys. Each sort key consists of the order, the expression for the key value, and the URI of the collation.
+/// The 'eval' closure evaluates a sort key expression for a member of the vector.
+/// The sort is stable.
+pub(crate) fn sort_by_keys<
+    T,
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    v: Vec<T>,
+    o: &[(Order, Transform<N>, Option<String>)],
+    stctxt: &mut StaticContext<N, F, G, H>,
+    mut eval: impl FnMut(
+        &T,
+        &Transform<N>,
+        &mut StaticContext<N, F, G, H>,
+    ) -> Result<Sequence<N>, Error>,
+) -> Result<Vec<T>, Error> {
+    let collations = o
+        .iter()
+        .map(|(_, _, c)| find_collation(stctxt, c))
+        .collect::<Result<Vec<Collation>, Error>>()?;
+    let mut keyed = Vec::with_capacity(v.len());
+    for t in v {
+        // Assume string data type for now
+        // TODO: support number data type
+        let mut keys = vec![];
+        for (_, k, _) in o {
+            keys.push(eval(&t, k, stctxt)?.to_string())
+        }
+        keyed.push((keys, t))
+    }
+    keyed.sort_by(|(a, _), (b, _)| {
+        o.iter()
+            .zip(&collations)
+            .zip(a.iter().zip(b))
+            .map(|(((ord, _, _), c), (x, y))| match ord {
+                Order::Ascending => c.compare(x, y),
+                Order::Descending => c.compare(y, x),
+            })
+            .find(|r| *r != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    });
+    Ok(keyed.into_iter().map(|(_, t)| t).collect())
+}
+
 /// Determine how a collection is to be divided into groups.
 /// This value would normally be inside an Option.
 /// A None value for the option means that the collection is not to be grouped.
 #[derive(Clone, Debug)]
+/// Grouping keys are compared using the collation, given by its URI. If there is no collation then the codepoint collation is used.
 pub enum Grouping<N: Node> {
-    By(Vec<Transform<N>>),
+    By(Vec<Transform<N>>, Option<String>),
     StartingWith(Box<Pattern<N>>),
     EndingWith(Box<Pattern<N>>),
-    Adjacent(Vec<Transform<N>>),
+    Adjacent(Vec<Transform<N>>, Option<String>),
 }
 
 impl<N: Node> Grouping<N> {
     fn to_string(&self) -> String {
         match self {
-            Grouping::By(_) => "group-by".to_string(),
-            Grouping::Adjacent(_) => "group-adjacent".to_string(),
+            Grouping::By(..) => "group-by".to_string(),
+            Grouping::Adjacent(..) => "group-adjacent".to_string(),
             Grouping::StartingWith(_) => "group-starting-with".to_string(),
             Grouping::EndingWith(_) => "group-ending-with".to_string(),
         }
@@ -548,6 +1011,17 @@ impl NodeTest {
             NodeTest::Name(nm) => nm.matches(n),
         }
     }
+    /// Does a namespace node match the node test?
+    /// The namespace node for the default namespace has no name, but is matched by the wildcard "*".
+    pub fn matches_namespace<N: Node>(&self, n: &N) -> bool {
+        match self {
+            NodeTest::Name(NameTest::Wildcard(
+                WildcardOrNamespaceUri::Wildcard,
+                WildcardOrName::Wildcard,
+            )) => n.node_type() == NodeType::Namespace,
+            _ => self.matches(n),
+        }
+    }
 }
 
 impl fmt::Display for NodeTest {
@@ -681,7 +1155,8 @@ impl KindTest {
             (KindTest::Comment, _) => false,
             (KindTest::Text, NodeType::Text) => true,
             (KindTest::Text, _) => false,
-            (KindTest::Namespace, _) => false, // not yet implemented
+            (KindTest::Namespace, NodeType::Namespace) => true,
+            (KindTest::Namespace, _) => false,
             (KindTest::Any, _) => true,
         }
     }
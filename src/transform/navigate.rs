@@ -71,6 +71,39 @@ pub(crate) fn compose<
     Ok(context.context)
 }
 
+/// The simple map operator.
+/// Each transform after the first is evaluated once for each item produced by the previous transform,
+/// with that item as the context item. The results are concatenated in order; they are not sorted into document order, nor are duplicates removed.
+pub(crate) fn simple_map<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    v: &[Transform<N>],
+) -> Result<Sequence<N>, Error> {
+    let Some((first, rest)) = v.split_first() else {
+        return Ok(vec![]);
+    };
+    let mut seq = ctxt.dispatch(stctxt, first)?;
+    for t in rest {
+        let mut result = vec![];
+        for (j, i) in seq.iter().enumerate() {
+            let mut r = ContextBuilder::from(ctxt)
+                .context(vec![i.clone()])
+                .context_item(Some(i.clone()))
+                .index(j)
+                .build()
+                .dispatch(stctxt, t)?;
+            result.append(&mut r);
+        }
+        seq = result;
+    }
+    Ok(seq)
+}
+
 /// For each item in the current context, evaluate the given node matching operation.
 pub(crate) fn step<N: Node>(ctxt: &Context<N>, nm: &NodeMatch) -> Result<Sequence<N>, Error> {
     match ctxt.context.iter().try_fold(vec![], |mut acc, i| {
@@ -240,6 +273,23 @@ pub(crate) fn step<N: Node>(ctxt: &Context<N>, nm: &NodeMatch) -> Result<Sequenc
                         }
                         Ok(acc)
                     }
+                    Axis::Namespace => {
+                        // Only elements have namespace nodes.
+                        // A declaration on an ancestor is masked by a nearer declaration of the same prefix.
+                        if n.node_type() == NodeType::Element {
+                            let mut prefixes = vec![];
+                            for ns in n.namespace_iter() {
+                                let p = ns.as_namespace_prefix()?.cloned();
+                                if !prefixes.contains(&p) {
+                                    prefixes.push(p);
+                                    if nm.nodetest.matches_namespace(&ns) {
+                                        acc.push_node(&ns)
+                                    }
+                                }
+                            }
+                        }
+                        Ok(acc)
+                    }
                     _ => Err(Error::new(
                         ErrorKind::NotImplemented,
                         String::from("coming soon"),
@@ -3,19 +3,26 @@
 use qualname::{NcName, QName};
 use std::cmp::Ordering;
 use std::rc::Rc;
+use std::str::FromStr;
 use url::Url;
 
 use english_numbers::{Formatting, convert};
 use formato::Formato;
 use italian_numbers::roman_converter;
+use num_bigint::BigInt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive, Zero};
 
 use crate::item::{Item, Node, NodeType, Sequence, SequenceTrait};
 use crate::pattern::{Branch, Pattern, Step};
 use crate::transform::context::{Context, StaticContext};
+use crate::transform::datetime::{is_temporal, temporal_arithmetic};
+use crate::transform::types::error;
 use crate::transform::{
     ArithmeticOperand, ArithmeticOperator, Axis, KindTest, NameTest, NodeTest, Transform,
 };
-use crate::value::Value;
+use crate::validators::datatypes::double;
+use crate::value::{Value, ValueData};
 use crate::xdmerror::{Error, ErrorKind};
 
 /// Level value for xsl:number. See XSLT 12.3.
@@ -423,6 +430,9 @@ pub(crate) fn tr_range<
 }
 
 /// Perform an arithmetic operation.
+/// Numeric operands are promoted following the XPath rules (integer, decimal, float, double)
+/// and the operation is performed in the common type.
+/// Dates, times and durations have their own arithmetic.
 pub(crate) fn arithmetic<
     N: Node,
     F: FnMut(&str) -> Result<(), Error>,
@@ -433,35 +443,305 @@ pub(crate) fn arithmetic<
     stctxt: &mut StaticContext<N, F, G, H>,
     ops: &Vec<ArithmeticOperand<N>>,
 ) -> Result<Sequence<N>, Error> {
-    // Type: the result will be a number, but integer or double?
-    // If all of the operands are integers, then the result is integer otherwise double
-    // TODO: check the type of all operands to determine type of result (can probably do this in static analysis phase)
-    // In the meantime, let's assume the result will be double and convert any integers
-    let mut acc = 0.0;
+    let mut acc = Value::from(0);
     for o in ops {
-        let j = match ctxt.dispatch(stctxt, &o.operand) {
-            Ok(s) => s,
-            Err(_) => {
-                acc = f64::NAN;
-                break;
+        let j = ctxt.dispatch(stctxt, &o.operand)?;
+        match j.len() {
+            0 => return Ok(vec![]),
+            1 => {}
+            _ => {
+                return Err(error(
+                    ErrorKind::TypeError,
+                    "XPTY0004",
+                    String::from("operands of an arithmetic expression must be singletons"),
+                ));
+            }
+        }
+        acc = match (&j[0], o.op) {
+            (Item::Value(v), ArithmeticOperator::Noop) if is_temporal(v) => (**v).clone(),
+            (Item::Value(v), _) if is_temporal(v) || is_temporal(&acc) => {
+                temporal_arithmetic(&acc, o.op, v, ctxt.implicit_timezone())?
             }
+            (i, _) if is_temporal(&acc) => temporal_arithmetic(
+                &acc,
+                o.op,
+                &Value::from(i.to_double()),
+                ctxt.implicit_timezone(),
+            )?,
+            (i, ArithmeticOperator::Noop) => Numeric::from_item(i)?.into(),
+            (i, op) => Numeric::from_value(&acc)?
+                .operate(op, Numeric::from_item(i)?)?
+                .into(),
         };
-        if j.len() != 1 {
-            acc = f64::NAN;
-            break;
+    }
+    Ok(vec![Item::Value(Rc::new(acc))])
+}
+
+/// A numeric operand, atomized to one of the primitive numeric types.
+#[derive(Clone, Debug)]
+enum Numeric {
+    Integer(BigInt),
+    Decimal(Decimal),
+    Float(f32),
+    Double(f64),
+}
+
+impl Numeric {
+    fn from_item<N: Node>(i: &Item<N>) -> Result<Self, Error> {
+        match i {
+            Item::Value(v) => Self::from_value(v),
+            // Untyped data is cast to xs:double
+            Item::Node(n) => untyped_double(&n.to_string()).map(Numeric::Double),
+            _ => Ok(Numeric::Double(i.to_double())),
         }
-        let u = j[0].to_double();
-        match o.op {
-            ArithmeticOperator::Noop => acc = u,
-            ArithmeticOperator::Add => acc += u,
-            ArithmeticOperator::Subtract => acc -= u,
-            ArithmeticOperator::Multiply => acc *= u,
-            ArithmeticOperator::Divide => acc /= u,
-            ArithmeticOperator::IntegerDivide => acc /= u, // TODO: convert to integer
-            ArithmeticOperator::Modulo => acc %= u,
+    }
+    fn from_value(v: &Value) -> Result<Self, Error> {
+        Ok(match v.value_ref() {
+            ValueData::Integer(i) => Numeric::Integer(i.clone()),
+            ValueData::NonPositiveInteger(_)
+            | ValueData::NegativeInteger(_)
+            | ValueData::Long(_)
+            | ValueData::Int(_)
+            | ValueData::Short(_)
+            | ValueData::Byte(_)
+            | ValueData::NonNegativeInteger(_)
+            | ValueData::UnsignedLong(_)
+            | ValueData::UnsignedInt(_)
+            | ValueData::UnsignedShort(_)
+            | ValueData::UnsignedByte(_)
+            | ValueData::PositiveInteger(_) => {
+                Numeric::Integer(BigInt::from_str(&v.to_string()).map_err(|_| overflow())?)
+            }
+            ValueData::Decimal(d) => Numeric::Decimal(*d),
+            ValueData::Float(f) => Numeric::Float(*f),
+            ValueData::Double(d) => Numeric::Double(*d),
+            ValueData::Untyped | ValueData::UntypedAtomic => Numeric::Double(v.to_double()),
+            _ => {
+                return Err(error(
+                    ErrorKind::TypeError,
+                    "XPTY0004",
+                    format!(
+                        "operand of an arithmetic expression must be numeric, not {}",
+                        v.value_type()
+                    ),
+                ));
+            }
+        })
+    }
+    /// Position of the type in the promotion order.
+    fn rank(&self) -> u8 {
+        match self {
+            Numeric::Integer(_) => 0,
+            Numeric::Decimal(_) => 1,
+            Numeric::Float(_) => 2,
+            Numeric::Double(_) => 3,
         }
     }
-    Ok(vec![Item::Value(Rc::new(Value::from(acc)))])
+    /// Promote to the type with the given rank. Types are never demoted.
+    fn promote(self, rank: u8) -> Result<Self, Error> {
+        Ok(match (self, rank) {
+            (Numeric::Integer(i), 1) => {
+                Numeric::Decimal(Decimal::from_str(&i.to_string()).map_err(|_| overflow())?)
+            }
+            (Numeric::Integer(i), 2) => Numeric::Float(i.to_f32().unwrap_or(f32::NAN)),
+            (Numeric::Integer(i), 3) => Numeric::Double(i.to_f64().unwrap_or(f64::NAN)),
+            (Numeric::Decimal(d), 2) => Numeric::Float(d.to_f32().unwrap_or(f32::NAN)),
+            (Numeric::Decimal(d), 3) => Numeric::Double(d.to_f64().unwrap_or(f64::NAN)),
+            (Numeric::Float(f), 3) => Numeric::Double(f as f64),
+            (n, _) => n,
+        })
+    }
+    fn operate(self, op: ArithmeticOperator, other: Numeric) -> Result<Self, Error> {
+        let rank = self.rank().max(other.rank());
+        match (self.promote(rank)?, other.promote(rank)?) {
+            // Integer arithmetic is exact, so it cannot overflow
+            (Numeric::Integer(a), Numeric::Integer(b)) => match op {
+                ArithmeticOperator::Noop => Ok(Numeric::Integer(b)),
+                ArithmeticOperator::Add => Ok(Numeric::Integer(a + b)),
+                ArithmeticOperator::Subtract => Ok(Numeric::Integer(a - b)),
+                ArithmeticOperator::Multiply => Ok(Numeric::Integer(a * b)),
+                // Dividing two integers gives a decimal
+                ArithmeticOperator::Divide => Numeric::Integer(a)
+                    .promote(1)?
+                    .operate(op, Numeric::Integer(b)),
+                ArithmeticOperator::IntegerDivide => {
+                    nonzero(b.is_zero())?;
+                    Ok(Numeric::Integer(a / b))
+                }
+                ArithmeticOperator::Modulo => {
+                    nonzero(b.is_zero())?;
+                    Ok(Numeric::Integer(a % b))
+                }
+            },
+            (Numeric::Decimal(a), Numeric::Decimal(b)) => {
+                let r = match op {
+                    ArithmeticOperator::Noop => Some(b),
+                    ArithmeticOperator::Add => a.checked_add(b),
+                    ArithmeticOperator::Subtract => a.checked_sub(b),
+                    ArithmeticOperator::Multiply => a.checked_mul(b),
+                    ArithmeticOperator::Divide => {
+                        nonzero(b.is_zero())?;
+                        a.checked_div(b)
+                    }
+                    ArithmeticOperator::IntegerDivide => {
+                        nonzero(b.is_zero())?;
+                        return a
+                            .checked_div(b)
+                            .and_then(|q| q.trunc().to_i128())
+                            .map(|q| Numeric::Integer(BigInt::from(q)))
+                            .ok_or_else(overflow);
+                    }
+                    ArithmeticOperator::Modulo => {
+                        nonzero(b.is_zero())?;
+                        a.checked_rem(b)
+                    }
+                };
+                r.map(|d| Numeric::Decimal(d.normalize()))
+                    .ok_or_else(overflow)
+            }
+            (Numeric::Float(a), Numeric::Float(b)) => match op {
+                ArithmeticOperator::IntegerDivide => {
+                    float_idiv(a as f64, b as f64).map(Numeric::Integer)
+                }
+                _ => Ok(Numeric::Float(float_op(a as f64, op, b as f64) as f32)),
+            },
+            (Numeric::Double(a), Numeric::Double(b)) => match op {
+                ArithmeticOperator::IntegerDivide => float_idiv(a, b).map(Numeric::Integer),
+                _ => Ok(Numeric::Double(float_op(a, op, b))),
+            },
+            _ => unreachable!("operands are promoted to a common type"),
+        }
+    }
+}
+
+impl From<Numeric> for Value {
+    fn from(n: Numeric) -> Self {
+        match n {
+            Numeric::Integer(i) => Value::from(i),
+            Numeric::Decimal(d) => Value::from(d),
+            Numeric::Float(f) => Value::from(f),
+            Numeric::Double(d) => Value::from(d),
+        }
+    }
+}
+
+// IEEE 754 arithmetic for xs:float and xs:double
+fn float_op(a: f64, op: ArithmeticOperator, b: f64) -> f64 {
+    match op {
+        ArithmeticOperator::Noop => b,
+        ArithmeticOperator::Add => a + b,
+        ArithmeticOperator::Subtract => a - b,
+        ArithmeticOperator::Multiply => a * b,
+        ArithmeticOperator::Divide => a / b,
+        ArithmeticOperator::IntegerDivide => (a / b).trunc(),
+        ArithmeticOperator::Modulo => a % b,
+    }
+}
+
+// Integer division of floating point values must give a finite integer result
+fn float_idiv(a: f64, b: f64) -> Result<BigInt, Error> {
+    nonzero(b == 0.0)?;
+    let q = (a / b).trunc();
+    if a.is_nan() || b.is_nan() || a.is_infinite() || !q.is_finite() {
+        return Err(overflow());
+    }
+    BigInt::from_f64(q).ok_or_else(overflow)
+}
+
+fn nonzero(zero: bool) -> Result<(), Error> {
+    if zero {
+        Err(error(
+            ErrorKind::Unknown,
+            "FOAR0001",
+            String::from("division by zero"),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+// Cast untyped data to xs:double, raising FORG0001 if it is not in the lexical space.
+fn untyped_double(s: &str) -> Result<f64, Error> {
+    double(s).ok_or_else(|| {
+        error(
+            ErrorKind::TypeError,
+            "FORG0001",
+            format!("unable to cast \"{}\" to a number", s),
+        )
+    })
+}
+
+/// Unary plus or minus. The operand is atomized and must be a single numeric value, or empty.
+/// The type of the result is the same as the type of the operand, except that
+/// all subtypes of xs:integer give an xs:integer result.
+pub(crate) fn unary<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    op: &ArithmeticOperator,
+    operand: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let s = ctxt.dispatch(stctxt, operand)?;
+    let negate = *op == ArithmeticOperator::Subtract;
+    let v = match s.as_slice() {
+        [] => return Ok(vec![]),
+        [Item::Value(v)] => v.clone(),
+        [Item::Node(n)] => {
+            // Untyped data is cast to xs:double
+            Rc::new(Value::from(untyped_double(&n.to_string())?))
+        }
+        _ => {
+            return Err(error(
+                ErrorKind::TypeError,
+                "XPTY0004",
+                String::from("operand of a unary expression must be a single numeric value"),
+            ));
+        }
+    };
+    let result = match v.value_ref() {
+        ValueData::Double(d) => Value::from(if negate { -d } else { *d }),
+        ValueData::Float(f) => Value::from(if negate { -f } else { *f }),
+        ValueData::Decimal(d) => Value::from(if negate { -d } else { *d }),
+        ValueData::Integer(_)
+        | ValueData::NonPositiveInteger(_)
+        | ValueData::NegativeInteger(_)
+        | ValueData::Long(_)
+        | ValueData::Int(_)
+        | ValueData::Short(_)
+        | ValueData::Byte(_)
+        | ValueData::NonNegativeInteger(_)
+        | ValueData::UnsignedLong(_)
+        | ValueData::UnsignedInt(_)
+        | ValueData::UnsignedShort(_)
+        | ValueData::UnsignedByte(_)
+        | ValueData::PositiveInteger(_) => {
+            let i = BigInt::from_str(&v.to_string()).map_err(|_| overflow())?;
+            Value::from(if negate { -i } else { i })
+        }
+        _ => {
+            return Err(error(
+                ErrorKind::TypeError,
+                "XPTY0004",
+                format!(
+                    "operand of a unary expression must be numeric, not {}",
+                    v.value_type()
+                ),
+            ));
+        }
+    };
+    Ok(vec![Item::Value(Rc::new(result))])
+}
+
+fn overflow() -> Error {
+    error(
+        ErrorKind::Unknown,
+        "FOAR0002",
+        String::from("numeric operation overflow"),
+    )
 }
 
 /// XPath format-number function.
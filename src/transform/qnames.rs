@@ -0,0 +1,390 @@
+//! Qualified names, and the functions that construct them and take them apart.
+
+use std::rc::Rc;
+
+use qualname::{NamespacePrefix, NamespaceUri, NcName, QName};
+use url::Url;
+
+use crate::item::{Item, Node, NodeType, Sequence, SequenceTrait};
+use crate::transform::Transform;
+use crate::transform::context::{Context, StaticContext};
+use crate::transform::maps::atomize;
+use crate::transform::types::error;
+use crate::value::{Value, ValueBuilder, ValueData};
+use crate::xdmerror::{Error, ErrorKind};
+
+/// The namespace that is bound to the "xml" prefix.
+const XMLNS: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// The node that is the argument of a function, or the context item if there is no argument.
+/// The result is None if the argument is the empty sequence.
+pub(crate) fn node_argument<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    t: &Option<Box<Transform<N>>>,
+) -> Result<Option<N>, Error> {
+    let s = match t {
+        Some(t) => ctxt.dispatch(stctxt, t)?,
+        None => match &ctxt.context_item {
+            Some(i) => vec![i.clone()],
+            None => {
+                return Err(error(
+                    ErrorKind::DynamicAbsent,
+                    "XPDY0002",
+                    String::from("no context item"),
+                ));
+            }
+        },
+    };
+    match s.as_slice() {
+        [] => Ok(None),
+        [Item::Node(n)] => Ok(Some(n.clone())),
+        _ => Err(error(
+            ErrorKind::TypeError,
+            "XPTY0004",
+            String::from("expected a single node"),
+        )),
+    }
+}
+
+/// The node whose in-scope namespaces give the prefix of the node's name.
+/// Attributes do not have namespace nodes, so their parent element is used.
+pub(crate) fn namespace_scope<N: Node>(n: &N) -> N {
+    match (n.node_type(), n.parent()) {
+        (NodeType::Attribute, Some(p)) => p,
+        _ => n.clone(),
+    }
+}
+
+// The element that is the argument of a function.
+fn element_argument<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    t: &Transform<N>,
+) -> Result<N, Error> {
+    match ctxt.dispatch(stctxt, t)?.as_slice() {
+        [Item::Node(n)] if n.node_type() == NodeType::Element => Ok(n.clone()),
+        _ => Err(error(
+            ErrorKind::TypeError,
+            "XPTY0004",
+            String::from("expected a single element"),
+        )),
+    }
+}
+
+// An optional string argument. The result is None if the argument is the empty sequence.
+fn string_argument<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    t: &Transform<N>,
+) -> Result<Option<String>, Error> {
+    let mut v = atomize(&ctxt.dispatch(stctxt, t)?)?;
+    match v.len() {
+        0 => Ok(None),
+        1 => Ok(v.pop().map(|s| s.to_string())),
+        _ => Err(error(
+            ErrorKind::TypeError,
+            "XPTY0004",
+            String::from("expected a single string"),
+        )),
+    }
+}
+
+// The QName that is the argument of a function, and its prefix.
+// The result is None if the argument is the empty sequence.
+fn qname_argument<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    t: &Transform<N>,
+) -> Result<Option<(QName, Option<NamespacePrefix>)>, Error> {
+    let mut v = atomize(&ctxt.dispatch(stctxt, t)?)?;
+    match (v.pop(), v.is_empty()) {
+        (None, _) => Ok(None),
+        (Some(q), true) => match &q.value {
+            ValueData::QName(qn, p) => Ok(Some((qn.clone(), p.clone()))),
+            _ => Err(error(
+                ErrorKind::TypeError,
+                "XPTY0004",
+                format!("expected a QName, got a {}", q.value_type()),
+            )),
+        },
+        _ => Err(error(
+            ErrorKind::TypeError,
+            "XPTY0004",
+            String::from("expected a single QName"),
+        )),
+    }
+}
+
+// Split a lexical QName into its prefix and local part.
+fn lexical_qname(s: &str) -> Result<(Option<NamespacePrefix>, NcName), Error> {
+    let invalid = || {
+        error(
+            ErrorKind::TypeError,
+            "FOCA0002",
+            format!("\"{}\" is not a valid QName", s),
+        )
+    };
+    let t = s.trim();
+    match t.split_once(':') {
+        Some((p, l)) => Ok((
+            Some(NamespacePrefix::try_from(p).map_err(|_| invalid())?),
+            NcName::try_from(l).map_err(|_| invalid())?,
+        )),
+        None => Ok((None, NcName::try_from(t).map_err(|_| invalid())?)),
+    }
+}
+
+// The namespace URI that is bound to a prefix in the in-scope namespaces of an element.
+// No prefix gives the default namespace.
+fn in_scope_namespace<N: Node>(e: &N, prefix: &Option<NamespacePrefix>) -> Option<NamespaceUri> {
+    if prefix.as_ref().is_some_and(|p| p.to_string() == "xml") {
+        NamespaceUri::try_from(XMLNS).ok()
+    } else {
+        e.to_namespace_uri(prefix).ok()
+    }
+}
+
+fn qname_value<N: Node>(qn: QName, prefix: Option<NamespacePrefix>) -> Sequence<N> {
+    vec![Item::Value(Rc::new(
+        ValueBuilder::new()
+            .value(ValueData::QName(qn, prefix))
+            .build(),
+    ))]
+}
+
+// A namespace URI as an xs:anyURI value. No namespace is the zero-length URI.
+fn uri_value<N: Node>(uri: Option<NamespaceUri>) -> Sequence<N> {
+    vec![Item::Value(Rc::new(
+        uri.map_or_else(|| Value::from(""), Value::from),
+    ))]
+}
+
+/// XPath QName function. Construct a QName from a namespace URI and a lexical QName.
+pub(crate) fn qname<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    uri: &Transform<N>,
+    name: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let uri = string_argument(ctxt, stctxt, uri)?.filter(|u| !u.is_empty());
+    let name = ctxt.dispatch(stctxt, name)?.to_string();
+    let (prefix, local) = lexical_qname(&name)?;
+    let nsuri = match uri {
+        Some(u) => Some(NamespaceUri::try_from(u.as_str()).map_err(|_| {
+            error(
+                ErrorKind::TypeError,
+                "FOCA0002",
+                format!("\"{}\" is not a valid namespace URI", u),
+            )
+        })?),
+        None if prefix.is_some() => {
+            return Err(error(
+                ErrorKind::TypeError,
+                "FOCA0002",
+                format!("\"{}\" has a prefix, but there is no namespace URI", name),
+            ));
+        }
+        None => None,
+    };
+    Ok(qname_value(QName::new_from_parts(local, nsuri), prefix))
+}
+
+/// XPath resolve-QName function. Resolve a lexical QName using the in-scope namespaces of an element.
+pub(crate) fn resolve_qname<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    name: &Transform<N>,
+    element: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let Some(name) = string_argument(ctxt, stctxt, name)? else {
+        return Ok(vec![]);
+    };
+    let e = element_argument(ctxt, stctxt, element)?;
+    let (prefix, local) = lexical_qname(&name)?;
+    let nsuri = in_scope_namespace(&e, &prefix);
+    if prefix.is_some() && nsuri.is_none() {
+        return Err(error(
+            ErrorKind::DynamicAbsent,
+            "FONS0004",
+            format!("no namespace is declared for the prefix of \"{}\"", name),
+        ));
+    }
+    Ok(qname_value(QName::new_from_parts(local, nsuri), prefix))
+}
+
+/// XPath prefix-from-QName function.
+pub(crate) fn prefix_from_qname<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    q: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    Ok(qname_argument(ctxt, stctxt, q)?
+        .and_then(|(_, p)| p)
+        .and_then(|p| NcName::try_from(p.to_string().as_str()).ok())
+        .map_or_else(Vec::new, |p| vec![Item::Value(Rc::new(Value::from(p)))]))
+}
+
+/// XPath local-name-from-QName function.
+pub(crate) fn local_name_from_qname<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    q: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    Ok(
+        qname_argument(ctxt, stctxt, q)?.map_or_else(Vec::new, |(qn, _)| {
+            vec![Item::Value(Rc::new(Value::from(qn.local_name())))]
+        }),
+    )
+}
+
+/// XPath namespace-uri-from-QName function.
+pub(crate) fn namespace_uri_from_qname<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    q: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    Ok(qname_argument(ctxt, stctxt, q)?
+        .map_or_else(Vec::new, |(qn, _)| uri_value(qn.namespace_uri())))
+}
+
+/// XPath namespace-uri-for-prefix function.
+/// The zero-length prefix, or the empty sequence, gives the default namespace.
+pub(crate) fn namespace_uri_for_prefix<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    prefix: &Transform<N>,
+    element: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let prefix = string_argument(ctxt, stctxt, prefix)?.filter(|p| !p.is_empty());
+    let e = element_argument(ctxt, stctxt, element)?;
+    let prefix = match prefix {
+        Some(p) => match NamespacePrefix::try_from(p.as_str()) {
+            Ok(p) => Some(p),
+            Err(_) => return Ok(vec![]),
+        },
+        None => None,
+    };
+    Ok(in_scope_namespace(&e, &prefix).map_or_else(Vec::new, |u| uri_value(Some(u))))
+}
+
+/// XPath in-scope-prefixes function. The default namespace has the zero-length prefix.
+pub(crate) fn in_scope_prefixes<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    element: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let e = element_argument(ctxt, stctxt, element)?;
+    let mut prefixes: Vec<String> = e
+        .namespace_iter()
+        .filter(|ns| ns.is_in_scope())
+        .map(|ns| {
+            ns.as_namespace_prefix()
+                .ok()
+                .flatten()
+                .map_or_else(String::new, |p| p.to_string())
+        })
+        .collect();
+    if !prefixes.iter().any(|p| p == "xml") {
+        prefixes.push(String::from("xml"))
+    }
+    Ok(prefixes
+        .into_iter()
+        .map(|p| Item::Value(Rc::new(Value::from(p))))
+        .collect())
+}
+
+/// XPath node-name function. Nodes that do not have a name, such as text nodes, give the empty sequence.
+pub(crate) fn node_name<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    t: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    Ok(node_argument(ctxt, stctxt, t)?
+        .and_then(|n| n.name().map(|qn| (n, qn)))
+        .map_or_else(Vec::new, |(n, qn)| {
+            let prefix = qn
+                .namespace_uri()
+                .and_then(|u| namespace_scope(&n).to_namespace_prefix(&u).ok().flatten());
+            qname_value(qn, prefix)
+        }))
+}
+
+/// XPath namespace-uri function. A node that does not have a namespace gives the zero-length URI.
+pub(crate) fn namespace_uri<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    t: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    Ok(uri_value(
+        node_argument(ctxt, stctxt, t)?
+            .filter(|n| matches!(n.node_type(), NodeType::Element | NodeType::Attribute))
+            .and_then(|n| n.name())
+            .and_then(|qn| qn.namespace_uri()),
+    ))
+}
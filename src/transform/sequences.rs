@@ -0,0 +1,462 @@
+//! General functions on sequences, defined in XPath Functions 2.0 and 3.1.
+//! Value equality follows the rules for fn:distinct-values and fn:deep-equal: numeric values are compared by value regardless of their type, strings are compared using a collation,
+//! NaN is equal to itself, and values of incomparable types are simply not equal.
+
+use std::rc::Rc;
+use url::Url;
+
+use crate::item::{Item, Node, NodeType, Sequence, same_key};
+use crate::transform::Transform;
+use crate::transform::collation::{Collation, collation_argument};
+use crate::transform::context::{Context, StaticContext};
+use crate::transform::maps::{atomize, atomize_one};
+use crate::transform::types::error;
+use crate::value::{Value, ValueData};
+use crate::xdmerror::{Error, ErrorKind};
+
+/// XPath empty function.
+pub(crate) fn tr_empty<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    Ok(vec![Item::Value(Rc::new(Value::from(
+        ctxt.dispatch(stctxt, s)?.is_empty(),
+    )))])
+}
+
+/// XPath exists function.
+pub(crate) fn exists<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    Ok(vec![Item::Value(Rc::new(Value::from(
+        !ctxt.dispatch(stctxt, s)?.is_empty(),
+    )))])
+}
+
+/// XPath head function. Returns the first item of the sequence.
+pub(crate) fn head<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    Ok(ctxt.dispatch(stctxt, s)?.into_iter().take(1).collect())
+}
+
+/// XPath tail function. Returns all but the first item of the sequence.
+pub(crate) fn tail<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    Ok(ctxt.dispatch(stctxt, s)?.into_iter().skip(1).collect())
+}
+
+/// XPath insert-before function.
+/// A position less than 1 inserts at the start of the sequence, a position beyond the end appends to the sequence.
+pub(crate) fn insert_before<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    p: &Transform<N>,
+    i: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let mut seq = ctxt.dispatch(stctxt, s)?;
+    let pos = integer_argument(ctxt.dispatch(stctxt, p)?)?;
+    let inserts = ctxt.dispatch(stctxt, i)?;
+    let at = pos.clamp(1, seq.len() as i64 + 1) as usize - 1;
+    seq.splice(at..at, inserts);
+    Ok(seq)
+}
+
+/// XPath remove function. If the position is out of range then the sequence is returned unchanged.
+pub(crate) fn remove<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    p: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let mut seq = ctxt.dispatch(stctxt, s)?;
+    let pos = integer_argument(ctxt.dispatch(stctxt, p)?)?;
+    if pos >= 1 && pos <= seq.len() as i64 {
+        seq.remove(pos as usize - 1);
+    }
+    Ok(seq)
+}
+
+/// XPath reverse function.
+pub(crate) fn reverse<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let mut seq = ctxt.dispatch(stctxt, s)?;
+    seq.reverse();
+    Ok(seq)
+}
+
+/// XPath subsequence function.
+/// The result is the items whose position p satisfies round(start) <= p < round(start) + round(length).
+pub(crate) fn subsequence<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    st: &Transform<N>,
+    l: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let seq = ctxt.dispatch(stctxt, s)?;
+    let start = round_half_up(double_argument(ctxt.dispatch(stctxt, st)?)?);
+    let end = match l {
+        Some(t) => start + round_half_up(double_argument(ctxt.dispatch(stctxt, t)?)?),
+        None => f64::INFINITY,
+    };
+    Ok(seq
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| {
+            let p = (*i + 1) as f64;
+            p >= start && p < end
+        })
+        .map(|(_, item)| item)
+        .collect())
+}
+
+/// XPath unordered function. The order of the sequence is left unchanged.
+pub(crate) fn unordered<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    ctxt.dispatch(stctxt, s)
+}
+
+/// XPath distinct-values function. The first of each set of equal values is retained.
+pub(crate) fn distinct_values<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    c: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let values = atomize(&ctxt.dispatch(stctxt, s)?)?;
+    let collation = collation_argument(ctxt, stctxt, c)?;
+    let mut result: Vec<Rc<Value>> = vec![];
+    for v in values {
+        if !result.iter().any(|r| values_equal(r, &v, &collation)) {
+            result.push(v)
+        }
+    }
+    Ok(result.into_iter().map(Item::Value).collect())
+}
+
+/// XPath index-of function. Returns the positions of the items that are equal to the search value.
+pub(crate) fn index_of<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    t: &Transform<N>,
+    c: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let values = atomize(&ctxt.dispatch(stctxt, s)?)?;
+    let search = atomize_one(&ctxt.dispatch(stctxt, t)?)?;
+    let collation = collation_argument(ctxt, stctxt, c)?;
+    if is_nan(&search) {
+        // NaN is not equal to anything, including itself
+        return Ok(vec![]);
+    }
+    Ok(values
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| values_equal(v, &search, &collation))
+        .map(|(i, _)| Item::Value(Rc::new(Value::from(i as i64 + 1))))
+        .collect())
+}
+
+/// XPath deep-equal function.
+pub(crate) fn deep_equal<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    t: &Transform<N>,
+    c: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let a = ctxt.dispatch(stctxt, s)?;
+    let b = ctxt.dispatch(stctxt, t)?;
+    let collation = collation_argument(ctxt, stctxt, c)?;
+    Ok(vec![Item::Value(Rc::new(Value::from(
+        sequences_deep_equal(&a, &b, &collation)?,
+    )))])
+}
+
+/// XPath zero-or-one function.
+pub(crate) fn zero_or_one<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let seq = ctxt.dispatch(stctxt, s)?;
+    if seq.len() > 1 {
+        Err(error(
+            ErrorKind::Unknown,
+            "FORG0003",
+            String::from("fn:zero-or-one called with a sequence containing more than one item"),
+        ))
+    } else {
+        Ok(seq)
+    }
+}
+
+/// XPath one-or-more function.
+pub(crate) fn one_or_more<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let seq = ctxt.dispatch(stctxt, s)?;
+    if seq.is_empty() {
+        Err(error(
+            ErrorKind::Unknown,
+            "FORG0004",
+            String::from("fn:one-or-more called with an empty sequence"),
+        ))
+    } else {
+        Ok(seq)
+    }
+}
+
+/// XPath exactly-one function.
+pub(crate) fn exactly_one<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let seq = ctxt.dispatch(stctxt, s)?;
+    if seq.len() != 1 {
+        Err(error(
+            ErrorKind::Unknown,
+            "FORG0005",
+            format!(
+                "fn:exactly-one called with a sequence containing {} items",
+                seq.len()
+            ),
+        ))
+    } else {
+        Ok(seq)
+    }
+}
+
+// Are two sequences deep-equal?
+fn sequences_deep_equal<N: Node>(
+    a: &Sequence<N>,
+    b: &Sequence<N>,
+    collation: &Collation,
+) -> Result<bool, Error> {
+    if a.len() != b.len() {
+        return Ok(false);
+    }
+    for (i, j) in a.iter().zip(b) {
+        if !items_deep_equal(i, j, collation)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+// Are two items deep-equal?
+// Atomic values use the same equality as distinct-values. Function items cannot be compared.
+fn items_deep_equal<N: Node>(
+    a: &Item<N>,
+    b: &Item<N>,
+    collation: &Collation,
+) -> Result<bool, Error> {
+    match (a, b) {
+        (Item::Function(_), _) | (_, Item::Function(_)) => Err(error(
+            ErrorKind::TypeError,
+            "FOTY0015",
+            String::from("function items cannot be compared"),
+        )),
+        (Item::Value(v), Item::Value(w)) => Ok(values_equal(v, w, collation)),
+        (Item::Node(m), Item::Node(n)) => Ok(nodes_deep_equal(m, n)),
+        (Item::Array(x), Item::Array(y)) => {
+            if x.len() != y.len() {
+                return Ok(false);
+            }
+            for (m, n) in x.iter().zip(y.iter()) {
+                if !sequences_deep_equal(m, n, collation)? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+        (Item::Map(x), Item::Map(y)) => {
+            if x.len() != y.len() {
+                return Ok(false);
+            }
+            for (k, v) in x.iter() {
+                match y.get(k) {
+                    Some(w) => {
+                        if !sequences_deep_equal(v, w, collation)? {
+                            return Ok(false);
+                        }
+                    }
+                    None => return Ok(false),
+                }
+            }
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+// Are two atomic values equal? Strings are compared using the collation.
+fn values_equal(a: &Value, b: &Value, collation: &Collation) -> bool {
+    if is_string(a) && is_string(b) {
+        collation.equal(&a.to_string(), &b.to_string())
+    } else {
+        same_key(a, b)
+    }
+}
+
+fn is_string(v: &Value) -> bool {
+    matches!(
+        v.value,
+        ValueData::String(_)
+            | ValueData::NormalizedString(_)
+            | ValueData::Token
+            | ValueData::Language
+            | ValueData::NMTOKEN(_)
+            | ValueData::Name(_)
+            | ValueData::NCName(_)
+            | ValueData::ID(_)
+            | ValueData::IDREF(_)
+            | ValueData::ENTITY(_)
+            | ValueData::UntypedAtomic
+    )
+}
+
+// Are two nodes deep-equal?
+fn nodes_deep_equal<N: Node>(m: &N, n: &N) -> bool {
+    match (m.node_type(), n.node_type()) {
+        (NodeType::Attribute, NodeType::Attribute) => {
+            m.name() == n.name() && m.to_string() == n.to_string()
+        }
+        (NodeType::Comment, NodeType::Comment) => m.to_string() == n.to_string(),
+        (NodeType::Namespace, NodeType::Namespace) => {
+            m.name() == n.name() && m.to_string() == n.to_string()
+        }
+        _ => Node::eq(m, n),
+    }
+}
+
+// The single integer in a sequence, such as a position argument.
+fn integer_argument<N: Node>(s: Sequence<N>) -> Result<i64, Error> {
+    match s.as_slice() {
+        [i] => i.to_int(),
+        _ => Err(error(
+            ErrorKind::TypeError,
+            "XPTY0004",
+            String::from("expected a single integer"),
+        )),
+    }
+}
+
+// The single double in a sequence.
+fn double_argument<N: Node>(s: Sequence<N>) -> Result<f64, Error> {
+    match s.as_slice() {
+        [i] => Ok(i.to_double()),
+        _ => Err(error(
+            ErrorKind::TypeError,
+            "XPTY0004",
+            String::from("expected a single number"),
+        )),
+    }
+}
+
+// Round as fn:round does, i.e. halves are rounded towards positive infinity.
+fn round_half_up(d: f64) -> f64 {
+    (d + 0.5).floor()
+}
+
+fn is_nan(v: &Value) -> bool {
+    match v.value {
+        ValueData::Double(d) => d.is_nan(),
+        ValueData::Float(f) => f.is_nan(),
+        _ => false,
+    }
+}
@@ -1,18 +1,26 @@
 //! These functions are for features defined in XPath Functions 1.0 and 2.0.
 
+use std::cmp::Ordering;
 use std::rc::Rc;
 
-use qualname::{NamespacePrefix, NamespaceUri};
+use qualname::{NamespacePrefix, NamespaceUri, NcName, QName};
+use regex::Regex;
+use unicode_normalization::UnicodeNormalization;
 use unicode_segmentation::UnicodeSegmentation;
 use url::Url;
 
 use crate::item::{Item, Node, Sequence, SequenceTrait};
+use crate::parser::common::is_char10;
 use crate::transform::Transform;
+use crate::transform::collation::collation_argument;
 use crate::transform::context::{Context, StaticContext};
+use crate::transform::qnames::{namespace_scope, node_argument};
+use crate::transform::types::error;
+use crate::validators::datatypes::{regex_group_parents, xpath_regex};
 use crate::value::Value;
 use crate::xdmerror::{Error, ErrorKind};
 
-/// XPath local-name function.
+/// XPath local-name function. The argument is a node, or the context item if there is no argument.
 pub fn local_name<
     N: Node,
     F: FnMut(&str) -> Result<(), Error>,
@@ -23,49 +31,14 @@ pub fn local_name<
     stctxt: &mut StaticContext<N, F, G, H>,
     s: &Option<Box<Transform<N>>>,
 ) -> Result<Sequence<N>, Error> {
-    if s.is_none() && ctxt.context_item.is_none() {
-        return Err(Error::new(ErrorKind::DynamicAbsent, "no context item"));
-    }
-    s.as_ref().map_or_else(
-        || {
-            // Get the name of the context item
-            // TODO: handle the case of there not being a context item
-            match ctxt.context_item.as_ref().unwrap() {
-                Item::Node(m) => Ok(vec![Item::Value(Rc::new(Value::from(
-                    m.name()
-                        .map_or(String::from(""), |l| l.local_name().to_string()),
-                )))]),
-                _ => Err(Error::new(
-                    ErrorKind::TypeError,
-                    String::from("type error: not a node"),
-                )),
-            }
-        },
-        |t| {
-            // Get the name of the singleton node
-            let n = ctxt.dispatch(stctxt, t)?;
-            match n.len() {
-                0 => Ok(vec![Item::Value(Rc::new(Value::from("")))]),
-                1 => match n[0] {
-                    Item::Node(ref m) => Ok(vec![Item::Value(Rc::new(Value::from(
-                        m.name()
-                            .map_or(String::from(""), |l| l.local_name().to_string()),
-                    )))]),
-                    _ => Err(Error::new(
-                        ErrorKind::TypeError,
-                        String::from("type error: not a node"),
-                    )),
-                },
-                _ => Err(Error::new(
-                    ErrorKind::TypeError,
-                    String::from("type error: not a singleton node"),
-                )),
-            }
-        },
-    )
+    let l = node_argument(ctxt, stctxt, s)?
+        .and_then(|n| n.name())
+        .map_or(String::from(""), |qn| qn.local_name().to_string());
+    Ok(vec![Item::Value(Rc::new(Value::from(l)))])
 }
 
-/// XPath name function.
+/// XPath name function. The argument is a node, or the context item if there is no argument.
+/// The result may be a prefixed name.
 pub fn name<
     N: Node,
     F: FnMut(&str) -> Result<(), Error>,
@@ -76,79 +49,20 @@ pub fn name<
     stctxt: &mut StaticContext<N, F, G, H>,
     s: &Option<Box<Transform<N>>>,
 ) -> Result<Sequence<N>, Error> {
-    if s.is_none() && ctxt.context_item.is_none() {
-        return Err(Error::new(ErrorKind::DynamicAbsent, "no context item"));
-    }
-    s.as_ref().map_or_else(
-        || {
-            // Get the name of the context item.
-            // This may be a prefixed name.
-            // TODO: handle the case of there being no context item
-            match ctxt.context_item.as_ref().unwrap() {
-                Item::Node(m) => {
-                    if let Some(qn) = m.name() {
-                        if let Some(nsuri) = qn.namespace_uri() {
-                            Ok(vec![Item::Value(Rc::new(Value::from(
-                                get_prefix(ctxt, m, &nsuri)?.map_or_else(
-                                    || qn.local_name().to_string(),
-                                    |p| {
-                                        format!("{}:{}", p.to_string(), qn.local_name().to_string())
-                                    },
-                                ),
-                            )))])
-                        } else {
-                            Ok(vec![Item::Value(Rc::new(Value::from(qn.local_name())))])
-                        }
-                    } else {
-                        Ok(vec![Item::Value(Rc::new(Value::from("")))])
-                    }
-                }
-                _ => Err(Error::new(
-                    ErrorKind::TypeError,
-                    String::from("type error: not a node"),
-                )),
-            }
-        },
-        |t| {
-            // Get the name of the singleton node
-            let n = ctxt.dispatch(stctxt, t)?;
-            match n.len() {
-                0 => Ok(vec![Item::Value(Rc::new(Value::from("")))]),
-                1 => match n[0] {
-                    Item::Node(ref m) => {
-                        if let Some(qn) = m.name() {
-                            if let Some(nsuri) = qn.namespace_uri() {
-                                Ok(vec![Item::Value(Rc::new(Value::from(
-                                    get_prefix(ctxt, m, &nsuri)?.map_or_else(
-                                        || qn.local_name().to_string(),
-                                        |p| {
-                                            format!(
-                                                "{}:{}",
-                                                p.to_string(),
-                                                qn.local_name().to_string()
-                                            )
-                                        },
-                                    ),
-                                )))])
-                            } else {
-                                Ok(vec![Item::Value(Rc::new(Value::from(qn.local_name())))])
-                            }
-                        } else {
-                            Ok(vec![Item::Value(Rc::new(Value::from("")))])
-                        }
-                    }
-                    _ => Err(Error::new(
-                        ErrorKind::TypeError,
-                        String::from("type error: not a node"),
-                    )),
-                },
-                _ => Err(Error::new(
-                    ErrorKind::TypeError,
-                    String::from("type error: not a singleton node"),
-                )),
-            }
+    let n = match node_argument(ctxt, stctxt, s)? {
+        Some(m) => match m.name() {
+            Some(qn) => match qn.namespace_uri() {
+                Some(nsuri) => get_prefix(ctxt, &namespace_scope(&m), &nsuri)?.map_or_else(
+                    || qn.local_name().to_string(),
+                    |p| format!("{}:{}", p.to_string(), qn.local_name().to_string()),
+                ),
+                None => qn.local_name().to_string(),
+            },
+            None => String::from(""),
         },
-    )
+        None => String::from(""),
+    };
+    Ok(vec![Item::Value(Rc::new(Value::from(n)))])
 }
 
 // Find the prefix for the given XML Namespace URI.
@@ -215,7 +129,9 @@ pub fn starts_with<
     stctxt: &mut StaticContext<N, F, G, H>,
     s: &Transform<N>,
     t: &Transform<N>,
+    c: &Option<Box<Transform<N>>>,
 ) -> Result<Sequence<N>, Error> {
+    substring_collation(ctxt, stctxt, c)?;
     // s is the string to search, t is what to search for
     Ok(vec![Item::Value(Rc::new(Value::from(
         ctxt.dispatch(stctxt, s)?
@@ -235,7 +151,9 @@ pub fn ends_with<
     stctxt: &mut StaticContext<N, F, G, H>,
     s: &Transform<N>,
     t: &Transform<N>,
+    c: &Option<Box<Transform<N>>>,
 ) -> Result<Sequence<N>, Error> {
+    substring_collation(ctxt, stctxt, c)?;
     // s is the string to search, t is what to search for
     Ok(vec![Item::Value(Rc::new(Value::from(
         ctxt.dispatch(stctxt, s)?
@@ -255,7 +173,9 @@ pub fn contains<
     stctxt: &mut StaticContext<N, F, G, H>,
     s: &Transform<N>,
     t: &Transform<N>,
+    c: &Option<Box<Transform<N>>>,
 ) -> Result<Sequence<N>, Error> {
+    substring_collation(ctxt, stctxt, c)?;
     // s is the string to search, t is what to search for
     Ok(vec![Item::Value(Rc::new(Value::from(
         ctxt.dispatch(stctxt, s)?
@@ -311,7 +231,9 @@ pub fn substring_before<
     stctxt: &mut StaticContext<N, F, G, H>,
     s: &Transform<N>,
     t: &Transform<N>,
+    c: &Option<Box<Transform<N>>>,
 ) -> Result<Sequence<N>, Error> {
+    substring_collation(ctxt, stctxt, c)?;
     // s is the string to search,
     // t is the string to find.
     let u = ctxt.dispatch(stctxt, s)?.to_string();
@@ -343,7 +265,9 @@ pub fn substring_after<
     stctxt: &mut StaticContext<N, F, G, H>,
     s: &Transform<N>,
     t: &Transform<N>,
+    c: &Option<Box<Transform<N>>>,
 ) -> Result<Sequence<N>, Error> {
+    substring_collation(ctxt, stctxt, c)?;
     // s is the string to search,
     // t is the string to find.
     let u = ctxt.dispatch(stctxt, s)?.to_string();
@@ -471,3 +395,632 @@ pub(crate) fn tr_concat<
         Err(err) => Err(err),
     }
 }
+
+// Only the Unicode codepoint collation supports substring matching.
+fn substring_collation<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    c: &Option<Box<Transform<N>>>,
+) -> Result<(), Error> {
+    if collation_argument(ctxt, stctxt, c)?.is_codepoint() {
+        Ok(())
+    } else {
+        Err(error(
+            ErrorKind::Unknown,
+            "FOCH0004",
+            String::from("the collation does not support substring matching"),
+        ))
+    }
+}
+
+// Evaluate an optional string argument. The empty sequence gives None.
+fn optional_string<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+) -> Result<Option<String>, Error> {
+    let v = ctxt.dispatch(stctxt, s)?;
+    if v.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(v.to_string()))
+    }
+}
+
+fn string_value<N: Node>(s: String) -> Sequence<N> {
+    vec![Item::Value(Rc::new(Value::from(s)))]
+}
+
+/// XPath upper-case function.
+pub(crate) fn upper_case<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    Ok(string_value(
+        ctxt.dispatch(stctxt, s)?.to_string().to_uppercase(),
+    ))
+}
+
+/// XPath lower-case function.
+pub(crate) fn lower_case<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    Ok(string_value(
+        ctxt.dispatch(stctxt, s)?.to_string().to_lowercase(),
+    ))
+}
+
+/// XPath string-join function. The separator defaults to the zero-length string.
+pub(crate) fn string_join<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    sep: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let items = ctxt.dispatch(stctxt, s)?;
+    let sep = match sep {
+        Some(t) => ctxt.dispatch(stctxt, t)?.to_string(),
+        None => String::new(),
+    };
+    Ok(string_value(
+        items
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<String>>()
+            .join(&sep),
+    ))
+}
+
+/// XPath string-length function. The length is the number of characters (codepoints).
+pub(crate) fn string_length<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let u = match s {
+        Some(t) => ctxt.dispatch(stctxt, t)?.to_string(),
+        None => ctxt
+            .context_item
+            .as_ref()
+            .ok_or_else(|| Error::new(ErrorKind::DynamicAbsent, "no context item"))?
+            .to_string(),
+    };
+    Ok(vec![Item::Value(Rc::new(Value::from(
+        u.chars().count() as i64
+    )))])
+}
+
+/// XPath string-to-codepoints function.
+pub(crate) fn string_to_codepoints<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    Ok(ctxt
+        .dispatch(stctxt, s)?
+        .to_string()
+        .chars()
+        .map(|c| Item::Value(Rc::new(Value::from(c as i64))))
+        .collect())
+}
+
+/// XPath codepoints-to-string function.
+/// Each codepoint must be a legal XML character.
+pub(crate) fn codepoints_to_string<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let mut result = String::new();
+    for i in ctxt.dispatch(stctxt, s)? {
+        let cp = i.to_int()?;
+        match u32::try_from(cp).ok().and_then(char::from_u32) {
+            Some(c) if is_char10(&c) => result.push(c),
+            _ => {
+                return Err(error(
+                    ErrorKind::Unknown,
+                    "FOCH0001",
+                    format!("codepoint {} is not a valid XML character", cp),
+                ));
+            }
+        }
+    }
+    Ok(string_value(result))
+}
+
+/// XPath compare function.
+/// Returns -1, 0 or 1, or the empty sequence if either argument is the empty sequence.
+pub(crate) fn compare<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    t: &Transform<N>,
+    c: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let a = optional_string(ctxt, stctxt, s)?;
+    let b = optional_string(ctxt, stctxt, t)?;
+    let collation = collation_argument(ctxt, stctxt, c)?;
+    match (a, b) {
+        (Some(x), Some(y)) => {
+            let o: i64 = match collation.compare(&x, &y) {
+                Ordering::Less => -1,
+                Ordering::Equal => 0,
+                Ordering::Greater => 1,
+            };
+            Ok(vec![Item::Value(Rc::new(Value::from(o)))])
+        }
+        _ => Ok(vec![]),
+    }
+}
+
+/// XPath codepoint-equal function.
+/// Returns the empty sequence if either argument is the empty sequence.
+pub(crate) fn codepoint_equal<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    t: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    let a = optional_string(ctxt, stctxt, s)?;
+    let b = optional_string(ctxt, stctxt, t)?;
+    match (a, b) {
+        (Some(x), Some(y)) => Ok(vec![Item::Value(Rc::new(Value::from(x == y)))]),
+        _ => Ok(vec![]),
+    }
+}
+
+// Percent-encode the UTF-8 octets of each character for which escape returns true.
+fn percent_encode(s: &str, escape: impl Fn(char) -> bool) -> String {
+    let mut result = String::new();
+    for c in s.chars() {
+        if escape(c) {
+            let mut buf = [0; 4];
+            for b in c.encode_utf8(&mut buf).bytes() {
+                result.push_str(&format!("%{:02X}", b))
+            }
+        } else {
+            result.push(c)
+        }
+    }
+    result
+}
+
+/// XPath encode-for-uri function. All characters except the unreserved characters of RFC 3986 are escaped.
+pub(crate) fn encode_for_uri<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    Ok(string_value(percent_encode(
+        &ctxt.dispatch(stctxt, s)?.to_string(),
+        |c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '~')),
+    )))
+}
+
+/// XPath iri-to-uri function.
+/// Characters that are not allowed in a URI are escaped, including all non-ASCII characters.
+pub(crate) fn iri_to_uri<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    Ok(string_value(percent_encode(
+        &ctxt.dispatch(stctxt, s)?.to_string(),
+        |c| {
+            !(' '..='~').contains(&c)
+                || matches!(
+                    c,
+                    ' ' | '<' | '>' | '"' | '{' | '}' | '|' | '\\' | '^' | '`'
+                )
+        },
+    )))
+}
+
+/// XPath escape-html-uri function. Only characters outside the printable ASCII range are escaped.
+pub(crate) fn escape_html_uri<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+) -> Result<Sequence<N>, Error> {
+    Ok(string_value(percent_encode(
+        &ctxt.dispatch(stctxt, s)?.to_string(),
+        |c| !(' '..='~').contains(&c),
+    )))
+}
+
+/// XPath normalize-unicode function.
+/// The normalization form is one of NFC (the default), NFD, NFKC or NFKD.
+/// A zero-length normalization form means no normalization is performed.
+pub(crate) fn normalize_unicode<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    form: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let u = ctxt.dispatch(stctxt, s)?.to_string();
+    let f = match form {
+        Some(t) => ctxt.dispatch(stctxt, t)?.to_string().trim().to_uppercase(),
+        None => String::from("NFC"),
+    };
+    let result = match f.as_str() {
+        "NFC" => u.nfc().collect(),
+        "NFD" => u.nfd().collect(),
+        "NFKC" => u.nfkc().collect(),
+        "NFKD" => u.nfkd().collect(),
+        "" => u,
+        _ => {
+            return Err(error(
+                ErrorKind::Unknown,
+                "FOCH0003",
+                format!("normalization form \"{}\" is not supported", f),
+            ));
+        }
+    };
+    Ok(string_value(result))
+}
+
+/// XPath contains-token function.
+/// Each input string is split into tokens at whitespace, and the result is true if any token equals the given token.
+pub(crate) fn contains_token<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    t: &Transform<N>,
+    c: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let input = ctxt.dispatch(stctxt, s)?;
+    let token = ctxt.dispatch(stctxt, t)?.to_string();
+    let collation = collation_argument(ctxt, stctxt, c)?;
+    let token = token.trim_matches([' ', '\t', '\n', '\r']);
+    let found = !token.is_empty()
+        && input.iter().any(|i| {
+            i.to_string()
+                .split([' ', '\t', '\n', '\r'])
+                .any(|u| collation.equal(u, token))
+        });
+    Ok(vec![Item::Value(Rc::new(Value::from(found)))])
+}
+
+// The regular expression for a pattern argument and optional flags argument.
+// Also returns the pattern and flags.
+fn regex_argument<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    pattern: &Transform<N>,
+    flags: &Option<Box<Transform<N>>>,
+) -> Result<(Regex, String, String), Error> {
+    let p = ctxt.dispatch(stctxt, pattern)?.to_string();
+    let f = match flags {
+        Some(g) => ctxt.dispatch(stctxt, g)?.to_string(),
+        None => String::new(),
+    };
+    Ok((xpath_regex(&p, &f)?, p, f))
+}
+
+// Patterns used to replace or tokenize must not match a zero-length string.
+fn non_empty_regex(re: &Regex) -> Result<(), Error> {
+    if re.is_match("") {
+        Err(error(
+            ErrorKind::Unknown,
+            "FORX0003",
+            String::from("regular expression matches a zero-length string"),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// XPath matches function.
+pub(crate) fn matches<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    pattern: &Transform<N>,
+    flags: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let input = ctxt.dispatch(stctxt, s)?.to_string();
+    let (re, _, _) = regex_argument(ctxt, stctxt, pattern, flags)?;
+    Ok(vec![Item::Value(Rc::new(Value::from(re.is_match(&input))))])
+}
+
+/// XPath replace function.
+/// In the replacement string, "$N" refers to the N'th captured group, and "\$" and "\\" are a literal "$" and "\".
+pub(crate) fn replace<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    pattern: &Transform<N>,
+    replacement: &Transform<N>,
+    flags: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let input = ctxt.dispatch(stctxt, s)?.to_string();
+    let (re, _, f) = regex_argument(ctxt, stctxt, pattern, flags)?;
+    let r = ctxt.dispatch(stctxt, replacement)?.to_string();
+    non_empty_regex(&re)?;
+    let invalid = || {
+        error(
+            ErrorKind::Unknown,
+            "FORX0004",
+            format!("invalid replacement string \"{}\"", r),
+        )
+    };
+    let mut result = String::new();
+    let mut last = 0;
+    for caps in re.captures_iter(&input) {
+        let m = caps.get(0).unwrap();
+        result.push_str(&input[last..m.start()]);
+        last = m.end();
+        if f.contains('q') {
+            result.push_str(&r);
+            continue;
+        }
+        let mut chars = r.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some(d @ ('\\' | '$')) => result.push(d),
+                    _ => return Err(invalid()),
+                },
+                '$' => {
+                    // The group number is the longest sequence of digits that is a valid group number
+                    let mut n = chars
+                        .next()
+                        .and_then(|d| d.to_digit(10))
+                        .ok_or_else(invalid)? as usize;
+                    while let Some(d) = chars.peek().and_then(|d| d.to_digit(10)) {
+                        if n * 10 + (d as usize) < caps.len() {
+                            n = n * 10 + d as usize;
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                    if let Some(g) = caps.get(n) {
+                        result.push_str(g.as_str())
+                    }
+                }
+                _ => result.push(c),
+            }
+        }
+    }
+    result.push_str(&input[last..]);
+    Ok(vec![Item::Value(Rc::new(Value::from(result)))])
+}
+
+/// XPath tokenize function.
+/// Without a pattern, the input is split at whitespace, ignoring leading and trailing whitespace.
+pub(crate) fn tokenize<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    pattern: &Option<Box<Transform<N>>>,
+    flags: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let input = ctxt.dispatch(stctxt, s)?.to_string();
+    let tokens: Vec<String> = match pattern {
+        None => input
+            .split([' ', '\t', '\n', '\r'])
+            .filter(|t| !t.is_empty())
+            .map(String::from)
+            .collect(),
+        Some(p) => {
+            let (re, _, _) = regex_argument(ctxt, stctxt, p, flags)?;
+            non_empty_regex(&re)?;
+            if input.is_empty() {
+                vec![]
+            } else {
+                re.split(&input).map(String::from).collect()
+            }
+        }
+    };
+    Ok(tokens
+        .into_iter()
+        .map(|t| Item::Value(Rc::new(Value::from(t))))
+        .collect())
+}
+
+/// The namespace for the standard functions.
+pub(crate) const FNNS: &str = "http://www.w3.org/2005/xpath-functions";
+
+/// XPath analyze-string function.
+/// The result is a fn:analyze-string-result element, containing fn:match and fn:non-match elements.
+/// Captured groups within a match are fn:group elements.
+pub(crate) fn analyze_string<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    s: &Transform<N>,
+    pattern: &Transform<N>,
+    flags: &Option<Box<Transform<N>>>,
+) -> Result<Sequence<N>, Error> {
+    let input = ctxt.dispatch(stctxt, s)?.to_string();
+    let (re, p, f) = regex_argument(ctxt, stctxt, pattern, flags)?;
+    non_empty_regex(&re)?;
+    let Some(r) = ctxt.rd.clone() else {
+        return Err(Error::new(
+            ErrorKind::Unknown,
+            String::from("context has no result document"),
+        ));
+    };
+    let parents = regex_group_parents(&p, &f);
+    let ns = NamespaceUri::try_from(FNNS)
+        .map_err(|_| Error::new(ErrorKind::Unknown, String::from("invalid namespace")))?;
+    let fn_element = |name: &str| {
+        r.new_element(QName::new_from_parts(
+            NcName::try_from(name).unwrap(),
+            Some(ns.clone()),
+        ))
+    };
+    let text = |e: &mut N, t: &str| -> Result<(), Error> {
+        if !t.is_empty() {
+            e.push(r.new_text(Rc::new(Value::from(t)))?)?
+        }
+        Ok(())
+    };
+
+    let mut result = fn_element("analyze-string-result")?;
+    result.add_namespace(r.new_namespace(ns.clone(), None, true)?)?;
+    let mut last = 0;
+    for caps in re.captures_iter(&input) {
+        let m = caps.get(0).unwrap();
+        if m.start() > last {
+            let mut nm = fn_element("non-match")?;
+            text(&mut nm, &input[last..m.start()])?;
+            result.push(nm)?;
+        }
+        last = m.end();
+        // Build the content of the match, and of each group, from the innermost groups outwards
+        let mut groups: Vec<N> = vec![fn_element("match")?];
+        let mut positions: Vec<usize> = vec![m.start()];
+        let mut ends: Vec<usize> = vec![m.end()];
+        let mut numbers: Vec<usize> = vec![0];
+        for g in 1..caps.len() {
+            let Some(c) = caps.get(g) else { continue };
+            // Close any groups that are not the parent of this group
+            while *numbers.last().unwrap() != parents.get(g).copied().unwrap_or(0)
+                && numbers.len() > 1
+            {
+                let mut e = groups.pop().unwrap();
+                text(
+                    &mut e,
+                    &input[positions.pop().unwrap()..ends.pop().unwrap()],
+                )?;
+                numbers.pop();
+                groups.last_mut().unwrap().push(e)?;
+            }
+            let pos = positions.last().copied().unwrap();
+            text(groups.last_mut().unwrap(), &input[pos..c.start()])?;
+            *positions.last_mut().unwrap() = c.end();
+            let e = fn_element("group")?;
+            e.add_attribute(r.new_attribute(
+                QName::from_local_name(NcName::try_from("nr").unwrap()),
+                Rc::new(Value::from(g as i64)),
+            )?)?;
+            groups.push(e);
+            positions.push(c.start());
+            ends.push(c.end());
+            numbers.push(g);
+        }
+        while let Some(mut e) = groups.pop() {
+            text(
+                &mut e,
+                &input[positions.pop().unwrap()..ends.pop().unwrap()],
+            )?;
+            numbers.pop();
+            match groups.last_mut() {
+                Some(parent) => {
+                    parent.push(e)?;
+                }
+                None => result.push(e)?,
+            }
+        }
+    }
+    if last < input.len() {
+        let mut nm = fn_element("non-match")?;
+        text(&mut nm, &input[last..])?;
+        result.push(nm)?;
+    }
+    Ok(vec![Item::Node(result)])
+}
@@ -102,7 +102,7 @@ pub(crate) fn apply_templates<
     stctxt: &mut StaticContext<N, F, G, H>,
     s: &Transform<N>,
     m: &Option<QName>,
-    o: &Vec<(Order, Transform<N>)>, // sort keys
+    o: &Vec<(Order, Transform<N>, Option<String>)>, // sort keys
 ) -> Result<Sequence<N>, Error> {
     // s is the select expression. Evaluate it, and then iterate over its items.
     // Each iteration becomes an item in the result sequence.
@@ -0,0 +1,569 @@
+//! Sequence types, and the operators that use them: instance of, treat as, cast as, and castable as.
+
+use std::fmt;
+use std::fmt::Formatter;
+use std::rc::Rc;
+use url::Url;
+
+use chrono::{Datelike, NaiveTime};
+use qualname::{NcName, QName};
+
+use crate::item::{Item, Node, Sequence};
+use crate::transform::context::{Context, StaticContext};
+use crate::transform::{KindTest, Transform};
+use crate::validators::datatypes;
+use crate::value::{Duration, Value, ValueBuilder, ValueData};
+use crate::xdmerror::{Error, ErrorKind};
+
+pub(crate) const XSDNS: &str = "http://www.w3.org/2001/XMLSchema";
+
+/// A SequenceType, as used by instance of and treat as.
+#[derive(Clone, Debug)]
+pub enum SequenceType {
+    /// empty-sequence()
+    Empty,
+    /// An item type with an occurrence indicator.
+    Item(ItemType, Occurrence),
+}
+
+/// The type of the items in a sequence.
+#[derive(Clone, Debug)]
+pub enum ItemType {
+    /// item(), i.e. any item.
+    Item,
+    /// A node that matches a kind test.
+    Kind(KindTest),
+    /// An atomic value whose type is the named type, or derived from it.
+    Atomic(QName),
+    /// A function item. Maps and arrays are also functions.
+    Function,
+    /// map(*), i.e. any map.
+    Map,
+    /// array(*), i.e. any array.
+    Array,
+}
+
+/// How many items may occur in a sequence.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Occurrence {
+    /// Exactly one item.
+    One,
+    /// Zero or one item ('?').
+    Optional,
+    /// Zero or more items ('*').
+    ZeroOrMore,
+    /// One or more items ('+').
+    OneOrMore,
+}
+
+impl SequenceType {
+    /// Does the sequence match this type?
+    /// Returns an error if the type names an unknown atomic type.
+    pub fn matches<N: Node>(&self, s: &Sequence<N>) -> Result<bool, Error> {
+        match self {
+            SequenceType::Empty => Ok(s.is_empty()),
+            SequenceType::Item(t, o) => {
+                let count = match o {
+                    Occurrence::One => s.len() == 1,
+                    Occurrence::Optional => s.len() <= 1,
+                    Occurrence::ZeroOrMore => true,
+                    Occurrence::OneOrMore => !s.is_empty(),
+                };
+                if !count {
+                    return Ok(false);
+                }
+                for i in s {
+                    if !t.matches(i)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+        }
+    }
+}
+
+impl ItemType {
+    /// Does the item match this type?
+    pub fn matches<N: Node>(&self, i: &Item<N>) -> Result<bool, Error> {
+        match (self, i) {
+            (ItemType::Item, _) => Ok(true),
+            (ItemType::Kind(k), _) => Ok(k.matches_item(i)),
+            (ItemType::Function, Item::Function(_) | Item::Map(_) | Item::Array(_)) => Ok(true),
+            (ItemType::Function, _) => Ok(false),
+            (ItemType::Map, Item::Map(_)) => Ok(true),
+            (ItemType::Map, _) => Ok(false),
+            (ItemType::Array, Item::Array(_)) => Ok(true),
+            (ItemType::Array, _) => Ok(false),
+            (ItemType::Atomic(qn), Item::Value(v)) => {
+                let t = atomic_type_name(qn)?;
+                Ok(derives_from(type_name(&v.value), &t))
+            }
+            (ItemType::Atomic(qn), _) => atomic_type_name(qn).map(|_| false),
+        }
+    }
+}
+
+impl fmt::Display for SequenceType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SequenceType::Empty => write!(f, "empty-sequence()"),
+            SequenceType::Item(t, o) => write!(f, "{}{}", t, o),
+        }
+    }
+}
+
+impl fmt::Display for ItemType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ItemType::Item => write!(f, "item()"),
+            ItemType::Kind(k) => write!(f, "{}", k),
+            ItemType::Atomic(qn) => write!(f, "{}", qn),
+            ItemType::Function => write!(f, "function(*)"),
+            ItemType::Map => write!(f, "map(*)"),
+            ItemType::Array => write!(f, "array(*)"),
+        }
+    }
+}
+
+impl fmt::Display for Occurrence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Occurrence::One => Ok(()),
+            Occurrence::Optional => write!(f, "?"),
+            Occurrence::ZeroOrMore => write!(f, "*"),
+            Occurrence::OneOrMore => write!(f, "+"),
+        }
+    }
+}
+
+pub(crate) fn error(kind: ErrorKind, code: &str, message: String) -> Error {
+    Error::new_with_code(
+        kind,
+        message,
+        Some(QName::from_local_name(NcName::try_from(code).unwrap())),
+    )
+}
+
+// The local name of a known atomic type in the XML Schema namespace.
+fn atomic_type_name(qn: &QName) -> Result<String, Error> {
+    let t = qn.local_name().to_string();
+    if qn.namespace_uri().map(|u| u.to_string()).as_deref() == Some(XSDNS)
+        && (t == "anyAtomicType" || t == "numeric" || base_type(&t).is_some())
+    {
+        Ok(t)
+    } else {
+        Err(error(
+            ErrorKind::StaticUndefined,
+            "XPST0051",
+            format!("\"{}\" is not a known atomic type", qn),
+        ))
+    }
+}
+
+/// The name of the XML Schema datatype of a value.
+fn type_name(v: &ValueData) -> &'static str {
+    match v {
+        ValueData::String(_) => "string",
+        ValueData::NormalizedString(_) => "normalizedString",
+        ValueData::Token => "token",
+        ValueData::Language => "language",
+        ValueData::NMTOKEN(_) => "NMTOKEN",
+        ValueData::Name(_) => "Name",
+        ValueData::NCName(_) => "NCName",
+        ValueData::ID(_) => "ID",
+        ValueData::IDREF(_) => "IDREF",
+        ValueData::ENTITY(_) => "ENTITY",
+        ValueData::IDREFS(_) => "IDREFS",
+        ValueData::NMTOKENS(_) => "NMTOKENS",
+        ValueData::ENTITIES(_) => "ENTITIES",
+        ValueData::Boolean(_) => "boolean",
+        ValueData::Decimal(_) => "decimal",
+        ValueData::Float(_) => "float",
+        ValueData::Double(_) => "double",
+        ValueData::Integer(_) => "integer",
+        ValueData::NonPositiveInteger(_) => "nonPositiveInteger",
+        ValueData::NegativeInteger(_) => "negativeInteger",
+        ValueData::Long(_) => "long",
+        ValueData::Int(_) => "int",
+        ValueData::Short(_) => "short",
+        ValueData::Byte(_) => "byte",
+        ValueData::NonNegativeInteger(_) => "nonNegativeInteger",
+        ValueData::UnsignedLong(_) => "unsignedLong",
+        ValueData::UnsignedInt(_) => "unsignedInt",
+        ValueData::UnsignedShort(_) => "unsignedShort",
+        ValueData::UnsignedByte(_) => "unsignedByte",
+        ValueData::PositiveInteger(_) => "positiveInteger",
+        ValueData::Duration(_) => "duration",
+        ValueData::YearMonthDuration(_) => "yearMonthDuration",
+        ValueData::DayTimeDuration(_) => "dayTimeDuration",
+        ValueData::DateTime(..) => "dateTime",
+        ValueData::DateTimeStamp(..) => "dateTimeStamp",
+        ValueData::Date(..) => "date",
+        ValueData::Time(..) => "time",
+        ValueData::GYearMonth(..) => "gYearMonth",
+        ValueData::GYear(..) => "gYear",
+        ValueData::GMonthDay(..) => "gMonthDay",
+        ValueData::GMonth(..) => "gMonth",
+        ValueData::GDay(..) => "gDay",
+        ValueData::Base64Binary(_) => "base64Binary",
+        ValueData::HexBinary(_) => "hexBinary",
+        ValueData::QName(..) => "QName",
+        ValueData::NamespaceUri(_) => "anyURI",
+        ValueData::UntypedAtomic => "untypedAtomic",
+        ValueData::AnyType
+        | ValueData::Untyped
+        | ValueData::AnySimpleType
+        | ValueData::Numeric
+        | ValueData::AnyAtomicType => "anyAtomicType",
+    }
+}
+
+/// The base type of a built-in atomic type, or None if the type is not known.
+fn base_type(t: &str) -> Option<&'static str> {
+    match t {
+        "untypedAtomic" | "string" | "boolean" | "decimal" | "float" | "double" | "duration"
+        | "dateTime" | "time" | "date" | "gYearMonth" | "gYear" | "gMonthDay" | "gDay"
+        | "gMonth" | "hexBinary" | "base64Binary" | "anyURI" | "QName" | "NOTATION" => {
+            Some("anyAtomicType")
+        }
+        "normalizedString" => Some("string"),
+        "token" => Some("normalizedString"),
+        "language" | "NMTOKEN" | "Name" => Some("token"),
+        "NCName" => Some("Name"),
+        "ID" | "IDREF" | "ENTITY" => Some("NCName"),
+        "integer" => Some("decimal"),
+        "nonPositiveInteger" | "long" | "nonNegativeInteger" => Some("integer"),
+        "negativeInteger" => Some("nonPositiveInteger"),
+        "int" => Some("long"),
+        "short" => Some("int"),
+        "byte" => Some("short"),
+        "unsignedLong" | "positiveInteger" => Some("nonNegativeInteger"),
+        "unsignedInt" => Some("unsignedLong"),
+        "unsignedShort" => Some("unsignedInt"),
+        "unsignedByte" => Some("unsignedShort"),
+        "dateTimeStamp" => Some("dateTime"),
+        "yearMonthDuration" | "dayTimeDuration" => Some("duration"),
+        _ => None,
+    }
+}
+
+/// Is the type the same as, or derived from, the base type?
+/// xs:numeric is the union of xs:decimal, xs:float and xs:double.
+fn derives_from(t: &str, base: &str) -> bool {
+    if base == "numeric" {
+        return ["decimal", "float", "double"]
+            .iter()
+            .any(|b| derives_from(t, b));
+    }
+    let mut cur = Some(t);
+    while let Some(c) = cur {
+        if c == base {
+            return true;
+        }
+        cur = base_type(c);
+    }
+    base == "anyAtomicType"
+}
+
+fn is_numeric(t: &str) -> bool {
+    derives_from(t, "numeric")
+}
+
+// The lexical form of a value, used when casting via a string.
+fn lexical(v: &ValueData) -> String {
+    match v {
+        ValueData::Double(d) => double_lexical(*d),
+        ValueData::Float(d) => double_lexical(f64::from(*d)),
+        _ => v.to_string(),
+    }
+}
+
+fn double_lexical(d: f64) -> String {
+    if d.is_nan() {
+        String::from("NaN")
+    } else if d.is_infinite() {
+        String::from(if d > 0.0 { "INF" } else { "-INF" })
+    } else {
+        d.to_string()
+    }
+}
+
+/// Cast a value to the named built-in atomic type, following the casting rules of XPath F&O 19.
+pub(crate) fn cast_value(v: &ValueData, target: &str) -> Result<ValueData, Error> {
+    let source = type_name(v);
+    let invalid = || {
+        error(
+            ErrorKind::TypeError,
+            "FORG0001",
+            format!("cannot cast \"{}\" to xs:{}", lexical(v), target),
+        )
+    };
+    let result = match (v, target) {
+        // There is no representation of an untyped atomic value, so it becomes a string
+        (_, "string") | (_, "untypedAtomic") => return Ok(ValueData::String(lexical(v))),
+        (_, "boolean") if is_numeric(source) => {
+            let d: f64 = lexical(v).parse().unwrap_or(f64::NAN);
+            return Ok(ValueData::Boolean(!(d == 0.0 || d.is_nan())));
+        }
+        (ValueData::Boolean(b), t) if is_numeric(t) => {
+            datatypes::parse(t, if *b { "1" } else { "0" })
+        }
+        (ValueData::Double(_) | ValueData::Float(_), t) if derives_from(t, "decimal") => {
+            let d: f64 = lexical(v).parse().unwrap_or(f64::NAN);
+            if !d.is_finite() {
+                return Err(error(
+                    ErrorKind::TypeError,
+                    "FOCA0002",
+                    format!("cannot cast {} to xs:{}", lexical(v), t),
+                ));
+            }
+            if derives_from(t, "integer") {
+                datatypes::parse(t, &d.trunc().to_string())
+            } else {
+                datatypes::parse(t, &d.to_string())
+            }
+        }
+        (ValueData::Decimal(d), t) if derives_from(t, "integer") => {
+            datatypes::parse(t, &d.trunc().to_string())
+        }
+        // The timezone, if any, is kept
+        (ValueData::DateTimeStamp(dt, tz), _) => {
+            return cast_value(&ValueData::DateTime(*dt, Some(*tz)), target);
+        }
+        (ValueData::DateTime(dt, tz), "date") => Ok(ValueData::Date(dt.date(), *tz)),
+        (ValueData::DateTime(dt, tz), "time") => Ok(ValueData::Time(dt.time(), *tz)),
+        (ValueData::Date(d, tz), "dateTime") => {
+            Ok(ValueData::DateTime(d.and_time(NaiveTime::MIN), *tz))
+        }
+        (ValueData::DateTime(dt, tz), "gYearMonth" | "gYear" | "gMonthDay" | "gMonth" | "gDay") => {
+            return cast_value(&ValueData::Date(dt.date(), *tz), target);
+        }
+        (ValueData::Date(d, tz), "gYearMonth") => {
+            Ok(ValueData::GYearMonth(d.year(), d.month(), *tz))
+        }
+        (ValueData::Date(d, tz), "gYear") => Ok(ValueData::GYear(d.year(), *tz)),
+        (ValueData::Date(d, tz), "gMonthDay") => Ok(ValueData::GMonthDay(d.month(), d.day(), *tz)),
+        (ValueData::Date(d, tz), "gMonth") => Ok(ValueData::GMonth(d.month(), *tz)),
+        (ValueData::Date(d, tz), "gDay") => Ok(ValueData::GDay(d.day(), *tz)),
+        (ValueData::Base64Binary(b), "hexBinary") => Ok(ValueData::HexBinary(b.clone())),
+        (ValueData::HexBinary(b), "base64Binary") => Ok(ValueData::Base64Binary(b.clone())),
+        // Casting between duration types keeps only the components of the target type
+        (
+            ValueData::Duration(d)
+            | ValueData::YearMonthDuration(d)
+            | ValueData::DayTimeDuration(d),
+            "duration" | "yearMonthDuration" | "dayTimeDuration",
+        ) => Ok(match target {
+            "yearMonthDuration" => ValueData::YearMonthDuration(Duration::from_months(d.months())),
+            "dayTimeDuration" => ValueData::DayTimeDuration(Duration::from_seconds(d.seconds())),
+            _ => ValueData::Duration(d.clone()),
+        }),
+        // Otherwise the value is cast via its lexical form.
+        // This is only permitted from strings, between numeric types, and between types derived from a common primitive type.
+        (_, t)
+            if matches!(source, "string" | "untypedAtomic")
+                || derives_from(source, t)
+                || derives_from(t, source)
+                || (is_numeric(source) && is_numeric(t))
+                || primitive(source) == primitive(t) =>
+        {
+            datatypes::parse(t, &lexical(v))
+        }
+        (_, t) => {
+            return Err(error(
+                ErrorKind::TypeError,
+                "XPTY0004",
+                format!("casting from xs:{} to xs:{} is not supported", source, t),
+            ));
+        }
+    };
+    result.map_err(|e| {
+        if e.kind == ErrorKind::TypeError {
+            invalid()
+        } else {
+            e
+        }
+    })
+}
+
+// The primitive type from which a built-in type is derived.
+fn primitive(t: &str) -> &str {
+    let mut cur = t;
+    while let Some(b) = base_type(cur) {
+        if b == "anyAtomicType" {
+            break;
+        }
+        cur = b;
+    }
+    cur
+}
+
+// Check that the target of a cast is a known, non-abstract, atomic type.
+fn cast_target(qn: &QName) -> Result<String, Error> {
+    let t = atomic_type_name(qn).map_err(|_| {
+        error(
+            ErrorKind::StaticUndefined,
+            "XQST0052",
+            format!("\"{}\" is not a known atomic type", qn),
+        )
+    })?;
+    if matches!(t.as_str(), "anyAtomicType" | "NOTATION" | "numeric") {
+        Err(error(
+            ErrorKind::StaticUndefined,
+            "XPST0080",
+            format!("cannot cast to the abstract type \"{}\"", qn),
+        ))
+    } else {
+        Ok(t)
+    }
+}
+
+// Atomize a sequence that is the operand of a cast.
+// The result is None for an empty sequence.
+fn atomize_single<N: Node>(s: Sequence<N>) -> Result<Option<ValueData>, Error> {
+    if s.len() > 1 {
+        return Err(error(
+            ErrorKind::TypeError,
+            "XPTY0004",
+            String::from("the operand of a cast must be a single item"),
+        ));
+    }
+    match s.into_iter().next() {
+        None => Ok(None),
+        Some(Item::Value(v)) => Ok(Some(v.value.clone())),
+        Some(Item::Node(n)) => Ok(Some(ValueData::String(n.to_string()))),
+        Some(Item::Array(a)) => atomize_single(a.iter().flatten().cloned().collect()),
+        Some(Item::Function(_) | Item::Map(_)) => Err(error(
+            ErrorKind::TypeError,
+            "FOTY0013",
+            String::from("a function item cannot be atomized"),
+        )),
+    }
+}
+
+/// Test whether the result of a transformation matches a sequence type.
+pub(crate) fn instance_of<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    t: &Transform<N>,
+    st: &SequenceType,
+) -> Result<Sequence<N>, Error> {
+    let b = st.matches(&ctxt.dispatch(stctxt, t)?)?;
+    Ok(vec![Item::Value(Rc::new(Value::from(b)))])
+}
+
+/// Return the result of a transformation, provided it matches a sequence type.
+pub(crate) fn treat_as<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    t: &Transform<N>,
+    st: &SequenceType,
+) -> Result<Sequence<N>, Error> {
+    let s = ctxt.dispatch(stctxt, t)?;
+    if st.matches(&s)? {
+        Ok(s)
+    } else {
+        Err(error(
+            ErrorKind::DynamicAbsent,
+            "XPDY0050",
+            format!("sequence does not match the type {}", st),
+        ))
+    }
+}
+
+/// Cast the result of a transformation to an atomic type.
+/// If the empty sequence is allowed, the result for an empty sequence is an empty sequence.
+pub(crate) fn cast_as<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    t: &Transform<N>,
+    qn: &QName,
+    empty: bool,
+) -> Result<Sequence<N>, Error> {
+    let target = cast_target(qn)?;
+    match atomize_single(ctxt.dispatch(stctxt, t)?)? {
+        None if empty => Ok(vec![]),
+        None => Err(error(
+            ErrorKind::TypeError,
+            "XPTY0004",
+            format!("cannot cast the empty sequence to {}", qn),
+        )),
+        Some(v) => Ok(vec![Item::Value(Rc::new(
+            ValueBuilder::new().value(cast_value(&v, &target)?).build(),
+        ))]),
+    }
+}
+
+/// Test whether the result of a transformation can be cast to an atomic type.
+pub(crate) fn castable_as<
+    N: Node,
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<N, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    ctxt: &Context<N>,
+    stctxt: &mut StaticContext<N, F, G, H>,
+    t: &Transform<N>,
+    qn: &QName,
+    empty: bool,
+) -> Result<Sequence<N>, Error> {
+    let target = cast_target(qn)?;
+    let b = match atomize_single(ctxt.dispatch(stctxt, t)?) {
+        Ok(None) => empty,
+        Ok(Some(v)) => cast_value(&v, &target).is_ok(),
+        Err(_) => false,
+    };
+    Ok(vec![Item::Value(Rc::new(Value::from(b)))])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn type_derivation() {
+        assert!(derives_from("byte", "integer"));
+        assert!(derives_from("integer", "numeric"));
+        assert!(derives_from("ID", "string"));
+        assert!(!derives_from("string", "integer"));
+        assert!(derives_from("date", "anyAtomicType"));
+    }
+
+    #[test]
+    fn cast_values() {
+        assert!(matches!(
+            cast_value(&ValueData::String(String::from("42")), "integer"),
+            Ok(ValueData::Integer(i)) if i == BigInt::from(42)
+        ));
+        assert!(matches!(
+            cast_value(&ValueData::Double(3.7), "integer"),
+            Ok(ValueData::Integer(i)) if i == BigInt::from(3)
+        ));
+        assert!(matches!(
+            cast_value(&ValueData::Integer(BigInt::ZERO), "boolean"),
+            Ok(ValueData::Boolean(false))
+        ));
+        assert!(
+            cast_value(&ValueData::Integer(BigInt::from(300)), "byte")
+                .is_err_and(|e| e.code.is_some_and(|c| c.to_string() == "FORG0001"))
+        );
+        assert!(cast_value(&ValueData::Boolean(true), "date").is_err());
+    }
+}
@@ -55,8 +55,8 @@ use qualname::{NamespacePrefix, NamespaceUri, NcName, QName};
 use regex::Regex;
 use std::cell::RefCell;
 use std::cmp::Ordering;
-use std::collections::BTreeMap;
 use std::collections::btree_map::IntoIter;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::fmt::{Debug, Formatter};
 use std::rc::{Rc, Weak};
@@ -70,6 +70,7 @@ enum NodeInner {
         RefCell<Vec<RNode>>, // Child nodes
         RefCell<Vec<RNode>>, // Unattached nodes
         RefCell<Option<DTD>>,
+        RefCell<HashMap<String, (Weak<Node>, Rc<Value>)>>, // Typed values from schema validation, by node id
     ), // to be well-formed, only one of the child nodes can be an element-type node
     Element(
         RefCell<Weak<Node>>,             // Parent: must be a Document or an Element
@@ -99,6 +100,7 @@ impl Node {
             RefCell::new(vec![]),
             RefCell::new(vec![]),
             None.into(),
+            RefCell::new(HashMap::new()),
         ))
     }
     fn ns_prefix(&self) -> Option<NamespacePrefix> {
@@ -125,7 +127,7 @@ impl Node {
 impl PartialEq for Node {
     fn eq(&self, other: &Self) -> bool {
         match (&self.0, &other.0) {
-            (NodeInner::Document(_, c, _, _), NodeInner::Document(_, d, _, _)) => {
+            (NodeInner::Document(_, c, _, _, _), NodeInner::Document(_, d, _, _, _)) => {
                 c.borrow()
                     .iter()
                     .zip(d.borrow().iter())
@@ -204,7 +206,7 @@ impl ItemNode for RNode {
 
     fn unattached(&self) -> Vec<Self> {
         match &self.0 {
-            NodeInner::Document(_, _, u, _) => u.borrow().clone(),
+            NodeInner::Document(_, _, u, _, _) => u.borrow().clone(),
             _ => vec![],
         }
     }
@@ -217,7 +219,7 @@ impl ItemNode for RNode {
             | NodeInner::ProcessingInstruction(p, _, _) => {
                 if let Some(q) = p.borrow().upgrade() {
                     match &q.0 {
-                        NodeInner::Document(_, _, u, _) => {
+                        NodeInner::Document(_, _, u, _, _) => {
                             u.borrow().iter().any(|a| a.is_same(self))
                         }
                         _ => false,
@@ -232,7 +234,7 @@ impl ItemNode for RNode {
 
     fn node_type(&self) -> NodeType {
         match &self.0 {
-            NodeInner::Document(_, _, _, _) => NodeType::Document,
+            NodeInner::Document(_, _, _, _, _) => NodeType::Document,
             NodeInner::Element(_, _, _, _, _) => NodeType::Element,
             NodeInner::Attribute(_, _, _) => NodeType::Attribute,
             NodeInner::Text(_, _) => NodeType::Text,
@@ -341,13 +343,17 @@ impl ItemNode for RNode {
         match &self.0 {
             NodeInner::Text(_, v)
             | NodeInner::Comment(_, v)
-            | NodeInner::ProcessingInstruction(_, _, v)
-            | NodeInner::Attribute(_, _, v) => v.clone(),
+            | NodeInner::ProcessingInstruction(_, _, v) => v.clone(),
+            NodeInner::Attribute(_, _, v) => self.typed_value().unwrap_or_else(|| v.clone()),
             NodeInner::Namespace(_, _, ns, inscope) => Rc::new(if *inscope {
                 Value::from(ns.clone())
             } else {
                 Value::from("")
             }),
+            // An element that has been given a typed value by schema validation
+            NodeInner::Element(_, _, _, _, _) => self
+                .typed_value()
+                .unwrap_or_else(|| Rc::new(Value::from(""))),
             _ => Rc::new(Value::from("")),
         }
     }
@@ -358,7 +364,7 @@ impl ItemNode for RNode {
 
     fn to_string(&self) -> String {
         match &self.0 {
-            NodeInner::Document(_, c, _, _) | NodeInner::Element(_, _, _, c, _) => {
+            NodeInner::Document(_, c, _, _, _) | NodeInner::Element(_, _, _, c, _) => {
                 c.borrow().iter().fold(String::new(), |mut acc, n| {
                     acc.push_str(n.to_string().as_str());
                     acc
@@ -388,10 +394,10 @@ impl ItemNode for RNode {
     }
     fn is_attached(&self) -> bool {
         match &self.0 {
-            NodeInner::Document(_, _, _, _) => false,
+            NodeInner::Document(_, _, _, _, _) => false,
             NodeInner::Namespace(_, _, _, _) => false,
             _ => {
-                if let NodeInner::Document(_, _, u, _) = &self.owner_document().0 {
+                if let NodeInner::Document(_, _, u, _, _) = &self.owner_document().0 {
                     u.borrow()
                         .iter()
                         .find(|p| self.is_same(p))
@@ -410,7 +416,7 @@ impl ItemNode for RNode {
     // There is always a document node, so this will not panic.
     fn owner_document(&self) -> Self {
         match &self.0 {
-            NodeInner::Document(_, _, _, _) => self.clone(),
+            NodeInner::Document(_, _, _, _, _) => self.clone(),
             _ => self.ancestor_iter().last().unwrap(),
         }
     }
@@ -563,7 +569,7 @@ impl ItemNode for RNode {
     // The node is added to the unattached list of the owner document.
     fn pop(&mut self) -> Result<(), Error> {
         match &self.0 {
-            NodeInner::Document(_, _, _, _) => {
+            NodeInner::Document(_, _, _, _, _) => {
                 return Err(Error::new(
                     ErrorKind::TypeError,
                     String::from("cannot remove document node"),
@@ -583,7 +589,7 @@ impl ItemNode for RNode {
                                 let doc = self.owner_document();
                                 unattached(&doc, self.clone());
                             }
-                            NodeInner::Document(_, _, _, _) => {} // attr was in the unattached list
+                            NodeInner::Document(_, _, _, _, _) => {} // attr was in the unattached list
                             _ => {
                                 return Err(Error::new(
                                     ErrorKind::TypeError,
@@ -616,7 +622,7 @@ impl ItemNode for RNode {
                                 let doc = self.owner_document();
                                 unattached(&doc, self.clone());
                             }
-                            NodeInner::Document(_, _, _, _) => {} // attr was in the unattached list
+                            NodeInner::Document(_, _, _, _, _) => {} // attr was in the unattached list
                             _ => {
                                 return Err(Error::new(
                                     ErrorKind::TypeError,
@@ -653,7 +659,7 @@ impl ItemNode for RNode {
                         let doc = self.owner_document();
                         unattached(&doc, self.clone())
                     }
-                    NodeInner::Document(_, c, _, _) => {
+                    NodeInner::Document(_, c, _, _, _) => {
                         let idx = find_index(&p, self);
                         match idx {
                             Ok(u) => {
@@ -784,7 +790,7 @@ impl ItemNode for RNode {
                 let parent = Weak::upgrade(&p.borrow()).unwrap();
                 let idx = find_index(&parent, self)?;
                 match &parent.0 {
-                    NodeInner::Document(_, children, _, _)
+                    NodeInner::Document(_, children, _, _, _)
                     | NodeInner::Element(_, _, _, children, _) => {
                         children.borrow_mut().insert(idx, n.clone());
                         make_parent(n, parent.clone())
@@ -810,11 +816,12 @@ impl ItemNode for RNode {
         // All new nodes are parentless, i.e. they are unattached to the tree
         // The new element will have the same set of in-scope namespaces as the original element.
         match &self.0 {
-            NodeInner::Document(x, _, _, _) => Ok(Rc::new(Node(NodeInner::Document(
+            NodeInner::Document(x, _, _, _, _) => Ok(Rc::new(Node(NodeInner::Document(
                 x.clone(),
                 RefCell::new(vec![]),
                 RefCell::new(vec![]),
                 None.into(),
+                RefCell::new(HashMap::new()),
             )))),
             NodeInner::Element(p, qn, _, _, ns) => {
                 let new = Rc::new(Node(NodeInner::Element(
@@ -878,7 +885,7 @@ impl ItemNode for RNode {
     // For special character escaping rules, see section 3.4.
     fn get_canonical(&self) -> Result<Self, Error> {
         match &self.0 {
-            NodeInner::Document(_, e, _, _) => {
+            NodeInner::Document(_, e, _, _, _) => {
                 let mut result = self.shallow_copy()?;
                 for n in e.borrow().iter() {
                     if let Ok(rn) = n.get_canonical() {
@@ -927,7 +934,7 @@ impl ItemNode for RNode {
     }
     fn set_xmldecl(&mut self, decl: XMLDecl) -> Result<(), Error> {
         match &self.0 {
-            NodeInner::Document(x, _, _, _) => {
+            NodeInner::Document(x, _, _, _, _) => {
                 *x.borrow_mut() = Some(decl);
                 Ok(())
             }
@@ -940,7 +947,7 @@ impl ItemNode for RNode {
     }
     fn xmldecl(&self) -> XMLDecl {
         match &self.0 {
-            NodeInner::Document(d, _, _, _) => d
+            NodeInner::Document(d, _, _, _, _) => d
                 .borrow()
                 .clone()
                 .map_or_else(|| XMLDeclBuilder::new().build(), |x| x.clone()),
@@ -951,7 +958,7 @@ impl ItemNode for RNode {
     fn is_id(&self) -> bool {
         match &self.0 {
             //TODO Add Element XML ID support
-            NodeInner::Attribute(_, _, v) => matches!(v.as_ref().value, ValueData::ID(_)),
+            NodeInner::Attribute(_, _, _) => matches!(self.value().value, ValueData::ID(_)),
             _ => false,
         }
     }
@@ -959,8 +966,11 @@ impl ItemNode for RNode {
     fn is_idrefs(&self) -> bool {
         match &self.0 {
             //TODO Add Element XML ID REF support
-            NodeInner::Attribute(_, _, v) => {
-                matches!(v.as_ref().value, ValueData::IDREF(_) | ValueData::IDREFS(_))
+            NodeInner::Attribute(_, _, _) => {
+                matches!(
+                    self.value().value,
+                    ValueData::IDREF(_) | ValueData::IDREFS(_)
+                )
             }
             _ => false,
         }
@@ -968,14 +978,14 @@ impl ItemNode for RNode {
 
     fn get_dtd(&self) -> Option<DTD> {
         match &self.0 {
-            NodeInner::Document(_, _, _, dtd) => dtd.borrow().clone(),
+            NodeInner::Document(_, _, _, dtd, _) => dtd.borrow().clone(),
             _ => self.owner_document().get_dtd(),
         }
     }
 
     fn set_dtd(&self, dtd: DTD) -> Result<(), Error> {
         match &self.0 {
-            NodeInner::Document(_, _, _, d) => {
+            NodeInner::Document(_, _, _, d, _) => {
                 *d.borrow_mut() = Some(dtd);
                 Ok(())
             }
@@ -987,6 +997,39 @@ impl ItemNode for RNode {
         }
     }
 
+    fn typed_value(&self) -> Option<Rc<Value>> {
+        // A node whose tree has been dropped has no document, and so no typed value
+        match &self.ancestor_iter().last()?.0 {
+            NodeInner::Document(_, _, _, _, t) => {
+                let t = t.borrow();
+                if t.is_empty() {
+                    None
+                } else {
+                    t.get(&self.get_id()).map(|(_, v)| v.clone())
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn set_typed_value(&self, v: Rc<Value>) -> Result<(), Error> {
+        match (&self.0, &self.owner_document().0) {
+            (
+                NodeInner::Element(_, _, _, _, _) | NodeInner::Attribute(_, _, _),
+                NodeInner::Document(_, _, _, _, t),
+            ) => {
+                // Holding a weak reference means the node's id cannot be reused by another node
+                t.borrow_mut()
+                    .insert(self.get_id(), (Rc::downgrade(self), v));
+                Ok(())
+            }
+            _ => Err(Error::new(
+                ErrorKind::TypeError,
+                String::from("only elements and attributes have a typed value"),
+            )),
+        }
+    }
+
     fn validate(&self, sch: Schema) -> Result<(), ValidationError> {
         crate::validators::validate(self, sch)
     }
@@ -995,7 +1038,7 @@ impl ItemNode for RNode {
 impl Debug for Node {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match &self.0 {
-            NodeInner::Document(_, _, _, _) => write!(f, "document"),
+            NodeInner::Document(_, _, _, _, _) => write!(f, "document"),
             NodeInner::Element(_, qn, ats, _, _) => {
                 let attrs = ats.borrow();
                 write!(
@@ -1035,7 +1078,7 @@ fn format_attrs(ats: &BTreeMap<QName, RNode>) -> String {
 
 // Debugging aid - produce a detailed view of the given document
 pub fn dump_tree(d: &RNode) -> String {
-    if let NodeInner::Document(decl, children, _, dtd) = &d.0 {
+    if let NodeInner::Document(decl, children, _, dtd, _) = &d.0 {
         format!(
             "XML Declaration: {:?}\nDTD: {:?}\n{}",
             decl.borrow(),
@@ -1052,7 +1095,9 @@ fn dump_tree_children(cv: Vec<RNode>, indent: usize) -> String {
         result.push('\n');
         (0..indent).for_each(|_| result.push(' '));
         match &c.0 {
-            NodeInner::Document(_, _, _, _) => result.push_str("child node cannot be a Document"),
+            NodeInner::Document(_, _, _, _, _) => {
+                result.push_str("child node cannot be a Document")
+            }
             NodeInner::Element(_parent, qn, attrs, children, nsd) => {
                 result.push_str(format!("Element node \"{:?}\"\n", qn).as_str());
                 (0..indent + 4).for_each(|_| result.push(' '));
@@ -1099,7 +1144,7 @@ fn dump_tree_children(cv: Vec<RNode>, indent: usize) -> String {
 fn unattached(d: &RNode, n: RNode) {
     // Is it already in the unattached list? If so then do nothing
     match &d.0 {
-        NodeInner::Document(_, _, u, _) => {
+        NodeInner::Document(_, _, u, _, _) => {
             if u.borrow().iter().any(|f| f.is_same(&n)) {
                 return;
             }
@@ -1108,7 +1153,7 @@ fn unattached(d: &RNode, n: RNode) {
         }
         NodeInner::Element(_, _, _, _, _) => {
             let doc = d.owner_document();
-            if let NodeInner::Document(_, _, u, _) = &doc.0 {
+            if let NodeInner::Document(_, _, u, _, _) = &doc.0 {
                 if u.borrow().iter().any(|f| f.is_same(&n)) {
                     return;
                 }
@@ -1145,7 +1190,7 @@ fn detach(n: RNode) {
         | NodeInner::ProcessingInstruction(p, _, _) => {
             let doc = Weak::upgrade(&p.borrow()).unwrap();
             match &doc.0 {
-                NodeInner::Document(_, _, u, _) => {
+                NodeInner::Document(_, _, u, _, _) => {
                     let i = u.borrow().iter().position(|x| Rc::ptr_eq(x, &n));
                     if let Some(i) = i {
                         u.borrow_mut().remove(i);
@@ -1166,7 +1211,7 @@ fn push_node(parent: &RNode, child: RNode) -> Result<(), Error> {
         ));
     }
     match &parent.0 {
-        NodeInner::Document(_, c, _, _) => {
+        NodeInner::Document(_, c, _, _, _) => {
             c.borrow_mut().push(child.clone());
         }
         NodeInner::Element(_, _, _, c, _) => {
@@ -1186,7 +1231,7 @@ fn push_node(parent: &RNode, child: RNode) -> Result<(), Error> {
 // Find the document order of ancestors
 fn doc_order(n: &RNode) -> Vec<usize> {
     match &n.0 {
-        NodeInner::Document(_, _, _, _) => vec![1usize],
+        NodeInner::Document(_, _, _, _, _) => vec![1usize],
         NodeInner::Attribute(_, _, _) => {
             let mut a = doc_order(&n.parent().unwrap());
             a.push(2);
@@ -1217,7 +1262,7 @@ fn doc_order(n: &RNode) -> Vec<usize> {
 // Find the position of this node in the parent's child list.
 fn find_index(parent: &RNode, child: &RNode) -> Result<usize, Error> {
     let idx = match &parent.0 {
-        NodeInner::Document(_, c, _, _) | NodeInner::Element(_, _, _, c, _) => {
+        NodeInner::Document(_, c, _, _, _) | NodeInner::Element(_, _, _, c, _) => {
             c.borrow().iter().enumerate().fold(None, |mut acc, (i, v)| {
                 if Rc::ptr_eq(child, v) {
                     acc = Some(i)
@@ -1278,7 +1323,7 @@ fn to_xml_int(
     ns_in_scope: Vec<NamespaceUri>,
 ) -> String {
     match &node.0 {
-        NodeInner::Document(_, _, _, _) => {
+        NodeInner::Document(_, _, _, _, _) => {
             node.child_iter().fold(String::new(), |mut result, c| {
                 result.push_str(to_xml_int(&c, od, indent + 2, ns_in_scope.clone()).as_str());
                 result
@@ -1391,7 +1436,7 @@ pub struct Children {
 impl Children {
     fn new(n: &RNode) -> Self {
         match &n.0 {
-            NodeInner::Document(_, c, _, _) | NodeInner::Element(_, _, _, c, _) => Children {
+            NodeInner::Document(_, c, _, _, _) | NodeInner::Element(_, _, _, c, _) => Children {
                 v: c.borrow().clone(),
                 i: 0,
             },
@@ -1428,7 +1473,7 @@ impl Iterator for Ancestors {
 
     fn next(&mut self) -> Option<RNode> {
         let parent = match &self.cur.0 {
-            NodeInner::Document(_, _, _, _) => None,
+            NodeInner::Document(_, _, _, _, _) => None,
             NodeInner::Element(p, _, _, _, _)
             | NodeInner::Attribute(p, _, _)
             | NodeInner::Text(p, _)
@@ -1568,7 +1613,7 @@ pub struct NamespaceNodes {
 impl NamespaceNodes {
     fn new(n: RNode) -> Self {
         match &n.0 {
-            NodeInner::Document(_, _, _, _) => {
+            NodeInner::Document(_, _, _, _, _) => {
                 let top = n.child_iter().find(|c| c.node_type() == NodeType::Element);
                 if let Some(t) = top {
                     NamespaceNodes::new(t)
@@ -0,0 +1,1002 @@
+//! # XML Schema Datatypes
+//!
+//! The built-in datatypes of W3C XML Schema Part 2 (<https://www.w3.org/TR/xmlschema-2/>), for use by the validators.
+//!
+//! A lexical value is first whitespace-processed as required by the datatype, then mapped to a [ValueData].
+//! Constraining facets are given as (name, value) pairs, which is how they appear as RELAX NG params.
+
+use std::cmp::Ordering;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use chrono::{FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use num_bigint::BigInt;
+use qualname::{NcName, QName};
+use regex::Regex;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::parser::common::{is_namechar, is_namestartchar, is_ncnamechar, is_ncnamestartchar};
+use crate::value::{
+    Duration, ENTITY, ID, IDREF, NMTOKEN, Name, NegativeInteger, NonNegativeInteger,
+    NonPositiveInteger, NormalizedString, PositiveInteger, ValueData, base64_decode, utc,
+};
+use crate::xdmerror::{Error, ErrorKind};
+
+/// The namespace URI used as the datatypeLibrary for XML Schema datatypes.
+pub(crate) const XSDDATATYPES: &str = "http://www.w3.org/2001/XMLSchema-datatypes";
+
+static DECIMAL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[+-]?(\d+(\.\d*)?|\.\d+)$").unwrap());
+static INTEGER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[+-]?\d+$").unwrap());
+static FLOAT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?|[+-]?INF|NaN)$").unwrap()
+});
+const TIMEZONE: &str = r"(Z|[+-]((0\d|1[0-3]):[0-5]\d|14:00))?";
+static DATETIME: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"^-?\d{{4,}}-\d{{2}}-\d{{2}}T\d{{2}}:\d{{2}}:\d{{2}}(\.\d+)?{}$",
+        TIMEZONE
+    ))
+    .unwrap()
+});
+static DATE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(&format!(r"^-?\d{{4,}}-\d{{2}}-\d{{2}}{}$", TIMEZONE)).unwrap());
+static TIME: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(r"^\d{{2}}:\d{{2}}:\d{{2}}(\.\d+)?{}$", TIMEZONE)).unwrap()
+});
+static GYEARMONTH: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(&format!(r"^-?\d{{4,}}-(0[1-9]|1[0-2]){}$", TIMEZONE)).unwrap());
+static GYEAR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(&format!(r"^-?\d{{4,}}{}$", TIMEZONE)).unwrap());
+static GMONTHDAY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"^--(0[1-9]|1[0-2])-(0[1-9]|[12]\d|3[01]){}$",
+        TIMEZONE
+    ))
+    .unwrap()
+});
+static GDAY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(&format!(r"^---(0[1-9]|[12]\d|3[01]){}$", TIMEZONE)).unwrap());
+static GMONTH: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(&format!(r"^--(0[1-9]|1[0-2]){}$", TIMEZONE)).unwrap());
+static DURATION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?<neg>-)?P(?:(?<y>\d+)Y)?(?:(?<mo>\d+)M)?(?:(?<d>\d+)D)?(?:T(?:(?<h>\d+)H)?(?:(?<mi>\d+)M)?(?:(?<s>\d+(?:\.\d+)?)S)?)?$").unwrap()
+});
+static LANGUAGE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[a-zA-Z]{1,8}(-[a-zA-Z0-9]{1,8})*$").unwrap());
+static HEXBINARY: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^([0-9a-fA-F]{2})*$").unwrap());
+static BASE64BINARY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(([A-Za-z0-9+/]{4})*([A-Za-z0-9+/]{2}==|[A-Za-z0-9+/]{3}=)?)$").unwrap()
+});
+
+#[derive(Clone, Copy, PartialEq)]
+enum WhiteSpace {
+    Preserve,
+    Replace,
+    Collapse,
+}
+
+fn whitespace_facet(datatype: &str) -> WhiteSpace {
+    match datatype {
+        "string" => WhiteSpace::Preserve,
+        "normalizedString" => WhiteSpace::Replace,
+        _ => WhiteSpace::Collapse,
+    }
+}
+
+/// Apply whitespace processing to a lexical value.
+fn normalize(datatype: &str, s: &str) -> String {
+    match whitespace_facet(datatype) {
+        WhiteSpace::Preserve => s.to_string(),
+        WhiteSpace::Replace => s.replace(['\t', '\n', '\r'], " "),
+        WhiteSpace::Collapse => s.split_ascii_whitespace().collect::<Vec<_>>().join(" "),
+    }
+}
+
+fn invalid(datatype: &str, s: &str) -> Error {
+    Error::new(
+        ErrorKind::TypeError,
+        format!("\"{}\" is not a valid {}", s, datatype),
+    )
+}
+
+fn check(re: &Regex, datatype: &str, s: &str) -> Result<(), Error> {
+    if re.is_match(s) {
+        Ok(())
+    } else {
+        Err(invalid(datatype, s))
+    }
+}
+
+fn is_name(s: &str) -> bool {
+    let mut c = s.chars();
+    c.next().is_some_and(|d| is_namestartchar(&d)) && c.all(|d| is_namechar(&d))
+}
+fn is_ncname(s: &str) -> bool {
+    let mut c = s.chars();
+    c.next().is_some_and(|d| is_ncnamestartchar(&d)) && c.all(|d| is_ncnamechar(&d))
+}
+fn is_nmtoken(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|d| is_namechar(&d))
+}
+
+// Split a date/time lexical value into the value and its timezone.
+fn split_timezone(s: &str) -> (&str, Option<&str>) {
+    if let Some(t) = s.strip_suffix('Z') {
+        (t, Some("Z"))
+    } else if s.len() > 6
+        && matches!(&s[s.len() - 6..s.len() - 5], "+" | "-")
+        && &s[s.len() - 3..s.len() - 2] == ":"
+    {
+        (&s[..s.len() - 6], Some(&s[s.len() - 6..]))
+    } else {
+        (s, None)
+    }
+}
+
+// The offset of a lexical timezone, "Z" or (+|-)hh:mm, which must be no more than 14 hours.
+fn timezone(datatype: &str, s: &str, tz: Option<&str>) -> Result<Option<FixedOffset>, Error> {
+    let Some(z) = tz else {
+        return Ok(None);
+    };
+    if z == "Z" {
+        return Ok(FixedOffset::east_opt(0));
+    }
+    let h = z[1..3].parse::<i32>().map_err(|_| invalid(datatype, s))?;
+    let m = z[4..6].parse::<i32>().map_err(|_| invalid(datatype, s))?;
+    let secs = h * 3600 + m * 60;
+    if m > 59 || secs > 14 * 3600 {
+        return Err(invalid(datatype, s));
+    }
+    Ok(FixedOffset::east_opt(if z.starts_with('-') {
+        -secs
+    } else {
+        secs
+    }))
+}
+
+// A numeric field of a date/time lexical value.
+fn field<T: FromStr>(datatype: &str, s: &str, f: &str) -> Result<T, Error> {
+    f.parse::<T>().map_err(|_| invalid(datatype, s))
+}
+
+fn integer(datatype: &str, s: &str) -> Result<BigInt, Error> {
+    check(&INTEGER, datatype, s)?;
+    BigInt::from_str(s.strip_prefix('+').unwrap_or(s)).map_err(|_| invalid(datatype, s))
+}
+
+// An integer type whose value space is unbounded, but is represented by a 64-bit integer.
+fn long_integer(datatype: &str, s: &str) -> Result<i64, Error> {
+    i64::try_from(&integer(datatype, s)?).map_err(|_| {
+        Error::new(
+            ErrorKind::NotImplemented,
+            format!("{} value \"{}\" is too large", datatype, s),
+        )
+    })
+}
+
+// Map a lexical duration to its months and seconds.
+fn duration(datatype: &str, s: &str) -> Result<Duration, Error> {
+    // At least one component must be present, and a "T" must be followed by a time component
+    let c = DURATION
+        .captures(s)
+        .filter(|_| !s.ends_with('P') && !s.ends_with('T'))
+        .ok_or_else(|| invalid(datatype, s))?;
+    let part = |name: &str, unit: i64| -> Result<Decimal, Error> {
+        c.name(name).map_or(Ok(Decimal::ZERO), |m| {
+            Decimal::from_str(m.as_str())
+                .ok()
+                .and_then(|d| d.checked_mul(Decimal::from(unit)))
+                .ok_or_else(|| invalid(datatype, s))
+        })
+    };
+    let months = (part("y", 12)? + part("mo", 1)?)
+        .to_i64()
+        .ok_or_else(|| invalid(datatype, s))?;
+    let seconds = [("d", 86400), ("h", 3600), ("mi", 60), ("s", 1)]
+        .iter()
+        .try_fold(Decimal::ZERO, |acc, &(name, unit)| {
+            acc.checked_add(part(name, unit)?)
+                .ok_or_else(|| invalid(datatype, s))
+        })?;
+    if c.name("neg").is_some() {
+        Duration::new(-months, -seconds)
+    } else {
+        Duration::new(months, seconds)
+    }
+}
+
+fn list<T>(datatype: &str, s: &str, f: impl Fn(&str) -> Result<T, Error>) -> Result<Vec<T>, Error> {
+    if s.is_empty() {
+        return Err(invalid(datatype, s));
+    }
+    s.split(' ').map(f).collect()
+}
+
+/// Map a lexical value to a value of the given built-in datatype.
+///
+/// Returns a TypeError if the string is not in the lexical space of the datatype,
+/// and a StaticUndefined error if the datatype is not known.
+pub(crate) fn parse(datatype: &str, s: &str) -> Result<ValueData, Error> {
+    let n = normalize(datatype, s);
+    let t = n.as_str();
+    match datatype {
+        "string" | "anySimpleType" => Ok(ValueData::String(n)),
+        "normalizedString" => Ok(ValueData::NormalizedString(NormalizedString::try_from(t)?)),
+        "token" | "anyURI" => Ok(ValueData::String(n)),
+        "language" => check(&LANGUAGE, datatype, t).map(|_| ValueData::String(n)),
+        "Name" if is_name(t) => Ok(ValueData::Name(Name::try_from(t)?)),
+        "NCName" if is_ncname(t) => NcName::try_from(t)
+            .map(ValueData::NCName)
+            .map_err(|_| invalid(datatype, t)),
+        "ID" if is_ncname(t) => Ok(ValueData::ID(ID::try_from(t)?)),
+        "IDREF" if is_ncname(t) => Ok(ValueData::IDREF(IDREF::try_from(t)?)),
+        "ENTITY" if is_ncname(t) => Ok(ValueData::ENTITY(ENTITY::try_from(t)?)),
+        "NMTOKEN" if is_nmtoken(t) => Ok(ValueData::NMTOKEN(NMTOKEN::try_from(t)?)),
+        "IDREFS" => list(datatype, t, |i| {
+            if is_ncname(i) {
+                IDREF::try_from(i)
+            } else {
+                Err(invalid(datatype, t))
+            }
+        })
+        .map(ValueData::IDREFS),
+        "ENTITIES" => list(datatype, t, |i| {
+            if is_ncname(i) {
+                ENTITY::try_from(i)
+            } else {
+                Err(invalid(datatype, t))
+            }
+        })
+        .map(ValueData::ENTITIES),
+        "NMTOKENS" => list(datatype, t, |i| {
+            if is_nmtoken(i) {
+                NMTOKEN::try_from(i)
+            } else {
+                Err(invalid(datatype, t))
+            }
+        })
+        .map(ValueData::NMTOKENS),
+        "Name" | "NCName" | "ID" | "IDREF" | "ENTITY" | "NMTOKEN" => Err(invalid(datatype, t)),
+        "QName" | "NOTATION" => match t.split_once(':') {
+            Some((p, l)) if is_ncname(p) && is_ncname(l) => Ok(ValueData::String(n)),
+            None if is_ncname(t) => Ok(ValueData::String(n)),
+            _ => Err(invalid(datatype, t)),
+        },
+        "boolean" => match t {
+            "true" | "1" => Ok(ValueData::Boolean(true)),
+            "false" | "0" => Ok(ValueData::Boolean(false)),
+            _ => Err(invalid(datatype, t)),
+        },
+        "decimal" => {
+            check(&DECIMAL, datatype, t)?;
+            let d = t.trim_end_matches('.');
+            Decimal::from_str(
+                if d.starts_with('.') {
+                    format!("0{}", d)
+                } else {
+                    d.to_string()
+                }
+                .as_str(),
+            )
+            .map(ValueData::Decimal)
+            .map_err(|_| invalid(datatype, t))
+        }
+        "float" | "double" => {
+            check(&FLOAT, datatype, t)?;
+            let d = match t {
+                "INF" | "+INF" => f64::INFINITY,
+                "-INF" => f64::NEG_INFINITY,
+                "NaN" => f64::NAN,
+                _ => t.parse::<f64>().map_err(|_| invalid(datatype, t))?,
+            };
+            if datatype == "float" {
+                Ok(ValueData::Float(d as f32))
+            } else {
+                Ok(ValueData::Double(d))
+            }
+        }
+        "integer" => integer(datatype, t).map(ValueData::Integer),
+        "long" => i64::try_from(&integer(datatype, t)?)
+            .map(ValueData::Long)
+            .map_err(|_| invalid(datatype, t)),
+        "int" => i32::try_from(&integer(datatype, t)?)
+            .map(ValueData::Int)
+            .map_err(|_| invalid(datatype, t)),
+        "short" => i16::try_from(&integer(datatype, t)?)
+            .map(ValueData::Short)
+            .map_err(|_| invalid(datatype, t)),
+        "byte" => i8::try_from(&integer(datatype, t)?)
+            .map(ValueData::Byte)
+            .map_err(|_| invalid(datatype, t)),
+        "nonPositiveInteger" => NonPositiveInteger::try_from(long_integer(datatype, t)?)
+            .map(ValueData::NonPositiveInteger),
+        "negativeInteger" => {
+            NegativeInteger::try_from(long_integer(datatype, t)?).map(ValueData::NegativeInteger)
+        }
+        "nonNegativeInteger" => NonNegativeInteger::try_from(long_integer(datatype, t)?)
+            .map(ValueData::NonNegativeInteger),
+        "positiveInteger" => {
+            PositiveInteger::try_from(long_integer(datatype, t)?).map(ValueData::PositiveInteger)
+        }
+        "unsignedLong" => u64::try_from(&integer(datatype, t)?)
+            .map(ValueData::UnsignedLong)
+            .map_err(|_| invalid(datatype, t)),
+        "unsignedInt" => u32::try_from(&integer(datatype, t)?)
+            .map(ValueData::UnsignedInt)
+            .map_err(|_| invalid(datatype, t)),
+        "unsignedShort" => u16::try_from(&integer(datatype, t)?)
+            .map(ValueData::UnsignedShort)
+            .map_err(|_| invalid(datatype, t)),
+        "unsignedByte" => u8::try_from(&integer(datatype, t)?)
+            .map(ValueData::UnsignedByte)
+            .map_err(|_| invalid(datatype, t)),
+        "dateTime" | "dateTimeStamp" => {
+            check(&DATETIME, datatype, t)?;
+            let (dt, tz) = split_timezone(t);
+            if datatype == "dateTimeStamp" && tz.is_none() {
+                return Err(invalid(datatype, t));
+            }
+            let z = timezone(datatype, t, tz)?;
+            let ndt = NaiveDateTime::parse_from_str(dt, "%Y-%m-%dT%H:%M:%S%.f")
+                .map_err(|_| invalid(datatype, t))?;
+            Ok(match z {
+                Some(o) if datatype == "dateTimeStamp" => ValueData::DateTimeStamp(ndt, o),
+                _ => ValueData::DateTime(ndt, z),
+            })
+        }
+        "date" => {
+            check(&DATE, datatype, t)?;
+            let (d, tz) = split_timezone(t);
+            let z = timezone(datatype, t, tz)?;
+            NaiveDate::parse_from_str(d, "%Y-%m-%d")
+                .map(|nd| ValueData::Date(nd, z))
+                .map_err(|_| invalid(datatype, t))
+        }
+        "time" => {
+            check(&TIME, datatype, t)?;
+            let (tm, tz) = split_timezone(t);
+            let z = timezone(datatype, t, tz)?;
+            NaiveTime::parse_from_str(tm, "%H:%M:%S%.f")
+                .map(|nt| ValueData::Time(nt, z))
+                .map_err(|_| invalid(datatype, t))
+        }
+        "duration" => duration(datatype, t).map(ValueData::Duration),
+        "yearMonthDuration" if !t.contains(['D', 'T']) => {
+            duration(datatype, t).map(ValueData::YearMonthDuration)
+        }
+        "dayTimeDuration" if !t.split('T').next().unwrap_or(t).contains(['Y', 'M']) => {
+            duration(datatype, t).map(ValueData::DayTimeDuration)
+        }
+        "yearMonthDuration" | "dayTimeDuration" => Err(invalid(datatype, t)),
+        "gYearMonth" => {
+            check(&GYEARMONTH, datatype, t)?;
+            let (v, tz) = split_timezone(t);
+            let (y, m) = v.rsplit_once('-').ok_or_else(|| invalid(datatype, t))?;
+            Ok(ValueData::GYearMonth(
+                field(datatype, t, y)?,
+                field(datatype, t, m)?,
+                timezone(datatype, t, tz)?,
+            ))
+        }
+        "gYear" => {
+            check(&GYEAR, datatype, t)?;
+            let (v, tz) = split_timezone(t);
+            Ok(ValueData::GYear(
+                field(datatype, t, v)?,
+                timezone(datatype, t, tz)?,
+            ))
+        }
+        "gMonthDay" => {
+            check(&GMONTHDAY, datatype, t)?;
+            let (v, tz) = split_timezone(t);
+            let m = field(datatype, t, &v[2..4])?;
+            let d = field(datatype, t, &v[5..7])?;
+            // The day must exist in that month of a leap year
+            if NaiveDate::from_ymd_opt(1972, m, d).is_none() {
+                return Err(invalid(datatype, t));
+            }
+            Ok(ValueData::GMonthDay(m, d, timezone(datatype, t, tz)?))
+        }
+        "gDay" => {
+            check(&GDAY, datatype, t)?;
+            let (v, tz) = split_timezone(t);
+            Ok(ValueData::GDay(
+                field(datatype, t, &v[3..5])?,
+                timezone(datatype, t, tz)?,
+            ))
+        }
+        "gMonth" => {
+            check(&GMONTH, datatype, t)?;
+            let (v, tz) = split_timezone(t);
+            Ok(ValueData::GMonth(
+                field(datatype, t, &v[2..4])?,
+                timezone(datatype, t, tz)?,
+            ))
+        }
+        "hexBinary" => {
+            check(&HEXBINARY, datatype, t)?;
+            Ok(ValueData::HexBinary(
+                (0..t.len())
+                    .step_by(2)
+                    .map(|i| u8::from_str_radix(&t[i..i + 2], 16))
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| invalid(datatype, t))?,
+            ))
+        }
+        "base64Binary" => {
+            let b: String = n.split_whitespace().collect();
+            check(&BASE64BINARY, datatype, &b)?;
+            base64_decode(&b)
+                .map(ValueData::Base64Binary)
+                .ok_or_else(|| invalid(datatype, n))
+        }
+        _ => Err(Error::new(
+            ErrorKind::StaticUndefined,
+            format!("unknown datatype \"{}\"", datatype),
+        )),
+    }
+}
+
+/// Cast a string to xs:double. Only the xs:double lexical form is accepted,
+/// so "INF", "-INF" and "NaN" are recognised but "inf" and "infinity" are not.
+pub(crate) fn double(s: &str) -> Option<f64> {
+    match parse("double", s) {
+        Ok(ValueData::Double(d)) => Some(d),
+        _ => None,
+    }
+}
+
+/// Is the datatype a built-in datatype known to this library?
+pub(crate) fn is_builtin(datatype: &str) -> bool {
+    !matches!(parse(datatype, ""), Err(e) if e.kind == ErrorKind::StaticUndefined)
+}
+
+/// Does the datatype, constrained by the given facets, allow the string?
+///
+/// Returns an error if the datatype or a facet is not supported.
+pub(crate) fn allows(datatype: &str, params: &[(String, String)], s: &str) -> Result<bool, Error> {
+    let v = match parse(datatype, s) {
+        Ok(v) => v,
+        Err(e) if e.kind == ErrorKind::TypeError => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    let n = normalize(datatype, s);
+
+    // Values of the enumeration facet are alternatives; all other facets must be satisfied.
+    let enumeration: Vec<&String> = params
+        .iter()
+        .filter(|(f, _)| f == "enumeration")
+        .map(|(_, e)| e)
+        .collect();
+    if !enumeration.is_empty() {
+        let mut found = false;
+        for e in enumeration {
+            if equal(datatype, e, s)? {
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            return Ok(false);
+        }
+    }
+    // Multiple pattern facets are alternatives
+    let patterns: Vec<&String> = params
+        .iter()
+        .filter(|(f, _)| f == "pattern")
+        .map(|(_, p)| p)
+        .collect();
+    if !patterns.is_empty() {
+        let mut found = false;
+        for p in patterns {
+            if xsd_regex(p)?.is_match(&n) {
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            return Ok(false);
+        }
+    }
+
+    for (facet, fv) in params {
+        let ok = match facet.as_str() {
+            "enumeration" | "pattern" => true,
+            "length" => length(datatype, &v, &n)? == facet_size(facet, fv)?,
+            "minLength" => length(datatype, &v, &n)? >= facet_size(facet, fv)?,
+            "maxLength" => length(datatype, &v, &n)? <= facet_size(facet, fv)?,
+            "minInclusive" => order(datatype, &v, fv)?.is_some_and(|o| o != Ordering::Less),
+            "maxInclusive" => order(datatype, &v, fv)?.is_some_and(|o| o != Ordering::Greater),
+            "minExclusive" => order(datatype, &v, fv)? == Some(Ordering::Greater),
+            "maxExclusive" => order(datatype, &v, fv)? == Some(Ordering::Less),
+            "totalDigits" => digits(datatype, &v)?.0 <= facet_size(facet, fv)?,
+            "fractionDigits" => digits(datatype, &v)?.1 <= facet_size(facet, fv)?,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::StaticUndefined,
+                    format!("unknown facet \"{}\"", facet),
+                ));
+            }
+        };
+        if !ok {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Are two lexical values equal in the value space of the datatype?
+///
+/// A string that is not a valid lexical value is not equal to anything.
+pub(crate) fn equal(datatype: &str, s1: &str, s2: &str) -> Result<bool, Error> {
+    let v1 = match parse(datatype, s1) {
+        Ok(v) => v,
+        Err(e) if e.kind == ErrorKind::TypeError => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    let v2 = match parse(datatype, s2) {
+        Ok(v) => v,
+        Err(e) if e.kind == ErrorKind::TypeError => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    Ok(match (&v1, &v2) {
+        (ValueData::Boolean(a), ValueData::Boolean(b)) => a == b,
+        (ValueData::Float(a), ValueData::Float(b)) => a == b || (a.is_nan() && b.is_nan()),
+        (ValueData::Double(a), ValueData::Double(b)) => a == b || (a.is_nan() && b.is_nan()),
+        (ValueData::DateTime(..), ValueData::DateTime(..))
+        | (ValueData::DateTimeStamp(..), ValueData::DateTimeStamp(..))
+        | (ValueData::Time(..), ValueData::Time(..))
+        | (ValueData::Date(..), ValueData::Date(..))
+        | (ValueData::GYearMonth(..), ValueData::GYearMonth(..))
+        | (ValueData::GYear(..), ValueData::GYear(..))
+        | (ValueData::GMonthDay(..), ValueData::GMonthDay(..))
+        | (ValueData::GMonth(..), ValueData::GMonth(..))
+        | (ValueData::GDay(..), ValueData::GDay(..)) => timeline(&v1) == timeline(&v2),
+        (ValueData::Duration(a), ValueData::Duration(b))
+        | (ValueData::YearMonthDuration(a), ValueData::YearMonthDuration(b))
+        | (ValueData::DayTimeDuration(a), ValueData::DayTimeDuration(b)) => a == b,
+        _ => match (as_decimal(&v1), as_decimal(&v2)) {
+            (Some(a), Some(b)) => a == b,
+            _ => v1.to_string() == v2.to_string(),
+        },
+    })
+}
+
+fn facet_size(facet: &str, v: &str) -> Result<usize, Error> {
+    v.trim().parse::<usize>().map_err(|_| {
+        Error::new(
+            ErrorKind::TypeError,
+            format!(
+                "value \"{}\" of facet \"{}\" is not a non-negative integer",
+                v, facet
+            ),
+        )
+    })
+}
+
+// The length of a value: the number of characters, octets, or list items.
+fn length(datatype: &str, v: &ValueData, n: &str) -> Result<usize, Error> {
+    match v {
+        ValueData::IDREFS(l) => Ok(l.len()),
+        ValueData::NMTOKENS(l) => Ok(l.len()),
+        ValueData::ENTITIES(l) => Ok(l.len()),
+        ValueData::Base64Binary(b) | ValueData::HexBinary(b) => Ok(b.len()),
+        _ => match datatype {
+            "string" | "normalizedString" | "token" | "language" | "Name" | "NCName" | "ID"
+            | "IDREF" | "ENTITY" | "NMTOKEN" | "anyURI" | "QName" | "NOTATION" => {
+                Ok(n.chars().count())
+            }
+            _ => Err(Error::new(
+                ErrorKind::TypeError,
+                format!("length facets do not apply to datatype \"{}\"", datatype),
+            )),
+        },
+    }
+}
+
+// The point on the timeline of a date or time, including the Gregorian types. A value without a timezone is treated as UTC.
+fn timeline(v: &ValueData) -> Option<NaiveDateTime> {
+    utc(v, FixedOffset::east_opt(0)?)
+}
+
+fn as_decimal(v: &ValueData) -> Option<Decimal> {
+    match v {
+        ValueData::Decimal(d) => Some(*d),
+        ValueData::Integer(_)
+        | ValueData::Long(_)
+        | ValueData::Int(_)
+        | ValueData::Short(_)
+        | ValueData::Byte(_)
+        | ValueData::NonPositiveInteger(_)
+        | ValueData::NegativeInteger(_)
+        | ValueData::NonNegativeInteger(_)
+        | ValueData::PositiveInteger(_)
+        | ValueData::UnsignedLong(_)
+        | ValueData::UnsignedInt(_)
+        | ValueData::UnsignedShort(_)
+        | ValueData::UnsignedByte(_) => Decimal::from_str(&v.to_string()).ok(),
+        _ => None,
+    }
+}
+
+// Compare a value with the value of a range facet. None means the values are incomparable (e.g. NaN).
+fn order(datatype: &str, v: &ValueData, facet: &str) -> Result<Option<Ordering>, Error> {
+    let f = parse(datatype, facet)?;
+    match (v, &f) {
+        (ValueData::Float(a), ValueData::Float(b)) => Ok(a.partial_cmp(b)),
+        (ValueData::Double(a), ValueData::Double(b)) => Ok(a.partial_cmp(b)),
+        (ValueData::DateTime(..), ValueData::DateTime(..))
+        | (ValueData::DateTimeStamp(..), ValueData::DateTimeStamp(..))
+        | (ValueData::Time(..), ValueData::Time(..))
+        | (ValueData::Date(..), ValueData::Date(..))
+        | (ValueData::GYearMonth(..), ValueData::GYearMonth(..))
+        | (ValueData::GYear(..), ValueData::GYear(..))
+        | (ValueData::GMonthDay(..), ValueData::GMonthDay(..))
+        | (ValueData::GMonth(..), ValueData::GMonth(..))
+        | (ValueData::GDay(..), ValueData::GDay(..)) => Ok(Some(timeline(v).cmp(&timeline(&f)))),
+        _ => match (as_decimal(v), as_decimal(&f)) {
+            (Some(a), Some(b)) => Ok(Some(a.cmp(&b))),
+            _ => Err(Error::new(
+                ErrorKind::TypeError,
+                format!("range facets do not apply to datatype \"{}\"", datatype),
+            )),
+        },
+    }
+}
+
+// The total number of digits, and the number of fraction digits, of a decimal value.
+fn digits(datatype: &str, v: &ValueData) -> Result<(usize, usize), Error> {
+    let d = as_decimal(v).ok_or_else(|| {
+        Error::new(
+            ErrorKind::TypeError,
+            format!("digits facets do not apply to datatype \"{}\"", datatype),
+        )
+    })?;
+    let s = d.normalize().abs().to_string();
+    let (i, f) = s.split_once('.').unwrap_or((s.as_str(), ""));
+    let i = i.trim_start_matches('0');
+    Ok((i.len() + f.len(), f.len()))
+}
+
+/// Compile an XML Schema regular expression.
+///
+/// XML Schema regular expressions are implicitly anchored at both ends.
+pub(crate) fn xsd_regex(pattern: &str) -> Result<Regex, Error> {
+    let result = translate_regex(pattern, true, false, false)
+        .map(|r| format!("^(?:{})$", r))
+        .and_then(|r| Regex::new(&r).map_err(|e| e.to_string()));
+    result.map_err(|e| {
+        Error::new(
+            ErrorKind::ParseError,
+            format!("invalid regular expression \"{}\": {}", pattern, e),
+        )
+    })
+}
+
+/// Compile an XPath regular expression, with the given flags (see XPath F&O 5.6.1).
+///
+/// XPath regular expressions are not anchored, and "^" and "$" match at the start and end of the string
+/// (or of lines, with the "m" flag).
+pub(crate) fn xpath_regex(pattern: &str, flags: &str) -> Result<Regex, Error> {
+    let mut prefix = String::new();
+    let (mut dot_all, mut extended, mut literal) = (false, false, false);
+    for f in flags.chars() {
+        match f {
+            's' => dot_all = true,
+            'm' => prefix.push_str("(?m)"),
+            'i' => prefix.push_str("(?i)"),
+            'x' => extended = true,
+            'q' => literal = true,
+            _ => {
+                return Err(Error::new_with_code(
+                    ErrorKind::ParseError,
+                    format!("invalid regular expression flags \"{}\"", flags),
+                    Some(QName::from_local_name(
+                        NcName::try_from("FORX0001").unwrap(),
+                    )),
+                ));
+            }
+        }
+    }
+    let result = if literal {
+        // "m", "s" and "x" have no effect on a literal pattern
+        Ok(format!(
+            "{}{}",
+            if flags.contains('i') { "(?i)" } else { "" },
+            regex::escape(pattern)
+        ))
+    } else {
+        translate_regex(pattern, false, dot_all, extended).map(|r| format!("{}{}", prefix, r))
+    }
+    .and_then(|r| Regex::new(&r).map_err(|e| e.to_string()));
+    result.map_err(|e| {
+        Error::new_with_code(
+            ErrorKind::ParseError,
+            format!("invalid regular expression \"{}\": {}", pattern, e),
+            Some(QName::from_local_name(
+                NcName::try_from("FORX0002").unwrap(),
+            )),
+        )
+    })
+}
+
+/// The parent of each capturing group in an XPath regular expression.
+/// Groups are numbered from 1, in the order of their opening parenthesis. The parent of a top-level group is 0.
+pub(crate) fn regex_group_parents(pattern: &str, flags: &str) -> Vec<usize> {
+    let mut parents = vec![0];
+    if flags.contains('q') {
+        return parents;
+    }
+    let mut stack: Vec<Option<usize>> = vec![];
+    let mut depth = 0;
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '[' => depth += 1,
+            ']' if depth > 0 => depth -= 1,
+            '(' if depth == 0 => {
+                if chars.peek() == Some(&'?') {
+                    stack.push(None)
+                } else {
+                    parents.push(stack.iter().rev().find_map(|g| *g).unwrap_or(0));
+                    stack.push(Some(parents.len() - 1))
+                }
+            }
+            ')' if depth == 0 => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+    parents
+}
+
+// Translate an XML Schema, or XPath, regular expression to the syntax of the regex crate.
+//
+// The multi-character escapes for XML names (\i, \I, \c, \C), whitespace (\s, \S) and word characters (\w, \W)
+// are translated to the equivalent character classes.
+// Character class subtraction ("[a-z-[aeiou]]") is translated to a class difference.
+// The "." character does not match a newline or carriage return, unless dot_all is set.
+// If extended is set then whitespace outside of character classes is removed.
+fn translate_regex(
+    pattern: &str,
+    xsd: bool,
+    dot_all: bool,
+    extended: bool,
+) -> Result<String, String> {
+    const NAMESTART: &str = r"_:A-Za-z\x{C0}-\x{D6}\x{D8}-\x{F6}\x{F8}-\x{2FF}\x{370}-\x{37D}\x{37F}-\x{1FFF}\x{200C}-\x{200D}\x{2070}-\x{218F}\x{2C00}-\x{2FEF}\x{3001}-\x{D7FF}\x{F900}-\x{FDCF}\x{FDF0}-\x{FFFD}\x{10000}-\x{EFFFF}";
+    const NAMECHAR: &str = r"\-.0-9\x{B7}\x{300}-\x{36F}\x{203F}-\x{2040}";
+    const SPACE: &str = r"\x20\t\n\r";
+    const NONWORD: &str = r"\p{P}\p{Z}\p{C}";
+    let mut result = String::new();
+    let mut depth = 0;
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('i') if depth > 0 => result.push_str(NAMESTART),
+                Some('i') => result.push_str(&format!("[{}]", NAMESTART)),
+                Some('I') => result.push_str(&format!("[^{}]", NAMESTART)),
+                Some('c') if depth > 0 => {
+                    result.push_str(NAMESTART);
+                    result.push_str(NAMECHAR)
+                }
+                Some('c') => result.push_str(&format!("[{}{}]", NAMESTART, NAMECHAR)),
+                Some('C') => result.push_str(&format!("[^{}{}]", NAMESTART, NAMECHAR)),
+                Some('s') if depth > 0 => result.push_str(SPACE),
+                Some('s') => result.push_str(&format!("[{}]", SPACE)),
+                Some('S') => result.push_str(&format!("[^{}]", SPACE)),
+                Some('w') => result.push_str(&format!("[^{}]", NONWORD)),
+                Some('W') if depth > 0 => result.push_str(NONWORD),
+                Some('W') => result.push_str(&format!("[{}]", NONWORD)),
+                Some(d @ '1'..='9') if !xsd && depth == 0 => {
+                    return Err(format!("back-reference \\{} is not supported", d));
+                }
+                Some(d) => {
+                    result.push('\\');
+                    result.push(d)
+                }
+                None => return Err(String::from("missing escaped character")),
+            },
+            '-' if depth > 0 && chars.peek() == Some(&'[') => {
+                // Character class subtraction
+                chars.next();
+                depth += 1;
+                result.push_str("--[")
+            }
+            '[' => {
+                depth += 1;
+                result.push(c)
+            }
+            ']' => {
+                if depth > 0 {
+                    depth -= 1
+                }
+                result.push(c)
+            }
+            '.' if depth == 0 => {
+                if dot_all {
+                    result.push_str("(?s:.)")
+                } else {
+                    result.push_str(r"[^\n\r]")
+                }
+            }
+            '\t' | '\n' | '\r' | ' ' if extended && depth == 0 => {}
+            // Anchors are not special in XML Schema regular expressions
+            '^' if xsd && depth == 0 => result.push_str(r"\^"),
+            '$' if xsd => result.push_str(r"\$"),
+            _ => result.push(c),
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(p: &[(&str, &str)]) -> Vec<(String, String)> {
+        p.iter()
+            .map(|(n, v)| (n.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn datatype_date() {
+        assert!(allows("date", &[], "2002-10-10").unwrap());
+        assert!(allows("date", &[], " 2002-10-10Z ").unwrap());
+        assert!(!allows("date", &[], "2002-13-10").unwrap());
+        assert!(!allows("date", &[], "10/10/2002").unwrap());
+    }
+
+    #[test]
+    fn datatype_integer_range() {
+        let p = params(&[("minInclusive", "1"), ("maxExclusive", "10")]);
+        assert!(allows("integer", &p, "1").unwrap());
+        assert!(allows("integer", &p, "+9").unwrap());
+        assert!(!allows("integer", &p, "10").unwrap());
+        assert!(!allows("integer", &p, "0").unwrap());
+        assert!(!allows("integer", &p, "1.5").unwrap());
+    }
+
+    #[test]
+    fn datatype_large_integer() {
+        assert!(allows("integer", &[], "123456789012345678901234567890").unwrap());
+        assert!(equal("integer", "+012345678901234567890", "12345678901234567890").unwrap());
+        assert!(!allows("long", &[], "9223372036854775808").unwrap());
+        assert!(allows("unsignedLong", &[], "18446744073709551615").unwrap());
+    }
+
+    #[test]
+    fn datatype_pattern() {
+        let p = params(&[("pattern", r"\d{3}-[A-Z]{2}")]);
+        assert!(allows("token", &p, "123-AB").unwrap());
+        assert!(!allows("token", &p, "x123-AB").unwrap());
+        assert!(allows("NCName", &params(&[("pattern", r"\i\c*")]), "a-b").unwrap());
+    }
+
+    #[test]
+    fn regex_dialect() {
+        assert!(xsd_regex("[a-z-[aeiou]]+").unwrap().is_match("bcd"));
+        assert!(!xsd_regex("[a-z-[aeiou]]+").unwrap().is_match("bad"));
+        assert!(xsd_regex("a$").unwrap().is_match("a$"));
+        assert!(xpath_regex("^\\i\\c*$", "").unwrap().is_match("x-1"));
+        assert!(xpath_regex("A B", "ix").unwrap().is_match("xaby"));
+        assert!(xpath_regex("a.b", "").unwrap().find("a\nb").is_none());
+        assert!(xpath_regex("a.b", "s").unwrap().is_match("a\nb"));
+        assert!(xpath_regex("a.b", "q").unwrap().is_match("xa.by"));
+        assert!(xpath_regex("a", "k").is_err_and(|e| e.code.unwrap().to_string() == "FORX0001"));
+        assert!(xpath_regex("(a", "").is_err_and(|e| e.code.unwrap().to_string() == "FORX0002"));
+        assert_eq!(
+            regex_group_parents("(a(b)(?:c(d)))(e)", ""),
+            vec![0, 0, 1, 1, 0]
+        );
+    }
+
+    #[test]
+    fn datatype_length() {
+        let p = params(&[("minLength", "2"), ("maxLength", "3")]);
+        assert!(allows("string", &p, "ab").unwrap());
+        assert!(!allows("string", &p, "abcd").unwrap());
+        assert!(allows("hexBinary", &params(&[("length", "2")]), "0fA0").unwrap());
+        assert!(allows("NMTOKENS", &params(&[("length", "3")]), " a  b c ").unwrap());
+    }
+
+    #[test]
+    fn datatype_digits() {
+        let p = params(&[("totalDigits", "4"), ("fractionDigits", "2")]);
+        assert!(allows("decimal", &p, "12.30").unwrap());
+        assert!(!allows("decimal", &p, "123.45").unwrap());
+        assert!(!allows("decimal", &p, "1.234").unwrap());
+    }
+
+    #[test]
+    fn datatype_enumeration() {
+        let p = params(&[("enumeration", "1.0"), ("enumeration", "2")]);
+        assert!(allows("decimal", &p, "1").unwrap());
+        assert!(!allows("decimal", &p, "3").unwrap());
+    }
+
+    #[test]
+    fn datatype_equality() {
+        assert!(equal("token", " a  b ", "a b").unwrap());
+        assert!(!equal("string", " a  b ", "a b").unwrap());
+        assert!(equal("decimal", "1.50", "+1.5").unwrap());
+        assert!(equal("boolean", "1", "true").unwrap());
+        assert!(equal("hexBinary", "0fa0", "0FA0").unwrap());
+        assert!(
+            equal(
+                "dateTime",
+                "2002-10-10T12:00:00Z",
+                "2002-10-10T14:00:00+02:00"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn datatype_binary() {
+        assert!(equal("base64Binary", "SGVs bG8=", "SGVsbG8=").unwrap());
+        assert!(!equal("base64Binary", "SGVsbG8=", "SGVsbA==").unwrap());
+        assert!(allows("base64Binary", &params(&[("length", "5")]), "SGVsbG8=").unwrap());
+        assert!(!allows("base64Binary", &params(&[("length", "4")]), "SGVsbG8=").unwrap());
+        assert!(parse("base64Binary", "SGVsbG8").is_err());
+        assert!(parse("hexBinary", "0fa").is_err());
+        assert_eq!(
+            parse("base64Binary", "SGVs\nbG8=").unwrap().to_string(),
+            "SGVsbG8="
+        );
+        assert_eq!(parse("hexBinary", "0fa0").unwrap().to_string(), "0FA0");
+    }
+
+    #[test]
+    fn datatype_duration() {
+        assert!(equal("duration", "P1D", "PT24H").unwrap());
+        assert!(equal("duration", "P1Y", "P12M").unwrap());
+        assert!(!allows("duration", &[], "P").unwrap());
+        assert!(!allows("duration", &[], "P1DT").unwrap());
+        assert!(allows("yearMonthDuration", &[], "-P1Y2M").unwrap());
+        assert!(!allows("yearMonthDuration", &[], "P1Y2D").unwrap());
+        assert!(allows("dayTimeDuration", &[], "P3DT1M").unwrap());
+        assert!(!allows("dayTimeDuration", &[], "P1M").unwrap());
+        assert_eq!(
+            parse("duration", "-P1Y14MT36H0.50S").unwrap().to_string(),
+            "-P2Y2M1DT12H0.5S"
+        );
+    }
+
+    #[test]
+    fn datatype_gregorian() {
+        assert_eq!(parse("gYear", "2019").unwrap().to_string(), "2019");
+        assert_eq!(parse("gYear", "-0044Z").unwrap().to_string(), "-0044Z");
+        assert_eq!(
+            parse("gYearMonth", "2019-05+01:00").unwrap().to_string(),
+            "2019-05+01:00"
+        );
+        assert_eq!(
+            parse("gMonthDay", "--02-29").unwrap().to_string(),
+            "--02-29"
+        );
+        assert!(!allows("gMonthDay", &[], "--02-30").unwrap());
+        assert!(!allows("gYearMonth", &[], "2019-13").unwrap());
+        assert_eq!(parse("gMonth", "--05").unwrap().to_string(), "--05");
+        assert_eq!(parse("gDay", "---31Z").unwrap().to_string(), "---31Z");
+        assert!(equal("gDay", "---01Z", "---01+00:00").unwrap());
+        assert!(!allows("dateTimeStamp", &[], "2019-05-01T00:00:00").unwrap());
+        assert!(matches!(
+            parse("dateTimeStamp", "2019-05-01T00:00:00Z").unwrap(),
+            ValueData::DateTimeStamp(..)
+        ));
+    }
+
+    #[test]
+    fn datatype_unknown() {
+        assert!(allows("nosuchtype", &[], "x").is_err());
+        assert!(allows("string", &params(&[("nosuchfacet", "1")]), "x").is_err());
+    }
+}
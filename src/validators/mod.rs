@@ -1,11 +1,19 @@
+pub(crate) mod datatypes;
 pub mod dtd;
+pub mod relaxng;
+pub mod schematron;
+pub mod xmlmodel;
+pub mod xsd;
 
 use crate::item::{Node, NodeType};
 use crate::validators::dtd::validate_dtd;
+use crate::validators::xsd::{XsdSchema, validate_xsd};
+use std::rc::Rc;
 
 #[derive(Clone)]
 pub enum Schema {
     DTD, //Will add the rest as they become available.
+    XSD(Rc<XsdSchema>),
 }
 
 #[derive(Debug)]
@@ -18,6 +26,7 @@ pub(crate) fn validate(doc: &impl Node, schema: Schema) -> Result<(), Validation
     match doc.node_type() {
         NodeType::Document => match schema {
             Schema::DTD => validate_dtd(doc.clone()),
+            Schema::XSD(x) => validate_xsd(doc, &x),
         },
         _ => Err(ValidationError::DocumentError(
             "Node provided was not a document".to_string(),
@@ -0,0 +1,1642 @@
+//! # RELAX NG Compact Syntax
+//!
+//! A parser for the RELAX NG compact syntax (<https://relaxng.org/compact-20021121.html>), as a parser combinator.
+//!
+//! The compact schema is translated to the equivalent schema in the RELAX NG XML syntax,
+//! which is then simplified in the same way as a schema that was written in the XML syntax.
+//!
+//! The translation differs from the one given in the specification in a couple of ways:
+//! * Names are always emitted in their expanded form, i.e. as a name element with an explicit ns attribute.
+//! * Patterns given by "external" and grammars given by "include" are fetched, parsed as compact syntax,
+//!   and copied into the result rather than emitted as externalRef and include elements.
+
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::sync::LazyLock;
+
+use qualname::{NamespacePrefix, NamespaceUri, NcName, QName};
+use url::Url;
+
+use crate::item::Node;
+use crate::parser::combinators::alt::{alt2, alt3, alt4, alt5};
+use crate::parser::combinators::delimited::delimited;
+use crate::parser::combinators::many::many0;
+use crate::parser::combinators::map::map;
+use crate::parser::combinators::opt::opt;
+use crate::parser::combinators::pair::pair;
+use crate::parser::combinators::tuple::{tuple3, tuple4, tuple5};
+use crate::parser::combinators::value::value;
+use crate::parser::common::{is_ncnamechar, is_ncnamestartchar};
+use crate::parser::{ParseError, ParseInput, ParserState, StaticState, StaticStateBuilder};
+use crate::trees::smite::RNode;
+use crate::validators::datatypes::XSDDATATYPES;
+use crate::value::Value;
+use crate::xdmerror::{Error, ErrorKind};
+
+static RNGNS: LazyLock<NamespaceUri> =
+    LazyLock::new(|| NamespaceUri::try_from("http://relaxng.org/ns/structure/1.0").unwrap());
+const ANNOTATIONNS: &str = "http://relaxng.org/ns/compatibility/annotations/1.0";
+const XMLNS: &str = "http://www.w3.org/XML/1998/namespace";
+
+const KEYWORDS: [&str; 19] = [
+    "attribute",
+    "default",
+    "datatypes",
+    "div",
+    "element",
+    "empty",
+    "external",
+    "grammar",
+    "include",
+    "inherit",
+    "list",
+    "mixed",
+    "namespace",
+    "notAllowed",
+    "parent",
+    "start",
+    "string",
+    "text",
+    "token",
+];
+
+/// A name that may have a prefix.
+type Name = (Option<String>, String);
+
+#[derive(Clone, Debug)]
+enum NamespaceUriLiteral {
+    Uri(String),
+    Inherit,
+}
+
+#[derive(Clone, Debug)]
+enum Decl {
+    Namespace(String, NamespaceUriLiteral),
+    DefaultNamespace(Option<String>, NamespaceUriLiteral),
+    Datatypes(String, String),
+}
+
+#[derive(Clone, Debug)]
+enum NameClass {
+    Name(Name),
+    NsName(String, Option<Box<NameClass>>),
+    AnyName(Option<Box<NameClass>>),
+    Choice(Vec<NameClass>),
+}
+
+#[derive(Clone, Debug)]
+enum Pattern {
+    Element(NameClass, Box<Pattern>),
+    Attribute(NameClass, Box<Pattern>),
+    Group(Vec<Pattern>),
+    Interleave(Vec<Pattern>),
+    Choice(Vec<Pattern>),
+    Optional(Box<Pattern>),
+    ZeroOrMore(Box<Pattern>),
+    OneOrMore(Box<Pattern>),
+    List(Box<Pattern>),
+    Mixed(Box<Pattern>),
+    Ref(String),
+    ParentRef(String),
+    Empty,
+    Text,
+    NotAllowed,
+    Value(Option<Name>, String), // datatype, value
+    Data(Name, Vec<(String, String)>, Option<Box<Pattern>>), // datatype, parameters, except
+    External(String, Option<String>), // URI, inherited prefix
+    Grammar(Vec<Component>),
+    Annotated(Annotation, Box<Pattern>, Vec<AnnotationElement>), // leading and following annotations
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Combine {
+    Assign,
+    Choice,
+    Interleave,
+}
+
+#[derive(Clone, Debug)]
+enum Component {
+    Start(Combine, Pattern),
+    Define(String, Combine, Pattern),
+    Div(Vec<Component>),
+    Include(String, Option<String>, Vec<Component>), // URI, inherited prefix, overriding components
+    Annotation(AnnotationElement),
+    Annotated(Annotation, Box<Component>),
+}
+
+#[derive(Clone, Debug, Default)]
+struct Annotation {
+    documentation: Vec<String>,
+    items: Vec<AnnotationItem>,
+}
+
+#[derive(Clone, Debug)]
+struct AnnotationElement {
+    name: Name,
+    content: Vec<AnnotationItem>,
+}
+
+#[derive(Clone, Debug)]
+enum AnnotationItem {
+    Attribute(Name, String),
+    Element(AnnotationElement),
+    Text(String),
+}
+
+/// Parse a schema written in the RELAX NG compact syntax.
+///
+/// The result is a document containing the equivalent schema in the RELAX NG XML syntax.
+/// External patterns and included grammars are resolved against the base URL and retrieved using the fetcher.
+pub fn parse_compact<F>(rnc: &str, base: Option<Url>, mut fetcher: F) -> Result<RNode, Error>
+where
+    F: FnMut(&Url) -> Result<String, Error>,
+{
+    let (decls, pat) = parse_schema(rnc)?;
+    let env = Env::new(&decls, base.clone(), "");
+    let mut doc = RNode::new_document();
+    let mut b = Builder {
+        doc: doc.clone(),
+        fetcher: &mut fetcher,
+        open: base.into_iter().collect(),
+    };
+    let root = b.pattern(&pat, None, &env)?;
+
+    root.add_namespace(doc.new_namespace(RNGNS.clone(), None, true)?)?;
+    for (p, u) in env.namespaces.iter() {
+        if p != "xml" && !u.is_empty() {
+            root.add_namespace(doc.new_namespace(
+                NamespaceUri::try_from(u.as_str()).map_err(|_| {
+                    Error::new(
+                        ErrorKind::ParseError,
+                        format!("invalid namespace URI \"{}\"", u),
+                    )
+                })?,
+                Some(NamespacePrefix::try_from(p.as_str()).map_err(|_| {
+                    Error::new(ErrorKind::ParseError, format!("invalid prefix \"{}\"", p))
+                })?),
+                true,
+            )?)?;
+        }
+    }
+    if !env.namespaces.contains_key("a") {
+        root.add_namespace(doc.new_namespace(
+            NamespaceUri::try_from(ANNOTATIONNS).unwrap(),
+            Some(NamespacePrefix::try_from("a").unwrap()),
+            true,
+        )?)?;
+    }
+    doc.push(root)?;
+    Ok(doc)
+}
+
+fn parse_schema(rnc: &str) -> Result<(Vec<Decl>, Pattern), Error> {
+    let expanded = expand_escapes(rnc)?;
+    let state: ParserState<RNode> = ParserState::new();
+    let mut static_state = StaticStateBuilder::new()
+        .namespace(|_| Err(ParseError::MissingNameSpace))
+        .build();
+    match schema::<RNode, _>()((expanded.as_str(), state), &mut static_state) {
+        Ok(((rest, _), s)) => {
+            let rest = skip_space(rest, true);
+            if rest.is_empty() {
+                Ok(s)
+            } else {
+                Err(Error::new(
+                    ErrorKind::ParseError,
+                    format!(
+                        "unexpected input in compact schema: \"{}\"",
+                        rest.chars().take(40).collect::<String>()
+                    ),
+                ))
+            }
+        }
+        Err(_) => Err(Error::new(
+            ErrorKind::ParseError,
+            String::from("unable to parse compact schema"),
+        )),
+    }
+}
+
+// Escape sequences (\x{...}) are replaced before any other lexical processing (section 3).
+fn expand_escapes(s: &str) -> Result<String, Error> {
+    let mut result = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(i) = rest.find('\\') {
+        result.push_str(&rest[..i]);
+        let after = &rest[i + 1..];
+        let xs = after.len() - after.trim_start_matches('x').len();
+        if xs > 0 && after[xs..].starts_with('{') {
+            let body = &after[xs + 1..];
+            let end = body
+                .find('}')
+                .ok_or_else(|| Error::new(ErrorKind::ParseError, "unterminated escape sequence"))?;
+            let c = u32::from_str_radix(body[..end].trim(), 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::ParseError,
+                        format!("invalid escape sequence \"\\x{{{}}}\"", &body[..end]),
+                    )
+                })?;
+            result.push(c);
+            rest = &body[end + 1..];
+        } else {
+            // An escaped identifier
+            result.push('\\');
+            rest = after;
+        }
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+// topLevel ::= decl* (pattern | grammarContent*)
+fn schema<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, (Vec<Decl>, Pattern)), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(move |input, ss| {
+        let (input1, decls) = many0(decl())(input, ss)?;
+        let (input2, comps) = many0(component())(input1.clone(), ss)?;
+        if skip_space(input2.0, true).is_empty() {
+            Ok((input2, (decls, Pattern::Grammar(comps))))
+        } else {
+            let (input3, p) = pattern()(input1, ss)?;
+            Ok((input3, (decls, p)))
+        }
+    })
+}
+
+// decl ::= "namespace" identifierOrKeyword "=" namespaceURILiteral
+//        | "default" "namespace" [identifierOrKeyword] "=" namespaceURILiteral
+//        | "datatypes" identifierOrKeyword "=" literal
+fn decl<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(ParseInput<'a, N>, &mut StaticState<L>) -> Result<(ParseInput<'a, N>, Decl), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(alt3(
+        map(
+            tuple4(
+                keyword("namespace"),
+                identifier_or_keyword(),
+                sym("="),
+                namespace_uri_literal(),
+            ),
+            |(_, p, _, u)| Decl::Namespace(p, u),
+        ),
+        map(
+            tuple5(
+                keyword("default"),
+                keyword("namespace"),
+                opt(identifier_or_keyword()),
+                sym("="),
+                namespace_uri_literal(),
+            ),
+            |(_, _, p, _, u)| Decl::DefaultNamespace(p, u),
+        ),
+        map(
+            tuple4(
+                keyword("datatypes"),
+                identifier_or_keyword(),
+                sym("="),
+                literal(),
+            ),
+            |(_, p, _, u)| Decl::Datatypes(p, u),
+        ),
+    ))
+}
+
+// namespaceURILiteral ::= literal | "inherit"
+fn namespace_uri_literal<'a, N: Node + 'a, L>() -> impl Fn(
+    ParseInput<'a, N>,
+    &mut StaticState<L>,
+) -> Result<
+    (ParseInput<'a, N>, NamespaceUriLiteral),
+    ParseError,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    alt2(
+        map(literal(), NamespaceUriLiteral::Uri),
+        value(keyword("inherit"), NamespaceUriLiteral::Inherit),
+    )
+}
+
+// pattern ::= particle ("," particle)* | particle ("|" particle)* | particle ("&" particle)*
+// Different connectors may not be mixed without parentheses.
+fn pattern<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, Pattern), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(move |input, ss| {
+        let (mut input, first) = particle()(input, ss)?;
+        let mut items = vec![first];
+        let mut connector: Option<char> = None;
+        loop {
+            match connector_op()(input.clone(), ss) {
+                Ok((input1, c)) => {
+                    if connector.is_some_and(|d| d != c) {
+                        return Err(ParseError::NotWellFormed(String::from(
+                            "different connectors must be separated by parentheses",
+                        )));
+                    }
+                    connector = Some(c);
+                    let (input2, p) = particle()(input1, ss)?;
+                    items.push(p);
+                    input = input2;
+                }
+                Err(ParseError::Combinator(_)) => break,
+                Err(e) => return Err(e),
+            }
+        }
+        let p = match connector {
+            None => items.pop().unwrap(),
+            Some(',') => Pattern::Group(items),
+            Some('|') => Pattern::Choice(items),
+            Some(_) => Pattern::Interleave(items),
+        };
+        Ok((input, p))
+    })
+}
+
+fn pattern_wrapper<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, Pattern), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(move |input, ss| pattern::<N, L>()(input, ss))
+}
+
+// particle ::= primary ("?" | "*" | "+")?
+fn particle<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, Pattern), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(move |input, ss| {
+        let (input1, p) = primary()(input, ss)?;
+        let (input2, suffix) = opt(alt3(
+            value(sym("?"), '?'),
+            value(sym("*"), '*'),
+            value(sym("+"), '+'),
+        ))(input1, ss)?;
+        let q = match suffix {
+            None => p,
+            Some('?') => Pattern::Optional(Box::new(p)),
+            Some('*') => Pattern::ZeroOrMore(Box::new(p)),
+            Some(_) => Pattern::OneOrMore(Box::new(p)),
+        };
+        Ok((input2, q))
+    })
+}
+
+// A primary pattern, with optional leading annotations and following (">>") annotations.
+fn primary<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, Pattern), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(move |input, ss| {
+        let (input1, ann) = annotations()(input, ss)?;
+        let (input2, p) = alt4(
+            alt5(
+                map(
+                    tuple5(
+                        keyword("element"),
+                        name_class(),
+                        sym("{"),
+                        pattern_wrapper(),
+                        close("}"),
+                    ),
+                    |(_, nc, _, p, _)| Pattern::Element(nc, Box::new(p)),
+                ),
+                map(
+                    tuple5(
+                        keyword("attribute"),
+                        name_class(),
+                        sym("{"),
+                        pattern_wrapper(),
+                        close("}"),
+                    ),
+                    |(_, nc, _, p, _)| Pattern::Attribute(nc, Box::new(p)),
+                ),
+                map(
+                    tuple4(keyword("list"), sym("{"), pattern_wrapper(), close("}")),
+                    |(_, _, p, _)| Pattern::List(Box::new(p)),
+                ),
+                map(
+                    tuple4(keyword("mixed"), sym("{"), pattern_wrapper(), close("}")),
+                    |(_, _, p, _)| Pattern::Mixed(Box::new(p)),
+                ),
+                map(pair(keyword("parent"), identifier()), |(_, n)| {
+                    Pattern::ParentRef(n)
+                }),
+            ),
+            alt5(
+                alt3(
+                    value(keyword("empty"), Pattern::Empty),
+                    value(keyword("text"), Pattern::Text),
+                    value(keyword("notAllowed"), Pattern::NotAllowed),
+                ),
+                map(
+                    tuple3(keyword("external"), literal(), opt(inherit())),
+                    |(_, u, i)| Pattern::External(u, i),
+                ),
+                map(
+                    tuple4(
+                        keyword("grammar"),
+                        sym("{"),
+                        many0(component_wrapper()),
+                        close("}"),
+                    ),
+                    |(_, _, c, _)| Pattern::Grammar(c),
+                ),
+                map(
+                    tuple3(sym("("), pattern_wrapper(), close(")")),
+                    |(_, p, _)| p,
+                ),
+                datatype(),
+            ),
+            map(identifier(), Pattern::Ref),
+            map(literal(), |v| Pattern::Value(None, v)),
+        )(input1, ss)?;
+        let (input3, follow) =
+            many0(map(pair(sym(">>"), annotation_element()), |(_, e)| e))(input2, ss)?;
+        if ann.is_none() && follow.is_empty() {
+            Ok((input3, p))
+        } else {
+            Ok((
+                input3,
+                Pattern::Annotated(ann.unwrap_or_default(), Box::new(p), follow),
+            ))
+        }
+    })
+}
+
+fn primary_wrapper<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, Pattern), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(move |input, ss| primary::<N, L>()(input, ss))
+}
+
+// datatypeName datatypeValue
+// | datatypeName ("{" param* "}")? ("-" primary)?
+fn datatype<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, Pattern), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(move |input, ss| {
+        let (input1, dt) = alt3(
+            value(keyword("string"), (None, String::from("string"))),
+            value(keyword("token"), (None, String::from("token"))),
+            map(cname(), |(p, l)| (Some(p), l)),
+        )(input, ss)?;
+        match literal()(input1.clone(), ss) {
+            Ok((input2, v)) => return Ok((input2, Pattern::Value(Some(dt), v))),
+            Err(ParseError::Combinator(_)) => {}
+            Err(e) => return Err(e),
+        }
+        let (input2, params) = opt(delimited(sym("{"), many0(param()), close("}")))(input1, ss)?;
+        let (input3, except) = opt(pair(sym("-"), primary_wrapper()))(input2, ss)?;
+        Ok((
+            input3,
+            Pattern::Data(
+                dt,
+                params.unwrap_or_default(),
+                except.map(|(_, p)| Box::new(p)),
+            ),
+        ))
+    })
+}
+
+// param ::= annotations identifierOrKeyword "=" literal
+// Annotations on parameters are accepted, but not retained.
+fn param<'a, N: Node + 'a, L>() -> impl Fn(
+    ParseInput<'a, N>,
+    &mut StaticState<L>,
+) -> Result<(ParseInput<'a, N>, (String, String)), ParseError>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    map(
+        tuple4(annotations(), identifier_or_keyword(), sym("="), literal()),
+        |(_, n, _, v)| (n, v),
+    )
+}
+
+// inherit ::= "inherit" "=" identifierOrKeyword
+fn inherit<'a, N: Node + 'a, L>()
+-> impl Fn(ParseInput<'a, N>, &mut StaticState<L>) -> Result<(ParseInput<'a, N>, String), ParseError>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    map(
+        tuple3(keyword("inherit"), sym("="), identifier_or_keyword()),
+        |(_, _, p)| p,
+    )
+}
+
+// nameClass ::= nameClassPrimary ("|" nameClassPrimary)*
+fn name_class<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, NameClass), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(move |input, ss| {
+        let (input1, first) = name_class_primary()(input, ss)?;
+        let (input2, mut rest) =
+            many0(map(pair(sym("|"), name_class_primary()), |(_, n)| n))(input1, ss)?;
+        if rest.is_empty() {
+            Ok((input2, first))
+        } else {
+            rest.insert(0, first);
+            Ok((input2, NameClass::Choice(rest)))
+        }
+    })
+}
+
+// Annotations on name classes are accepted, but not retained.
+fn name_class_primary<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, NameClass), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(move |input, ss| {
+        let (input1, _) = annotations()(input, ss)?;
+        alt5(
+            map(
+                tuple3(
+                    sym("("),
+                    Box::new(|input, ss| name_class::<N, L>()(input, ss)),
+                    close(")"),
+                ),
+                |(_, nc, _)| nc,
+            ),
+            map(pair(sym("*"), opt(name_class_except())), |(_, e)| {
+                NameClass::AnyName(e)
+            }),
+            map(pair(nsname(), opt(name_class_except())), |(p, e)| {
+                NameClass::NsName(p, e)
+            }),
+            map(cname(), |(p, l)| NameClass::Name((Some(p), l))),
+            map(identifier_or_keyword(), |l| NameClass::Name((None, l))),
+        )(input1, ss)
+    })
+}
+
+fn name_class_except<'a, N: Node + 'a, L>() -> impl Fn(
+    ParseInput<'a, N>,
+    &mut StaticState<L>,
+) -> Result<
+    (ParseInput<'a, N>, Box<NameClass>),
+    ParseError,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    map(
+        pair(
+            sym("-"),
+            Box::new(|input, ss| name_class_primary::<N, L>()(input, ss)),
+        ),
+        |(_, n)| Box::new(n),
+    )
+}
+
+// grammarContent ::= start | define | "div" "{" grammarContent* "}"
+//                  | "include" anyURILiteral [inherit] ["{" includeContent* "}"]
+//                  | annotationElement
+fn component<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, Component), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(move |input, ss| {
+        let (input1, ann) = annotations()(input, ss)?;
+        let (input2, c) = alt5(
+            map(
+                tuple3(keyword("start"), assign_method(), pattern_wrapper()),
+                |(_, c, p)| Component::Start(c, p),
+            ),
+            map(
+                tuple4(
+                    keyword("div"),
+                    sym("{"),
+                    many0(component_wrapper()),
+                    close("}"),
+                ),
+                |(_, _, c, _)| Component::Div(c),
+            ),
+            map(
+                tuple4(
+                    keyword("include"),
+                    literal(),
+                    opt(inherit()),
+                    opt(delimited(sym("{"), many0(component_wrapper()), close("}"))),
+                ),
+                |(_, u, i, c)| Component::Include(u, i, c.unwrap_or_default()),
+            ),
+            map(
+                tuple3(identifier(), assign_method(), pattern_wrapper()),
+                |(n, c, p)| Component::Define(n, c, p),
+            ),
+            map(annotation_element(), Component::Annotation),
+        )(input1, ss)?;
+        Ok((
+            input2,
+            match ann {
+                Some(a) => Component::Annotated(a, Box::new(c)),
+                None => c,
+            },
+        ))
+    })
+}
+
+fn component_wrapper<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, Component), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(move |input, ss| component::<N, L>()(input, ss))
+}
+
+// assignMethod ::= "=" | "|=" | "&="
+fn assign_method<'a, N: Node + 'a, L>()
+-> impl Fn(ParseInput<'a, N>, &mut StaticState<L>) -> Result<(ParseInput<'a, N>, Combine), ParseError>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    alt3(
+        value(sym("|="), Combine::Choice),
+        value(sym("&="), Combine::Interleave),
+        value(sym("="), Combine::Assign),
+    )
+}
+
+// Leading annotations: documentation comments followed by an optional annotation block.
+fn annotations<'a, N: Node + 'a, L>() -> impl Fn(
+    ParseInput<'a, N>,
+    &mut StaticState<L>,
+) -> Result<
+    (ParseInput<'a, N>, Option<Annotation>),
+    ParseError,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    map(
+        pair(
+            opt(documentation()),
+            opt(delimited(
+                sym("["),
+                many0(alt2(annotation_attribute(), annotation_element_item())),
+                close("]"),
+            )),
+        ),
+        |(d, a)| match (d, a) {
+            (None, None) => None,
+            (d, a) => Some(Annotation {
+                documentation: d.unwrap_or_default(),
+                items: a.unwrap_or_default(),
+            }),
+        },
+    )
+}
+
+// annotationElement ::= name "[" (annotationAttribute | annotationElement | literal)* "]"
+fn annotation_element<'a, N: Node + 'a, L>() -> Box<
+    dyn Fn(
+            ParseInput<'a, N>,
+            &mut StaticState<L>,
+        ) -> Result<(ParseInput<'a, N>, AnnotationElement), ParseError>
+        + 'a,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    Box::new(map(
+        tuple4(
+            any_name(),
+            sym("["),
+            many0(alt3(
+                annotation_attribute(),
+                annotation_element_item(),
+                map(literal(), AnnotationItem::Text),
+            )),
+            close("]"),
+        ),
+        |(name, _, content, _)| AnnotationElement { name, content },
+    ))
+}
+
+fn annotation_element_item<'a, N: Node + 'a, L>() -> impl Fn(
+    ParseInput<'a, N>,
+    &mut StaticState<L>,
+) -> Result<
+    (ParseInput<'a, N>, AnnotationItem),
+    ParseError,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    map(
+        Box::new(|input, ss| annotation_element::<N, L>()(input, ss)),
+        AnnotationItem::Element,
+    )
+}
+
+// annotationAttribute ::= name "=" literal
+fn annotation_attribute<'a, N: Node + 'a, L>() -> impl Fn(
+    ParseInput<'a, N>,
+    &mut StaticState<L>,
+) -> Result<
+    (ParseInput<'a, N>, AnnotationItem),
+    ParseError,
+>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    map(tuple3(any_name(), sym("="), literal()), |(n, _, v)| {
+        AnnotationItem::Attribute(n, v)
+    })
+}
+
+fn any_name<'a, N: Node + 'a, L>()
+-> impl Fn(ParseInput<'a, N>, &mut StaticState<L>) -> Result<(ParseInput<'a, N>, Name), ParseError>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError> + 'a,
+{
+    alt2(
+        map(cname(), |(p, l)| (Some(p), l)),
+        map(identifier_or_keyword(), |l| (None, l)),
+    )
+}
+
+// Lexical productions
+
+/// Skip whitespace and comments. Documentation comments ("##") are only skipped if requested.
+fn skip_space(mut input: &str, documentation: bool) -> &str {
+    loop {
+        input = input.trim_start_matches([' ', '\t', '\r', '\n']);
+        if input.starts_with('#') && (documentation || !input.starts_with("##")) {
+            input = input.find('\n').map_or("", |i| &input[i..]);
+        } else {
+            return input;
+        }
+    }
+}
+
+fn scan_ncname(s: &str) -> Option<(&str, &str)> {
+    let mut chars = s.char_indices();
+    match chars.next() {
+        Some((_, c)) if is_ncnamestartchar(&c) => {
+            let end = chars
+                .find(|(_, c)| !is_ncnamechar(c))
+                .map_or(s.len(), |(i, _)| i);
+            Some((&s[..end], &s[end..]))
+        }
+        _ => None,
+    }
+}
+
+fn sym<'a, N: Node, L>(
+    s: &'static str,
+) -> impl Fn(ParseInput<'a, N>, &mut StaticState<L>) -> Result<(ParseInput<'a, N>, ()), ParseError>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError>,
+{
+    move |(input, state), _ss| {
+        skip_space(input, false).strip_prefix(s).map_or_else(
+            || Err(ParseError::Combinator(format!("expected \"{}\"", s))),
+            |rest| Ok(((rest, state), ())),
+        )
+    }
+}
+
+/// A closing delimiter. Any documentation comments before the delimiter are discarded.
+fn close<'a, N: Node, L>(
+    s: &'static str,
+) -> impl Fn(ParseInput<'a, N>, &mut StaticState<L>) -> Result<(ParseInput<'a, N>, ()), ParseError>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError>,
+{
+    move |(input, state), _ss| {
+        skip_space(input, true).strip_prefix(s).map_or_else(
+            || Err(ParseError::Combinator(format!("expected \"{}\"", s))),
+            |rest| Ok(((rest, state), ())),
+        )
+    }
+}
+
+fn keyword<'a, N: Node, L>(
+    k: &'static str,
+) -> impl Fn(ParseInput<'a, N>, &mut StaticState<L>) -> Result<(ParseInput<'a, N>, ()), ParseError>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError>,
+{
+    move |(input, state), _ss| match skip_space(input, false).strip_prefix(k) {
+        Some(rest) if !rest.starts_with(|c: char| is_ncnamechar(&c) || c == ':') => {
+            Ok(((rest, state), ()))
+        }
+        _ => Err(ParseError::Combinator(format!(
+            "expected keyword \"{}\"",
+            k
+        ))),
+    }
+}
+
+// The connector between particles. Not to be confused with an assignment method ("|=", "&=").
+fn connector_op<'a, N: Node, L>()
+-> impl Fn(ParseInput<'a, N>, &mut StaticState<L>) -> Result<(ParseInput<'a, N>, char), ParseError>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError>,
+{
+    move |(input, state), _ss| {
+        let rest = skip_space(input, false);
+        let mut chars = rest.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), n) if matches!(c, ',' | '|' | '&') && n != Some('=') => {
+                Ok(((&rest[1..], state), c))
+            }
+            _ => Err(ParseError::Combinator(String::from("expected connector"))),
+        }
+    }
+}
+
+// identifier ::= (NCName - keyword) | "\" NCName
+fn identifier<'a, N: Node, L>()
+-> impl Fn(ParseInput<'a, N>, &mut StaticState<L>) -> Result<(ParseInput<'a, N>, String), ParseError>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError>,
+{
+    move |(input, state), _ss| {
+        let rest = skip_space(input, false);
+        let (escaped, r) = rest.strip_prefix('\\').map_or((false, rest), |r| (true, r));
+        match scan_ncname(r) {
+            Some((name, r2)) if !r2.starts_with(':') && (escaped || !KEYWORDS.contains(&name)) => {
+                Ok(((r2, state), name.to_string()))
+            }
+            _ => Err(ParseError::Combinator(String::from("expected identifier"))),
+        }
+    }
+}
+
+fn identifier_or_keyword<'a, N: Node, L>()
+-> impl Fn(ParseInput<'a, N>, &mut StaticState<L>) -> Result<(ParseInput<'a, N>, String), ParseError>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError>,
+{
+    move |(input, state), _ss| {
+        let rest = skip_space(input, false);
+        let r = rest.strip_prefix('\\').unwrap_or(rest);
+        match scan_ncname(r) {
+            Some((name, r2)) if !r2.starts_with(':') => Ok(((r2, state), name.to_string())),
+            _ => Err(ParseError::Combinator(String::from(
+                "expected identifier or keyword",
+            ))),
+        }
+    }
+}
+
+// CName ::= NCName ":" NCName
+fn cname<'a, N: Node, L>() -> impl Fn(
+    ParseInput<'a, N>,
+    &mut StaticState<L>,
+) -> Result<(ParseInput<'a, N>, (String, String)), ParseError>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError>,
+{
+    move |(input, state), _ss| {
+        let rest = skip_space(input, false);
+        scan_ncname(rest)
+            .and_then(|(p, r)| r.strip_prefix(':').map(|r1| (p, r1)))
+            .and_then(|(p, r1)| scan_ncname(r1).map(|(l, r2)| (p, l, r2)))
+            .map_or_else(
+                || Err(ParseError::Combinator(String::from("expected CName"))),
+                |(p, l, r2)| Ok(((r2, state), (p.to_string(), l.to_string()))),
+            )
+    }
+}
+
+// nsName ::= NCName ":*"
+fn nsname<'a, N: Node, L>()
+-> impl Fn(ParseInput<'a, N>, &mut StaticState<L>) -> Result<(ParseInput<'a, N>, String), ParseError>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError>,
+{
+    move |(input, state), _ss| {
+        let rest = skip_space(input, false);
+        scan_ncname(rest)
+            .and_then(|(p, r)| r.strip_prefix(":*").map(|r1| (p, r1)))
+            .map_or_else(
+                || Err(ParseError::Combinator(String::from("expected nsName"))),
+                |(p, r1)| Ok(((r1, state), p.to_string())),
+            )
+    }
+}
+
+// literal ::= literalSegment ("~" literalSegment)*
+fn literal<'a, N: Node, L>()
+-> impl Fn(ParseInput<'a, N>, &mut StaticState<L>) -> Result<(ParseInput<'a, N>, String), ParseError>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError>,
+{
+    move |(input, state), _ss| {
+        let mut rest = skip_space(input, false);
+        let mut result = String::new();
+        let mut first = true;
+        loop {
+            match literal_segment(rest) {
+                Some((seg, r)) => {
+                    result.push_str(seg);
+                    match skip_space(r, false).strip_prefix('~') {
+                        Some(r1) => {
+                            rest = skip_space(r1, false);
+                            first = false;
+                        }
+                        None => return Ok(((r, state), result)),
+                    }
+                }
+                None if first => {
+                    return Err(ParseError::Combinator(String::from("expected literal")));
+                }
+                None => {
+                    return Err(ParseError::NotWellFormed(String::from(
+                        "expected literal after \"~\"",
+                    )));
+                }
+            }
+        }
+    }
+}
+
+fn literal_segment(s: &str) -> Option<(&str, &str)> {
+    for delim in ["\"\"\"", "'''", "\"", "'"] {
+        if let Some(r) = s.strip_prefix(delim) {
+            let end = r.find(delim)?;
+            return Some((&r[..end], &r[end + delim.len()..]));
+        }
+    }
+    None
+}
+
+// One or more consecutive documentation comments, as lines of text.
+fn documentation<'a, N: Node, L>() -> impl Fn(
+    ParseInput<'a, N>,
+    &mut StaticState<L>,
+) -> Result<(ParseInput<'a, N>, Vec<String>), ParseError>
+where
+    L: FnMut(&NamespacePrefix) -> Result<NamespaceUri, ParseError>,
+{
+    move |(input, state), _ss| {
+        let mut rest = input;
+        let mut lines = vec![];
+        while let Some(r) = skip_space(rest, false).strip_prefix("##") {
+            let r = r.trim_start_matches('#');
+            let (line, r1) = r.find('\n').map_or((r, ""), |i| (&r[..i], &r[i..]));
+            let line = line.trim_end_matches('\r');
+            lines.push(line.strip_prefix(' ').unwrap_or(line).to_string());
+            rest = r1;
+        }
+        if lines.is_empty() {
+            Err(ParseError::Combinator(String::from(
+                "expected documentation",
+            )))
+        } else {
+            Ok(((rest, state), lines))
+        }
+    }
+}
+
+// Translation to the XML syntax
+
+/// The namespace and datatype library declarations in effect for a schema.
+struct Env {
+    namespaces: HashMap<String, String>,
+    default_namespace: String,
+    datatypes: HashMap<String, String>,
+    base: Option<Url>,
+}
+
+impl Env {
+    /// The inherited namespace is the one given by the including schema; for a top-level schema this is the null namespace.
+    fn new(decls: &[Decl], base: Option<Url>, inherited: &str) -> Self {
+        let uri = |u: &NamespaceUriLiteral| match u {
+            NamespaceUriLiteral::Uri(s) => s.clone(),
+            NamespaceUriLiteral::Inherit => inherited.to_string(),
+        };
+        let mut namespaces = HashMap::from([(String::from("xml"), String::from(XMLNS))]);
+        let mut datatypes = HashMap::from([(String::from("xsd"), String::from(XSDDATATYPES))]);
+        let mut default_namespace = inherited.to_string();
+        decls.iter().for_each(|d| match d {
+            Decl::Namespace(p, u) => {
+                namespaces.insert(p.clone(), uri(u));
+            }
+            Decl::DefaultNamespace(p, u) => {
+                default_namespace = uri(u);
+                if let Some(q) = p {
+                    namespaces.insert(q.clone(), uri(u));
+                }
+            }
+            Decl::Datatypes(p, u) => {
+                datatypes.insert(p.clone(), u.clone());
+            }
+        });
+        Env {
+            namespaces,
+            default_namespace,
+            datatypes,
+            base,
+        }
+    }
+    fn namespace(&self, p: &str) -> Result<String, Error> {
+        self.namespaces.get(p).cloned().ok_or_else(|| {
+            Error::new(
+                ErrorKind::ParseError,
+                format!("undeclared namespace prefix \"{}\"", p),
+            )
+        })
+    }
+    /// The namespace inherited by an external pattern or included grammar.
+    fn inherited(&self, inherit: &Option<String>) -> Result<String, Error> {
+        inherit
+            .as_ref()
+            .map_or_else(|| Ok(self.default_namespace.clone()), |p| self.namespace(p))
+    }
+    /// Find the datatype library and local name of a datatype name
+    fn datatype(&self, dt: &Option<Name>) -> Result<(String, String), Error> {
+        match dt {
+            None => Ok((String::new(), String::from("token"))),
+            Some((None, l)) => Ok((String::new(), l.clone())),
+            Some((Some(p), l)) => self.datatypes.get(p).map_or_else(
+                || {
+                    Err(Error::new(
+                        ErrorKind::ParseError,
+                        format!("undeclared datatypes prefix \"{}\"", p),
+                    ))
+                },
+                |lib| Ok((lib.clone(), l.clone())),
+            ),
+        }
+    }
+}
+
+struct Builder<'f, F: FnMut(&Url) -> Result<String, Error>> {
+    doc: RNode,
+    fetcher: &'f mut F,
+    // The schemas currently being translated, to detect recursive inclusion
+    open: Vec<Url>,
+}
+
+impl<F: FnMut(&Url) -> Result<String, Error>> Builder<'_, F> {
+    /// Create an element in the RELAX NG namespace, with any leading annotations.
+    fn element(&self, local: &str, ann: Option<&Annotation>, env: &Env) -> Result<RNode, Error> {
+        let mut e = self.doc.new_element(QName::new_from_parts(
+            NcName::try_from(local).unwrap(),
+            Some(RNGNS.clone()),
+        ))?;
+        if let Some(a) = ann {
+            self.annotate(&mut e, a, env)?
+        }
+        Ok(e)
+    }
+    fn annotate(&self, e: &mut RNode, ann: &Annotation, env: &Env) -> Result<(), Error> {
+        if !ann.documentation.is_empty() {
+            let mut d = self
+                .doc
+                .new_element(qname(ANNOTATIONNS, "documentation")?)?;
+            d.push(
+                self.doc
+                    .new_text(Rc::new(Value::from(ann.documentation.join("\n"))))?,
+            )?;
+            e.push(d)?
+        }
+        ann.items
+            .iter()
+            .try_for_each(|i| self.annotation_item(e, i, env))
+    }
+    fn annotation_item(&self, e: &mut RNode, i: &AnnotationItem, env: &Env) -> Result<(), Error> {
+        match i {
+            AnnotationItem::Attribute((p, l), v) => {
+                let ns = p
+                    .as_ref()
+                    .map_or_else(|| Ok(String::new()), |q| env.namespace(q))?;
+                e.add_attribute(
+                    self.doc
+                        .new_attribute(qname(&ns, l)?, Rc::new(Value::from(v.clone())))?,
+                )
+            }
+            AnnotationItem::Element(ae) => e.push(self.annotation_element(ae, env)?),
+            AnnotationItem::Text(t) => e.push(self.doc.new_text(Rc::new(Value::from(t.clone())))?),
+        }
+    }
+    fn annotation_element(&self, ae: &AnnotationElement, env: &Env) -> Result<RNode, Error> {
+        let ns = ae
+            .name
+            .0
+            .as_ref()
+            .map_or_else(|| Ok(String::new()), |p| env.namespace(p))?;
+        let mut e = self.doc.new_element(qname(&ns, &ae.name.1)?)?;
+        ae.content
+            .iter()
+            .try_for_each(|i| self.annotation_item(&mut e, i, env))?;
+        Ok(e)
+    }
+    fn attribute(&self, e: &RNode, name: &str, v: &str) -> Result<(), Error> {
+        e.add_attribute(self.doc.new_attribute(
+            QName::from_local_name(NcName::try_from(name).unwrap()),
+            Rc::new(Value::from(v)),
+        )?)
+    }
+    fn text(&self, e: &mut RNode, v: &str) -> Result<(), Error> {
+        e.push(self.doc.new_text(Rc::new(Value::from(v)))?)
+    }
+
+    /// Translate a pattern and append it to the parent, along with any following annotations.
+    fn push_pattern(&mut self, parent: &mut RNode, p: &Pattern, env: &Env) -> Result<(), Error> {
+        match p {
+            Pattern::Annotated(a, q, follow) => {
+                parent.push(self.pattern(q, Some(a), env)?)?;
+                follow
+                    .iter()
+                    .try_for_each(|f| parent.push(self.annotation_element(f, env)?))
+            }
+            _ => parent.push(self.pattern(p, None, env)?),
+        }
+    }
+    fn wrap(
+        &mut self,
+        local: &str,
+        ps: &[Pattern],
+        ann: Option<&Annotation>,
+        env: &Env,
+    ) -> Result<RNode, Error> {
+        let mut e = self.element(local, ann, env)?;
+        ps.iter()
+            .try_for_each(|p| self.push_pattern(&mut e, p, env))?;
+        Ok(e)
+    }
+    fn pattern(
+        &mut self,
+        p: &Pattern,
+        ann: Option<&Annotation>,
+        env: &Env,
+    ) -> Result<RNode, Error> {
+        match p {
+            Pattern::Element(nc, q) | Pattern::Attribute(nc, q) => {
+                let is_att = matches!(p, Pattern::Attribute(_, _));
+                let mut e = self.element(if is_att { "attribute" } else { "element" }, ann, env)?;
+                e.push(self.name_class(nc, is_att, env)?)?;
+                self.push_pattern(&mut e, q, env)?;
+                Ok(e)
+            }
+            Pattern::Group(v) => self.wrap("group", v, ann, env),
+            Pattern::Interleave(v) => self.wrap("interleave", v, ann, env),
+            Pattern::Choice(v) => self.wrap("choice", v, ann, env),
+            Pattern::Optional(q) => self.wrap("optional", std::slice::from_ref(q), ann, env),
+            Pattern::ZeroOrMore(q) => self.wrap("zeroOrMore", std::slice::from_ref(q), ann, env),
+            Pattern::OneOrMore(q) => self.wrap("oneOrMore", std::slice::from_ref(q), ann, env),
+            Pattern::List(q) => self.wrap("list", std::slice::from_ref(q), ann, env),
+            Pattern::Mixed(q) => self.wrap("mixed", std::slice::from_ref(q), ann, env),
+            Pattern::Ref(n) | Pattern::ParentRef(n) => {
+                let e = self.element(
+                    if matches!(p, Pattern::Ref(_)) {
+                        "ref"
+                    } else {
+                        "parentRef"
+                    },
+                    ann,
+                    env,
+                )?;
+                self.attribute(&e, "name", n)?;
+                Ok(e)
+            }
+            Pattern::Empty => self.element("empty", ann, env),
+            Pattern::Text => self.element("text", ann, env),
+            Pattern::NotAllowed => self.element("notAllowed", ann, env),
+            Pattern::Value(dt, v) => {
+                let (lib, t) = env.datatype(dt)?;
+                let mut e = self.element("value", ann, env)?;
+                self.attribute(&e, "type", &t)?;
+                self.attribute(&e, "datatypeLibrary", &lib)?;
+                // The namespace used to resolve QName values
+                self.attribute(&e, "ns", &env.default_namespace)?;
+                self.text(&mut e, v)?;
+                Ok(e)
+            }
+            Pattern::Data(dt, params, except) => {
+                let (lib, t) = env.datatype(&Some(dt.clone()))?;
+                let mut e = self.element("data", ann, env)?;
+                self.attribute(&e, "type", &t)?;
+                self.attribute(&e, "datatypeLibrary", &lib)?;
+                for (n, v) in params {
+                    let mut pe = self.element("param", None, env)?;
+                    self.attribute(&pe, "name", n)?;
+                    self.text(&mut pe, v)?;
+                    e.push(pe)?
+                }
+                if let Some(x) = except {
+                    let ex = self.wrap("except", std::slice::from_ref(x), None, env)?;
+                    e.push(ex)?
+                }
+                Ok(e)
+            }
+            Pattern::External(href, inherit) => {
+                let mut e = self.external(href, inherit, env)?;
+                if let Some(a) = ann {
+                    self.annotate(&mut e, a, env)?
+                }
+                Ok(e)
+            }
+            Pattern::Grammar(comps) => {
+                let mut e = self.element("grammar", ann, env)?;
+                comps
+                    .iter()
+                    .try_for_each(|c| self.component(&mut e, c, None, env))?;
+                Ok(e)
+            }
+            Pattern::Annotated(a, q, _) => {
+                // Following annotations are only retained when the pattern has a parent
+                let merged = ann.map_or_else(
+                    || a.clone(),
+                    |b| {
+                        let mut m = b.clone();
+                        m.documentation.extend(a.documentation.iter().cloned());
+                        m.items.extend(a.items.iter().cloned());
+                        m
+                    },
+                );
+                self.pattern(q, Some(&merged), env)
+            }
+        }
+    }
+
+    fn name_class(&self, nc: &NameClass, attribute: bool, env: &Env) -> Result<RNode, Error> {
+        match nc {
+            NameClass::Name((p, l)) => {
+                // Unprefixed attribute names are in the null namespace
+                let ns = match p {
+                    Some(q) => env.namespace(q)?,
+                    None if attribute => String::new(),
+                    None => env.default_namespace.clone(),
+                };
+                let mut e = self.element("name", None, env)?;
+                self.attribute(&e, "ns", &ns)?;
+                self.text(&mut e, l)?;
+                Ok(e)
+            }
+            NameClass::NsName(p, except) => {
+                let mut e = self.element("nsName", None, env)?;
+                self.attribute(&e, "ns", &env.namespace(p)?)?;
+                if let Some(x) = except {
+                    let mut ex = self.element("except", None, env)?;
+                    ex.push(self.name_class(x, attribute, env)?)?;
+                    e.push(ex)?
+                }
+                Ok(e)
+            }
+            NameClass::AnyName(except) => {
+                let mut e = self.element("anyName", None, env)?;
+                if let Some(x) = except {
+                    let mut ex = self.element("except", None, env)?;
+                    ex.push(self.name_class(x, attribute, env)?)?;
+                    e.push(ex)?
+                }
+                Ok(e)
+            }
+            NameClass::Choice(v) => {
+                let mut e = self.element("choice", None, env)?;
+                v.iter()
+                    .try_for_each(|n| e.push(self.name_class(n, attribute, env)?))?;
+                Ok(e)
+            }
+        }
+    }
+
+    fn component(
+        &mut self,
+        parent: &mut RNode,
+        c: &Component,
+        ann: Option<&Annotation>,
+        env: &Env,
+    ) -> Result<(), Error> {
+        match c {
+            Component::Start(combine, p) | Component::Define(_, combine, p) => {
+                let mut e = if let Component::Define(n, _, _) = c {
+                    let d = self.element("define", ann, env)?;
+                    self.attribute(&d, "name", n)?;
+                    d
+                } else {
+                    self.element("start", ann, env)?
+                };
+                match combine {
+                    Combine::Assign => {}
+                    Combine::Choice => self.attribute(&e, "combine", "choice")?,
+                    Combine::Interleave => self.attribute(&e, "combine", "interleave")?,
+                }
+                self.push_pattern(&mut e, p, env)?;
+                parent.push(e)
+            }
+            Component::Div(comps) => {
+                let mut e = self.element("div", ann, env)?;
+                comps
+                    .iter()
+                    .try_for_each(|d| self.component(&mut e, d, None, env))?;
+                parent.push(e)
+            }
+            Component::Include(href, inherit, body) => {
+                let e = self.include(href, inherit, body, ann, env)?;
+                parent.push(e)
+            }
+            Component::Annotation(ae) => parent.push(self.annotation_element(ae, env)?),
+            Component::Annotated(a, d) => self.component(parent, d, Some(a), env),
+        }
+    }
+
+    fn fetch(&mut self, href: &str, env: &Env) -> Result<(Url, String), Error> {
+        let url = env
+            .base
+            .as_ref()
+            .map_or_else(|| Url::parse(href), |b| b.join(href))
+            .map_err(|_| {
+                Error::new(
+                    ErrorKind::TypeError,
+                    format!("unable to resolve URL \"{}\"", href),
+                )
+            })?;
+        if self.open.contains(&url) {
+            return Err(Error::new(
+                ErrorKind::ParseError,
+                format!("schema \"{}\" refers to itself", url),
+            ));
+        }
+        let text = (self.fetcher)(&url)?;
+        Ok((url, text))
+    }
+
+    /// The pattern of an external schema is copied in place of the reference.
+    fn external(
+        &mut self,
+        href: &str,
+        inherit: &Option<String>,
+        env: &Env,
+    ) -> Result<RNode, Error> {
+        let (url, text) = self.fetch(href, env)?;
+        let (decls, p) = parse_schema(&text)?;
+        let sub = Env::new(&decls, Some(url.clone()), &env.inherited(inherit)?);
+        self.open.push(url);
+        let result = self.pattern(&p, None, &sub);
+        self.open.pop();
+        result
+    }
+
+    /// An included grammar becomes a div.
+    /// Definitions in the body of the include replace those of the same name in the included grammar (section 4.7 of the RELAX NG specification).
+    fn include(
+        &mut self,
+        href: &str,
+        inherit: &Option<String>,
+        body: &[Component],
+        ann: Option<&Annotation>,
+        env: &Env,
+    ) -> Result<RNode, Error> {
+        let (url, text) = self.fetch(href, env)?;
+        let (decls, p) = parse_schema(&text)?;
+        let comps = match p {
+            Pattern::Grammar(c) => c,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::ParseError,
+                    format!("included schema \"{}\" is not a grammar", url),
+                ));
+            }
+        };
+        let sub = Env::new(&decls, Some(url.clone()), &env.inherited(inherit)?);
+
+        let mut defines = HashSet::new();
+        let mut start = false;
+        overrides(body, &mut defines, &mut start);
+
+        let mut e = self.element("div", ann, env)?;
+        self.open.push(url);
+        let result = comps
+            .iter()
+            .filter_map(|c| prune(c, &defines, start))
+            .try_for_each(|c| self.component(&mut e, &c, None, &sub));
+        self.open.pop();
+        result?;
+        body.iter()
+            .try_for_each(|c| self.component(&mut e, c, None, env))?;
+        Ok(e)
+    }
+}
+
+// Find the definitions, and start, given in the body of an include.
+fn overrides(comps: &[Component], defines: &mut HashSet<String>, start: &mut bool) {
+    comps.iter().for_each(|c| match c {
+        Component::Start(_, _) => *start = true,
+        Component::Define(n, _, _) => {
+            defines.insert(n.clone());
+        }
+        Component::Div(d) => overrides(d, defines, start),
+        Component::Annotated(_, d) => overrides(std::slice::from_ref(d.as_ref()), defines, start),
+        _ => {}
+    })
+}
+
+// Remove overridden components from an included grammar.
+fn prune(c: &Component, defines: &HashSet<String>, start: bool) -> Option<Component> {
+    match c {
+        Component::Start(_, _) if start => None,
+        Component::Define(n, _, _) if defines.contains(n) => None,
+        Component::Div(d) => Some(Component::Div(
+            d.iter().filter_map(|e| prune(e, defines, start)).collect(),
+        )),
+        Component::Annotated(a, d) => {
+            prune(d, defines, start).map(|e| Component::Annotated(a.clone(), Box::new(e)))
+        }
+        _ => Some(c.clone()),
+    }
+}
+
+fn qname(ns: &str, local: &str) -> Result<QName, Error> {
+    let l = NcName::try_from(local)
+        .map_err(|_| Error::new(ErrorKind::ParseError, format!("invalid name \"{}\"", local)))?;
+    if ns.is_empty() {
+        Ok(QName::from_local_name(l))
+    } else {
+        Ok(QName::new_from_parts(
+            l,
+            Some(NamespaceUri::try_from(ns).map_err(|_| {
+                Error::new(
+                    ErrorKind::ParseError,
+                    format!("invalid namespace URI \"{}\"", ns),
+                )
+            })?),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_fetch(_: &Url) -> Result<String, Error> {
+        Err(Error::new(ErrorKind::Unknown, "no fetcher"))
+    }
+
+    fn root(doc: &RNode) -> RNode {
+        doc.child_iter().next().expect("no document element")
+    }
+
+    #[test]
+    fn compact_element() {
+        let doc =
+            parse_compact("element foo { empty }", None, no_fetch).expect("unable to parse schema");
+        let r = root(&doc);
+        assert_eq!(r.name().unwrap().local_name().to_string(), "element");
+        let mut c = r.child_iter();
+        let n = c.next().unwrap();
+        assert_eq!(n.name().unwrap().local_name().to_string(), "name");
+        assert_eq!(n.to_string(), "foo");
+        assert_eq!(
+            c.next().unwrap().name().unwrap().local_name().to_string(),
+            "empty"
+        );
+    }
+
+    #[test]
+    fn compact_grammar() {
+        let doc = parse_compact(
+            "default namespace = \"urn:test\"\nstart = doc\ndoc = element doc { attribute id { xsd:ID }?, item* }\nitem |= element item { text }",
+            None,
+            no_fetch,
+        )
+        .expect("unable to parse schema");
+        let r = root(&doc);
+        assert_eq!(r.name().unwrap().local_name().to_string(), "grammar");
+        let c: Vec<RNode> = r.child_iter().collect();
+        assert_eq!(c.len(), 3);
+        assert_eq!(c[0].name().unwrap().local_name().to_string(), "start");
+        assert_eq!(
+            c[2].get_attribute(&QName::from_local_name(
+                NcName::try_from("combine").unwrap()
+            ))
+            .to_string(),
+            "choice"
+        );
+    }
+
+    #[test]
+    fn compact_mixed_connectors() {
+        assert!(parse_compact("element a { text, empty | text }", None, no_fetch).is_err())
+    }
+
+    #[test]
+    fn compact_datatype_params() {
+        let doc = parse_compact(
+            "element n { xsd:integer { minInclusive = \"1\" maxInclusive = \"10\" } }",
+            None,
+            no_fetch,
+        )
+        .expect("unable to parse schema");
+        let data = root(&doc).child_iter().nth(1).unwrap();
+        assert_eq!(data.name().unwrap().local_name().to_string(), "data");
+        assert_eq!(
+            data.get_attribute(&QName::from_local_name(
+                NcName::try_from("datatypeLibrary").unwrap()
+            ))
+            .to_string(),
+            XSDDATATYPES
+        );
+        assert_eq!(data.child_iter().count(), 2);
+    }
+
+    #[test]
+    fn compact_include() {
+        let doc = parse_compact(
+            "include \"inc.rnc\" { start = element b { empty } }",
+            Some(Url::parse("file:///schemas/main.rnc").unwrap()),
+            |u: &Url| {
+                assert_eq!(u.as_str(), "file:///schemas/inc.rnc");
+                Ok(String::from(
+                    "## The start\nstart = element a { empty }\nx = text",
+                ))
+            },
+        )
+        .expect("unable to parse schema");
+        let div = root(&doc).child_iter().next().unwrap();
+        assert_eq!(div.name().unwrap().local_name().to_string(), "div");
+        // The included start is replaced, the define remains
+        let c: Vec<RNode> = div.child_iter().collect();
+        assert_eq!(c.len(), 2);
+        assert_eq!(c[0].name().unwrap().local_name().to_string(), "define");
+        assert_eq!(c[1].name().unwrap().local_name().to_string(), "start");
+    }
+}
@@ -1,28 +1,271 @@
+//! # RELAX NG validation
+//!
+//! Validate a document against a schema written in the RELAX NG XML syntax or compact syntax.
+//! The schema is simplified and the document is checked using pattern derivatives.
+
+mod compact;
 mod derive;
 mod pattern;
 
-use crate::trees::smite::RNode;
-use crate::validators::relaxng::derive::{derive, is_nullable};
+pub use compact::parse_compact;
+
+use crate::item::{Node, NodeType};
 use crate::validators::ValidationError;
+use crate::validators::relaxng::derive::{child_deriv, is_nullable};
+use crate::validators::relaxng::pattern::Grammar;
+use crate::xdmerror::Error;
+use url::Url;
 
-pub fn validate_relaxng(doc: &RNode, schema: &RNode) -> Result<(), ValidationError> {
-    let schemapattern = pattern::prepare(schema);
-
-    match schemapattern {
-        Err(_) => Err(ValidationError::SchemaError(
-            "Pattern Prep Error".to_string(),
-        )),
-        Ok((pat, refs)) => {
-            if is_nullable(derive(doc, pat, &refs)) {
-                Ok(())
-            } else {
-                Err(ValidationError::DocumentError("Some Error".to_string()))
-            }
-        }
+/// Validate a document against a schema written in the RELAX NG XML syntax.
+pub fn validate_relaxng<N: Node>(doc: &N, schema: &N) -> Result<(), ValidationError> {
+    let g = pattern::prepare(schema).map_err(|e| ValidationError::SchemaError(e.message))?;
+    validate_grammar(doc, &g)
+}
+
+/// Validate a document against a schema written in the RELAX NG compact syntax.
+/// External patterns and included grammars are retrieved using the fetcher.
+pub fn validate_relaxng_compact<N, F>(
+    doc: &N,
+    schema: &str,
+    base: Option<Url>,
+    fetcher: F,
+) -> Result<(), ValidationError>
+where
+    N: Node,
+    F: FnMut(&Url) -> Result<String, Error>,
+{
+    let sch = parse_compact(schema, base, fetcher)
+        .map_err(|e| ValidationError::SchemaError(e.message))?;
+    let g = pattern::prepare(&sch).map_err(|e| ValidationError::SchemaError(e.message))?;
+    validate_grammar(doc, &g)
+}
+
+fn validate_grammar<N: Node>(doc: &N, g: &Grammar) -> Result<(), ValidationError> {
+    let top = match doc.node_type() {
+        NodeType::Document => doc
+            .child_iter()
+            .find(|c| c.node_type() == NodeType::Element)
+            .ok_or_else(|| {
+                ValidationError::DocumentError("document has no document element".to_string())
+            })?,
+        _ => doc.clone(),
+    };
+    if is_nullable(child_deriv(g.start.clone(), &top, g), g) {
+        Ok(())
+    } else {
+        Err(ValidationError::DocumentError(format!(
+            "element \"{}\" is not valid against the RELAX NG schema",
+            top.name().map_or_else(String::new, |qn| qn.to_string())
+        )))
     }
 }
 
-//TODO:
-//Patterns split in two
-//Pattern and elementrefs hashmap
-//adjust validator to do lookups against hashmap when it encounters refs
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{ParseError, xml};
+    use crate::trees::smite::RNode;
+    use crate::xdmerror::ErrorKind;
+
+    fn parse(s: &str) -> RNode {
+        xml::parse(
+            RNode::new_document(),
+            s,
+            Some(|_: &_| Err(ParseError::MissingNameSpace)),
+        )
+        .expect("unable to parse document")
+    }
+
+    fn no_fetch(_: &Url) -> Result<String, Error> {
+        Err(Error::new(ErrorKind::Unknown, "no fetcher"))
+    }
+
+    fn valid(schema: &str, doc: &str) -> bool {
+        match validate_relaxng_compact(&parse(doc), schema, None, no_fetch) {
+            Ok(()) => true,
+            Err(ValidationError::DocumentError(_)) => false,
+            Err(ValidationError::SchemaError(e)) => panic!("schema error: {}", e),
+        }
+    }
+
+    const ADDRESSBOOK: &str = r#"element addressBook {
+  element card {
+    attribute id { text }?,
+    element name { text },
+    element email { text }
+  }*
+}"#;
+
+    #[test]
+    fn relaxng_compact_valid() {
+        assert!(valid(
+            ADDRESSBOOK,
+            "<addressBook><card id='a'><name>John</name><email>js@example.com</email></card>\n<card><name>Fred</name><email>fb@example.net</email></card></addressBook>"
+        ));
+        assert!(valid(ADDRESSBOOK, "<addressBook/>"));
+    }
+
+    #[test]
+    fn relaxng_compact_invalid() {
+        // Out of order
+        assert!(!valid(
+            ADDRESSBOOK,
+            "<addressBook><card><email>js@example.com</email><name>John</name></card></addressBook>"
+        ));
+        // Unexpected attribute
+        assert!(!valid(
+            ADDRESSBOOK,
+            "<addressBook><card ref='x'><name>John</name><email>js@example.com</email></card></addressBook>"
+        ));
+        // Text where only elements are allowed
+        assert!(!valid(ADDRESSBOOK, "<addressBook>cards</addressBook>"));
+        // Wrong document element
+        assert!(!valid(ADDRESSBOOK, "<card/>"));
+    }
+
+    #[test]
+    fn relaxng_compact_grammar() {
+        let schema = r#"default namespace = "urn:test"
+start = doc
+doc = element doc { item+ }
+item = element item { attribute n { text }, (text | item)* }"#;
+        assert!(valid(
+            schema,
+            "<doc xmlns='urn:test'><item n='1'>a<item n='2'/>b</item><item n='3'/></doc>"
+        ));
+        assert!(!valid(schema, "<doc><item n='1'/></doc>"));
+        assert!(!valid(schema, "<doc xmlns='urn:test'><item/></doc>"));
+    }
+
+    #[test]
+    fn relaxng_compact_interleave() {
+        let schema = "element a { element b { empty } & element c { empty }* & text }";
+        assert!(valid(schema, "<a><c/>x<b/><c/></a>"));
+        assert!(!valid(schema, "<a><c/><c/></a>"));
+        assert!(!valid(schema, "<a><b/><b/></a>"));
+    }
+
+    #[test]
+    fn relaxng_compact_name_classes() {
+        let schema = r#"namespace x = "urn:x"
+start = element * - x:* { attribute x:* { text }*, anyElement* }
+anyElement = element * { any* }
+any = anyElement | attribute * { text } | text"#;
+        assert!(valid(
+            schema,
+            "<a xmlns:x='urn:x' x:b='1'><b c='2'>t</b></a>"
+        ));
+        assert!(!valid(schema, "<x:a xmlns:x='urn:x'/>"));
+        assert!(!valid(schema, "<a b='1'/>"));
+    }
+
+    #[test]
+    fn relaxng_compact_list() {
+        let schema = "element a { list { xsd:integer+ } }";
+        assert!(valid(schema, "<a> 1 2\n3 </a>"));
+        assert!(!valid(schema, "<a>1 two</a>"));
+        assert!(!valid(schema, "<a> </a>"));
+    }
+
+    #[test]
+    fn relaxng_compact_schema_error() {
+        assert!(matches!(
+            validate_relaxng_compact(&parse("<a/>"), "start = b", None, no_fetch),
+            Err(ValidationError::SchemaError(_))
+        ));
+        assert!(matches!(
+            validate_relaxng_compact(
+                &parse("<a/>"),
+                "start = a\na = a | element a { empty }",
+                None,
+                no_fetch
+            ),
+            Err(ValidationError::SchemaError(_))
+        ));
+    }
+
+    #[test]
+    fn relaxng_xml_syntax() {
+        let schema = parse(
+            r#"<grammar xmlns="http://relaxng.org/ns/structure/1.0" xmlns:a="http://relaxng.org/ns/compatibility/annotations/1.0">
+  <a:documentation>A list of items</a:documentation>
+  <start><ref name="list"/></start>
+  <define name="list">
+    <element name="list">
+      <oneOrMore><ref name="item"/></oneOrMore>
+    </element>
+  </define>
+  <define name="item">
+    <element name="item"><optional><attribute name="n"/></optional><text/></element>
+  </define>
+  <define name="item" combine="choice">
+    <element name="sep"><empty/></element>
+  </define>
+</grammar>"#,
+        );
+        assert!(
+            validate_relaxng(
+                &parse("<list><item n='1'>a</item><sep/><item/></list>"),
+                &schema
+            )
+            .is_ok()
+        );
+        assert!(matches!(
+            validate_relaxng(&parse("<list/>"), &schema),
+            Err(ValidationError::DocumentError(_))
+        ));
+    }
+
+    #[test]
+    fn relaxng_datatype_params() {
+        let schema = r#"element a {
+  attribute code { xsd:string { pattern = "[A-Z]{2}[0-9]+" maxLength = "5" } },
+  xsd:date { minInclusive = "2000-01-01" }
+}"#;
+        assert!(valid(schema, "<a code='AB12'>2024-02-29</a>"));
+        assert!(!valid(schema, "<a code='ab12'>2024-02-29</a>"));
+        assert!(!valid(schema, "<a code='AB1234'>2024-02-29</a>"));
+        assert!(!valid(schema, "<a code='AB12'>2023-02-29</a>"));
+        assert!(!valid(schema, "<a code='AB12'>1999-12-31</a>"));
+        // The built-in datatypes have no parameters
+        assert!(matches!(
+            validate_relaxng_compact(
+                &parse("<a/>"),
+                "element a { token { length = \"1\" } }",
+                None,
+                no_fetch
+            ),
+            Err(ValidationError::SchemaError(_))
+        ));
+    }
+
+    #[test]
+    fn relaxng_typed_value() {
+        let schema =
+            r#"element a { attribute n { xsd:integer "10" }, (xsd:date "2024-01-01Z" | "a  b") }"#;
+        assert!(valid(schema, "<a n='+010'>2024-01-01+00:00</a>"));
+        assert!(valid(schema, "<a n='10'> a b </a>"));
+        assert!(!valid(schema, "<a n='10.0'>2024-01-01Z</a>"));
+        assert!(!valid(schema, "<a n='10'>2024-01-02Z</a>"));
+        assert!(!valid(schema, "<a n='10'>ab</a>"));
+    }
+
+    #[test]
+    fn relaxng_data_except() {
+        let schema = r#"element a { xsd:integer - ("0" | xsd:integer { minInclusive = "100" }) }"#;
+        assert!(valid(schema, "<a>42</a>"));
+        assert!(!valid(schema, "<a>0</a>"));
+        assert!(!valid(schema, "<a>100</a>"));
+        assert!(!valid(schema, "<a>x</a>"));
+        // An except element must contain a pattern
+        let sch = parse(
+            r#"<element name="a" xmlns="http://relaxng.org/ns/structure/1.0" datatypeLibrary="http://www.w3.org/2001/XMLSchema-datatypes">
+  <data type="integer"><except/></data>
+</element>"#,
+        );
+        assert!(matches!(
+            validate_relaxng(&parse("<a>1</a>"), &sch),
+            Err(ValidationError::SchemaError(_))
+        ));
+    }
+}
@@ -1,561 +1,613 @@
-use std::collections::HashMap;
-use crate::xdmerror::Error;
-use crate::item::{Node, Item};
-use crate::trees::smite::RNode;
-use crate::parser::xml::{parse as xmlparse};
-use crate::transform::context::{StaticContextBuilder};
-use crate::xslt::from_document;
-
+//! Simplification of a RELAX NG schema.
+//!
+//! A schema in the XML syntax is converted to a [Grammar]: a start [Pattern] together with the
+//! definitions that it refers to. This follows section 4 of the RELAX NG specification:
+//! annotations are dropped, the ns and datatypeLibrary attributes are inherited,
+//! the abbreviated patterns (optional, zeroOrMore, mixed) are expanded,
+//! and the components of a grammar are combined according to their combine attributes.
+//! The definitions of nested grammars are kept apart by qualifying their names with the grammar they belong to.
+//!
+//! The include and externalRef elements are not supported in the XML syntax,
+//! since the compact syntax parser has already copied external schemas into the result.
+
+use std::collections::{HashMap, HashSet};
+
+use qualname::QName;
+
+use crate::item::{Node, NodeType};
+use crate::validators::datatypes::{self, XSDDATATYPES};
+use crate::xdmerror::{Error, ErrorKind};
+
+pub(crate) const RNGNS: &str = "http://relaxng.org/ns/structure/1.0";
+
+/// A datatype: the datatype library URI and the name of the datatype.
+pub(crate) type Datatype = (String, String);
+/// A datatype parameter: the name of the parameter and its value.
 pub(crate) type Param = (String, String);
 
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum NameClass {
+    AnyName,
+    AnyNameExcept(Box<NameClass>),
+    /// A namespace URI and a local name
+    Name(String, String),
+    NsName(String),
+    NsNameExcept(String, Box<NameClass>),
+    Choice(Box<NameClass>, Box<NameClass>),
+}
+
+impl NameClass {
+    /// Is the name a member of the name class?
+    pub(crate) fn contains(&self, qn: &QName) -> bool {
+        let ns = qn
+            .namespace_uri()
+            .map_or_else(String::new, |u| u.to_string());
+        self.contains_name(&ns, &qn.local_name().to_string())
+    }
+    fn contains_name(&self, ns: &str, local: &str) -> bool {
+        match self {
+            NameClass::AnyName => true,
+            NameClass::AnyNameExcept(nc) => !nc.contains_name(ns, local),
+            NameClass::Name(n, l) => n == ns && l == local,
+            NameClass::NsName(n) => n == ns,
+            NameClass::NsNameExcept(n, nc) => n == ns && !nc.contains_name(ns, local),
+            NameClass::Choice(nc1, nc2) => {
+                nc1.contains_name(ns, local) || nc2.contains_name(ns, local)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Pattern {
+    Empty,
+    NotAllowed,
+    Text,
+    Choice(Box<Pattern>, Box<Pattern>),
+    Interleave(Box<Pattern>, Box<Pattern>),
+    Group(Box<Pattern>, Box<Pattern>),
+    OneOrMore(Box<Pattern>),
+    List(Box<Pattern>),
+    Data(Datatype, Vec<Param>),
+    DataExcept(Datatype, Vec<Param>, Box<Pattern>),
+    Value(Datatype, String),
+    Attribute(NameClass, Box<Pattern>),
+    Element(NameClass, Box<Pattern>),
+    /// A reference to a definition, by its qualified name
+    Ref(String),
+    /// Only used during validation
+    After(Box<Pattern>, Box<Pattern>),
+}
+
+/// A simplified schema.
+#[derive(Clone, Debug)]
+pub(crate) struct Grammar {
+    pub(crate) start: Pattern,
+    pub(crate) defines: HashMap<String, Pattern>,
+}
+
+/// Simplify a schema in the RELAX NG XML syntax. The argument may be the document or its document element.
+pub(crate) fn prepare<N: Node>(schema: &N) -> Result<Grammar, Error> {
+    let top = match schema.node_type() {
+        NodeType::Document => schema
+            .child_iter()
+            .find(|c| c.node_type() == NodeType::Element)
+            .ok_or_else(|| schema_error("schema has no document element"))?,
+        _ => schema.clone(),
+    };
+    let mut s = Simplifier {
+        defines: HashMap::new(),
+        grammars: 0,
+    };
+    let ctx = Context {
+        ns: String::new(),
+        datatype_library: String::new(),
+        grammars: vec![],
+    };
+    if !is_rng(&top) {
+        return Err(schema_error(
+            "document element is not in the RELAX NG namespace",
+        ));
+    }
+    let start = s.pattern(&top, &ctx)?;
+    let g = Grammar {
+        start,
+        defines: s.defines,
+    };
+    check_refs(&g)?;
+    Ok(g)
+}
+
+// The inherited attributes, and the grammars that enclose a pattern.
+#[derive(Clone)]
+struct Context {
+    ns: String,
+    datatype_library: String,
+    grammars: Vec<usize>,
+}
+
+impl Context {
+    fn inherit<N: Node>(&self, e: &N) -> Context {
+        let mut ctx = self.clone();
+        if let Some(ns) = attribute(e, "ns") {
+            ctx.ns = ns
+        }
+        if let Some(dl) = attribute(e, "datatypeLibrary") {
+            ctx.datatype_library = dl
+        }
+        ctx
+    }
+    // The qualified name of a definition in the grammar at the given depth from the innermost grammar
+    fn define_name(&self, depth: usize, name: &str) -> Result<String, Error> {
+        self.grammars
+            .len()
+            .checked_sub(depth + 1)
+            .map(|i| format!("{}:{}", self.grammars[i], name))
+            .ok_or_else(|| {
+                schema_error(format!(
+                    "reference to \"{}\" is not inside a {}grammar",
+                    name,
+                    if depth == 0 { "" } else { "nested " }
+                ))
+            })
+    }
+}
+
+// The components of a grammar, before they are combined: the combine attribute and the pattern.
+type Components = Vec<(Option<String>, Pattern)>;
+
+struct Simplifier {
+    defines: HashMap<String, Pattern>,
+    grammars: usize,
+}
+
+impl Simplifier {
+    fn pattern<N: Node>(&mut self, e: &N, ctx: &Context) -> Result<Pattern, Error> {
+        let ctx = ctx.inherit(e);
+        let local = local_name(e);
+        match local.as_str() {
+            "element" | "attribute" => {
+                let (nc, content) = self.name_and_content(e, &ctx, local == "attribute")?;
+                if local == "element" {
+                    Ok(Pattern::Element(nc, Box::new(self.group(&content, &ctx)?)))
+                } else if content.is_empty() {
+                    Ok(Pattern::Attribute(nc, Box::new(Pattern::Text)))
+                } else {
+                    Ok(Pattern::Attribute(
+                        nc,
+                        Box::new(self.group(&content, &ctx)?),
+                    ))
+                }
+            }
+            "group" => self.group(&children(e), &ctx),
+            "interleave" => self.fold(&children(e), &ctx, Pattern::Interleave),
+            "choice" => self.fold(&children(e), &ctx, Pattern::Choice),
+            "optional" => Ok(Pattern::Choice(
+                Box::new(self.group(&children(e), &ctx)?),
+                Box::new(Pattern::Empty),
+            )),
+            "zeroOrMore" => Ok(Pattern::Choice(
+                Box::new(Pattern::OneOrMore(Box::new(
+                    self.group(&children(e), &ctx)?,
+                ))),
+                Box::new(Pattern::Empty),
+            )),
+            "oneOrMore" => Ok(Pattern::OneOrMore(Box::new(
+                self.group(&children(e), &ctx)?,
+            ))),
+            "mixed" => Ok(Pattern::Interleave(
+                Box::new(self.group(&children(e), &ctx)?),
+                Box::new(Pattern::Text),
+            )),
+            "list" => Ok(Pattern::List(Box::new(self.group(&children(e), &ctx)?))),
+            "empty" => Ok(Pattern::Empty),
+            "text" => Ok(Pattern::Text),
+            "notAllowed" => Ok(Pattern::NotAllowed),
+            "ref" => Ok(Pattern::Ref(
+                ctx.define_name(0, required(e, "name")?.trim())?,
+            )),
+            "parentRef" => Ok(Pattern::Ref(
+                ctx.define_name(1, required(e, "name")?.trim())?,
+            )),
+            "value" => {
+                // The default datatype is the built-in token datatype
+                let dt = match attribute(e, "type") {
+                    Some(t) => datatype(&ctx, t.trim())?,
+                    None => (String::new(), String::from("token")),
+                };
+                Ok(Pattern::Value(dt, e.to_string()))
+            }
+            "data" => {
+                let dt = datatype(&ctx, required(e, "type")?.trim())?;
+                let mut params = vec![];
+                let mut except = None;
+                for c in children(e) {
+                    match local_name(&c).as_str() {
+                        "param" => {
+                            params.push((required(&c, "name")?.trim().to_string(), c.to_string()))
+                        }
+                        "except" => {
+                            except =
+                                Some(self.fold(&children(&c), &ctx.inherit(&c), Pattern::Choice)?)
+                        }
+                        other => {
+                            return Err(schema_error(format!(
+                                "unexpected element \"{}\" in data pattern",
+                                other
+                            )));
+                        }
+                    }
+                }
+                check_params(&dt, &params)?;
+                Ok(match except {
+                    Some(p) => Pattern::DataExcept(dt, params, Box::new(p)),
+                    None => Pattern::Data(dt, params),
+                })
+            }
+            "grammar" => self.grammar(e, &ctx),
+            "externalRef" | "include" => Err(schema_error(format!(
+                "{} is not supported in the XML syntax",
+                local
+            ))),
+            other => Err(schema_error(format!("unexpected element \"{}\"", other))),
+        }
+    }
+
+    // A sequence of patterns is an implicit group
+    fn group<N: Node>(&mut self, c: &[N], ctx: &Context) -> Result<Pattern, Error> {
+        self.fold(c, ctx, Pattern::Group)
+    }
+
+    fn fold<N: Node, F>(&mut self, c: &[N], ctx: &Context, f: F) -> Result<Pattern, Error>
+    where
+        F: Fn(Box<Pattern>, Box<Pattern>) -> Pattern,
+    {
+        let mut result: Option<Pattern> = None;
+        for p in c {
+            let q = self.pattern(p, ctx)?;
+            result = Some(match result {
+                None => q,
+                Some(r) => f(Box::new(r), Box::new(q)),
+            })
+        }
+        result.ok_or_else(|| schema_error("a pattern is missing"))
+    }
+
+    // The name class of an element or attribute pattern, and the elements that give its content
+    fn name_and_content<N: Node>(
+        &mut self,
+        e: &N,
+        ctx: &Context,
+        is_attribute: bool,
+    ) -> Result<(NameClass, Vec<N>), Error> {
+        let mut c = children(e);
+        match attribute(e, "name") {
+            Some(n) => {
+                // An unqualified attribute name is in no namespace, unless the pattern has an ns attribute
+                let ns = if is_attribute {
+                    attribute(e, "ns").unwrap_or_default()
+                } else {
+                    ctx.ns.clone()
+                };
+                Ok((name(e, n.trim(), &ns)?, c))
+            }
+            None => {
+                if c.is_empty() {
+                    return Err(schema_error(format!(
+                        "{} pattern has no name",
+                        local_name(e)
+                    )));
+                }
+                let nc = self.name_class(&c.remove(0), ctx)?;
+                Ok((nc, c))
+            }
+        }
+    }
+
+    fn name_class<N: Node>(&mut self, e: &N, ctx: &Context) -> Result<NameClass, Error> {
+        let ctx = ctx.inherit(e);
+        match local_name(e).as_str() {
+            "name" => name(e, e.to_string().trim(), &ctx.ns),
+            "anyName" => Ok(match self.except(e, &ctx)? {
+                Some(nc) => NameClass::AnyNameExcept(Box::new(nc)),
+                None => NameClass::AnyName,
+            }),
+            "nsName" => Ok(match self.except(e, &ctx)? {
+                Some(nc) => NameClass::NsNameExcept(ctx.ns, Box::new(nc)),
+                None => NameClass::NsName(ctx.ns),
+            }),
+            "choice" => {
+                let mut result: Option<NameClass> = None;
+                for c in children(e) {
+                    let nc = self.name_class(&c, &ctx)?;
+                    result = Some(match result {
+                        None => nc,
+                        Some(r) => NameClass::Choice(Box::new(r), Box::new(nc)),
+                    })
+                }
+                result.ok_or_else(|| schema_error("a name class is missing"))
+            }
+            other => Err(schema_error(format!(
+                "unexpected element \"{}\" in name class",
+                other
+            ))),
+        }
+    }
+
+    fn except<N: Node>(&mut self, e: &N, ctx: &Context) -> Result<Option<NameClass>, Error> {
+        match children(e).first() {
+            None => Ok(None),
+            Some(x) if local_name(x) == "except" => {
+                let xctx = ctx.inherit(x);
+                let mut result: Option<NameClass> = None;
+                for c in children(x) {
+                    let nc = self.name_class(&c, &xctx)?;
+                    result = Some(match result {
+                        None => nc,
+                        Some(r) => NameClass::Choice(Box::new(r), Box::new(nc)),
+                    })
+                }
+                result
+                    .map(Some)
+                    .ok_or_else(|| schema_error("a name class is missing"))
+            }
+            Some(x) => Err(schema_error(format!(
+                "unexpected element \"{}\" in name class",
+                local_name(x)
+            ))),
+        }
+    }
+
+    fn grammar<N: Node>(&mut self, e: &N, ctx: &Context) -> Result<Pattern, Error> {
+        let id = self.grammars;
+        self.grammars += 1;
+        let mut gctx = ctx.clone();
+        gctx.grammars.push(id);
+
+        let mut starts: Components = vec![];
+        let mut defines: Vec<(String, Components)> = vec![];
+        self.components(e, &gctx, &mut starts, &mut defines)?;
 
-#[derive(Debug)]
-pub(crate) enum PatternError<'a>{
-    NotRelaxNG,
-    MissingName,
-    Other(&'a str)
+        for (name, d) in defines {
+            let p = combine(&name, d)?;
+            self.defines.insert(format!("{}:{}", id, name), p);
+        }
+        if starts.is_empty() {
+            return Err(schema_error("grammar has no start"));
+        }
+        combine("start", starts)
+    }
+
+    // Collect the components of a grammar, including those in div elements
+    fn components<N: Node>(
+        &mut self,
+        e: &N,
+        ctx: &Context,
+        starts: &mut Components,
+        defines: &mut Vec<(String, Components)>,
+    ) -> Result<(), Error> {
+        for c in children(e) {
+            let cctx = ctx.inherit(&c);
+            match local_name(&c).as_str() {
+                "start" => {
+                    let p = self.group(&children(&c), &cctx)?;
+                    starts.push((attribute(&c, "combine"), p))
+                }
+                "define" => {
+                    let name = required(&c, "name")?.trim().to_string();
+                    let p = self.group(&children(&c), &cctx)?;
+                    let comp = (attribute(&c, "combine"), p);
+                    match defines.iter_mut().find(|(n, _)| *n == name) {
+                        Some((_, d)) => d.push(comp),
+                        None => defines.push((name, vec![comp])),
+                    }
+                }
+                "div" => self.components(&c, &cctx, starts, defines)?,
+                "include" => {
+                    return Err(schema_error("include is not supported in the XML syntax"));
+                }
+                other => {
+                    return Err(schema_error(format!(
+                        "unexpected element \"{}\" in grammar",
+                        other
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
-pub(super) fn prepare(schemadoc: &RNode) -> Result<(RNode, HashMap<String,RNode>), PatternError> {
-    //TODO implement
-
-    let patternprepper = r#"<?xml version="1.0" encoding="utf-8"?>
-<xsl:stylesheet version="2.0"
-                xmlns:rng="http://relaxng.org/ns/structure/1.0"
-                xmlns:xsl="http://www.w3.org/1999/XSL/Transform"
-                exclude-result-prefixes="rng"
->
-
-    <xsl:output
-            method="xml"
-            encoding="utf-8"
-            indent="yes"
-    />
-    <xsl:mode name="Step4.1" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.2" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.3" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.4" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.5" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.6" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.7" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.8" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.9" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.10" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.11" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.12" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.13" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.14" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.15" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.16" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.17" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.18" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.19" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.20" on-no-match="shallow-copy"/>
-    <xsl:mode name="Step4.21" on-no-match="shallow-copy"/>
-
-
-    <xsl:template match="/">
-        <xsl:variable name="s4.1" ><xsl:apply-templates mode="Step4.1"  select="/"      /></xsl:variable>
-        <xsl:variable name="s4.2" ><xsl:apply-templates mode="Step4.2"  select="$s4.1"  /></xsl:variable>
-        <xsl:variable name="s4.3" ><xsl:apply-templates mode="Step4.3"  select="$s4.2"  /></xsl:variable>
-        <xsl:variable name="s4.4" ><xsl:apply-templates mode="Step4.4"  select="$s4.3"  /></xsl:variable>
-        <xsl:variable name="s4.5" ><xsl:apply-templates mode="Step4.5"  select="$s4.4"  /></xsl:variable>
-        <xsl:variable name="s4.6" ><xsl:apply-templates mode="Step4.6"  select="$s4.5"  /></xsl:variable>
-        <xsl:variable name="s4.7" ><xsl:apply-templates mode="Step4.7"  select="$s4.6"  /></xsl:variable>
-        <xsl:variable name="s4.8" ><xsl:apply-templates mode="Step4.8"  select="$s4.7"  /></xsl:variable>
-        <xsl:variable name="s4.9" ><xsl:apply-templates mode="Step4.9"  select="$s4.8"  /></xsl:variable>
-        <xsl:variable name="s4.10"><xsl:apply-templates mode="Step4.10" select="$s4.9"  /></xsl:variable>
-        <xsl:variable name="s4.11"><xsl:apply-templates mode="Step4.11" select="$s4.10" /></xsl:variable>
-        <xsl:variable name="s4.12"><xsl:apply-templates mode="Step4.12" select="$s4.11" /></xsl:variable>
-        <xsl:variable name="s4.13"><xsl:apply-templates mode="Step4.13" select="$s4.12" /></xsl:variable>
-        <xsl:variable name="s4.14"><xsl:apply-templates mode="Step4.14" select="$s4.13" /></xsl:variable>
-        <xsl:variable name="s4.15"><xsl:apply-templates mode="Step4.15" select="$s4.14" /></xsl:variable>
-        <xsl:variable name="s4.16"><xsl:apply-templates mode="Step4.16" select="$s4.15" /></xsl:variable>
-        <xsl:variable name="s4.17"><xsl:apply-templates mode="Step4.17" select="$s4.16" /></xsl:variable>
-        <xsl:variable name="s4.18"><xsl:apply-templates mode="Step4.18" select="$s4.17" /></xsl:variable>
-        <xsl:variable name="s4.19"><xsl:apply-templates mode="Step4.19" select="$s4.18" /></xsl:variable>
-        <xsl:variable name="s4.20"><xsl:apply-templates mode="Step4.20" select="$s4.19" /></xsl:variable>
-        <xsl:variable name="s4.21"><xsl:apply-templates mode="Step4.21" select="$s4.20" /></xsl:variable>
-        <xsl:copy-of select="$s4.21"/>
-    </xsl:template>
-
-    <!-- 4.1 Annotations -->
-    <xsl:template mode="Step4.1" match="*[namespace-uri() != 'http://relaxng.org/ns/structure/1.0']"/>
-    <xsl:template mode="Step4.1" match="@*[name() != 'ns' and
-                                        name() != 'type' and
-                                        name() != 'href' and
-                                        name() != 'combine' and
-                                        name() != 'datatypeLibrary' and
-                                        name() != 'name']"/>
-    <xsl:template mode="Step4.1" match="comment()"/>
-    <xsl:template mode="Step4.1" match="processing-instruction()"/>
-    <!-- 4.2 Whitespace -->
-    <xsl:template mode="Step4.2" match="text()[normalize-space(.) = '' and ancestor::*[not(self::value or self::param)]]"/>
-
-    <!-- 4.3 datatypeLibrary attribute -->
-    <xsl:template mode="Step4.3" match="rng:*[name()='data' or name()='value'][not(@datatypeLibrary)]">
-        <xsl:copy>
-            <!-- Add or inherit "datatypeLibrary" attribute -->
-            <xsl:if test="not(@datatypeLibrary)">
-                <xsl:attribute name="datatypeLibrary">
-                    <xsl:choose>
-                        <!-- If nearest ancestor has the attribute, inherit it -->
-                        <xsl:when test="ancestor::rng:*[@datatypeLibrary][1]/@datatypeLibrary">
-                            <xsl:value-of select="ancestor::*[@datatypeLibrary][1]/@datatypeLibrary"/>
-                        </xsl:when>
-                        <!-- Otherwise, default to a predefined value or remove if desired -->
-                        <xsl:otherwise>default_value</xsl:otherwise>
-                    </xsl:choose>
-                </xsl:attribute>
-            </xsl:if>
-            <!-- Copy existing attributes -->
-            <xsl:apply-templates mode="Step4.3" select="@*"/>
-            <!-- Process child nodes -->
-            <xsl:apply-templates mode="Step4.3" select="node()"/>
-        </xsl:copy>
-    </xsl:template>
-
-    <!-- 4.4 type attribute of value element -->
-    <xsl:template mode="Step4.4" match="rng:value[not(@type)]">
-        <xsl:copy>
-            <xsl:attribute name="type">token</xsl:attribute>
-            <xsl:attribute name="datatypeLibrary"/>
-            <!-- Copy existing attributes -->
-            <xsl:apply-templates mode="Step4.4" select="@*"/>
-            <!-- Process child nodes -->
-            <xsl:apply-templates mode="Step4.4" select="node()"/>
-        </xsl:copy>
-    </xsl:template>
-
-
-    <!-- 4.5 href attribute -->
-    <!-- 4.6 externalRef element -->
-    <xsl:template mode="Step4.6" match="rng:externalRef">
-        <xsl:value-of select="doc(@href)"/>
-    </xsl:template>
-
-    <!-- 4.7 include element -->
-    <!--
-    <xsl:template mode="Step1" match="rng:include"/>
-    -->
-
-    <!-- 4.8 name attribute of element and attribute elements -->
-    <xsl:template mode="Step4.8" match="rng:element">
-        <xsl:copy>
-            <xsl:apply-templates mode="Step4.8" select="@*[name() != 'name']"/>
-            <rng:name><xsl:value-of select="@name"/></rng:name>
-            <xsl:apply-templates mode="Step4.8" select="node()"/>
-        </xsl:copy>
-    </xsl:template>
-    <xsl:template mode="Step4.8" match="rng:attribute">
-        <xsl:copy>
-            <xsl:apply-templates mode="Step4.8" select="@*[name() != 'name']"/>
-            <rng:name>
-                <xsl:if test="@name and not(@ns)">
-                    <xsl:attribute name="ns"></xsl:attribute>
-                </xsl:if>
-                <xsl:value-of select="@name"/>
-            </rng:name>
-            <xsl:apply-templates mode="Step4.8" select="node()"/>
-        </xsl:copy>
-    </xsl:template>
-
-    <!-- 4.9 ns attribute -->
-    <xsl:template  mode="Step4.9" match="rng:value[not(@ns)] | rng:name[not(@ns)] | rng:nsname[not(@ns)] ">
-        <xsl:copy>
-            <xsl:attribute name="ns" select="ancestor::*[value|name|nsname][@ns]"/>
-            <xsl:apply-templates mode="Step4.9"/>
-        </xsl:copy>
-    </xsl:template>
-
-    <xsl:template  mode="Step4.9" match="*[not(rng:name or rng:nsname or rng:value) and @ns]">
-        <xsl:copy>
-            <xsl:attribute name="ns"/>
-            <xsl:apply-templates mode="Step4.9"/>
-        </xsl:copy>
-    </xsl:template>
-
-    <!-- 4.10 QNames -->
-    <!-- TODO -->
-
-
-    <!-- 4.11 div -->
-    <xsl:template mode="Step4.11" match="rng:div">
-        <xsl:apply-templates mode="Step4.11"/>
-    </xsl:template>
-
-
-    <!-- 4.12 number of child elements -->
-    <xsl:template mode="Step4.12" match="*[rng:define|rng:oneOrMore|rng:zeroOrMore|rng:optional|rng:list|rng:mixed][count(*) &gt; 1]">
-        <xsl:copy>
-            <rng:group>
-                <xsl:apply-templates mode="Step4.12"/>
-            </rng:group>
-        </xsl:copy>
-    </xsl:template>
-
-    <xsl:template mode="Step41.12" match="rng:element[count(*) &gt; 2]">
-        <xsl:copy>
-            <xsl:copy select="rng:name"/>
-            <rng:group>
-                <xsl:apply-templates mode="Step4.12" select="*[name() != 'name']"/>
-            </rng:group>
-        </xsl:copy>
-    </xsl:template>
-
-    <xsl:template mode="Step4.12" match="rng:except[count(*) &gt; 1]">
-        <xsl:copy>
-            <rng:choice>
-                <xsl:apply-templates mode="Step4.12"/>
-            </rng:choice>
-        </xsl:copy>
-    </xsl:template>
-
-    <xsl:template mode="Step4.12" match="rng:except[count(*) &gt; 1]">
-        <xsl:copy>
-            <rng:choice>
-                <xsl:apply-templates mode="Step4.12"/>
-            </rng:choice>
-        </xsl:copy>
-    </xsl:template>
-
-    <xsl:template mode="Step4.12" match="rng:attribute[count(*) = 1 and name() = 'name']">
-        <xsl:copy>
-            <rng:text/>
-        </xsl:copy>
-    </xsl:template>
-
-    <xsl:template mode="Step4.12" match="[rng:choice|rng:group|rng:interleave][count(*) = 1]">
-        <xsl:apply-templates mode="Step4.12"/>
-    </xsl:template>
-
-    <xsl:template mode="Step4.12" match="rng:choice[count(*) &gt; 2]">
-        <xsl:call-template name="choices_splitter">
-            <xsl:with-param name="choices" select="./child::*"/>
-        </xsl:call-template>
-    </xsl:template>
-    <xsl:template name="choices_splitter">
-        <xsl:param name="choices"/>
-        <xsl:choose>
-            <xsl:when test="count($choices) = 1">
-                <xsl:apply-templates mode="Step4.12" select="$choices"/>
-            </xsl:when>
-            <xsl:otherwise>
-                <rng:choice>
-                    <xsl:apply-templates mode="Step4.12" select="$choices[position() = 1]"/>
-                    <xsl:call-template name="choices_splitter">
-                        <xsl:with-param name="choices" select="$choices[position() &gt; 1]"/>
-                    </xsl:call-template>
-                </rng:choice>
-            </xsl:otherwise>
-        </xsl:choose>
-    </xsl:template>
-
-    <xsl:template mode="Step4.12" match="rng:group[count(*) &gt; 2]">
-        <xsl:call-template name="groups_splitter">
-            <xsl:with-param name="groups" select="./child::*"/>
-        </xsl:call-template>
-    </xsl:template>
-    <xsl:template name="groups_splitter">
-        <xsl:param name="groups"/>
-        <xsl:choose>
-            <xsl:when test="count($groups) = 1">
-                <xsl:apply-templates mode="Step4.12" select="$groups"/>
-            </xsl:when>
-            <xsl:otherwise>
-                <rng:group>
-                    <xsl:apply-templates mode="Step4.12" select="$groups[position() = 1]"/>
-                    <xsl:call-template name="groups_splitter">
-                        <xsl:with-param name="groups" select="$groups[position() &gt; 1]"/>
-                    </xsl:call-template>
-                </rng:group>
-            </xsl:otherwise>
-        </xsl:choose>
-    </xsl:template>
-
-    <xsl:template mode="Step4.12" match="rng:interleave[count(*) &gt; 2]">
-        <xsl:call-template name="interleave_splitter">
-            <xsl:with-param name="interleaves" select="./child::*"/>
-        </xsl:call-template>
-    </xsl:template>
-    <xsl:template name="interleave_splitter">
-        <xsl:param name="interleaves"/>
-        <xsl:choose>
-            <xsl:when test="count($interleaves) = 1">
-                <xsl:apply-templates mode="Step4.12" select="$interleaves"/>
-            </xsl:when>
-            <xsl:otherwise>
-                <rng:group>
-                    <xsl:apply-templates mode="Step4.12" select="$interleaves[position() = 1]"/>
-                    <xsl:call-template name="interleave_splitter">
-                        <xsl:with-param name="interleaves" select="$interleaves[position() &gt; 1]"/>
-                    </xsl:call-template>
-                </rng:group>
-            </xsl:otherwise>
-        </xsl:choose>
-    </xsl:template>
-
-
-    <!-- 4.13  mixed element -->
-    <xsl:template match="rng:mixed" mode="Step4.13">
-        <rng:interleave>
-            <xsl:apply-templates mode="Step4.13"/>
-            <rng:text/>
-        </rng:interleave>
-    </xsl:template>
-
-    <!-- 4.14 optional element -->
-    <xsl:template match="rng:optional" mode="Step4.14">
-        <rng:choice>
-            <xsl:apply-templates mode="Step4.14"/>
-            <rng:empty/>
-        </rng:choice>
-    </xsl:template>
-
-    <!-- 4.15 zeroOrMore element -->
-    <xsl:template match="rng:zeroOrMore" mode="Step4.15">
-        <rng:choice>
-            <rng:oneOrMore>
-                <xsl:apply-templates mode="Step4.15"/>
-            </rng:oneOrMore>
-            <rng:empty/>
-        </rng:choice>
-    </xsl:template>
-
-    <!-- 4.16 Constraints -->
-    <!-- TODO -->
-
-    <!-- 4.17 combine attribute -->
-    <xsl:template mode="Step4.17" match="rng:grammar">
-        <xsl:copy>
-            <xsl:for-each-group select=".//rng:define[@combine='choice']" group-by="@name">
-                <rng:define>
-                    <xsl:attribute name="name" select="@name"/>
-                    <rng:choice>
-                        <xsl:value-of select="current-group()"/>
-                    </rng:choice>
-                </rng:define>
-            </xsl:for-each-group>
-            <xsl:for-each-group select="//rng:define[@combine='interleave']" group-by="@name">
-                <rng:define>
-                    <xsl:attribute name="name" select="@name"/>
-                    <rng:interleave>
-                        <xsl:value-of select="current-group()"/>
-                    </rng:interleave>
-                </rng:define>
-            </xsl:for-each-group>
-            <xsl:apply-templates mode="Step4.17"/>
-        </xsl:copy>
-    </xsl:template>
-
-    <!-- 4.18 grammar element -->
-    <xsl:template mode="Step4.18" match="/*[not(local-name()='grammar')]">
-        <rng:grammar>
-            <rng:start>
-                <xsl:copy-of select="."/>
-            </rng:start>
-            <xsl:copy-of select="//rng:define"/>
-        </rng:grammar>
-    </xsl:template>
-
-    <xsl:template mode="Step4.18" match="/rng:grammar">
-        <xsl:copy>
-            <xsl:choose>
-                <xsl:when test="rng:group/rng:start">
-                    <xsl:copy-of select="rng:group/rng:start"/>
-                </xsl:when>
-                <xsl:otherwise>
-                    <rng:start>
-                        <xsl:copy-of select="*"/>
-                    </rng:start>
-                </xsl:otherwise>
-            </xsl:choose>
-            <xsl:copy-of select="//rng:define"/>
-        </xsl:copy>
-    </xsl:template>
-
-    <!-- All except root node -->
-    <xsl:template mode="Step4.18" match="rng:grammar[parent::*]"/>
-
-    <!-- 4.19. define and ref elements -->
-    <xsl:template mode="Step4.19" match="rng:element[not(ancestor::rng:define)]">
-        <rng:ref>
-            <xsl:attribute name="name">
-                <xsl:value-of select="./rng:name"/>
-            </xsl:attribute>
-        </rng:ref>
-    </xsl:template>
-    <xsl:template mode="Step4.19" match="/rng:grammar">
-        <xsl:copy>
-            <xsl:apply-templates mode="Step4.19"/>
-            <xsl:for-each select="//rng:element[not(ancestor::rng:define)]">
-                <rng:define>
-                    <xsl:attribute name="name">
-                        <xsl:value-of select="./rng:name"/>
-                    </xsl:attribute>
-                    <xsl:copy-of select="/rng:grammar/rng:start/*"/>
-                </rng:define>
-            </xsl:for-each>
-        </xsl:copy>
-    </xsl:template>
-
-    <!-- 4.20. notAllowed element -->
-    <xsl:template mode="Step4.20" match="rng:attribute[rng:notAllowed]"><rng:notAllowed/></xsl:template>
-    <xsl:template mode="Step4.20" match="rng:list[rng:notAllowed]"><rng:notAllowed/></xsl:template>
-    <xsl:template mode="Step4.20" match="rng:group[rng:notAllowed]"><rng:notAllowed/></xsl:template>
-    <xsl:template mode="Step4.20" match="rng:interleave[rng:notAllowed]"><rng:notAllowed/></xsl:template>
-    <xsl:template mode="Step4.20" match="rng:oneOrMore[rng:notAllowed]"><rng:notAllowed/></xsl:template>
-
-    <xsl:template mode="Step4.20" match="rng:choice[count(rng:notAllowed) = 2]">
-        <rng:notAllowed/>
-    </xsl:template>
-
-    <xsl:template mode="Step4.20" match="rng:choice[count(rng:notAllowed) != 2]">
-        <xsl:variable name="child1">
-            <xsl:apply-templates mode="Step4.20" select="*[1]"/>
-        </xsl:variable>
-        <xsl:variable name="child2">
-            <xsl:apply-templates mode="Step4.20" select="*[2]"/>
-        </xsl:variable>
-        <xsl:choose>
-            <xsl:when test="name($child1)= 'notAllowed' and name($child2)= 'notAllowed'">
-                <rng:notAllowed/>
-            </xsl:when>
-            <xsl:otherwise>
-                <rng:choice>
-                    <xsl:copy-of select="$child1"/>
-                    <xsl:copy-of select="$child2"/>
-                </rng:choice>
-            </xsl:otherwise>
-        </xsl:choose>
-    </xsl:template>
-
-    <xsl:template mode="Step4.20" match="rng:except[rng:notAllowed]"/>
-
-
-    <!-- 4.21. empty element -->
-    <xsl:template mode="Step4.21" match="rng:group">
-        <xsl:variable name="child1">
-            <xsl:apply-templates mode="Step4.21" select="*[1]"/>
-        </xsl:variable>
-        <xsl:variable name="child2">
-            <xsl:apply-templates mode="Step4.21" select="*[2]"/>
-        </xsl:variable>
-        <xsl:choose>
-            <xsl:when test="name($child1)= 'empty' and name($child2)= 'empty'">
-                <rng:empty/>
-            </xsl:when>
-            <xsl:when test="name($child1)= 'empty'">
-                <xsl:copy-of select="$child2"/>
-            </xsl:when>
-            <xsl:when test="name($child2)= 'empty'">
-                <xsl:copy-of select="$child1"/>
-            </xsl:when>
-            <xsl:otherwise>
-                <rng:group>
-                    <xsl:copy-of select="$child1"/>
-                    <xsl:copy-of select="$child2"/>
-                </rng:group>
-            </xsl:otherwise>
-        </xsl:choose>
-    </xsl:template>
-
-    <xsl:template mode="Step4.21" match="rng:interleave">
-        <xsl:variable name="child1">
-            <xsl:apply-templates mode="Step4.21" select="*[1]"/>
-        </xsl:variable>
-        <xsl:variable name="child2">
-            <xsl:apply-templates mode="Step4.21" select="*[2]"/>
-        </xsl:variable>
-        <xsl:choose>
-            <xsl:when test="name($child1)= 'empty' and name($child2)= 'empty'">
-                <rng:empty/>
-            </xsl:when>
-            <xsl:when test="name($child1)= 'empty'">
-                <xsl:copy-of select="$child2"/>
-            </xsl:when>
-            <xsl:when test="name($child2)= 'empty'">
-                <xsl:copy-of select="$child1"/>
-            </xsl:when>
-            <xsl:otherwise>
-                <rng:interleave>
-                    <xsl:copy-of select="$child1"/>
-                    <xsl:copy-of select="$child2"/>
-                </rng:interleave>
-            </xsl:otherwise>
-        </xsl:choose>
-    </xsl:template>
-
-    <xsl:template mode="Step4.21" match="rng:choice">
-        <xsl:variable name="child1">
-            <xsl:apply-templates mode="Step4.21" select="*[1]"/>
-        </xsl:variable>
-        <xsl:variable name="child2">
-            <xsl:apply-templates mode="Step4.21" select="*[2]"/>
-        </xsl:variable>
-        <xsl:choose>
-            <xsl:when test="name($child1)= 'empty' and name($child2)= 'empty'">
-                <rng:empty/>
-            </xsl:when>
-            <xsl:when test="name($child2)= 'empty'">
-                <rng:choice>
-                    <xsl:copy-of select="$child2"/>
-                    <xsl:copy-of select="$child1"/>
-                </rng:choice>
-            </xsl:when>
-            <xsl:otherwise>
-                <rng:choice>
-                    <xsl:copy-of select="$child1"/>
-                    <xsl:copy-of select="$child2"/>
-                </rng:choice>
-            </xsl:otherwise>
-        </xsl:choose>
-    </xsl:template>
-
-</xsl:stylesheet>"#;
-
-    let styledoc = parse_from_str(patternprepper).expect("TODO: panic message");
-    let mut stctxt = StaticContextBuilder::new()
-        .message(|_| Ok(()))
-        .parser(|_s| Ok(RNode::new_document()))
-        .fetcher(|_url| Ok(String::new()))
-        .build();
-    let c = from_document(
-        styledoc,
-        None,
-        |_s| Ok(RNode::new_document()),
-        |_| Ok(String::new()),
-    );
-    match c {
-        Ok(mut ctxt) => {
-            ctxt.context(vec![Item::Node(schemadoc.clone())], 0);
-            ctxt.result_document(RNode::new_document());
-            ctxt.populate_key_values(&mut stctxt, schemadoc.clone()).expect("TODO: panic message");
-            let rest =  ctxt.evaluate(&mut stctxt);
-            println!("res-{:?}",rest);
+// Combine the definitions of a name. At most one definition may omit the combine attribute,
+// and all those that have one must agree.
+fn combine(name: &str, d: Components) -> Result<Pattern, Error> {
+    if d.iter().filter(|(c, _)| c.is_none()).count() > 1 {
+        return Err(schema_error(format!(
+            "\"{}\" is defined more than once without a combine attribute",
+            name
+        )));
+    }
+    let mut method: Option<String> = None;
+    for m in d.iter().filter_map(|(c, _)| c.as_deref()) {
+        let m = m.trim();
+        if m != "choice" && m != "interleave" {
+            return Err(schema_error(format!("invalid combine method \"{}\"", m)));
+        }
+        if method.as_ref().is_some_and(|n| n != m) {
+            return Err(schema_error(format!(
+                "\"{}\" is combined using both choice and interleave",
+                name
+            )));
         }
-        Err(e) => {
-            println!("reserre-{:?}",e);
+        method = Some(m.to_string())
+    }
+    let mut result: Option<Pattern> = None;
+    for (_, p) in d {
+        result = Some(match result {
+            None => p,
+            Some(r) if method.as_deref() == Some("interleave") => {
+                Pattern::Interleave(Box::new(r), Box::new(p))
+            }
+            Some(r) => Pattern::Choice(Box::new(r), Box::new(p)),
+        })
+    }
+    result.ok_or_else(|| schema_error(format!("\"{}\" has no definition", name)))
+}
 
+// Every reference must be to a definition, and a definition may not refer to itself
+// other than through an element pattern.
+fn check_refs(g: &Grammar) -> Result<(), Error> {
+    let mut all = vec![];
+    refs(&g.start, true, &mut all);
+    for p in g.defines.values() {
+        refs(p, true, &mut all);
+    }
+    if let Some(r) = all.iter().find(|r| !g.defines.contains_key(*r)) {
+        return Err(schema_error(format!(
+            "reference to undefined pattern \"{}\"",
+            unqualified(r)
+        )));
+    }
+    for name in g.defines.keys() {
+        let mut seen = HashSet::new();
+        let mut pending = vec![name.clone()];
+        while let Some(n) = pending.pop() {
+            let mut unguarded = vec![];
+            refs(&g.defines[&n], false, &mut unguarded);
+            for r in unguarded {
+                if r == *name {
+                    return Err(schema_error(format!(
+                        "pattern \"{}\" refers to itself other than through an element",
+                        unqualified(name)
+                    )));
+                }
+                if seen.insert(r.clone()) {
+                    pending.push(r)
+                }
+            }
         }
     }
+    Ok(())
+}
 
-        /*
-    let mut ci = schemadoc.child_iter();
-    let pat = ci.next().unwrap();
-    let mut refs = HashMap::new();
-    for r in ci {
-        refs.insert(r.name().get_localname(), r);
+// The references in a pattern. Unless deep is true, the content of element patterns is not searched.
+fn refs(p: &Pattern, deep: bool, result: &mut Vec<String>) {
+    match p {
+        Pattern::Ref(r) => result.push(r.clone()),
+        Pattern::Element(_, c) => {
+            if deep {
+                refs(c, deep, result)
+            }
+        }
+        Pattern::Choice(p1, p2)
+        | Pattern::Interleave(p1, p2)
+        | Pattern::Group(p1, p2)
+        | Pattern::After(p1, p2) => {
+            refs(p1, deep, result);
+            refs(p2, deep, result)
+        }
+        Pattern::OneOrMore(p1)
+        | Pattern::List(p1)
+        | Pattern::Attribute(_, p1)
+        | Pattern::DataExcept(_, _, p1) => refs(p1, deep, result),
+        _ => {}
     }
-    Ok((pat, refs))
+}
+
+fn unqualified(r: &str) -> &str {
+    r.split_once(':').map_or(r, |(_, n)| n)
+}
+
+// The datatype of a data or value pattern. Only the built-in library and XML Schema datatypes are supported.
+fn datatype(ctx: &Context, t: &str) -> Result<Datatype, Error> {
+    match ctx.datatype_library.as_str() {
+        "" if t == "string" || t == "token" => Ok((String::new(), t.to_string())),
+        XSDDATATYPES if datatypes::is_builtin(t) => {
+            Ok((ctx.datatype_library.clone(), t.to_string()))
+        }
+        "" | XSDDATATYPES => Err(schema_error(format!("unknown datatype \"{}\"", t))),
+        dl => Err(schema_error(format!(
+            "datatype library \"{}\" is not supported",
+            dl
+        ))),
+    }
+}
+
+// The parameters of a data pattern. The built-in datatypes have no parameters,
+// and the XML Schema datatypes allow the facets other than enumeration and whiteSpace.
+fn check_params(dt: &Datatype, params: &[Param]) -> Result<(), Error> {
+    for (name, value) in params {
+        match (dt.0.as_str(), name.as_str()) {
+            (XSDDATATYPES, "pattern") => {
+                datatypes::xsd_regex(value)?;
+            }
+            (
+                XSDDATATYPES,
+                "length" | "minLength" | "maxLength" | "minInclusive" | "maxInclusive"
+                | "minExclusive" | "maxExclusive" | "totalDigits" | "fractionDigits",
+            ) => {}
+            _ => {
+                return Err(schema_error(format!(
+                    "parameter \"{}\" is not allowed for datatype \"{}\"",
+                    name, dt.1
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+// Resolve a name, possibly prefixed, to a name class
+fn name<N: Node>(e: &N, n: &str, ns: &str) -> Result<NameClass, Error> {
+    if n.contains(':') {
+        let qn = e.to_qname(n)?;
+        Ok(NameClass::Name(
+            qn.namespace_uri()
+                .map_or_else(String::new, |u| u.to_string()),
+            qn.local_name().to_string(),
+        ))
+    } else {
+        Ok(NameClass::Name(ns.to_string(), n.to_string()))
+    }
+}
+
+// The child elements in the RELAX NG namespace. Other elements are annotations.
+fn children<N: Node>(e: &N) -> Vec<N> {
+    e.child_iter().filter(is_rng).collect()
+}
+
+fn is_rng<N: Node>(e: &N) -> bool {
+    e.node_type() == NodeType::Element
+        && e.name()
+            .is_some_and(|qn| qn.namespace_uri().is_some_and(|u| u.to_string() == RNGNS))
+}
+
+fn local_name<N: Node>(e: &N) -> String {
+    e.name()
+        .map_or_else(String::new, |qn| qn.local_name().to_string())
+}
+
+// An unqualified attribute of a pattern element
+fn attribute<N: Node>(e: &N, name: &str) -> Option<String> {
+    e.attribute_iter()
+        .find(|a| {
+            a.name().is_some_and(|qn| {
+                qn.namespace_uri().is_none() && qn.local_name().to_string() == name
+            })
+        })
+        .map(|a| a.value().to_string())
+}
 
-         */
-    //println!("res-{:?}",rest);
-    Ok((RNode::new_document(), HashMap::new()))
+fn required<N: Node>(e: &N, name: &str) -> Result<String, Error> {
+    attribute(e, name).ok_or_else(|| {
+        schema_error(format!(
+            "{} pattern has no {} attribute",
+            local_name(e),
+            name
+        ))
+    })
 }
 
-fn parse_from_str(s: &str) -> Result<RNode, Error> {
-    let doc = RNode::new_document();
-    xmlparse(doc.clone(), s, None)?;
-    Ok(doc)
+fn schema_error(msg: impl Into<String>) -> Error {
+    Error::new(ErrorKind::ParseError, msg)
 }
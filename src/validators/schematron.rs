@@ -0,0 +1,797 @@
+//! # ISO Schematron validation
+//!
+//! Validation of a document against an ISO Schematron schema (ISO/IEC 19757-3).
+//!
+//! Rule contexts are compiled as XSLT [Pattern]s and assertions are compiled as XPath expressions,
+//! so the query language binding is that of the xrust XPath and XSLT engine.
+//! Schema, phase, pattern, and rule variables (sch:let), abstract patterns (with parameters), abstract rules (sch:extends),
+//! and phases are supported.
+//!
+//! The result of validation is a Schematron Validation Report Language (SVRL) document.
+//! A document is valid if the report has no svrl:failed-assert elements.
+//!
+//! ```rust
+//! use xrust::item::Node;
+//! use xrust::parser::{ParseError, xml};
+//! use xrust::trees::smite::RNode;
+//! use xrust::validators::schematron::{Schematron, validate_schematron};
+//!
+//! let schema = xml::parse(RNode::new_document(), r#"<schema xmlns="http://purl.oclc.org/dsdl/schematron">
+//!   <pattern>
+//!     <rule context="item">
+//!       <assert test="number(@price) > 0">an item must have a price</assert>
+//!     </rule>
+//!   </pattern>
+//! </schema>"#, Some(|_: &_| Err(ParseError::MissingNameSpace)))
+//!     .expect("unable to parse schema");
+//! let doc = xml::parse(RNode::new_document(), "<order><item price='0'/></order>", Some(|_: &_| Err(ParseError::MissingNameSpace)))
+//!     .expect("unable to parse document");
+//!
+//! let sch = Schematron::new(&schema, None).expect("unable to compile schema");
+//! let svrl = validate_schematron(&doc, &sch).expect("validation failed");
+//! assert!(svrl.to_xml().contains("an item must have a price"))
+//! ```
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use qualname::{NamespacePrefix, NamespaceUri, NcName, QName};
+use url::Url;
+
+use crate::item::{Item, Node, NodeType, Sequence, SequenceTrait};
+use crate::parser::xpath;
+use crate::pattern::Pattern;
+use crate::transform::Transform;
+use crate::transform::context::{Context, ContextBuilder, StaticContext, StaticContextBuilder};
+use crate::trees::smite::RNode;
+use crate::validators::ValidationError;
+use crate::value::Value;
+use crate::xdmerror::{Error, ErrorKind};
+
+const SCHNS: &str = "http://purl.oclc.org/dsdl/schematron";
+const SVRLNS: &str = "http://purl.oclc.org/dsdl/svrl";
+
+/// A compiled Schematron schema, for a particular phase.
+pub struct Schematron {
+    title: Option<String>,
+    phase: Option<String>,
+    schema_version: Option<String>,
+    namespaces: Vec<(String, String)>, // prefix, URI
+    lets: Vec<Let>,
+    patterns: Vec<SchPattern>,
+}
+
+struct Let {
+    name: String,
+    value: Transform<RNode>,
+}
+
+struct SchPattern {
+    id: Option<String>,
+    name: Option<String>,
+    lets: Vec<Let>,
+    rules: Vec<Rule>,
+}
+
+struct Rule {
+    context: Pattern<RNode>,
+    source: String,
+    id: Option<String>,
+    role: Option<String>,
+    flag: Option<String>,
+    lets: Vec<Let>,
+    checks: Vec<Check>,
+}
+
+// An assert or a report
+struct Check {
+    report: bool,
+    test: Transform<RNode>,
+    source: String,
+    id: Option<String>,
+    role: Option<String>,
+    flag: Option<String>,
+    message: Vec<Message>,
+}
+
+enum Message {
+    Text(String),
+    ValueOf(Transform<RNode>),
+    Name(Option<Transform<RNode>>),
+}
+
+impl Schematron {
+    /// Compile a Schematron schema. If a phase is not given, then the schema's default phase is used.
+    /// The phase "#ALL" selects all patterns.
+    pub fn new(schema: &RNode, phase: Option<&str>) -> Result<Self, ValidationError> {
+        let root = if schema.node_type() == NodeType::Document {
+            schema
+                .child_iter()
+                .find(|c| c.node_type() == NodeType::Element)
+                .ok_or_else(|| schema_error("schema document has no document element"))?
+        } else {
+            schema.clone()
+        };
+        if !is_sch(&root, "schema") {
+            return Err(schema_error("document element is not sch:schema"));
+        }
+        match attr(&root, "queryBinding").as_deref() {
+            None | Some("xslt") | Some("xslt2") | Some("xslt3") | Some("xpath")
+            | Some("xpath2") | Some("xpath3") | Some("xpath31") => {}
+            Some(q) => {
+                return Err(schema_error(format!(
+                    "query language binding \"{}\" is not supported",
+                    q
+                )));
+            }
+        }
+
+        // The prefixes declared by sch:ns are attached to an element that is used to resolve names in expressions
+        let mut namespaces = vec![];
+        let mut nsdoc = RNode::new_document();
+        let resolver = nsdoc
+            .new_element(QName::from_local_name(NcName::try_from("ns").unwrap()))
+            .and_then(|e| nsdoc.push(e.clone()).map(|_| e))
+            .map_err(|e| schema_error(e.to_string()))?;
+        for ns in sch_children(&root, "ns") {
+            let (p, u) = attr(&ns, "prefix")
+                .zip(attr(&ns, "uri"))
+                .ok_or_else(|| schema_error("sch:ns must have a prefix and a uri"))?;
+            let prefix = NamespacePrefix::try_from(p.as_str())
+                .map_err(|_| schema_error(format!("invalid prefix \"{}\"", p)))?;
+            let uri = NamespaceUri::try_from(u.as_str())
+                .map_err(|_| schema_error(format!("invalid namespace URI \"{}\"", u)))?;
+            resolver
+                .add_namespace(
+                    nsdoc
+                        .new_namespace(uri, Some(prefix), true)
+                        .map_err(|e| schema_error(e.to_string()))?,
+                )
+                .map_err(|e| schema_error(e.to_string()))?;
+            namespaces.push((p, u))
+        }
+        let c = Compiler {
+            resolver,
+            abstract_rules: root
+                .descend_iter()
+                .filter(|r| is_sch(r, "rule") && is_abstract(r))
+                .filter_map(|r| attr(&r, "id").map(|id| (id, r)))
+                .collect(),
+        };
+
+        let mut lets = c.lets(&root, &[])?;
+        let patterns: Vec<RNode> = sch_children(&root, "pattern");
+        let phase = phase
+            .map(String::from)
+            .or_else(|| attr(&root, "defaultPhase"))
+            .filter(|p| p != "#ALL");
+        let active: Vec<RNode> = match &phase {
+            None => patterns
+                .iter()
+                .filter(|p| !is_abstract(p))
+                .cloned()
+                .collect(),
+            Some(p) => {
+                let ph = sch_children(&root, "phase")
+                    .into_iter()
+                    .find(|ph| attr(ph, "id").as_ref() == Some(p))
+                    .ok_or_else(|| schema_error(format!("phase \"{}\" is not defined", p)))?;
+                lets.extend(c.lets(&ph, &[])?);
+                let ids: Vec<String> = sch_children(&ph, "active")
+                    .iter()
+                    .filter_map(|a| attr(a, "pattern"))
+                    .collect();
+                patterns
+                    .iter()
+                    .filter(|p| {
+                        !is_abstract(p) && attr(p, "id").is_some_and(|id| ids.contains(&id))
+                    })
+                    .cloned()
+                    .collect()
+            }
+        };
+
+        let mut compiled = vec![];
+        for p in active {
+            let (def, params) = match attr(&p, "is-a") {
+                Some(a) => {
+                    let def = patterns
+                        .iter()
+                        .find(|d| is_abstract(d) && attr(d, "id").as_ref() == Some(&a))
+                        .ok_or_else(|| {
+                            schema_error(format!("abstract pattern \"{}\" is not defined", a))
+                        })?;
+                    let mut params = vec![];
+                    for pm in sch_children(&p, "param") {
+                        params.push(attr(&pm, "name").zip(attr(&pm, "value")).ok_or_else(|| {
+                            schema_error("sch:param must have a name and a value")
+                        })?)
+                    }
+                    (def.clone(), params)
+                }
+                None => (p.clone(), vec![]),
+            };
+            let mut rules = vec![];
+            for r in sch_children(&def, "rule")
+                .iter()
+                .filter(|r| !is_abstract(r))
+            {
+                rules.push(c.rule(r, &params)?)
+            }
+            compiled.push(SchPattern {
+                id: attr(&p, "id"),
+                name: sch_children(&def, "title").first().map(|t| t.to_string()),
+                lets: c.lets(&def, &params)?,
+                rules,
+            })
+        }
+
+        Ok(Schematron {
+            title: sch_children(&root, "title").first().map(|t| t.to_string()),
+            phase,
+            schema_version: attr(&root, "schemaVersion"),
+            namespaces,
+            lets,
+            patterns: compiled,
+        })
+    }
+}
+
+struct Compiler {
+    resolver: RNode,
+    abstract_rules: HashMap<String, RNode>,
+}
+
+impl Compiler {
+    fn expr(
+        &self,
+        s: &str,
+        params: &[(String, String)],
+    ) -> Result<Transform<RNode>, ValidationError> {
+        let s = substitute(s, params);
+        xpath::parse(&s, Some(self.resolver.clone()), None)
+            .map_err(|e| schema_error(format!("unable to compile expression \"{}\": {}", s, e)))
+    }
+
+    fn lets(&self, n: &RNode, params: &[(String, String)]) -> Result<Vec<Let>, ValidationError> {
+        let mut lets = vec![];
+        for l in sch_children(n, "let") {
+            let name = attr(&l, "name").ok_or_else(|| schema_error("sch:let must have a name"))?;
+            let value = attr(&l, "value")
+                .ok_or_else(|| schema_error(format!("sch:let \"{}\" must have a value", name)))?;
+            lets.push(Let {
+                name,
+                value: self.expr(&value, params)?,
+            })
+        }
+        Ok(lets)
+    }
+
+    fn rule(&self, r: &RNode, params: &[(String, String)]) -> Result<Rule, ValidationError> {
+        let source = substitute(
+            &attr(r, "context").ok_or_else(|| schema_error("sch:rule must have a context"))?,
+            params,
+        );
+        let context = Pattern::try_from((source.as_str(), self.resolver.clone())).map_err(|e| {
+            schema_error(format!(
+                "unable to compile rule context \"{}\": {}",
+                source, e
+            ))
+        })?;
+        let mut lets = vec![];
+        let mut checks = vec![];
+        self.rule_body(r, params, &mut lets, &mut checks, &mut vec![])?;
+        Ok(Rule {
+            context,
+            source,
+            id: attr(r, "id"),
+            role: attr(r, "role"),
+            flag: attr(r, "flag"),
+            lets,
+            checks,
+        })
+    }
+
+    // The variables and checks of a rule, including those of the abstract rules that it extends.
+    fn rule_body(
+        &self,
+        r: &RNode,
+        params: &[(String, String)],
+        lets: &mut Vec<Let>,
+        checks: &mut Vec<Check>,
+        extending: &mut Vec<String>,
+    ) -> Result<(), ValidationError> {
+        for c in r
+            .child_iter()
+            .filter(|c| c.node_type() == NodeType::Element)
+        {
+            if is_sch(&c, "let") {
+                let name =
+                    attr(&c, "name").ok_or_else(|| schema_error("sch:let must have a name"))?;
+                let value = attr(&c, "value").ok_or_else(|| {
+                    schema_error(format!("sch:let \"{}\" must have a value", name))
+                })?;
+                lets.push(Let {
+                    name,
+                    value: self.expr(&value, params)?,
+                })
+            } else if is_sch(&c, "assert") || is_sch(&c, "report") {
+                let source = substitute(
+                    &attr(&c, "test").ok_or_else(|| {
+                        schema_error("sch:assert and sch:report must have a test")
+                    })?,
+                    params,
+                );
+                checks.push(Check {
+                    report: is_sch(&c, "report"),
+                    test: self.expr(&source, &[])?,
+                    source,
+                    id: attr(&c, "id"),
+                    role: attr(&c, "role"),
+                    flag: attr(&c, "flag"),
+                    message: self.message(&c, params)?,
+                })
+            } else if is_sch(&c, "extends") {
+                let id = attr(&c, "rule")
+                    .ok_or_else(|| schema_error("sch:extends must have a rule attribute"))?;
+                if extending.contains(&id) {
+                    return Err(schema_error(format!("rule \"{}\" extends itself", id)));
+                }
+                let a = self.abstract_rules.get(&id).ok_or_else(|| {
+                    schema_error(format!("abstract rule \"{}\" is not defined", id))
+                })?;
+                extending.push(id);
+                self.rule_body(a, params, lets, checks, extending)?;
+                extending.pop();
+            }
+        }
+        Ok(())
+    }
+
+    fn message(
+        &self,
+        n: &RNode,
+        params: &[(String, String)],
+    ) -> Result<Vec<Message>, ValidationError> {
+        let mut m = vec![];
+        for c in n.child_iter() {
+            match c.node_type() {
+                NodeType::Text => m.push(Message::Text(c.to_string())),
+                NodeType::Element if is_sch(&c, "value-of") => {
+                    let s = attr(&c, "select")
+                        .ok_or_else(|| schema_error("sch:value-of must have a select"))?;
+                    m.push(Message::ValueOf(self.expr(&s, params)?))
+                }
+                NodeType::Element if is_sch(&c, "name") => {
+                    m.push(Message::Name(match attr(&c, "path") {
+                        Some(p) => Some(self.expr(&p, params)?),
+                        None => None,
+                    }))
+                }
+                NodeType::Element => m.extend(self.message(&c, params)?),
+                _ => {}
+            }
+        }
+        Ok(m)
+    }
+}
+
+/// Validate a document, producing an SVRL report.
+pub fn validate_schematron(doc: &RNode, schema: &Schematron) -> Result<RNode, ValidationError> {
+    let mut stctxt = StaticContextBuilder::new()
+        .message(|_| Ok(()))
+        .fetcher(|_: &Url| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .build();
+    let doc = doc.owner_document();
+    let report = Report::new(schema).map_err(|e| document_error(e.to_string()))?;
+
+    let mut vars: HashMap<String, Vec<Sequence<RNode>>> = HashMap::new();
+    bind(&schema.lets, &doc, &mut vars, &mut stctxt)?;
+
+    // Every node in the document, in document order, that may be the context of a rule
+    let mut nodes = vec![doc.clone()];
+    for n in doc.descend_iter() {
+        let atts: Vec<RNode> = n.attribute_iter().collect();
+        nodes.push(n);
+        nodes.extend(atts)
+    }
+
+    for p in &schema.patterns {
+        report
+            .active_pattern(p)
+            .map_err(|e| document_error(e.to_string()))?;
+        let mut pvars = vars.clone();
+        bind(&p.lets, &doc, &mut pvars, &mut stctxt)?;
+        for n in &nodes {
+            let ctxt = context(n, &pvars);
+            // Only the first matching rule in a pattern fires
+            if let Some(r) = p.rules.iter().find(|r| {
+                r.context
+                    .matches(&ctxt, &mut stctxt, &Item::Node(n.clone()))
+            }) {
+                report
+                    .fired_rule(r)
+                    .map_err(|e| document_error(e.to_string()))?;
+                let mut rvars = pvars.clone();
+                bind(&r.lets, n, &mut rvars, &mut stctxt)?;
+                let ctxt = context(n, &rvars);
+                for c in &r.checks {
+                    let result = ctxt
+                        .dispatch(&mut stctxt, &c.test)
+                        .map_err(|e| evaluation_error(&c.source, e))?
+                        .to_bool();
+                    if result == c.report {
+                        let text = message(c, n, &ctxt, &mut stctxt)?;
+                        report
+                            .check(c, n, text)
+                            .map_err(|e| document_error(e.to_string()))?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(report.doc)
+}
+
+fn context(n: &RNode, vars: &HashMap<String, Vec<Sequence<RNode>>>) -> Context<RNode> {
+    ContextBuilder::new()
+        .context(vec![Item::Node(n.clone())])
+        .variables(vars.clone())
+        .build()
+}
+
+// Evaluate variables in turn, so that each may refer to those before it.
+fn bind<
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<RNode, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    lets: &[Let],
+    n: &RNode,
+    vars: &mut HashMap<String, Vec<Sequence<RNode>>>,
+    stctxt: &mut StaticContext<RNode, F, G, H>,
+) -> Result<(), ValidationError> {
+    for l in lets {
+        let v = context(n, vars)
+            .dispatch(stctxt, &l.value)
+            .map_err(|e| evaluation_error(&l.name, e))?;
+        vars.insert(l.name.clone(), vec![v]);
+    }
+    Ok(())
+}
+
+fn message<
+    F: FnMut(&str) -> Result<(), Error>,
+    G: FnMut(&str) -> Result<RNode, Error>,
+    H: FnMut(&Url) -> Result<String, Error>,
+>(
+    c: &Check,
+    n: &RNode,
+    ctxt: &Context<RNode>,
+    stctxt: &mut StaticContext<RNode, F, G, H>,
+) -> Result<String, ValidationError> {
+    let mut s = String::new();
+    for m in &c.message {
+        match m {
+            Message::Text(t) => s.push_str(t),
+            Message::ValueOf(t) => s.push_str(
+                &ctxt
+                    .dispatch(stctxt, t)
+                    .map_err(|e| evaluation_error(&c.source, e))?
+                    .to_string(),
+            ),
+            Message::Name(None) => s.push_str(&n.name().map_or(String::new(), |q| q.to_string())),
+            Message::Name(Some(t)) => s.push_str(
+                &ctxt
+                    .dispatch(stctxt, t)
+                    .map_err(|e| evaluation_error(&c.source, e))?
+                    .first()
+                    .and_then(|i| i.name())
+                    .map_or(String::new(), |q| q.to_string()),
+            ),
+        }
+    }
+    Ok(s.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+// The SVRL document being constructed
+struct Report {
+    doc: RNode,
+    root: RNode,
+}
+
+impl Report {
+    fn new(schema: &Schematron) -> Result<Self, Error> {
+        let mut doc = RNode::new_document();
+        let root = doc.new_element(svrl_name("schematron-output"))?;
+        doc.push(root.clone())?;
+        root.add_namespace(doc.new_namespace(
+            NamespaceUri::try_from(SVRLNS).unwrap(),
+            Some(NamespacePrefix::try_from("svrl").unwrap()),
+            true,
+        )?)?;
+        let r = Report { doc, root };
+        r.attributes(
+            &r.root,
+            &[
+                ("title", &schema.title),
+                ("phase", &schema.phase),
+                ("schemaVersion", &schema.schema_version),
+            ],
+        )?;
+        for (p, u) in &schema.namespaces {
+            let ns = r.element("ns-prefix-in-attribute-values")?;
+            r.attributes(
+                &ns,
+                &[("prefix", &Some(p.clone())), ("uri", &Some(u.clone()))],
+            )?
+        }
+        Ok(r)
+    }
+
+    fn element(&self, name: &str) -> Result<RNode, Error> {
+        let e = self.doc.new_element(svrl_name(name))?;
+        self.root.clone().push(e.clone())?;
+        Ok(e)
+    }
+
+    fn attributes(&self, e: &RNode, atts: &[(&str, &Option<String>)]) -> Result<(), Error> {
+        for (name, value) in atts {
+            if let Some(v) = value {
+                e.add_attribute(self.doc.new_attribute(
+                    QName::from_local_name(NcName::try_from(*name).unwrap()),
+                    Rc::new(Value::from(v.as_str())),
+                )?)?
+            }
+        }
+        Ok(())
+    }
+
+    fn active_pattern(&self, p: &SchPattern) -> Result<(), Error> {
+        let e = self.element("active-pattern")?;
+        self.attributes(&e, &[("id", &p.id), ("name", &p.name)])
+    }
+
+    fn fired_rule(&self, r: &Rule) -> Result<(), Error> {
+        let e = self.element("fired-rule")?;
+        self.attributes(
+            &e,
+            &[
+                ("context", &Some(r.source.clone())),
+                ("id", &r.id),
+                ("role", &r.role),
+                ("flag", &r.flag),
+            ],
+        )
+    }
+
+    fn check(&self, c: &Check, n: &RNode, text: String) -> Result<(), Error> {
+        let e = self.element(if c.report {
+            "successful-report"
+        } else {
+            "failed-assert"
+        })?;
+        self.attributes(
+            &e,
+            &[
+                ("test", &Some(c.source.clone())),
+                ("location", &Some(location(n))),
+                ("id", &c.id),
+                ("role", &c.role),
+                ("flag", &c.flag),
+            ],
+        )?;
+        let mut t = self.doc.new_element(svrl_name("text"))?;
+        e.clone().push(t.clone())?;
+        t.push(self.doc.new_text(Rc::new(Value::from(text)))?)
+    }
+}
+
+/// An XPath expression that locates the node, using EQNames so that it does not depend on namespace prefixes.
+fn location(n: &RNode) -> String {
+    let parent = n
+        .parent()
+        .filter(|p| p.node_type() != NodeType::Document)
+        .map_or(String::new(), |p| location(&p));
+    let position = |test: &dyn Fn(&RNode) -> bool| {
+        n.parent().map_or(1, |p| {
+            p.child_iter()
+                .take_while(|c| !c.is_same(n))
+                .filter(test)
+                .count()
+                + 1
+        })
+    };
+    match n.node_type() {
+        NodeType::Document => String::from("/"),
+        NodeType::Element => {
+            let qn = n.name().unwrap();
+            format!(
+                "{}/{}[{}]",
+                parent,
+                eqname(&qn),
+                position(&|c| c.node_type() == NodeType::Element && c.name() == Some(qn.clone()))
+            )
+        }
+        NodeType::Attribute => format!("{}/@{}", parent, eqname(&n.name().unwrap())),
+        NodeType::Text => format!(
+            "{}/text()[{}]",
+            parent,
+            position(&|c| c.node_type() == NodeType::Text)
+        ),
+        NodeType::Comment => format!(
+            "{}/comment()[{}]",
+            parent,
+            position(&|c| c.node_type() == NodeType::Comment)
+        ),
+        NodeType::ProcessingInstruction => format!(
+            "{}/processing-instruction()[{}]",
+            parent,
+            position(&|c| c.node_type() == NodeType::ProcessingInstruction)
+        ),
+        _ => parent,
+    }
+}
+
+fn eqname(qn: &QName) -> String {
+    match qn.namespace_uri() {
+        Some(u) => format!("Q{{{}}}{}", u, qn.local_name()),
+        None => qn.local_name().to_string(),
+    }
+}
+
+// Replace references to the parameters of an abstract pattern.
+fn substitute(s: &str, params: &[(String, String)]) -> String {
+    let mut result = String::new();
+    let mut rest = s;
+    while let Some(i) = rest.find('$') {
+        result.push_str(&rest[..i]);
+        let after = &rest[i + 1..];
+        let len = after
+            .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':')))
+            .unwrap_or(after.len());
+        match params.iter().find(|(name, _)| *name == after[..len]) {
+            Some((_, value)) => result.push_str(value),
+            None => {
+                result.push('$');
+                result.push_str(&after[..len])
+            }
+        }
+        rest = &after[len..];
+    }
+    result.push_str(rest);
+    result
+}
+
+fn svrl_name(local: &str) -> QName {
+    QName::new_from_parts(
+        NcName::try_from(local).unwrap(),
+        Some(NamespaceUri::try_from(SVRLNS).unwrap()),
+    )
+}
+
+fn is_sch(n: &RNode, local: &str) -> bool {
+    n.node_type() == NodeType::Element
+        && n.name().is_some_and(|qn| {
+            qn.namespace_uri().is_some_and(|u| u.to_string() == SCHNS)
+                && qn.local_name().to_string() == local
+        })
+}
+
+fn is_abstract(n: &RNode) -> bool {
+    attr(n, "abstract").as_deref() == Some("true")
+}
+
+fn sch_children(n: &RNode, local: &str) -> Vec<RNode> {
+    n.child_iter().filter(|c| is_sch(c, local)).collect()
+}
+
+fn attr(n: &RNode, name: &str) -> Option<String> {
+    n.get_attribute_node(&QName::from_local_name(NcName::try_from(name).unwrap()))
+        .map(|a| a.to_string())
+}
+
+fn schema_error(msg: impl Into<String>) -> ValidationError {
+    ValidationError::SchemaError(msg.into())
+}
+
+fn document_error(msg: impl Into<String>) -> ValidationError {
+    ValidationError::DocumentError(msg.into())
+}
+
+fn evaluation_error(expr: &str, e: Error) -> ValidationError {
+    document_error(format!("error evaluating \"{}\": {}", expr, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{ParseError, xml};
+
+    fn parse(s: &str) -> RNode {
+        xml::parse(
+            RNode::new_document(),
+            s,
+            Some(|_: &_| Err(ParseError::MissingNameSpace)),
+        )
+        .expect("unable to parse document")
+    }
+
+    fn failures(svrl: &RNode) -> Vec<String> {
+        svrl.descend_iter()
+            .filter(|n| {
+                n.name()
+                    .is_some_and(|qn| qn.local_name().to_string() == "failed-assert")
+            })
+            .map(|n| n.to_string())
+            .collect()
+    }
+
+    const SCHEMA: &str = r#"<schema xmlns="http://purl.oclc.org/dsdl/schematron" defaultPhase="prices">
+  <ns prefix="o" uri="urn:orders"/>
+  <let name="max" value="100"/>
+  <phase id="prices"><active pattern="price"/></phase>
+  <phase id="names"><active pattern="named"/></phase>
+  <pattern id="price">
+    <rule context="o:item">
+      <let name="p" value="number(@price)"/>
+      <assert test="$p &lt;= $max">price is <value-of select="$p"/></assert>
+      <report test="$p = 0">free item</report>
+    </rule>
+  </pattern>
+  <pattern id="required" abstract="true">
+    <rule context="$element">
+      <assert test="$attribute">missing attribute</assert>
+    </rule>
+  </pattern>
+  <pattern id="named" is-a="required">
+    <param name="element" value="o:item"/>
+    <param name="attribute" value="@name"/>
+  </pattern>
+</schema>"#;
+
+    const DOC: &str =
+        r#"<order xmlns="urn:orders"><item price="150" name="a"/><item price="0"/></order>"#;
+
+    #[test]
+    fn schematron_assert() {
+        let sch = Schematron::new(&parse(SCHEMA), None).expect("unable to compile schema");
+        let svrl = validate_schematron(&parse(DOC), &sch).expect("validation failed");
+        assert_eq!(failures(&svrl), vec!["price is 150"]);
+        assert_eq!(
+            svrl.descend_iter()
+                .filter(|n| n
+                    .name()
+                    .is_some_and(|qn| qn.local_name().to_string() == "successful-report"))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn schematron_abstract_pattern() {
+        let sch = Schematron::new(&parse(SCHEMA), Some("names")).expect("unable to compile schema");
+        let svrl = validate_schematron(&parse(DOC), &sch).expect("validation failed");
+        assert_eq!(failures(&svrl), vec!["missing attribute"]);
+    }
+
+    #[test]
+    fn schematron_location() {
+        let doc = parse("<a><b/><b><c/></b></a>");
+        let c = doc
+            .descend_iter()
+            .find(|n| {
+                n.name()
+                    .is_some_and(|qn| qn.local_name().to_string() == "c")
+            })
+            .unwrap();
+        assert_eq!(location(&c), "/a[1]/b[2]/c[1]")
+    }
+
+    #[test]
+    fn schematron_substitute() {
+        let params = vec![(String::from("a"), String::from("@x"))];
+        assert_eq!(substitute("$a = $ab", &params), "@x = $ab")
+    }
+}
@@ -0,0 +1,423 @@
+//! # Schema association
+//!
+//! Validate a document against the schemas associated with it by xml-model processing instructions (<https://www.w3.org/TR/xml-model/>).
+//!
+//! ```xml
+//! <?xml-model href="order.xsd" schematypens="http://www.w3.org/2001/XMLSchema"?>
+//! <?xml-model href="rules.sch" schematypens="http://purl.oclc.org/dsdl/schematron" phase="prices"?>
+//! <order>...</order>
+//! ```
+//!
+//! Each schema is retrieved using a resolver closure, which is given the value of the href pseudo-attribute.
+//! The kind of schema is determined by the schematypens pseudo-attribute, or failing that the type pseudo-attribute or the file extension of the href.
+//! DTDs, W3C XML Schemas, ISO Schematron schemas, and RELAX NG schemas in either the XML or compact syntax are supported.
+//! Files included by a compact syntax schema are also retrieved using the resolver.
+
+use std::rc::Rc;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use url::Url;
+
+use crate::item::{Node, NodeType};
+use crate::parser::{ParseError, xml};
+use crate::trees::smite::RNode;
+use crate::validators::dtd::validate_with_dtd;
+use crate::validators::relaxng::{validate_relaxng, validate_relaxng_compact};
+use crate::validators::schematron::{Schematron, validate_schematron};
+use crate::validators::xsd::XsdSchema;
+use crate::validators::{Schema, ValidationError};
+use crate::xdmerror::Error;
+
+const RNGNS: &str = "http://relaxng.org/ns/structure/1.0";
+const SCHNS: &str = "http://purl.oclc.org/dsdl/schematron";
+const XSDNS: &str = "http://www.w3.org/2001/XMLSchema";
+
+static PSEUDO_ATTRIBUTE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"([A-Za-z_][\w.-]*)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap());
+
+/// The combined result of validating a document against all of its associated schemas.
+#[derive(Debug)]
+pub struct ModelReport {
+    pub results: Vec<ModelResult>,
+}
+
+impl ModelReport {
+    /// A document is valid if it was successfully validated against every associated schema.
+    pub fn is_valid(&self) -> bool {
+        self.results
+            .iter()
+            .all(|r| matches!(r.outcome, ModelOutcome::Valid))
+    }
+}
+
+/// The result of validating against one associated schema.
+#[derive(Debug)]
+pub struct ModelResult {
+    pub href: String,
+    pub schematypens: Option<String>,
+    pub outcome: ModelOutcome,
+    /// The SVRL report, for a Schematron schema.
+    pub svrl: Option<RNode>,
+}
+
+#[derive(Debug)]
+pub enum ModelOutcome {
+    Valid,
+    /// The document is not valid. Gives the reasons why.
+    Invalid(Vec<String>),
+    /// The schema could not be retrieved or compiled.
+    SchemaError(String),
+    /// The kind of schema is not supported.
+    Unsupported(String),
+}
+
+enum SchemaKind {
+    Dtd,
+    Xsd,
+    Schematron,
+    RelaxNG,
+    RelaxNGCompact,
+    Unknown(String),
+}
+
+/// Validate a document against the schemas declared by its xml-model processing instructions.
+/// The resolver is called with the href of each schema and returns the schema's text.
+/// The document is not changed, except that a W3C XML Schema gives typed values to valid nodes.
+pub fn validate_xml_model<F>(doc: &RNode, mut resolver: F) -> Result<ModelReport, ValidationError>
+where
+    F: FnMut(&str) -> Result<String, Error>,
+{
+    let doc = doc.owner_document();
+    let mut results = vec![];
+    // xml-model processing instructions must appear in the prolog
+    for pi in doc
+        .child_iter()
+        .take_while(|c| c.node_type() != NodeType::Element)
+    {
+        if pi.node_type() != NodeType::ProcessingInstruction
+            || pi
+                .name()
+                .is_none_or(|n| n.local_name().to_string() != "xml-model")
+        {
+            continue;
+        }
+        let atts = pseudo_attributes(&pi.value().to_string());
+        let get = |n: &str| atts.iter().find(|(k, _)| k == n).map(|(_, v)| v.clone());
+        let href = get("href").ok_or_else(|| {
+            ValidationError::DocumentError(String::from(
+                "xml-model processing instruction has no href",
+            ))
+        })?;
+        let schematypens = get("schematypens");
+        let kind = schema_kind(&href, schematypens.as_deref(), get("type").as_deref());
+        let mut svrl = None;
+        let outcome = match kind {
+            SchemaKind::Unknown(k) => {
+                ModelOutcome::Unsupported(format!("unknown kind of schema \"{}\"", k))
+            }
+            _ => match resolver(&href) {
+                Err(e) => {
+                    ModelOutcome::SchemaError(format!("unable to retrieve \"{}\": {}", href, e))
+                }
+                Ok(text) => match kind {
+                    SchemaKind::Dtd => validate_dtd(&doc, &text),
+                    SchemaKind::Xsd => validate_xsd(&doc, &text),
+                    SchemaKind::RelaxNG => validate_rng(&doc, &text),
+                    SchemaKind::RelaxNGCompact => {
+                        // Included schemas are resolved against the schema's own location, when that is absolute
+                        outcome(validate_relaxng_compact(
+                            &doc,
+                            &text,
+                            Url::parse(&href).ok(),
+                            |u: &Url| resolver(u.as_str()),
+                        ))
+                    }
+                    _ => {
+                        let (o, r) = validate_sch(&doc, &text, get("phase").as_deref());
+                        svrl = r;
+                        o
+                    }
+                },
+            },
+        };
+        results.push(ModelResult {
+            href,
+            schematypens,
+            outcome,
+            svrl,
+        })
+    }
+    Ok(ModelReport { results })
+}
+
+fn schema_kind(href: &str, schematypens: Option<&str>, mime: Option<&str>) -> SchemaKind {
+    match (schematypens, mime) {
+        (Some(RNGNS), _) if href.to_ascii_lowercase().ends_with(".rnc") => {
+            SchemaKind::RelaxNGCompact
+        }
+        (Some(RNGNS), _) => SchemaKind::RelaxNG,
+        (Some(SCHNS), _) => SchemaKind::Schematron,
+        (Some(XSDNS), _) => SchemaKind::Xsd,
+        (Some(ns), _) => SchemaKind::Unknown(ns.to_string()),
+        (None, Some("application/xml-dtd")) => SchemaKind::Dtd,
+        (None, Some("application/relax-ng-compact-syntax")) => SchemaKind::RelaxNGCompact,
+        _ => match href
+            .rsplit_once('.')
+            .map(|(_, e)| e.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("dtd") => SchemaKind::Dtd,
+            Some("xsd") => SchemaKind::Xsd,
+            Some("sch") => SchemaKind::Schematron,
+            Some("rng") => SchemaKind::RelaxNG,
+            Some("rnc") => SchemaKind::RelaxNGCompact,
+            _ => SchemaKind::Unknown(href.to_string()),
+        },
+    }
+}
+
+// The pseudo-attributes of a processing instruction, with predefined entity and character references expanded.
+fn pseudo_attributes(s: &str) -> Vec<(String, String)> {
+    PSEUDO_ATTRIBUTE
+        .captures_iter(s)
+        .map(|c| {
+            let v = c.get(2).or_else(|| c.get(3)).map_or("", |m| m.as_str());
+            (c[1].to_string(), unescape(v))
+        })
+        .collect()
+}
+
+fn unescape(s: &str) -> String {
+    let mut result = String::new();
+    let mut rest = s;
+    while let Some(i) = rest.find('&') {
+        result.push_str(&rest[..i]);
+        let r = &rest[i..];
+        let expanded = r.find(';').and_then(|j| {
+            let c = match &r[1..j] {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                e => e
+                    .strip_prefix("#x")
+                    .map_or_else(
+                        || e.strip_prefix('#').and_then(|d| d.parse().ok()),
+                        |h| u32::from_str_radix(h, 16).ok(),
+                    )
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, j + 1))
+        });
+        match expanded {
+            Some((c, len)) => {
+                result.push(c);
+                rest = &r[len..]
+            }
+            None => {
+                result.push('&');
+                rest = &r[1..]
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+fn parse(text: &str) -> Result<RNode, Error> {
+    xml::parse(
+        RNode::new_document(),
+        text,
+        Some(|_: &_| Err(ParseError::MissingNameSpace)),
+    )
+}
+
+fn outcome(r: Result<(), ValidationError>) -> ModelOutcome {
+    match r {
+        Ok(()) => ModelOutcome::Valid,
+        Err(ValidationError::DocumentError(m)) => ModelOutcome::Invalid(vec![m]),
+        Err(ValidationError::SchemaError(m)) => ModelOutcome::SchemaError(m),
+    }
+}
+
+// The DTD is parsed as the internal subset of a minimal document.
+// The document is validated against it without being changed, so its own DTD, if any, is kept.
+fn validate_dtd(doc: &RNode, text: &str) -> ModelOutcome {
+    let Some(root) = doc
+        .child_iter()
+        .find(|c| c.node_type() == NodeType::Element)
+    else {
+        return ModelOutcome::Invalid(vec![String::from("document has no document element")]);
+    };
+    let name = root.to_prefixed_name();
+    let dtd = match parse(&format!("<!DOCTYPE {} [\n{}\n]><{}/>", name, text, name))
+        .map(|d| d.get_dtd())
+    {
+        Ok(Some(dtd)) => dtd,
+        Ok(None) => return ModelOutcome::SchemaError(String::from("no DTD found")),
+        Err(e) => return ModelOutcome::SchemaError(format!("unable to parse DTD: {}", e)),
+    };
+    // The DTD validator reports an invalid document as a schema error
+    match validate_with_dtd(doc.clone(), dtd) {
+        Ok(()) => ModelOutcome::Valid,
+        Err(ValidationError::DocumentError(m)) | Err(ValidationError::SchemaError(m)) => {
+            ModelOutcome::Invalid(vec![m])
+        }
+    }
+}
+
+fn validate_xsd(doc: &RNode, text: &str) -> ModelOutcome {
+    match parse(text) {
+        Err(e) => ModelOutcome::SchemaError(format!("unable to parse schema: {}", e)),
+        Ok(s) => match XsdSchema::new(&s) {
+            Err(e) => outcome(Err(e)),
+            Ok(x) => outcome(doc.validate(Schema::XSD(Rc::new(x)))),
+        },
+    }
+}
+
+fn validate_rng(doc: &RNode, text: &str) -> ModelOutcome {
+    match parse(text) {
+        Err(e) => ModelOutcome::SchemaError(format!("unable to parse schema: {}", e)),
+        Ok(s) => outcome(validate_relaxng(doc, &s)),
+    }
+}
+
+fn validate_sch(doc: &RNode, text: &str, phase: Option<&str>) -> (ModelOutcome, Option<RNode>) {
+    let sch = match parse(text) {
+        Err(e) => {
+            return (
+                ModelOutcome::SchemaError(format!("unable to parse schema: {}", e)),
+                None,
+            );
+        }
+        Ok(s) => match Schematron::new(&s, phase) {
+            Err(e) => return (outcome(Err(e)), None),
+            Ok(sch) => sch,
+        },
+    };
+    match validate_schematron(doc, &sch) {
+        Err(e) => (outcome(Err(e)), None),
+        Ok(svrl) => {
+            let failures: Vec<String> = svrl
+                .descend_iter()
+                .filter(|n| {
+                    n.name()
+                        .is_some_and(|qn| qn.local_name().to_string() == "failed-assert")
+                })
+                .map(|n| {
+                    let location = n
+                        .attribute_iter()
+                        .find(|a| {
+                            a.name()
+                                .is_some_and(|qn| qn.local_name().to_string() == "location")
+                        })
+                        .map_or(String::new(), |a| a.to_string());
+                    format!("{}: {}", location, n.to_string())
+                })
+                .collect();
+            if failures.is_empty() {
+                (ModelOutcome::Valid, Some(svrl))
+            } else {
+                (ModelOutcome::Invalid(failures), Some(svrl))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xml_model_pseudo_attributes() {
+        assert_eq!(
+            pseudo_attributes(
+                r#"href="a&amp;b.sch" schematypens='http://purl.oclc.org/dsdl/schematron'"#
+            ),
+            vec![
+                (String::from("href"), String::from("a&b.sch")),
+                (String::from("schematypens"), String::from(SCHNS)),
+            ]
+        )
+    }
+
+    #[test]
+    fn xml_model_dispatch() {
+        let doc = parse(
+            r#"<?xml-model href="rules.sch" schematypens="http://purl.oclc.org/dsdl/schematron"?>
+<?xml-model href="order.rng"?>
+<?xml-model href="order.xyz"?>
+<order><item/></order>"#,
+        )
+        .expect("unable to parse document");
+        let report = validate_xml_model(&doc, |href| match href {
+            "rules.sch" => Ok(String::from(
+                r#"<schema xmlns="http://purl.oclc.org/dsdl/schematron"><pattern><rule context="item"><assert test="@id">no id</assert></rule></pattern></schema>"#,
+            )),
+            "order.rng" => Ok(String::from(
+                r#"<element name="order" xmlns="http://relaxng.org/ns/structure/1.0"><oneOrMore><element name="item"><empty/></element></oneOrMore></element>"#,
+            )),
+            _ => panic!("unexpected href \"{}\"", href),
+        })
+        .expect("validation failed");
+        assert!(!report.is_valid());
+        assert_eq!(report.results.len(), 3);
+        assert!(matches!(&report.results[0].outcome, ModelOutcome::Invalid(f) if f.len() == 1));
+        assert!(report.results[0].svrl.is_some());
+        assert!(matches!(report.results[1].outcome, ModelOutcome::Valid));
+        assert!(matches!(
+            report.results[2].outcome,
+            ModelOutcome::Unsupported(_)
+        ));
+    }
+
+    #[test]
+    fn xml_model_dtd() {
+        let resolver = |href: &str| match href {
+            "a.dtd" => Ok(String::from("<!ELEMENT a (b)>\n<!ELEMENT b EMPTY>")),
+            _ => panic!("unexpected href \"{}\"", href),
+        };
+        let doc = parse(r#"<?xml-model href="a.dtd" type="application/xml-dtd"?><a><b/></a>"#)
+            .expect("unable to parse document");
+        let report = validate_xml_model(&doc, resolver).expect("validation failed");
+        assert!(report.is_valid());
+        // The document is not changed by validation
+        assert!(doc.get_dtd().is_none());
+        let doc = parse(r#"<?xml-model href="a.dtd" type="application/xml-dtd"?><a/>"#)
+            .expect("unable to parse document");
+        let report = validate_xml_model(&doc, resolver).expect("validation failed");
+        assert!(matches!(
+            report.results[0].outcome,
+            ModelOutcome::Invalid(_)
+        ));
+        assert!(doc.get_dtd().is_none());
+    }
+
+    #[test]
+    fn xml_model_relaxng_compact() {
+        let doc = parse(
+            r#"<?xml-model href="http://example.org/schemas/order.rnc" schematypens="http://relaxng.org/ns/structure/1.0"?>
+<order><item id="a"/><item/></order>"#,
+        )
+        .expect("unable to parse document");
+        let report = validate_xml_model(&doc, |href| match href {
+            "http://example.org/schemas/order.rnc" => Ok(String::from(
+                "include \"item.rnc\"\nstart = element order { item+ }",
+            )),
+            "http://example.org/schemas/item.rnc" => Ok(String::from(
+                "item = element item { attribute id { xsd:ID } }",
+            )),
+            _ => panic!("unexpected href \"{}\"", href),
+        })
+        .expect("validation failed");
+        assert_eq!(report.results.len(), 1);
+        // The second item has no id
+        assert!(matches!(
+            report.results[0].outcome,
+            ModelOutcome::Invalid(_)
+        ));
+    }
+}
@@ -0,0 +1,1093 @@
+//! # XML Schema validation
+//!
+//! Validation of a document against a W3C XML Schema 1.0 (<https://www.w3.org/TR/xmlschema-1/>).
+//!
+//! A schema document is first compiled into an [XsdSchema], which is independent of the tree implementation.
+//! The compiled schema may then be used to validate any number of documents.
+//!
+//! Supported are element, attribute, and type declarations (both named and anonymous),
+//! model groups and wildcards, simple types with facets, list and union types,
+//! complex types with simple or complex content (including derivation by extension and restriction),
+//! substitution groups, xsi:type and xsi:nil, and identity constraints (unique, key, keyref).
+//! Schema composition (include, import, and redefine) is not supported.
+//!
+//! When a document is valid, attributes and elements with simple content are annotated with typed values,
+//! so that [Node::typed_value] gives a date, decimal, etc. as declared in the schema.
+//! The content of the document is not changed, other than by adding default values for absent attributes and empty elements.
+
+mod validate;
+
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use qualname::{NamespacePrefix, NamespaceUri, NcName, QName};
+
+use crate::item::{Node, NodeType};
+use crate::validators::ValidationError;
+use crate::validators::datatypes;
+
+pub(crate) use validate::validate_xsd;
+
+const XSDNS: &str = "http://www.w3.org/2001/XMLSchema";
+const XSINS: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+/// A compiled XML Schema.
+pub struct XsdSchema {
+    elements: BTreeMap<QName, Rc<ElementDecl>>,
+    attributes: BTreeMap<QName, Rc<AttributeDecl>>,
+    types: BTreeMap<QName, Type>,
+    // The direct members of each substitution group, by head element
+    substitutions: BTreeMap<QName, Vec<QName>>,
+    constraints: BTreeMap<QName, Rc<IdentityConstraint>>,
+}
+
+#[derive(Clone)]
+enum Type {
+    Simple(Rc<SimpleType>),
+    Complex(Rc<ComplexType>),
+}
+
+#[derive(Clone)]
+enum TypeRef {
+    Named(QName),
+    Anonymous(Type),
+}
+
+enum SimpleType {
+    Builtin(String),
+    Restriction(Rc<SimpleType>, Vec<(String, String)>), // base type, facets
+    List(Rc<SimpleType>),
+    Union(Vec<Rc<SimpleType>>),
+}
+
+struct ComplexType {
+    attributes: Vec<AttributeUse>,
+    attribute_wildcard: Option<Rc<Wildcard>>,
+    content: Content,
+}
+
+enum Content {
+    Empty,
+    Simple(Rc<SimpleType>),
+    Elements(Particle, bool), // content model, mixed
+}
+
+#[derive(Clone)]
+struct Particle {
+    min: usize,
+    max: Option<usize>, // None is unbounded
+    term: Term,
+}
+
+#[derive(Clone)]
+enum Term {
+    Element(ElementRef),
+    Any(Rc<Wildcard>),
+    Sequence(Vec<Particle>),
+    Choice(Vec<Particle>),
+    All(Vec<Particle>),
+}
+
+#[derive(Clone)]
+enum ElementRef {
+    Local(Rc<ElementDecl>),
+    Global(QName),
+}
+
+struct ElementDecl {
+    name: QName,
+    type_ref: Option<TypeRef>,
+    substitution_group: Option<QName>,
+    nillable: bool,
+    is_abstract: bool,
+    default: Option<String>,
+    fixed: Option<String>,
+    constraints: Vec<Rc<IdentityConstraint>>,
+}
+
+struct AttributeDecl {
+    name: QName,
+    type_ref: Option<TypeRef>,
+    default: Option<String>,
+    fixed: Option<String>,
+}
+
+#[derive(Clone)]
+struct AttributeUse {
+    decl: Rc<AttributeDecl>,
+    required: bool,
+    prohibited: bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ProcessContents {
+    Strict,
+    Lax,
+    Skip,
+}
+
+enum NamespaceConstraint {
+    Any,
+    Other(Option<String>), // any namespace other than the target namespace, and not absent
+    Set(Vec<Option<String>>),
+}
+
+struct Wildcard {
+    namespaces: NamespaceConstraint,
+    process: ProcessContents,
+}
+
+impl Wildcard {
+    fn allows(&self, ns: &Option<String>) -> bool {
+        match &self.namespaces {
+            NamespaceConstraint::Any => true,
+            NamespaceConstraint::Other(t) => ns.is_some() && ns != t,
+            NamespaceConstraint::Set(s) => s.contains(ns),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ConstraintKind {
+    Unique,
+    Key,
+    KeyRef,
+}
+
+struct IdentityConstraint {
+    kind: ConstraintKind,
+    name: QName,
+    refer: Option<QName>,
+    selector: Vec<IcPath>,
+    fields: Vec<Vec<IcPath>>,
+}
+
+/// A path in the XPath subset used by identity constraints.
+struct IcPath {
+    descendant: bool,
+    steps: Vec<IcStep>,
+    attribute: Option<IcNameTest>,
+}
+
+enum IcStep {
+    Current,
+    Child(IcNameTest),
+}
+
+enum IcNameTest {
+    Any,
+    Namespace(Option<String>),
+    Name(QName),
+}
+
+impl IcNameTest {
+    fn matches(&self, qn: &QName) -> bool {
+        match self {
+            IcNameTest::Any => true,
+            IcNameTest::Namespace(ns) => namespace_of(qn) == *ns,
+            IcNameTest::Name(n) => n == qn,
+        }
+    }
+}
+
+impl XsdSchema {
+    /// Compile a schema document.
+    pub fn new<N: Node>(schema: &N) -> Result<Self, ValidationError> {
+        let root = if schema.node_type() == NodeType::Document {
+            schema
+                .child_iter()
+                .find(|c| c.node_type() == NodeType::Element)
+                .ok_or_else(|| schema_error("schema document has no document element"))?
+        } else {
+            schema.clone()
+        };
+        if !is_xs(&root) || local(&root) != "schema" {
+            return Err(schema_error("document element is not xs:schema"));
+        }
+
+        let mut c = Compiler {
+            target: attr(&root, "targetNamespace").filter(|t| !t.is_empty()),
+            element_qualified: attr(&root, "elementFormDefault").as_deref() == Some("qualified"),
+            attribute_qualified: attr(&root, "attributeFormDefault").as_deref()
+                == Some("qualified"),
+            type_defs: BTreeMap::new(),
+            group_defs: BTreeMap::new(),
+            attribute_group_defs: BTreeMap::new(),
+            types: BTreeMap::new(),
+            pending: vec![],
+            attributes: BTreeMap::new(),
+            constraints: BTreeMap::new(),
+        };
+
+        let mut element_defs = vec![];
+        let mut attribute_defs = vec![];
+        for d in xs_children(&root) {
+            let l = local(&d);
+            match l.as_str() {
+                "element" => element_defs.push(d),
+                "attribute" => attribute_defs.push(d),
+                "simpleType" | "complexType" | "group" | "attributeGroup" => {
+                    let qn = c.target_name(&d)?;
+                    match l.as_str() {
+                        "group" => c.group_defs.insert(qn, d),
+                        "attributeGroup" => c.attribute_group_defs.insert(qn, d),
+                        _ => c.type_defs.insert(qn, d),
+                    };
+                }
+                "notation" => {}
+                _ => {
+                    return Err(schema_error(format!(
+                        "schema component xs:{} is not supported",
+                        l
+                    )));
+                }
+            }
+        }
+
+        for d in attribute_defs {
+            let a = c.attribute(&d, true)?;
+            c.attributes.insert(a.name.clone(), Rc::new(a));
+        }
+        let names: Vec<QName> = c.type_defs.keys().cloned().collect();
+        for qn in names {
+            c.named_type(&qn)?;
+        }
+        let mut elements = BTreeMap::new();
+        let mut substitutions: BTreeMap<QName, Vec<QName>> = BTreeMap::new();
+        for d in element_defs {
+            let e = c.element(&d, true)?;
+            if let Some(h) = &e.substitution_group {
+                substitutions
+                    .entry(h.clone())
+                    .or_default()
+                    .push(e.name.clone());
+            }
+            elements.insert(e.name.clone(), Rc::new(e));
+        }
+
+        Ok(XsdSchema {
+            elements,
+            attributes: c.attributes,
+            types: c.types,
+            substitutions,
+            constraints: c.constraints,
+        })
+    }
+
+    fn lookup_type(&self, qn: &QName) -> Option<Type> {
+        self.types.get(qn).cloned().or_else(|| builtin_type(qn))
+    }
+}
+
+struct Compiler<N: Node> {
+    target: Option<String>,
+    element_qualified: bool,
+    attribute_qualified: bool,
+    type_defs: BTreeMap<QName, N>,
+    group_defs: BTreeMap<QName, N>,
+    attribute_group_defs: BTreeMap<QName, N>,
+    types: BTreeMap<QName, Type>,
+    // Named types that are being compiled, to detect circular definitions
+    pending: Vec<QName>,
+    attributes: BTreeMap<QName, Rc<AttributeDecl>>,
+    constraints: BTreeMap<QName, Rc<IdentityConstraint>>,
+}
+
+impl<N: Node> Compiler<N> {
+    // The name of a top-level component, in the target namespace
+    fn target_name(&self, d: &N) -> Result<QName, ValidationError> {
+        let n = attr(d, "name")
+            .ok_or_else(|| schema_error(format!("top-level xs:{} must have a name", local(d))))?;
+        make_qname(&n, self.target.as_deref())
+    }
+
+    fn named_type(&mut self, qn: &QName) -> Result<Type, ValidationError> {
+        if let Some(t) = self.types.get(qn).cloned().or_else(|| builtin_type(qn)) {
+            return Ok(t);
+        }
+        if self.pending.contains(qn) {
+            return Err(schema_error(format!(
+                "type \"{}\" is derived from itself",
+                qn
+            )));
+        }
+        let d = self
+            .type_defs
+            .get(qn)
+            .cloned()
+            .ok_or_else(|| schema_error(format!("type \"{}\" is not defined", qn)))?;
+        self.pending.push(qn.clone());
+        let t = self.type_definition(&d);
+        self.pending.pop();
+        let t = t?;
+        self.types.insert(qn.clone(), t.clone());
+        Ok(t)
+    }
+
+    fn type_definition(&mut self, d: &N) -> Result<Type, ValidationError> {
+        if local(d) == "simpleType" {
+            Ok(Type::Simple(Rc::new(self.simple_type(d)?)))
+        } else {
+            Ok(Type::Complex(Rc::new(self.complex_type(d)?)))
+        }
+    }
+
+    fn simple_type_ref(&mut self, d: &N, v: &str) -> Result<Rc<SimpleType>, ValidationError> {
+        let qn = resolve(d, v, true).map_err(schema_error)?;
+        match self.named_type(&qn)? {
+            Type::Simple(s) => Ok(s),
+            Type::Complex(_) => Err(schema_error(format!(
+                "type \"{}\" is not a simple type",
+                qn
+            ))),
+        }
+    }
+
+    // The base type of a restriction or item type of a list, either named by an attribute or given as an anonymous type.
+    fn simple_base(&mut self, d: &N, attribute: &str) -> Result<Rc<SimpleType>, ValidationError> {
+        match attr(d, attribute) {
+            Some(b) => self.simple_type_ref(d, &b),
+            None => xs_children(d)
+                .iter()
+                .find(|c| local(*c) == "simpleType")
+                .map_or_else(
+                    || Err(schema_error(format!("xs:{} has no base type", local(d)))),
+                    |s| Ok(Rc::new(self.simple_type(s)?)),
+                ),
+        }
+    }
+
+    fn simple_type(&mut self, d: &N) -> Result<SimpleType, ValidationError> {
+        let def = xs_children(d)
+            .into_iter()
+            .next()
+            .ok_or_else(|| schema_error("xs:simpleType has no content"))?;
+        match local(&def).as_str() {
+            "restriction" => {
+                let base = self.simple_base(&def, "base")?;
+                Ok(SimpleType::Restriction(base, facets(&def)))
+            }
+            "list" => Ok(SimpleType::List(self.simple_base(&def, "itemType")?)),
+            "union" => {
+                let mut members = vec![];
+                for m in attr(&def, "memberTypes")
+                    .unwrap_or_default()
+                    .split_ascii_whitespace()
+                {
+                    members.push(self.simple_type_ref(&def, m)?)
+                }
+                for s in xs_children(&def) {
+                    members.push(Rc::new(self.simple_type(&s)?))
+                }
+                Ok(SimpleType::Union(members))
+            }
+            l => Err(schema_error(format!(
+                "unexpected xs:{} in xs:simpleType",
+                l
+            ))),
+        }
+    }
+
+    fn complex_type(&mut self, d: &N) -> Result<ComplexType, ValidationError> {
+        let mixed = attr(d, "mixed").is_some_and(|m| is_true(&m));
+        let children = xs_children(d);
+        match children.first().map(local).as_deref() {
+            Some("simpleContent") => {
+                let def = xs_children(&children[0])
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| schema_error("xs:simpleContent has no content"))?;
+                let b = attr(&def, "base")
+                    .ok_or_else(|| schema_error("xs:simpleContent derivation has no base"))?;
+                let base = self.named_type(&resolve(&def, &b, true).map_err(schema_error)?)?;
+                let (base_content, base_attributes) = match base {
+                    Type::Simple(s) => (s, vec![]),
+                    Type::Complex(ct) => match &ct.content {
+                        Content::Simple(s) => (s.clone(), ct.attributes.clone()),
+                        _ => {
+                            return Err(schema_error(format!(
+                                "base type \"{}\" of xs:simpleContent does not have simple content",
+                                b
+                            )));
+                        }
+                    },
+                };
+                let content = if local(&def) == "restriction" {
+                    Rc::new(SimpleType::Restriction(base_content, facets(&def)))
+                } else {
+                    base_content
+                };
+                let (attributes, attribute_wildcard) =
+                    self.attribute_uses(&xs_children(&def), base_attributes)?;
+                Ok(ComplexType {
+                    attributes,
+                    attribute_wildcard,
+                    content: Content::Simple(content),
+                })
+            }
+            Some("complexContent") => {
+                let mixed = attr(&children[0], "mixed").map_or(mixed, |m| is_true(&m));
+                let def = xs_children(&children[0])
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| schema_error("xs:complexContent has no content"))?;
+                let b = attr(&def, "base")
+                    .ok_or_else(|| schema_error("xs:complexContent derivation has no base"))?;
+                let base = match self.named_type(&resolve(&def, &b, true).map_err(schema_error)?)? {
+                    Type::Complex(ct) => ct,
+                    Type::Simple(_) => {
+                        return Err(schema_error(format!(
+                            "base type \"{}\" of xs:complexContent is a simple type",
+                            b
+                        )));
+                    }
+                };
+                let defs = xs_children(&def);
+                let particle = self.content_particle(&defs)?;
+                if local(&def) == "extension" {
+                    // The content of an extension is the base content followed by the extended content
+                    let content = match (&base.content, particle) {
+                        (Content::Elements(p, _), Some(q)) => Content::Elements(
+                            Particle {
+                                min: 1,
+                                max: Some(1),
+                                term: Term::Sequence(vec![p.clone(), q]),
+                            },
+                            mixed,
+                        ),
+                        (Content::Elements(p, _), None) => Content::Elements(p.clone(), mixed),
+                        (_, Some(q)) => Content::Elements(q, mixed),
+                        (_, None) => empty_content(mixed),
+                    };
+                    let (mut attributes, wildcard) =
+                        self.attribute_uses(&defs, base.attributes.clone())?;
+                    attributes.retain(|a| !a.prohibited);
+                    Ok(ComplexType {
+                        attributes,
+                        attribute_wildcard: wildcard.or_else(|| base.attribute_wildcard.clone()),
+                        content,
+                    })
+                } else {
+                    let (attributes, attribute_wildcard) =
+                        self.attribute_uses(&defs, base.attributes.clone())?;
+                    Ok(ComplexType {
+                        attributes,
+                        attribute_wildcard,
+                        content: particle
+                            .map_or_else(|| empty_content(mixed), |p| Content::Elements(p, mixed)),
+                    })
+                }
+            }
+            _ => {
+                let particle = self.content_particle(&children)?;
+                let (attributes, attribute_wildcard) = self.attribute_uses(&children, vec![])?;
+                Ok(ComplexType {
+                    attributes,
+                    attribute_wildcard,
+                    content: particle
+                        .map_or_else(|| empty_content(mixed), |p| Content::Elements(p, mixed)),
+                })
+            }
+        }
+    }
+
+    // The content model of a complex type is given by its first model group, if any.
+    fn content_particle(&mut self, defs: &[N]) -> Result<Option<Particle>, ValidationError> {
+        match defs
+            .iter()
+            .find(|c| matches!(local(*c).as_str(), "sequence" | "choice" | "all" | "group"))
+        {
+            Some(g) => self.particle(g).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn particle(&mut self, d: &N) -> Result<Particle, ValidationError> {
+        let min = match attr(d, "minOccurs") {
+            Some(m) => m
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| schema_error(format!("invalid minOccurs \"{}\"", m)))?,
+            None => 1,
+        };
+        let max = match attr(d, "maxOccurs").as_deref().map(str::trim) {
+            Some("unbounded") => None,
+            Some(m) => Some(
+                m.parse::<usize>()
+                    .map_err(|_| schema_error(format!("invalid maxOccurs \"{}\"", m)))?,
+            ),
+            None => Some(1),
+        };
+        let term = match local(d).as_str() {
+            "element" => match attr(d, "ref") {
+                Some(r) => Term::Element(ElementRef::Global(
+                    resolve(d, &r, true).map_err(schema_error)?,
+                )),
+                None => Term::Element(ElementRef::Local(Rc::new(self.element(d, false)?))),
+            },
+            "any" => Term::Any(Rc::new(self.wildcard(d)?)),
+            "sequence" | "choice" | "all" => {
+                let mut particles = vec![];
+                for c in xs_children(d) {
+                    particles.push(self.particle(&c)?)
+                }
+                match local(d).as_str() {
+                    "sequence" => Term::Sequence(particles),
+                    "choice" => Term::Choice(particles),
+                    _ => Term::All(particles),
+                }
+            }
+            "group" => {
+                let r = attr(d, "ref").ok_or_else(|| schema_error("xs:group has no ref"))?;
+                let qn = resolve(d, &r, true).map_err(schema_error)?;
+                let g = self
+                    .group_defs
+                    .get(&qn)
+                    .cloned()
+                    .ok_or_else(|| schema_error(format!("group \"{}\" is not defined", qn)))?;
+                let mg = xs_children(&g)
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| schema_error(format!("group \"{}\" is empty", qn)))?;
+                self.particle(&mg)?.term
+            }
+            l => {
+                return Err(schema_error(format!(
+                    "unexpected xs:{} in content model",
+                    l
+                )));
+            }
+        };
+        Ok(Particle { min, max, term })
+    }
+
+    fn wildcard(&self, d: &N) -> Result<Wildcard, ValidationError> {
+        let namespaces = match attr(d, "namespace").as_deref().map(str::trim) {
+            None | Some("##any") => NamespaceConstraint::Any,
+            Some("##other") => NamespaceConstraint::Other(self.target.clone()),
+            Some(l) => NamespaceConstraint::Set(
+                l.split_ascii_whitespace()
+                    .map(|n| match n {
+                        "##targetNamespace" => self.target.clone(),
+                        "##local" => None,
+                        _ => Some(n.to_string()),
+                    })
+                    .collect(),
+            ),
+        };
+        let process = match attr(d, "processContents").as_deref() {
+            None | Some("strict") => ProcessContents::Strict,
+            Some("lax") => ProcessContents::Lax,
+            Some("skip") => ProcessContents::Skip,
+            Some(p) => {
+                return Err(schema_error(format!("invalid processContents \"{}\"", p)));
+            }
+        };
+        Ok(Wildcard {
+            namespaces,
+            process,
+        })
+    }
+
+    fn element(&mut self, d: &N, global: bool) -> Result<ElementDecl, ValidationError> {
+        let n = attr(d, "name").ok_or_else(|| schema_error("xs:element has no name"))?;
+        let qualified =
+            global || attr(d, "form").map_or(self.element_qualified, |f| f == "qualified");
+        let name = make_qname(
+            &n,
+            if qualified {
+                self.target.as_deref()
+            } else {
+                None
+            },
+        )?;
+        let children = xs_children(d);
+        let type_ref = match attr(d, "type") {
+            Some(t) => Some(TypeRef::Named(resolve(d, &t, true).map_err(schema_error)?)),
+            None => match children
+                .iter()
+                .find(|c| matches!(local(*c).as_str(), "simpleType" | "complexType"))
+            {
+                Some(t) => Some(TypeRef::Anonymous(self.type_definition(t)?)),
+                None => None,
+            },
+        };
+        let substitution_group = match attr(d, "substitutionGroup") {
+            Some(h) => Some(resolve(d, &h, true).map_err(schema_error)?),
+            None => None,
+        };
+        let mut constraints = vec![];
+        for c in children
+            .iter()
+            .filter(|c| matches!(local(*c).as_str(), "unique" | "key" | "keyref"))
+        {
+            let ic = Rc::new(self.identity_constraint(c)?);
+            self.constraints.insert(ic.name.clone(), ic.clone());
+            constraints.push(ic)
+        }
+        Ok(ElementDecl {
+            name,
+            type_ref,
+            substitution_group,
+            nillable: attr(d, "nillable").is_some_and(|b| is_true(&b)),
+            is_abstract: attr(d, "abstract").is_some_and(|b| is_true(&b)),
+            default: attr(d, "default"),
+            fixed: attr(d, "fixed"),
+            constraints,
+        })
+    }
+
+    fn attribute(&mut self, d: &N, global: bool) -> Result<AttributeDecl, ValidationError> {
+        let n = attr(d, "name").ok_or_else(|| schema_error("xs:attribute has no name"))?;
+        let qualified =
+            global || attr(d, "form").map_or(self.attribute_qualified, |f| f == "qualified");
+        let name = make_qname(
+            &n,
+            if qualified {
+                self.target.as_deref()
+            } else {
+                None
+            },
+        )?;
+        let type_ref = match attr(d, "type") {
+            Some(t) => Some(TypeRef::Named(resolve(d, &t, true).map_err(schema_error)?)),
+            None => match xs_children(d).iter().find(|c| local(*c) == "simpleType") {
+                Some(t) => Some(TypeRef::Anonymous(Type::Simple(Rc::new(
+                    self.simple_type(t)?,
+                )))),
+                None => None,
+            },
+        };
+        Ok(AttributeDecl {
+            name,
+            type_ref,
+            default: attr(d, "default"),
+            fixed: attr(d, "fixed"),
+        })
+    }
+
+    // Attribute uses override those of the same name inherited from the base type.
+    fn attribute_uses(
+        &mut self,
+        defs: &[N],
+        mut uses: Vec<AttributeUse>,
+    ) -> Result<(Vec<AttributeUse>, Option<Rc<Wildcard>>), ValidationError> {
+        let mut wildcard = None;
+        for d in defs {
+            match local(d).as_str() {
+                "attribute" => {
+                    let decl = match attr(d, "ref") {
+                        Some(r) => {
+                            let qn = resolve(d, &r, true).map_err(schema_error)?;
+                            let g = self.attributes.get(&qn).cloned().ok_or_else(|| {
+                                schema_error(format!("attribute \"{}\" is not declared", qn))
+                            })?;
+                            if attr(d, "default").is_some() || attr(d, "fixed").is_some() {
+                                Rc::new(AttributeDecl {
+                                    name: g.name.clone(),
+                                    type_ref: g.type_ref.clone(),
+                                    default: attr(d, "default"),
+                                    fixed: attr(d, "fixed"),
+                                })
+                            } else {
+                                g
+                            }
+                        }
+                        None => Rc::new(self.attribute(d, false)?),
+                    };
+                    let u = attr(d, "use");
+                    uses.retain(|a| a.decl.name != decl.name);
+                    uses.push(AttributeUse {
+                        decl,
+                        required: u.as_deref() == Some("required"),
+                        prohibited: u.as_deref() == Some("prohibited"),
+                    })
+                }
+                "attributeGroup" => {
+                    let r = attr(d, "ref")
+                        .ok_or_else(|| schema_error("xs:attributeGroup has no ref"))?;
+                    let qn = resolve(d, &r, true).map_err(schema_error)?;
+                    let g = self.attribute_group_defs.get(&qn).cloned().ok_or_else(|| {
+                        schema_error(format!("attribute group \"{}\" is not defined", qn))
+                    })?;
+                    let (u, w) = self.attribute_uses(&xs_children(&g), uses)?;
+                    uses = u;
+                    wildcard = wildcard.or(w);
+                }
+                "anyAttribute" => wildcard = Some(Rc::new(self.wildcard(d)?)),
+                _ => {}
+            }
+        }
+        Ok((uses, wildcard))
+    }
+
+    fn identity_constraint(&mut self, d: &N) -> Result<IdentityConstraint, ValidationError> {
+        let kind = match local(d).as_str() {
+            "unique" => ConstraintKind::Unique,
+            "key" => ConstraintKind::Key,
+            _ => ConstraintKind::KeyRef,
+        };
+        let name = self.target_name(d)?;
+        let refer = match attr(d, "refer") {
+            Some(r) => Some(resolve(d, &r, true).map_err(schema_error)?),
+            None if kind == ConstraintKind::KeyRef => {
+                return Err(schema_error(format!("keyref \"{}\" has no refer", name)));
+            }
+            None => None,
+        };
+        let mut selector = None;
+        let mut fields = vec![];
+        for c in xs_children(d) {
+            let x = attr(&c, "xpath")
+                .ok_or_else(|| schema_error(format!("xs:{} has no xpath", local(&c))))?;
+            match local(&c).as_str() {
+                "selector" => selector = Some(ic_paths(&c, &x, false)?),
+                "field" => fields.push(ic_paths(&c, &x, true)?),
+                _ => {}
+            }
+        }
+        Ok(IdentityConstraint {
+            kind,
+            refer,
+            selector: selector
+                .ok_or_else(|| schema_error(format!("\"{}\" has no selector", name)))?,
+            fields,
+            name,
+        })
+    }
+}
+
+fn empty_content(mixed: bool) -> Content {
+    if mixed {
+        Content::Elements(
+            Particle {
+                min: 1,
+                max: Some(1),
+                term: Term::Sequence(vec![]),
+            },
+            true,
+        )
+    } else {
+        Content::Empty
+    }
+}
+
+fn facets<N: Node>(d: &N) -> Vec<(String, String)> {
+    xs_children(d)
+        .iter()
+        .filter(|f| !matches!(local(*f).as_str(), "simpleType" | "whiteSpace"))
+        .filter_map(|f| attr(f, "value").map(|v| (local(f), v)))
+        .collect()
+}
+
+// Parse the XPath subset for selectors and fields (section 3.11.6).
+fn ic_paths<N: Node>(d: &N, xpath: &str, field: bool) -> Result<Vec<IcPath>, ValidationError> {
+    let name_test = |t: &str| -> Result<IcNameTest, ValidationError> {
+        let t = t.trim();
+        if t == "*" {
+            Ok(IcNameTest::Any)
+        } else if let Some(p) = t.strip_suffix(":*") {
+            Ok(IcNameTest::Namespace(Some(
+                resolve(d, &format!("{}:x", p), false)
+                    .map_err(schema_error)
+                    .map(|qn| namespace_of(&qn).unwrap_or_default())?,
+            )))
+        } else {
+            // Unprefixed names are not in the default namespace
+            resolve(d, t, false)
+                .map(IcNameTest::Name)
+                .map_err(schema_error)
+        }
+    };
+    xpath
+        .split('|')
+        .map(|p| {
+            let p = p.trim();
+            let (descendant, rest) = p.strip_prefix(".//").map_or((false, p), |r| (true, r));
+            let mut steps = vec![];
+            let mut attribute = None;
+            let parts: Vec<&str> = rest.split('/').map(str::trim).collect();
+            for (i, s) in parts.iter().enumerate() {
+                if let Some(a) = s
+                    .strip_prefix('@')
+                    .or_else(|| s.strip_prefix("attribute::"))
+                {
+                    if !field || i != parts.len() - 1 {
+                        return Err(schema_error(format!(
+                            "attribute step not allowed in \"{}\"",
+                            xpath
+                        )));
+                    }
+                    attribute = Some(name_test(a)?)
+                } else if *s == "." {
+                    steps.push(IcStep::Current)
+                } else {
+                    steps.push(IcStep::Child(name_test(
+                        s.strip_prefix("child::").unwrap_or(s),
+                    )?))
+                }
+            }
+            Ok(IcPath {
+                descendant,
+                steps,
+                attribute,
+            })
+        })
+        .collect()
+}
+
+fn builtin_type(qn: &QName) -> Option<Type> {
+    if namespace_of(qn).as_deref() != Some(XSDNS) {
+        return None;
+    }
+    let l = qn.local_name().to_string();
+    if l == "anyType" {
+        Some(Type::Complex(Rc::new(any_type())))
+    } else if datatypes::is_builtin(&l) {
+        Some(Type::Simple(Rc::new(SimpleType::Builtin(l))))
+    } else {
+        None
+    }
+}
+
+// The ur-type: any attributes, and mixed content with any elements.
+fn any_type() -> ComplexType {
+    let any = Rc::new(Wildcard {
+        namespaces: NamespaceConstraint::Any,
+        process: ProcessContents::Lax,
+    });
+    ComplexType {
+        attributes: vec![],
+        attribute_wildcard: Some(any.clone()),
+        content: Content::Elements(
+            Particle {
+                min: 0,
+                max: None,
+                term: Term::Any(any),
+            },
+            true,
+        ),
+    }
+}
+
+fn schema_error(msg: impl Into<String>) -> ValidationError {
+    ValidationError::SchemaError(msg.into())
+}
+
+fn is_true(s: &str) -> bool {
+    matches!(s.trim(), "true" | "1")
+}
+
+fn is_xs<N: Node>(n: &N) -> bool {
+    n.name()
+        .is_some_and(|q| namespace_of(&q).as_deref() == Some(XSDNS))
+}
+
+fn local<N: Node>(n: &N) -> String {
+    n.name()
+        .map_or_else(String::new, |q| q.local_name().to_string())
+}
+
+fn namespace_of(qn: &QName) -> Option<String> {
+    qn.namespace_uri()
+        .map(|u| u.to_string())
+        .filter(|u| !u.is_empty())
+}
+
+// The schema components that are children of a schema element, ignoring annotations.
+fn xs_children<N: Node>(n: &N) -> Vec<N> {
+    n.child_iter()
+        .filter(|c| c.node_type() == NodeType::Element && is_xs(c) && local(c) != "annotation")
+        .collect()
+}
+
+fn attr<N: Node>(n: &N, name: &str) -> Option<String> {
+    n.get_attribute_node(&QName::from_local_name(NcName::try_from(name).unwrap()))
+        .map(|a| a.to_string())
+}
+
+fn make_qname(local: &str, ns: Option<&str>) -> Result<QName, ValidationError> {
+    let l = NcName::try_from(local.trim())
+        .map_err(|_| schema_error(format!("\"{}\" is not a valid name", local)))?;
+    match ns {
+        Some(u) if !u.is_empty() => Ok(QName::new_from_parts(
+            l,
+            Some(
+                NamespaceUri::try_from(u)
+                    .map_err(|_| schema_error(format!("\"{}\" is not a valid namespace URI", u)))?,
+            ),
+        )),
+        _ => Ok(QName::from_local_name(l)),
+    }
+}
+
+/// Resolve a QName-valued attribute using the namespace declarations in scope for a node.
+fn resolve<N: Node>(n: &N, v: &str, use_default: bool) -> Result<QName, String> {
+    let v = v.trim();
+    let (prefix, l) = match v.split_once(':') {
+        Some((p, l)) => (Some(p), l),
+        None => (None, v),
+    };
+    let ns = match prefix {
+        Some(p) => Some(
+            n.to_namespace_uri(&Some(
+                NamespacePrefix::try_from(p).map_err(|_| format!("invalid prefix \"{}\"", p))?,
+            ))
+            .map_err(|_| format!("namespace prefix \"{}\" is not declared", p))?
+            .to_string(),
+        ),
+        None if use_default => n.to_namespace_uri(&None).ok().map(|u| u.to_string()),
+        None => None,
+    };
+    make_qname(l, ns.as_deref()).map_err(|e| match e {
+        ValidationError::SchemaError(m) | ValidationError::DocumentError(m) => m,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{ParseError, xml};
+    use crate::trees::smite::RNode;
+    use crate::validators::Schema;
+    use crate::value::ValueData;
+    use num_bigint::BigInt;
+
+    const ORDER: &str = r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+  <xs:simpleType name="sku">
+    <xs:restriction base="xs:string">
+      <xs:pattern value="\d{3}-[A-Z]{2}"/>
+    </xs:restriction>
+  </xs:simpleType>
+  <xs:element name="order">
+    <xs:complexType>
+      <xs:sequence>
+        <xs:element name="date" type="xs:date"/>
+        <xs:element name="item" maxOccurs="unbounded">
+          <xs:complexType>
+            <xs:simpleContent>
+              <xs:extension base="xs:decimal">
+                <xs:attribute name="sku" type="sku" use="required"/>
+                <xs:attribute name="qty" type="xs:integer" default="1"/>
+              </xs:extension>
+            </xs:simpleContent>
+          </xs:complexType>
+        </xs:element>
+      </xs:sequence>
+    </xs:complexType>
+    <xs:key name="items">
+      <xs:selector xpath="item"/>
+      <xs:field xpath="@sku"/>
+    </xs:key>
+  </xs:element>
+</xs:schema>"#;
+
+    fn parse(s: &str) -> RNode {
+        xml::parse(
+            RNode::new_document(),
+            s,
+            Some(|_: &_| Err(ParseError::MissingNameSpace)),
+        )
+        .expect("unable to parse document")
+    }
+
+    fn validate(doc: &str) -> Result<RNode, ValidationError> {
+        let schema = XsdSchema::new(&parse(ORDER))?;
+        let d = parse(doc);
+        d.validate(Schema::XSD(Rc::new(schema)))?;
+        Ok(d)
+    }
+
+    #[test]
+    fn xsd_typed_values() {
+        let d = validate(
+            "<order><date>2024-02-29</date><item sku='123-AB' qty='2'>9.50</item><item sku='456-CD'>1</item></order>",
+        )
+        .expect("document is not valid");
+        let order = d.child_iter().next().unwrap();
+        let mut items = order.child_iter();
+        let date = items.next().unwrap();
+        assert!(matches!(date.value().value, ValueData::Date(..)));
+        let item = items.next().unwrap();
+        assert!(matches!(item.value().value, ValueData::Decimal(_)));
+        let qty = QName::from_local_name(NcName::try_from("qty").unwrap());
+        assert!(matches!(
+            item.get_attribute(&qty).value,
+            ValueData::Integer(ref i) if *i == BigInt::from(2)
+        ));
+        // A defaulted attribute is added with its typed value
+        assert!(matches!(
+            items.next().unwrap().get_attribute(&qty).value,
+            ValueData::Integer(ref i) if *i == BigInt::from(1)
+        ));
+    }
+
+    #[test]
+    fn xsd_content_unchanged() {
+        let d = validate(
+            "<order><date>2024-02-29</date><item sku='123-AB' qty=' 02 '><!-- price --> 9.50 </item></order>",
+        )
+        .expect("document is not valid");
+        let item = d.child_iter().next().unwrap().child_iter().nth(1).unwrap();
+        // The typed value is recorded, but the lexical content is left as it is
+        assert!(matches!(item.value().value, ValueData::Decimal(_)));
+        let c: Vec<RNode> = item.child_iter().collect();
+        assert_eq!(c.len(), 2);
+        assert_eq!(c[0].node_type(), NodeType::Comment);
+        assert_eq!(c[1].to_string(), " 9.50 ");
+        let qty = QName::from_local_name(NcName::try_from("qty").unwrap());
+        assert_eq!(item.get_attribute_node(&qty).unwrap().to_string(), " 02 ");
+        assert!(matches!(
+            item.get_attribute(&qty).value,
+            ValueData::Integer(ref i) if *i == BigInt::from(2)
+        ));
+        // An element that has not been validated has no typed value
+        let u = parse("<item>9.50</item>");
+        assert_eq!(u.child_iter().next().unwrap().value().to_string(), "");
+    }
+
+    #[test]
+    fn xsd_content_model() {
+        assert!(matches!(
+            validate("<order><item sku='123-AB'>1</item></order>"),
+            Err(ValidationError::DocumentError(_))
+        ));
+        assert!(matches!(
+            validate("<order><date>2024-01-01</date></order>"),
+            Err(ValidationError::DocumentError(_))
+        ));
+    }
+
+    #[test]
+    fn xsd_facets() {
+        assert!(matches!(
+            validate("<order><date>2024-01-01</date><item sku='12-AB'>1</item></order>"),
+            Err(ValidationError::DocumentError(_))
+        ));
+        assert!(matches!(
+            validate("<order><date>2024-13-01</date><item sku='123-AB'>1</item></order>"),
+            Err(ValidationError::DocumentError(_))
+        ));
+    }
+
+    #[test]
+    fn xsd_key() {
+        assert!(matches!(
+            validate(
+                "<order><date>2024-01-01</date><item sku='123-AB'>1</item><item sku='123-AB'>2</item></order>"
+            ),
+            Err(ValidationError::DocumentError(_))
+        ));
+    }
+
+    #[test]
+    fn xsd_schema_error() {
+        assert!(matches!(
+            XsdSchema::new(&parse(
+                "<xs:schema xmlns:xs='http://www.w3.org/2001/XMLSchema'><xs:element name='a' type='b'/></xs:schema>"
+            ))
+            .and_then(|s| parse("<a/>").validate(Schema::XSD(Rc::new(s)))),
+            Err(ValidationError::SchemaError(_))
+        ));
+    }
+}
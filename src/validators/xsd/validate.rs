@@ -0,0 +1,749 @@
+//! Validate a document against a compiled schema, and annotate it with typed values.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::rc::Rc;
+
+use qualname::QName;
+
+use crate::item::{Node, NodeType};
+use crate::validators::ValidationError;
+use crate::validators::datatypes;
+use crate::validators::xsd::{
+    ConstraintKind, Content, ElementDecl, ElementRef, IcPath, IcStep, IdentityConstraint, Particle,
+    ProcessContents, SimpleType, Term, Type, TypeRef, Wildcard, XSINS, XsdSchema, any_type,
+    is_true, namespace_of, resolve, schema_error,
+};
+use crate::value::{Value, ValueBuilder, ValueData};
+use crate::xdmerror::Error;
+
+/// Validate a document. If it is valid, then attributes and elements with simple content are given typed values.
+pub(crate) fn validate_xsd<N: Node>(doc: &N, schema: &XsdSchema) -> Result<(), ValidationError> {
+    let root = doc
+        .child_iter()
+        .find(|c| c.node_type() == NodeType::Element)
+        .ok_or_else(|| document_error("document has no document element"))?;
+    let name = root.name().unwrap();
+    let decl = schema
+        .elements
+        .get(&name)
+        .ok_or_else(|| document_error(format!("element \"{}\" is not declared", name)))?;
+
+    let mut v = Validator {
+        schema,
+        typed: HashMap::new(),
+        annotations: vec![],
+        ids: HashSet::new(),
+        idrefs: vec![],
+    };
+    v.element(&root, decl)?;
+    if let Some(r) = v.idrefs.iter().find(|r| !v.ids.contains(*r)) {
+        return Err(document_error(format!(
+            "IDREF \"{}\" does not refer to an ID",
+            r
+        )));
+    }
+    // The document is valid, so now it can be annotated
+    for a in v.annotations {
+        a.apply()
+            .map_err(|e| document_error(format!("unable to annotate document: {}", e)))?
+    }
+    Ok(())
+}
+
+fn document_error(msg: impl Into<String>) -> ValidationError {
+    ValidationError::DocumentError(msg.into())
+}
+
+// How a child element was matched by a content model
+#[derive(Clone)]
+enum Match {
+    Element(Rc<ElementDecl>),
+    Any(Rc<Wildcard>),
+}
+
+// The ways a content model can match a run of child elements, by the position following the run
+type Matches = BTreeMap<usize, Vec<Match>>;
+
+// A typed value to be given to the document once validation has succeeded.
+// The lexical content of the document is left as it is; defaults are added where content is missing.
+enum Annotation<N: Node> {
+    Typed(N, ValueData), // element with simple content, or attribute
+    Attribute(N, QName, String, ValueData), // element, name of a defaulted attribute, default, value
+    Default(N, String, ValueData),          // empty element, default, value
+}
+
+impl<N: Node> Annotation<N> {
+    fn apply(self) -> Result<(), Error> {
+        match self {
+            Annotation::Typed(n, v) => n.set_typed_value(typed(v)),
+            Annotation::Attribute(e, name, d, v) => {
+                let a = e
+                    .owner_document()
+                    .new_attribute(name, Rc::new(Value::from(d)))?;
+                e.add_attribute(a.clone())?;
+                a.set_typed_value(typed(v))
+            }
+            Annotation::Default(mut e, d, v) => {
+                let t = e.owner_document().new_text(Rc::new(Value::from(d)))?;
+                e.push(t)?;
+                e.set_typed_value(typed(v))
+            }
+        }
+    }
+}
+
+fn typed(v: ValueData) -> Rc<Value> {
+    Rc::new(ValueBuilder::new().value(v).build())
+}
+
+struct Validator<'a, N: Node> {
+    schema: &'a XsdSchema,
+    // Typed values of attributes and elements, by node id, for comparing identity constraint fields
+    typed: HashMap<String, ValueData>,
+    annotations: Vec<Annotation<N>>,
+    ids: HashSet<String>,
+    idrefs: Vec<String>,
+}
+
+impl<N: Node> Validator<'_, N> {
+    fn element(&mut self, e: &N, decl: &ElementDecl) -> Result<(), ValidationError> {
+        let name = e.name().unwrap();
+        if decl.is_abstract {
+            return Err(document_error(format!("element \"{}\" is abstract", name)));
+        }
+        let t = match xsi_attribute(e, "type") {
+            Some(t) => {
+                let qn = resolve(e, &t, true).map_err(document_error)?;
+                self.schema
+                    .lookup_type(&qn)
+                    .ok_or_else(|| document_error(format!("xsi:type \"{}\" is not defined", qn)))?
+            }
+            None => self.declared_type(decl)?,
+        };
+
+        if xsi_attribute(e, "nil").is_some_and(|n| is_true(&n)) {
+            if !decl.nillable {
+                return Err(document_error(format!(
+                    "element \"{}\" is not nillable",
+                    name
+                )));
+            }
+            if e.child_iter()
+                .any(|c| matches!(c.node_type(), NodeType::Element | NodeType::Text))
+            {
+                return Err(document_error(format!(
+                    "nil element \"{}\" must be empty",
+                    name
+                )));
+            }
+            if let Type::Complex(ct) = &t {
+                self.attributes(e, &ct.attributes, &ct.attribute_wildcard)?
+            }
+            return Ok(());
+        }
+
+        match &t {
+            Type::Simple(st) => {
+                self.attributes(e, &[], &None)?;
+                self.simple_content(e, st, decl)?
+            }
+            Type::Complex(ct) => {
+                self.attributes(e, &ct.attributes, &ct.attribute_wildcard)?;
+                let children: Vec<N> = e
+                    .child_iter()
+                    .filter(|c| c.node_type() == NodeType::Element)
+                    .collect();
+                match &ct.content {
+                    Content::Empty => {
+                        if !children.is_empty() || has_character_data(e) {
+                            return Err(document_error(format!(
+                                "element \"{}\" must be empty",
+                                name
+                            )));
+                        }
+                    }
+                    Content::Simple(st) => {
+                        if !children.is_empty() {
+                            return Err(document_error(format!(
+                                "element \"{}\" may not have child elements",
+                                name
+                            )));
+                        }
+                        self.simple_content(e, st, decl)?
+                    }
+                    Content::Elements(p, mixed) => {
+                        if !mixed && has_character_data(e) {
+                            return Err(document_error(format!(
+                                "element \"{}\" may not have character data",
+                                name
+                            )));
+                        }
+                        let m = self
+                            .particle(p, &children, 0)?
+                            .remove(&children.len())
+                            .ok_or_else(|| {
+                                document_error(format!(
+                                    "content of element \"{}\" does not match its type",
+                                    name
+                                ))
+                            })?;
+                        for (c, cm) in children.iter().zip(m) {
+                            match cm {
+                                Match::Element(d) => self.element(c, &d)?,
+                                Match::Any(w) => self.wildcard_element(c, w.process)?,
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        for ic in &decl.constraints {
+            self.identity_constraint(e, ic)?
+        }
+        Ok(())
+    }
+
+    // The type of an element without a type of its own is that of its substitution group head, or else anyType.
+    fn declared_type(&self, decl: &ElementDecl) -> Result<Type, ValidationError> {
+        match &decl.type_ref {
+            Some(TypeRef::Named(qn)) => self
+                .schema
+                .lookup_type(qn)
+                .ok_or_else(|| schema_error(format!("type \"{}\" is not defined", qn))),
+            Some(TypeRef::Anonymous(t)) => Ok(t.clone()),
+            None => match decl
+                .substitution_group
+                .as_ref()
+                .and_then(|h| self.schema.elements.get(h))
+            {
+                Some(h) if h.name != decl.name => self.declared_type(h),
+                _ => Ok(Type::Complex(Rc::new(any_type()))),
+            },
+        }
+    }
+
+    fn simple_type(&self, t: &Option<TypeRef>) -> Result<Rc<SimpleType>, ValidationError> {
+        match t {
+            None => Ok(Rc::new(SimpleType::Builtin(String::from("anySimpleType")))),
+            Some(TypeRef::Anonymous(Type::Simple(s))) => Ok(s.clone()),
+            Some(TypeRef::Named(qn)) => match self.schema.lookup_type(qn) {
+                Some(Type::Simple(s)) => Ok(s),
+                Some(Type::Complex(_)) => Err(schema_error(format!(
+                    "type \"{}\" of an attribute is not a simple type",
+                    qn
+                ))),
+                None => Err(schema_error(format!("type \"{}\" is not defined", qn))),
+            },
+            Some(TypeRef::Anonymous(Type::Complex(_))) => {
+                Err(schema_error("type of an attribute is not a simple type"))
+            }
+        }
+    }
+
+    fn simple_content(
+        &mut self,
+        e: &N,
+        st: &SimpleType,
+        decl: &ElementDecl,
+    ) -> Result<(), ValidationError> {
+        let name = e.name().unwrap();
+        let mut text: String = e
+            .child_iter()
+            .filter(|c| c.node_type() == NodeType::Text)
+            .map(|c| c.to_string())
+            .collect();
+        let mut default = None;
+        if e.child_iter().next().is_none()
+            && let Some(d) = decl.fixed.as_ref().or(decl.default.as_ref())
+        {
+            text = d.clone();
+            default = Some(d.clone())
+        }
+        let v = st
+            .validate(&text)
+            .map_err(|m| document_error(format!("element \"{}\": {}", name, m)))?;
+        if let Some(f) = &decl.fixed
+            && st.validate(f).map(|fv| fv.to_string()) != Ok(v.to_string())
+        {
+            return Err(document_error(format!(
+                "element \"{}\" must have the fixed value \"{}\"",
+                name, f
+            )));
+        }
+        self.record(e, &v)?;
+        self.annotations.push(match default {
+            Some(d) => Annotation::Default(e.clone(), d, v),
+            None => Annotation::Typed(e.clone(), v),
+        });
+        Ok(())
+    }
+
+    fn attributes(
+        &mut self,
+        e: &N,
+        uses: &[super::AttributeUse],
+        wildcard: &Option<Rc<Wildcard>>,
+    ) -> Result<(), ValidationError> {
+        let schema = self.schema;
+        let name = e.name().unwrap();
+        let mut seen = vec![];
+        let atts: Vec<N> = e.attribute_iter().collect();
+        for a in atts {
+            let qn = a.name().unwrap();
+            let ns = namespace_of(&qn);
+            if ns.as_deref() == Some(XSINS) {
+                continue;
+            }
+            match uses.iter().find(|u| u.decl.name == qn && !u.prohibited) {
+                Some(u) => {
+                    seen.push(qn);
+                    self.attribute(&a, &u.decl)?
+                }
+                None => match wildcard {
+                    Some(w) if w.allows(&ns) => match (w.process, schema.attributes.get(&qn)) {
+                        (ProcessContents::Skip, _) | (ProcessContents::Lax, None) => {}
+                        (_, Some(d)) => self.attribute(&a, d)?,
+                        (ProcessContents::Strict, None) => {
+                            return Err(document_error(format!(
+                                "attribute \"{}\" is not declared",
+                                qn
+                            )));
+                        }
+                    },
+                    _ => {
+                        return Err(document_error(format!(
+                            "attribute \"{}\" is not allowed on element \"{}\"",
+                            qn, name
+                        )));
+                    }
+                },
+            }
+        }
+        for u in uses
+            .iter()
+            .filter(|u| !u.prohibited && !seen.contains(&u.decl.name))
+        {
+            if u.required {
+                return Err(document_error(format!(
+                    "element \"{}\" is missing the required attribute \"{}\"",
+                    name, u.decl.name
+                )));
+            }
+            if let Some(d) = u.decl.fixed.as_ref().or(u.decl.default.as_ref()) {
+                let v = self
+                    .simple_type(&u.decl.type_ref)?
+                    .validate(d)
+                    .map_err(|m| {
+                        schema_error(format!("default for attribute \"{}\": {}", u.decl.name, m))
+                    })?;
+                self.annotations.push(Annotation::Attribute(
+                    e.clone(),
+                    u.decl.name.clone(),
+                    d.clone(),
+                    v,
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn attribute(&mut self, a: &N, decl: &super::AttributeDecl) -> Result<(), ValidationError> {
+        let st = self.simple_type(&decl.type_ref)?;
+        let v = st
+            .validate(&a.to_string())
+            .map_err(|m| document_error(format!("attribute \"{}\": {}", decl.name, m)))?;
+        if let Some(f) = &decl.fixed
+            && st.validate(f).map(|fv| fv.to_string()) != Ok(v.to_string())
+        {
+            return Err(document_error(format!(
+                "attribute \"{}\" must have the fixed value \"{}\"",
+                decl.name, f
+            )));
+        }
+        self.record(a, &v)?;
+        self.annotations.push(Annotation::Typed(a.clone(), v));
+        Ok(())
+    }
+
+    fn record(&mut self, n: &N, v: &ValueData) -> Result<(), ValidationError> {
+        match v {
+            ValueData::ID(_) => {
+                if !self.ids.insert(v.to_string()) {
+                    return Err(document_error(format!("duplicate ID \"{}\"", v)));
+                }
+            }
+            ValueData::IDREF(_) => self.idrefs.push(v.to_string()),
+            ValueData::IDREFS(r) => self.idrefs.extend(r.iter().map(|i| i.to_string())),
+            _ => {}
+        }
+        self.typed.insert(n.get_id(), v.clone());
+        Ok(())
+    }
+
+    // An element matched by a wildcard is validated if it is declared, unless processing is skipped.
+    fn wildcard_element(&mut self, e: &N, process: ProcessContents) -> Result<(), ValidationError> {
+        if process == ProcessContents::Skip {
+            return Ok(());
+        }
+        let schema = self.schema;
+        let name = e.name().unwrap();
+        match schema.elements.get(&name) {
+            Some(d) => self.element(e, d),
+            None if process == ProcessContents::Strict => Err(document_error(format!(
+                "element \"{}\" is not declared",
+                name
+            ))),
+            None => {
+                let children: Vec<N> = e
+                    .child_iter()
+                    .filter(|c| c.node_type() == NodeType::Element)
+                    .collect();
+                for c in children {
+                    self.wildcard_element(&c, process)?
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn particle(
+        &self,
+        p: &Particle,
+        children: &[N],
+        start: usize,
+    ) -> Result<Matches, ValidationError> {
+        let mut results = Matches::new();
+        if p.min == 0 {
+            results.insert(start, vec![]);
+        }
+        let mut frontier = Matches::from([(start, vec![])]);
+        let mut visited = BTreeSet::from([start]);
+        let mut count = 0;
+        while !frontier.is_empty() && p.max.is_none_or(|m| count < m) {
+            count += 1;
+            let mut next = Matches::new();
+            for (pos, prefix) in &frontier {
+                for (end, m) in self.term(&p.term, children, *pos)? {
+                    next.entry(end)
+                        .or_insert_with(|| prefix.iter().cloned().chain(m).collect());
+                }
+            }
+            if count >= p.min {
+                for (end, m) in &next {
+                    results.entry(*end).or_insert_with(|| m.clone());
+                }
+                // Once the minimum is reached, only positions not seen before can lead to new matches
+                next.retain(|end, _| visited.insert(*end));
+            }
+            frontier = next;
+        }
+        Ok(results)
+    }
+
+    fn term(&self, t: &Term, children: &[N], start: usize) -> Result<Matches, ValidationError> {
+        match t {
+            Term::Element(r) => {
+                let mut m = Matches::new();
+                if let Some(c) = children.get(start)
+                    && let Some(d) = self.element_match(r, &c.name().unwrap())?
+                {
+                    m.insert(start + 1, vec![Match::Element(d)]);
+                }
+                Ok(m)
+            }
+            Term::Any(w) => {
+                let mut m = Matches::new();
+                if let Some(c) = children.get(start)
+                    && w.allows(&namespace_of(&c.name().unwrap()))
+                {
+                    m.insert(start + 1, vec![Match::Any(w.clone())]);
+                }
+                Ok(m)
+            }
+            Term::Choice(ps) => {
+                let mut m = Matches::new();
+                for p in ps {
+                    for (end, a) in self.particle(p, children, start)? {
+                        m.entry(end).or_insert(a);
+                    }
+                }
+                Ok(m)
+            }
+            Term::Sequence(ps) => {
+                let mut m = Matches::from([(start, vec![])]);
+                for p in ps {
+                    let mut next = Matches::new();
+                    for (pos, prefix) in m {
+                        for (end, a) in self.particle(p, children, pos)? {
+                            next.entry(end)
+                                .or_insert_with(|| prefix.iter().cloned().chain(a).collect());
+                        }
+                    }
+                    m = next;
+                }
+                Ok(m)
+            }
+            Term::All(ps) => {
+                // Each particle may occur at most once, in any order
+                let mut used = vec![false; ps.len()];
+                let mut matched = vec![];
+                let mut pos = start;
+                'children: while pos < children.len() {
+                    for (i, p) in ps.iter().enumerate() {
+                        if !used[i]
+                            && let Some(m) = self.term(&p.term, children, pos)?.remove(&(pos + 1))
+                        {
+                            used[i] = true;
+                            matched.extend(m);
+                            pos += 1;
+                            continue 'children;
+                        }
+                    }
+                    break;
+                }
+                if ps.iter().zip(&used).any(|(p, u)| p.min > 0 && !u) {
+                    Ok(Matches::new())
+                } else {
+                    Ok(Matches::from([(pos, matched)]))
+                }
+            }
+        }
+    }
+
+    // Find the declaration for an element that matches a particle, allowing for substitution groups.
+    fn element_match(
+        &self,
+        r: &ElementRef,
+        name: &QName,
+    ) -> Result<Option<Rc<ElementDecl>>, ValidationError> {
+        match r {
+            ElementRef::Local(d) => Ok((d.name == *name).then(|| d.clone())),
+            ElementRef::Global(qn) => {
+                let d =
+                    self.schema.elements.get(qn).ok_or_else(|| {
+                        schema_error(format!("element \"{}\" is not declared", qn))
+                    })?;
+                if qn == name {
+                    return Ok(Some(d.clone()));
+                }
+                let mut heads = vec![qn.clone()];
+                let mut seen = vec![];
+                while let Some(h) = heads.pop() {
+                    for m in self.schema.substitutions.get(&h).into_iter().flatten() {
+                        if m == name {
+                            return Ok(self.schema.elements.get(m).cloned());
+                        }
+                        if !seen.contains(m) {
+                            seen.push(m.clone());
+                            heads.push(m.clone())
+                        }
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn identity_constraint(&self, e: &N, ic: &IdentityConstraint) -> Result<(), ValidationError> {
+        let rows = self.table(e, ic)?;
+        match ic.kind {
+            ConstraintKind::Unique | ConstraintKind::Key => {
+                let mut seen = HashSet::new();
+                for r in rows {
+                    if !seen.insert(r.clone()) {
+                        return Err(document_error(format!(
+                            "duplicate value \"{}\" for \"{}\"",
+                            r.join(", "),
+                            ic.name
+                        )));
+                    }
+                }
+            }
+            ConstraintKind::KeyRef => {
+                let refer = ic
+                    .refer
+                    .as_ref()
+                    .and_then(|r| self.schema.constraints.get(r))
+                    .ok_or_else(|| {
+                        schema_error(format!("keyref \"{}\" refers to an undefined key", ic.name))
+                    })?;
+                let keys: HashSet<Vec<String>> = self.table(e, refer)?.into_iter().collect();
+                if let Some(r) = rows.iter().find(|r| !keys.contains(*r)) {
+                    return Err(document_error(format!(
+                        "value \"{}\" for \"{}\" does not match a key",
+                        r.join(", "),
+                        ic.name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // The field values of each node selected by an identity constraint
+    fn table(&self, e: &N, ic: &IdentityConstraint) -> Result<Vec<Vec<String>>, ValidationError> {
+        let mut rows = vec![];
+        'rows: for s in select(e, &ic.selector) {
+            let mut row = vec![];
+            for f in &ic.fields {
+                match select(&s, f).as_slice() {
+                    [n] => row.push(
+                        self.typed
+                            .get(&n.get_id())
+                            .map_or_else(|| n.to_string(), |v| v.to_string()),
+                    ),
+                    [] if ic.kind == ConstraintKind::Key => {
+                        return Err(document_error(format!(
+                            "a node selected by key \"{}\" is missing a field",
+                            ic.name
+                        )));
+                    }
+                    [] => continue 'rows,
+                    _ => {
+                        return Err(document_error(format!(
+                            "a field of \"{}\" selects more than one node",
+                            ic.name
+                        )));
+                    }
+                }
+            }
+            rows.push(row)
+        }
+        Ok(rows)
+    }
+}
+
+// Evaluate a selector or field, giving the selected nodes without duplicates.
+fn select<N: Node>(e: &N, paths: &[IcPath]) -> Vec<N> {
+    let mut result: Vec<N> = vec![];
+    for p in paths {
+        let mut nodes = vec![e.clone()];
+        if p.descendant {
+            nodes.extend(
+                e.descend_iter()
+                    .filter(|d| d.node_type() == NodeType::Element),
+            )
+        }
+        for s in &p.steps {
+            if let IcStep::Child(t) = s {
+                nodes = nodes
+                    .iter()
+                    .flat_map(|n| {
+                        n.child_iter().filter(move |c| {
+                            c.node_type() == NodeType::Element && t.matches(&c.name().unwrap())
+                        })
+                    })
+                    .collect()
+            }
+        }
+        if let Some(t) = &p.attribute {
+            nodes = nodes
+                .iter()
+                .flat_map(|n| {
+                    n.attribute_iter()
+                        .filter(move |a| t.matches(&a.name().unwrap()))
+                })
+                .collect()
+        }
+        for n in nodes {
+            if !result.iter().any(|r| r.is_same(&n)) {
+                result.push(n)
+            }
+        }
+    }
+    result
+}
+
+fn has_character_data<N: Node>(e: &N) -> bool {
+    e.child_iter()
+        .any(|c| c.node_type() == NodeType::Text && !c.to_string().trim().is_empty())
+}
+
+fn xsi_attribute<N: Node>(e: &N, name: &str) -> Option<String> {
+    e.attribute_iter()
+        .find(|a| {
+            a.name().is_some_and(|qn| {
+                namespace_of(&qn).as_deref() == Some(XSINS) && qn.local_name().to_string() == name
+            })
+        })
+        .map(|a| a.to_string())
+}
+
+impl SimpleType {
+    // Map a lexical value to a typed value, checking the facets of the type.
+    fn validate(&self, s: &str) -> Result<ValueData, String> {
+        match self {
+            SimpleType::Builtin(t) => datatypes::parse(t, s).map_err(|e| e.message),
+            SimpleType::Restriction(base, facets) => {
+                let v = base.validate(s)?;
+                if facets.is_empty() {
+                    return Ok(v);
+                }
+                let ok = match base.primitive() {
+                    Some(t) => datatypes::allows(t, facets, s).map_err(|e| e.message)?,
+                    None => collection_allows(facets, &v.to_string())?,
+                };
+                if ok {
+                    Ok(v)
+                } else {
+                    Err(format!("\"{}\" does not satisfy the facets of its type", s))
+                }
+            }
+            SimpleType::List(item) => {
+                let items: Vec<&str> = s.split_ascii_whitespace().collect();
+                for i in &items {
+                    item.validate(i)?;
+                }
+                Ok(ValueData::String(items.join(" ")))
+            }
+            SimpleType::Union(members) => members
+                .iter()
+                .find_map(|m| m.validate(s).ok())
+                .ok_or_else(|| format!("\"{}\" is not valid for any member type of the union", s)),
+        }
+    }
+
+    // The built-in datatype that facets are applied to
+    fn primitive(&self) -> Option<&str> {
+        match self {
+            SimpleType::Builtin(t) => Some(t),
+            SimpleType::Restriction(b, _) => b.primitive(),
+            _ => None,
+        }
+    }
+}
+
+// Facets for restrictions of list and union types, where length is the number of items.
+fn collection_allows(facets: &[(String, String)], s: &str) -> Result<bool, String> {
+    let count = s.split_ascii_whitespace().count();
+    let size = |v: &str| {
+        v.trim()
+            .parse::<usize>()
+            .map_err(|_| format!("invalid facet value \"{}\"", v))
+    };
+    let mut enumeration = vec![];
+    for (f, v) in facets {
+        match f.as_str() {
+            "enumeration" => {
+                enumeration.push(v.split_ascii_whitespace().collect::<Vec<_>>().join(" "))
+            }
+            "pattern" => {
+                if !datatypes::xsd_regex(v).map_err(|e| e.message)?.is_match(s) {
+                    return Ok(false);
+                }
+            }
+            "length" if count != size(v)? => return Ok(false),
+            "minLength" if count < size(v)? => return Ok(false),
+            "maxLength" if count > size(v)? => return Ok(false),
+            "length" | "minLength" | "maxLength" => {}
+            _ => {
+                return Err(format!(
+                    "facet \"{}\" does not apply to list or union types",
+                    f
+                ));
+            }
+        }
+    }
+    Ok(enumeration.is_empty() || enumeration.iter().any(|e| e == s))
+}
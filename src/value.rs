@@ -2,20 +2,25 @@
 //!
 //! An atomic value that is an item in a sequence.
 
-use qualname::{NamespaceUri, NcName, QName};
+use qualname::{NamespacePrefix, NamespaceUri, NcName, QName};
 
 use crate::output::OutputSpec;
 use crate::xdmerror::{Error, ErrorKind};
-use chrono::{DateTime, Local, NaiveDate};
+use chrono::{
+    DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike,
+};
 use core::fmt;
 use core::hash::{Hash, Hasher};
+use num_bigint::BigInt;
 use rust_decimal::Decimal;
-use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::prelude::{ToPrimitive, Zero};
 #[cfg(test)]
 use rust_decimal_macros::dec;
 use std::cmp::Ordering;
 use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::LazyLock;
 
 /// Comparison operators for values
 #[derive(Copy, Clone, Debug)]
@@ -162,12 +167,17 @@ pub enum ValueData {
     AnyAtomicType,
     /// untyped atomic value
     UntypedAtomic,
-    Duration,
-    Time(DateTime<Local>), // Ignore the date part. Perhaps use Instant instead?
+    Duration(Duration),
+    /// A duration with only a months component
+    YearMonthDuration(Duration),
+    /// A duration with only a seconds component
+    DayTimeDuration(Duration),
+    /// A time of day, and its timezone if it has one
+    Time(NaiveTime, Option<FixedOffset>),
     Decimal(Decimal),
     Float(f32),
     Double(f64),
-    Integer(i64), // TODO: support num_bigint
+    Integer(BigInt),
     NonPositiveInteger(NonPositiveInteger),
     NegativeInteger(NegativeInteger),
     Long(i64),
@@ -180,14 +190,22 @@ pub enum ValueData {
     UnsignedShort(u16),
     UnsignedByte(u8),
     PositiveInteger(PositiveInteger),
-    DateTime(DateTime<Local>),
-    DateTimeStamp,
-    Date(NaiveDate),
-    // gYearMonth
-    // gYear
-    // gMonthDay
-    // gMonth
-    // gDay
+    /// A date and time of day, and its timezone if it has one
+    DateTime(NaiveDateTime, Option<FixedOffset>),
+    /// A date and time of day, with a timezone
+    DateTimeStamp(NaiveDateTime, FixedOffset),
+    /// A date, and its timezone if it has one
+    Date(NaiveDate, Option<FixedOffset>),
+    /// A year and month, and its timezone if it has one
+    GYearMonth(i32, u32, Option<FixedOffset>),
+    /// A year, and its timezone if it has one
+    GYear(i32, Option<FixedOffset>),
+    /// A month and day, and its timezone if it has one
+    GMonthDay(u32, u32, Option<FixedOffset>),
+    /// A month, and its timezone if it has one
+    GMonth(u32, Option<FixedOffset>),
+    /// A day of the month, and its timezone if it has one
+    GDay(u32, Option<FixedOffset>),
     String(String),
     NormalizedString(NormalizedString),
     /// Like normalizedString, but without leading, trailing and consecutive whitespace
@@ -207,11 +225,16 @@ pub enum ValueData {
     /// Same format as NCName
     ENTITY(ENTITY),
     Boolean(bool),
-    //base64binary,
-    //hexBinary,
+    /// Binary data, with a base64 lexical form
+    Base64Binary(Vec<u8>),
+    /// Binary data, with a hexadecimal lexical form
+    HexBinary(Vec<u8>),
     //anyURI,
-    /// Qualified Name
-    QName(QName),
+    /// Qualified Name, and the prefix of its lexical form, if it has one.
+    ///
+    /// NB. the prefix is a second field of this variant, which is a breaking change for code that
+    /// constructs or matches ValueData::QName(QName). Converting a QName using From gives no prefix.
+    QName(QName, Option<NamespacePrefix>),
     NamespaceUri(NamespaceUri),
     //NOTATION
 }
@@ -237,14 +260,64 @@ impl fmt::Display for ValueData {
             ValueData::NonNegativeInteger(i) => i.0.to_string(),
             ValueData::PositiveInteger(i) => i.0.to_string(),
             ValueData::NegativeInteger(i) => i.0.to_string(),
-            ValueData::Time(t) => t.format("%H:%M:%S.%f").to_string(),
-            ValueData::DateTime(dt) => dt.format("%Y-%m-%dT%H:%M:%S%z").to_string(),
-            ValueData::Date(d) => d.format("%Y-%m-%d").to_string(),
-            ValueData::QName(q) => q.to_string(),
+            ValueData::Duration(d) => d.to_string(),
+            ValueData::YearMonthDuration(d) => {
+                if d.months == 0 {
+                    String::from("P0M")
+                } else {
+                    d.to_string()
+                }
+            }
+            ValueData::DayTimeDuration(d) => d.to_string(),
+            ValueData::Time(t, tz) => format!("{}{}", time_lexical(t), timezone_lexical(tz)),
+            ValueData::DateTime(dt, tz) => format!(
+                "{}T{}{}",
+                dt.format("%Y-%m-%d"),
+                time_lexical(&dt.time()),
+                timezone_lexical(tz)
+            ),
+            ValueData::DateTimeStamp(dt, tz) => format!(
+                "{}T{}{}",
+                dt.format("%Y-%m-%d"),
+                time_lexical(&dt.time()),
+                timezone_lexical(&Some(*tz))
+            ),
+            ValueData::Date(d, tz) => format!("{}{}", d.format("%Y-%m-%d"), timezone_lexical(tz)),
+            ValueData::GYearMonth(y, m, tz) => {
+                format!("{}-{:02}{}", year_lexical(*y), m, timezone_lexical(tz))
+            }
+            ValueData::GYear(y, tz) => format!("{}{}", year_lexical(*y), timezone_lexical(tz)),
+            ValueData::GMonthDay(m, d, tz) => {
+                format!("--{:02}-{:02}{}", m, d, timezone_lexical(tz))
+            }
+            ValueData::GMonth(m, tz) => format!("--{:02}{}", m, timezone_lexical(tz)),
+            ValueData::GDay(d, tz) => format!("---{:02}{}", d, timezone_lexical(tz)),
+            ValueData::Base64Binary(b) => base64_encode(b),
+            ValueData::HexBinary(b) => b.iter().map(|o| format!("{:02X}", o)).collect(),
+            // An unprefixed name in a namespace is given in the URIQualifiedName form
+            ValueData::QName(q, p) => match (p, q.namespace_uri()) {
+                (Some(p), _) => format!("{}:{}", p, q.local_name()),
+                (None, Some(u)) => format!("Q{{{}}}{}", u, q.local_name()),
+                (None, None) => q.local_name().to_string(),
+            },
             ValueData::NCName(n) => n.to_string(),
             ValueData::NamespaceUri(n) => n.to_string(),
+            ValueData::Name(n) => n.to_string(),
+            ValueData::NMTOKEN(n) => n.to_string(),
+            ValueData::ENTITY(e) => e.to_string(),
+            ValueData::Boolean(b) => b.to_string(),
             ValueData::ID(s) => s.to_string(),
             ValueData::IDREF(s) => s.to_string(),
+            ValueData::NMTOKENS(s) => s
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(" "),
+            ValueData::ENTITIES(s) => s
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(" "),
             ValueData::IDREFS(s) => s.iter().map(|i| i.to_string()).enumerate().fold(
                 String::new(),
                 |mut acc, (j, i)| {
@@ -271,20 +344,27 @@ impl Hash for Value {
 impl Eq for Value {}
 
 impl Value {
-    /// Create a Time, ignoring the date part
-    pub fn new_time(t: DateTime<Local>) -> Self {
+    /// Create a time, with an optional timezone
+    pub fn new_time(t: NaiveTime, tz: Option<FixedOffset>) -> Self {
         Value {
-            value: ValueData::Time(t),
+            value: ValueData::Time(t, tz),
             output: OutputSpec::Normal,
         }
     }
-    /// Create a date
-    pub fn new_date(d: NaiveDate) -> Self {
+    /// Create a date, with an optional timezone
+    pub fn new_date(d: NaiveDate, tz: Option<FixedOffset>) -> Self {
         Value {
-            value: ValueData::Date(d),
+            value: ValueData::Date(d, tz),
             output: OutputSpec::Normal,
         }
     }
+    /// The point on the timeline, in UTC, of a date, time, or dateTime value.
+    /// A value without a timezone is taken to be in the implicit timezone.
+    /// A time is placed on the reference date 1972-12-31, and a date is the start of the day.
+    /// Gregorian values (gYear, etc.) are the start of the period, with missing components taken from the reference date.
+    pub fn to_utc(&self, implicit: FixedOffset) -> Option<NaiveDateTime> {
+        utc(&self.value, implicit)
+    }
     /// Give the effective boolean value.
     pub fn to_bool(&self) -> bool {
         match &self.value {
@@ -294,12 +374,14 @@ impl Value {
                 !t.is_empty()
             }
             ValueData::NormalizedString(s) => !s.0.is_empty(),
+            ValueData::Decimal(d) => !d.is_zero(),
+            ValueData::Float(f) => *f != 0.0 && !f.is_nan(),
             ValueData::Double(n) => *n != 0.0,
-            ValueData::Integer(i) => *i != 0,
+            ValueData::Integer(i) => !i.is_zero(),
             ValueData::Int(i) => *i != 0,
 
             // These are non-empty strings by definition, so must be true
-            ValueData::NCName(_) | ValueData::NamespaceUri(_) | ValueData::QName(_) => true,
+            ValueData::NCName(_) | ValueData::NamespaceUri(_) | ValueData::QName(..) => true,
 
             _ => false,
         }
@@ -309,7 +391,12 @@ impl Value {
     pub fn to_int(&self) -> Result<i64, Error> {
         match &self.value {
             ValueData::Int(i) => Ok(*i as i64),
-            ValueData::Integer(i) => Ok(*i),
+            ValueData::Integer(i) => i.to_i64().ok_or_else(|| {
+                Error::new(
+                    ErrorKind::Unknown,
+                    format!("integer value {} is out of range", i),
+                )
+            }),
             _ => match self.to_string().parse::<i64>() {
                 Ok(i) => Ok(i),
                 Err(e) => Result::Err(Error::new(
@@ -322,9 +409,11 @@ impl Value {
     /// Convert the value to a double. If the value cannot be converted, returns Nan.
     pub fn to_double(&self) -> f64 {
         match &self.value {
-            ValueData::String(s) => s.parse::<f64>().unwrap_or(f64::NAN),
-            ValueData::Integer(i) => (*i) as f64,
+            ValueData::String(s) => crate::validators::datatypes::double(s).unwrap_or(f64::NAN),
+            ValueData::Integer(i) => i.to_f64().unwrap_or(f64::NAN),
             ValueData::Int(i) => (*i) as f64,
+            ValueData::Decimal(d) => d.to_f64().unwrap_or(f64::NAN),
+            ValueData::Float(f) => f64::from(*f),
             ValueData::Double(d) => *d,
             _ => f64::NAN,
         }
@@ -340,8 +429,10 @@ impl Value {
             ValueData::Numeric => "Numeric",
             ValueData::AnyAtomicType => "AnyAtomicType",
             ValueData::UntypedAtomic => "UntypedAtomic",
-            ValueData::Duration => "Duration",
-            ValueData::Time(_) => "Time",
+            ValueData::Duration(_) => "Duration",
+            ValueData::YearMonthDuration(_) => "YearMonthDuration",
+            ValueData::DayTimeDuration(_) => "DayTimeDuration",
+            ValueData::Time(..) => "Time",
             ValueData::Decimal(_) => "Decimal",
             ValueData::Float(_) => "Float",
             ValueData::Double(_) => "Double",
@@ -358,9 +449,14 @@ impl Value {
             ValueData::UnsignedShort(_) => "UnsignedShort",
             ValueData::UnsignedByte(_) => "UnsignedByte",
             ValueData::PositiveInteger(_) => "PositiveInteger",
-            ValueData::DateTime(_) => "DateTime",
-            ValueData::DateTimeStamp => "DateTimeStamp",
-            ValueData::Date(_) => "Date",
+            ValueData::DateTime(..) => "DateTime",
+            ValueData::DateTimeStamp(..) => "DateTimeStamp",
+            ValueData::Date(..) => "Date",
+            ValueData::GYearMonth(..) => "GYearMonth",
+            ValueData::GYear(..) => "GYear",
+            ValueData::GMonthDay(..) => "GMonthDay",
+            ValueData::GMonth(..) => "GMonth",
+            ValueData::GDay(..) => "GDay",
             ValueData::String(_) => "String",
             ValueData::NormalizedString(_) => "NormalizedString",
             ValueData::Token => "Token",
@@ -373,10 +469,25 @@ impl Value {
             ValueData::IDREF(_) => "IDREF",
             ValueData::ENTITY(_) => "ENTITY",
             ValueData::Boolean(_) => "boolean",
-            ValueData::QName(_) => "QName",
+            ValueData::Base64Binary(_) => "Base64Binary",
+            ValueData::HexBinary(_) => "HexBinary",
+            ValueData::QName(..) => "QName",
         }
     }
+    /// Compare two values. Dates and times without a timezone are taken to be in the local timezone.
     pub fn compare(&self, other: &Value, op: Operator) -> Result<bool, Error> {
+        self.compare_with_timezone(other, op, local_timezone())
+    }
+    /// Compare two values. Dates and times without a timezone are taken to be in the given implicit timezone.
+    pub fn compare_with_timezone(
+        &self,
+        other: &Value,
+        op: Operator,
+        implicit: FixedOffset,
+    ) -> Result<bool, Error> {
+        if let Some(ord) = numeric_order(&self.value, &other.value) {
+            return ordering_result(ord, op);
+        }
         match &self.value {
             ValueData::Boolean(b) => {
                 let c = other.to_bool();
@@ -393,7 +504,7 @@ impl Value {
                 }
             }
             ValueData::Integer(i) => {
-                let c = other.to_int()?;
+                let c = BigInt::from(other.to_int()?);
                 match op {
                     Operator::Equal => Ok(*i == c),
                     Operator::NotEqual => Ok(*i != c),
@@ -420,19 +531,9 @@ impl Value {
                     }
                 }
             }
-            ValueData::Double(i) => {
-                let c = other.to_double();
-                match op {
-                    Operator::Equal => Ok(*i == c),
-                    Operator::NotEqual => Ok(*i != c),
-                    Operator::LessThan => Ok(*i < c),
-                    Operator::LessThanEqual => Ok(*i <= c),
-                    Operator::GreaterThan => Ok(*i > c),
-                    Operator::GreaterThanEqual => Ok(*i >= c),
-                    Operator::Is | Operator::Before | Operator::After => {
-                        Err(Error::new(ErrorKind::TypeError, String::from("type error")))
-                    }
-                }
+            // Any other operand is cast to xs:double
+            ValueData::Decimal(_) | ValueData::Float(_) | ValueData::Double(_) => {
+                ordering_result(self.to_double().partial_cmp(&other.to_double()), op)
             }
             ValueData::String(i) => {
                 let c = other.to_string();
@@ -448,9 +549,10 @@ impl Value {
                     }
                 }
             }
-            ValueData::QName(q) => match (op, &other.value) {
-                (Operator::Equal, ValueData::QName(r)) => Ok(*q == *r),
-                (Operator::NotEqual, ValueData::QName(r)) => Ok(*q != *r),
+            // The prefix of a QName is not significant
+            ValueData::QName(q, _) => match (op, &other.value) {
+                (Operator::Equal, ValueData::QName(r, _)) => Ok(*q == *r),
+                (Operator::NotEqual, ValueData::QName(r, _)) => Ok(*q != *r),
                 _ => Err(Error::new(ErrorKind::TypeError, String::from("type error"))),
             },
             ValueData::NCName(q) => match (op, &other.value) {
@@ -463,6 +565,29 @@ impl Value {
                 (Operator::NotEqual, ValueData::NamespaceUri(r)) => Ok(*q != *r),
                 _ => Err(Error::new(ErrorKind::TypeError, String::from("type error"))),
             },
+            // Binary values may only be compared for equality, with a value of the same type
+            ValueData::Base64Binary(a) => match (op, &other.value) {
+                (Operator::Equal, ValueData::Base64Binary(b)) => Ok(a == b),
+                (Operator::NotEqual, ValueData::Base64Binary(b)) => Ok(a != b),
+                _ => Err(Error::new(ErrorKind::TypeError, String::from("type error"))),
+            },
+            ValueData::HexBinary(a) => match (op, &other.value) {
+                (Operator::Equal, ValueData::HexBinary(b)) => Ok(a == b),
+                (Operator::NotEqual, ValueData::HexBinary(b)) => Ok(a != b),
+                _ => Err(Error::new(ErrorKind::TypeError, String::from("type error"))),
+            },
+            ValueData::Duration(_)
+            | ValueData::YearMonthDuration(_)
+            | ValueData::DayTimeDuration(_)
+            | ValueData::DateTime(..)
+            | ValueData::DateTimeStamp(..)
+            | ValueData::Date(..)
+            | ValueData::Time(..)
+            | ValueData::GYearMonth(..)
+            | ValueData::GYear(..)
+            | ValueData::GMonthDay(..)
+            | ValueData::GMonth(..)
+            | ValueData::GDay(..) => self.temporal_compare(other, op, implicit),
             _ => Result::Err(Error::new(
                 ErrorKind::Unknown,
                 format!(
@@ -472,6 +597,94 @@ impl Value {
             )),
         }
     }
+    // Compare a date, time, or duration value with another value of the same type.
+    // A string is first cast to the type of this value.
+    // Durations are only ordered if both are xs:yearMonthDuration or both are xs:dayTimeDuration.
+    // Dates and times are compared as points on the timeline, using the implicit timezone for values without a timezone.
+    // The Gregorian types (gYear, etc.) may only be compared for equality.
+    fn temporal_compare(
+        &self,
+        other: &Value,
+        op: Operator,
+        implicit: FixedOffset,
+    ) -> Result<bool, Error> {
+        let o = match &other.value {
+            ValueData::String(s) => crate::validators::datatypes::parse(
+                match self.value {
+                    ValueData::Duration(_) => "duration",
+                    ValueData::YearMonthDuration(_) => "yearMonthDuration",
+                    ValueData::DayTimeDuration(_) => "dayTimeDuration",
+                    ValueData::DateTime(..) => "dateTime",
+                    ValueData::DateTimeStamp(..) => "dateTimeStamp",
+                    ValueData::Date(..) => "date",
+                    ValueData::GYearMonth(..) => "gYearMonth",
+                    ValueData::GYear(..) => "gYear",
+                    ValueData::GMonthDay(..) => "gMonthDay",
+                    ValueData::GMonth(..) => "gMonth",
+                    ValueData::GDay(..) => "gDay",
+                    _ => "time",
+                },
+                s,
+            )?,
+            v => v.clone(),
+        };
+        let ord = match (&self.value, &o) {
+            (
+                ValueData::DateTime(..) | ValueData::DateTimeStamp(..),
+                ValueData::DateTime(..) | ValueData::DateTimeStamp(..),
+            )
+            | (ValueData::Time(..), ValueData::Time(..))
+            | (ValueData::Date(..), ValueData::Date(..)) => {
+                utc(&self.value, implicit).cmp(&utc(&o, implicit))
+            }
+            (ValueData::GYearMonth(..), ValueData::GYearMonth(..))
+            | (ValueData::GYear(..), ValueData::GYear(..))
+            | (ValueData::GMonthDay(..), ValueData::GMonthDay(..))
+            | (ValueData::GMonth(..), ValueData::GMonth(..))
+            | (ValueData::GDay(..), ValueData::GDay(..))
+                if matches!(op, Operator::Equal | Operator::NotEqual) =>
+            {
+                utc(&self.value, implicit).cmp(&utc(&o, implicit))
+            }
+            (ValueData::YearMonthDuration(a), ValueData::YearMonthDuration(b)) => {
+                a.months.cmp(&b.months)
+            }
+            (ValueData::DayTimeDuration(a), ValueData::DayTimeDuration(b)) => {
+                a.seconds.cmp(&b.seconds)
+            }
+            (
+                ValueData::Duration(a)
+                | ValueData::YearMonthDuration(a)
+                | ValueData::DayTimeDuration(a),
+                ValueData::Duration(b)
+                | ValueData::YearMonthDuration(b)
+                | ValueData::DayTimeDuration(b),
+            ) if matches!(op, Operator::Equal | Operator::NotEqual) => {
+                return Ok((a == b) == matches!(op, Operator::Equal));
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::TypeError,
+                    format!(
+                        "cannot compare {} with {}",
+                        self.value_type(),
+                        other.value_type()
+                    ),
+                ));
+            }
+        };
+        match op {
+            Operator::Equal => Ok(ord == Ordering::Equal),
+            Operator::NotEqual => Ok(ord != Ordering::Equal),
+            Operator::LessThan => Ok(ord == Ordering::Less),
+            Operator::LessThanEqual => Ok(ord != Ordering::Greater),
+            Operator::GreaterThan => Ok(ord == Ordering::Greater),
+            Operator::GreaterThanEqual => Ok(ord != Ordering::Less),
+            Operator::Is | Operator::Before | Operator::After => {
+                Err(Error::new(ErrorKind::TypeError, String::from("type error")))
+            }
+        }
+    }
 }
 
 impl PartialEq for Value {
@@ -486,8 +699,8 @@ impl PartialEq for Value {
                 ValueData::Decimal(e) => *d == e,
                 _ => false, // type error?
             },
-            ValueData::Integer(i) => match other.value {
-                ValueData::Integer(j) => *i == j,
+            ValueData::Integer(i) => match &other.value {
+                ValueData::Integer(j) => i == j,
                 _ => false, // type error? coerce to integer?
             },
             ValueData::Double(d) => match other.value {
@@ -498,14 +711,22 @@ impl PartialEq for Value {
                 ValueData::NCName(o) => *n == *o,
                 _ => false,
             },
-            ValueData::QName(n) => match &other.value {
-                ValueData::QName(o) => *n == *o,
+            ValueData::QName(n, _) => match &other.value {
+                ValueData::QName(o, _) => *n == *o,
                 _ => false,
             },
             ValueData::NamespaceUri(n) => match &other.value {
                 ValueData::NamespaceUri(o) => *n == *o,
                 _ => false,
             },
+            ValueData::Base64Binary(b) => match &other.value {
+                ValueData::Base64Binary(o) => *b == *o,
+                _ => false,
+            },
+            ValueData::HexBinary(b) => match &other.value {
+                ValueData::HexBinary(o) => *b == *o,
+                _ => false,
+            },
             _ => false, // not yet implemented
         }
     }
@@ -522,8 +743,8 @@ impl PartialOrd for Value {
                 ValueData::Decimal(e) => d.partial_cmp(&e),
                 _ => None, // type error?
             },
-            ValueData::Integer(d) => match other.value {
-                ValueData::Integer(e) => d.partial_cmp(&e),
+            ValueData::Integer(d) => match &other.value {
+                ValueData::Integer(e) => d.partial_cmp(e),
                 _ => None, // type error?
             },
             ValueData::Double(d) => match other.value {
@@ -550,8 +771,8 @@ impl Ord for Value {
                 ValueData::Decimal(e) => d.cmp(&e),
                 _ => Ordering::Equal, // type error?
             },
-            ValueData::Integer(d) => match other.value {
-                ValueData::Integer(e) => d.cmp(&e),
+            ValueData::Integer(d) => match &other.value {
+                ValueData::Integer(e) => d.cmp(e),
                 _ => Ordering::Equal, // type error?
             },
             ValueData::Double(d) => match other.value {
@@ -635,14 +856,19 @@ impl From<f64> for Value {
         }
     }
 }
-impl From<i64> for Value {
-    fn from(i: i64) -> Self {
+impl From<BigInt> for Value {
+    fn from(i: BigInt) -> Self {
         Value {
             value: ValueData::Integer(i),
             output: OutputSpec::Normal,
         }
     }
 }
+impl From<i64> for Value {
+    fn from(i: i64) -> Self {
+        Value::from(BigInt::from(i))
+    }
+}
 impl From<i32> for Value {
     fn from(i: i32) -> Self {
         Value {
@@ -750,7 +976,7 @@ impl From<Vec<IDREF>> for Value {
 impl From<QName> for Value {
     fn from(q: QName) -> Self {
         Value {
-            value: ValueData::QName(q),
+            value: ValueData::QName(q, None),
             output: OutputSpec::Normal,
         }
     }
@@ -773,13 +999,261 @@ impl From<NamespaceUri> for Value {
 }
 impl From<DateTime<Local>> for Value {
     fn from(dt: DateTime<Local>) -> Self {
+        Value::from(dt.fixed_offset())
+    }
+}
+impl From<DateTime<FixedOffset>> for Value {
+    fn from(dt: DateTime<FixedOffset>) -> Self {
         Value {
-            value: ValueData::DateTime(dt),
+            value: ValueData::DateTime(dt.naive_local(), Some(*dt.offset())),
             output: OutputSpec::Normal,
         }
     }
 }
 
+// The order of two numeric values, after promoting them to a common type.
+// None if either value is not numeric; Some(None) if the values are unordered (NaN).
+fn numeric_order(a: &ValueData, b: &ValueData) -> Option<Option<Ordering>> {
+    match (a, b) {
+        (ValueData::Integer(i), ValueData::Integer(j)) => Some(Some(i.cmp(j))),
+        (
+            ValueData::Integer(_) | ValueData::Decimal(_),
+            ValueData::Integer(_) | ValueData::Decimal(_),
+        ) => Some(match (as_decimal(a), as_decimal(b)) {
+            (Some(d), Some(e)) => Some(d.cmp(&e)),
+            // Beyond the range of xs:decimal
+            _ => as_double(a)?.partial_cmp(&as_double(b)?),
+        }),
+        _ => Some(as_double(a)?.partial_cmp(&as_double(b)?)),
+    }
+}
+
+fn as_decimal(v: &ValueData) -> Option<Decimal> {
+    match v {
+        ValueData::Decimal(d) => Some(*d),
+        ValueData::Integer(i) => Decimal::from_str(&i.to_string()).ok(),
+        _ => None,
+    }
+}
+
+fn as_double(v: &ValueData) -> Option<f64> {
+    match v {
+        ValueData::Integer(i) => i.to_f64(),
+        ValueData::Int(i) => Some(f64::from(*i)),
+        ValueData::Decimal(d) => d.to_f64(),
+        ValueData::Float(f) => Some(f64::from(*f)),
+        ValueData::Double(d) => Some(*d),
+        _ => None,
+    }
+}
+
+// The result of a comparison, given the order of the operands.
+fn ordering_result(ord: Option<Ordering>, op: Operator) -> Result<bool, Error> {
+    match op {
+        Operator::Equal => Ok(ord == Some(Ordering::Equal)),
+        Operator::NotEqual => Ok(ord != Some(Ordering::Equal)),
+        Operator::LessThan => Ok(ord == Some(Ordering::Less)),
+        Operator::LessThanEqual => Ok(matches!(ord, Some(Ordering::Less | Ordering::Equal))),
+        Operator::GreaterThan => Ok(ord == Some(Ordering::Greater)),
+        Operator::GreaterThanEqual => Ok(matches!(ord, Some(Ordering::Greater | Ordering::Equal))),
+        Operator::Is | Operator::Before | Operator::After => {
+            Err(Error::new(ErrorKind::TypeError, String::from("type error")))
+        }
+    }
+}
+
+// See Value::to_utc
+pub(crate) fn utc(v: &ValueData, implicit: FixedOffset) -> Option<NaiveDateTime> {
+    let day = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).map(|nd| nd.and_time(NaiveTime::MIN));
+    let (dt, tz) = match v {
+        ValueData::DateTime(dt, tz) => (*dt, *tz),
+        ValueData::DateTimeStamp(dt, tz) => (*dt, Some(*tz)),
+        ValueData::Date(d, tz) => (d.and_time(NaiveTime::MIN), *tz),
+        ValueData::Time(t, tz) => (NaiveDate::from_ymd_opt(1972, 12, 31)?.and_time(*t), *tz),
+        ValueData::GYearMonth(y, m, tz) => (day(*y, *m, 1)?, *tz),
+        ValueData::GYear(y, tz) => (day(*y, 1, 1)?, *tz),
+        ValueData::GMonthDay(m, d, tz) => (day(1972, *m, *d)?, *tz),
+        ValueData::GMonth(m, tz) => (day(1972, *m, 1)?, *tz),
+        ValueData::GDay(d, tz) => (day(1972, 12, *d)?, *tz),
+        _ => return None,
+    };
+    tz.unwrap_or(implicit)
+        .from_local_datetime(&dt)
+        .single()
+        .map(|d| d.naive_utc())
+}
+
+// The lexical form of a time of day. Fractional seconds are given only if they are not zero.
+fn time_lexical(t: &NaiveTime) -> String {
+    let mut s = t.format("%H:%M:%S").to_string();
+    if t.nanosecond() > 0 {
+        let f = format!("{:09}", t.nanosecond());
+        s.push('.');
+        s.push_str(f.trim_end_matches('0'));
+    }
+    s
+}
+
+// The lexical form of a year, which has at least four digits.
+fn year_lexical(y: i32) -> String {
+    if y < 0 {
+        format!("-{:04}", y.unsigned_abs())
+    } else {
+        format!("{:04}", y)
+    }
+}
+
+// The lexical form of a timezone, which is empty if there is no timezone.
+fn timezone_lexical(tz: &Option<FixedOffset>) -> String {
+    match tz {
+        None => String::new(),
+        Some(z) if z.local_minus_utc() == 0 => String::from("Z"),
+        Some(z) => {
+            let m = z.local_minus_utc() / 60;
+            format!(
+                "{}{:02}:{:02}",
+                if m < 0 { '-' } else { '+' },
+                m.abs() / 60,
+                m.abs() % 60
+            )
+        }
+    }
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The canonical base64 encoding of binary data.
+pub(crate) fn base64_encode(b: &[u8]) -> String {
+    let mut s = String::with_capacity(b.len().div_ceil(3) * 4);
+    for c in b.chunks(3) {
+        let n = c
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, o)| acc | (u32::from(*o) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= c.len() {
+                s.push(char::from(BASE64[(n >> (18 - 6 * i)) as usize & 0x3f]));
+            } else {
+                s.push('=');
+            }
+        }
+    }
+    s
+}
+
+/// Decode base64 data. Whitespace is ignored. The result is None if the data is not valid base64.
+pub(crate) fn base64_decode(s: &str) -> Option<Vec<u8>> {
+    let c: Vec<u8> = s.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    if c.len() % 4 != 0 {
+        return None;
+    }
+    let mut b = Vec::with_capacity(c.len() / 4 * 3);
+    for (q, quad) in c.chunks(4).enumerate() {
+        let last = q == c.len() / 4 - 1;
+        let pad = quad.iter().rev().take_while(|o| **o == b'=').count();
+        if pad > 2 || (pad > 0 && !last) {
+            return None;
+        }
+        let mut n = 0u32;
+        for (i, o) in quad[..4 - pad].iter().enumerate() {
+            let d = BASE64.iter().position(|e| e == o)? as u32;
+            n |= d << (18 - 6 * i);
+        }
+        for i in 0..3 - pad {
+            b.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+    Some(b)
+}
+
+static LOCAL_TIMEZONE: LazyLock<FixedOffset> = LazyLock::new(|| *Local::now().offset());
+
+/// The offset of the local timezone, as it was when first used.
+/// This is the implicit timezone when none has been given.
+pub(crate) fn local_timezone() -> FixedOffset {
+    *LOCAL_TIMEZONE
+}
+
+/// The value of a duration: a number of months and a (decimal) number of seconds.
+/// Both components have the same sign.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Duration {
+    months: i64,
+    seconds: Decimal,
+}
+impl Duration {
+    /// Create a duration. The months and seconds must not have different signs.
+    pub fn new(months: i64, seconds: Decimal) -> Result<Self, Error> {
+        if (months < 0 && seconds.is_sign_positive() && !seconds.is_zero())
+            || (months > 0 && seconds.is_sign_negative() && !seconds.is_zero())
+        {
+            Err(Error::new(
+                ErrorKind::TypeError,
+                String::from("the components of a duration must have the same sign"),
+            ))
+        } else {
+            Ok(Duration { months, seconds })
+        }
+    }
+    /// A duration of a number of months.
+    pub fn from_months(months: i64) -> Self {
+        Duration {
+            months,
+            seconds: Decimal::ZERO,
+        }
+    }
+    /// A duration of a number of seconds.
+    pub fn from_seconds(seconds: Decimal) -> Self {
+        Duration { months: 0, seconds }
+    }
+    pub fn months(&self) -> i64 {
+        self.months
+    }
+    pub fn seconds(&self) -> Decimal {
+        self.seconds
+    }
+    pub fn is_negative(&self) -> bool {
+        self.months < 0 || (self.seconds.is_sign_negative() && !self.seconds.is_zero())
+    }
+}
+/// The canonical lexical representation of an xs:duration.
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.months == 0 && self.seconds.is_zero() {
+            return f.write_str("PT0S");
+        }
+        let mut r = String::from(if self.is_negative() { "-P" } else { "P" });
+        let m = self.months.unsigned_abs();
+        if m >= 12 {
+            r.push_str(&format!("{}Y", m / 12));
+        }
+        if m % 12 != 0 {
+            r.push_str(&format!("{}M", m % 12));
+        }
+        let s = self.seconds.abs();
+        let days = (s / Decimal::from(86400)).trunc();
+        let hours = (s % Decimal::from(86400) / Decimal::from(3600)).trunc();
+        let minutes = (s % Decimal::from(3600) / Decimal::from(60)).trunc();
+        let secs = (s % Decimal::from(60)).normalize();
+        if !days.is_zero() {
+            r.push_str(&format!("{}D", days));
+        }
+        if !(hours.is_zero() && minutes.is_zero() && secs.is_zero()) {
+            r.push('T');
+            if !hours.is_zero() {
+                r.push_str(&format!("{}H", hours));
+            }
+            if !minutes.is_zero() {
+                r.push_str(&format!("{}M", minutes));
+            }
+            if !secs.is_zero() {
+                r.push_str(&format!("{}S", secs));
+            }
+        }
+        f.write_str(&r)
+    }
+}
+
 #[derive(Clone, Debug, Hash)]
 pub struct NonPositiveInteger(i64);
 impl TryFrom<i64> for NonPositiveInteger {
@@ -1388,6 +1862,25 @@ mod tests {
         assert_eq!(Operator::After.to_string(), ">>")
     }
 
+    // Durations
+    #[test]
+    fn duration_canonical() {
+        assert_eq!(Duration::default().to_string(), "PT0S");
+        assert_eq!(Duration::from_months(-14).to_string(), "-P1Y2M");
+        assert_eq!(
+            Duration::new(1, Decimal::from(90061)).unwrap().to_string(),
+            "P1M1DT1H1M1S"
+        );
+        assert_eq!(
+            ValueBuilder::new()
+                .value(ValueData::YearMonthDuration(Duration::default()))
+                .build()
+                .to_string(),
+            "P0M"
+        );
+        assert!(Duration::new(1, Decimal::from(-1)).is_err());
+    }
+
     // Building a value
     #[test]
     fn build_1() {
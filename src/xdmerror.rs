@@ -20,8 +20,6 @@ pub enum ErrorKind {
     /// XPST0005
     StaticUndefined,
     /// XPST0008
-    StaticNamespace,
-    /// XPST0010
     StaticBadFunction,
     /// XPST0017
     MixedTypes,
@@ -48,7 +46,6 @@ impl ErrorKind {
             ErrorKind::TypeError => "type error",
             ErrorKind::StaticData => "wrong static type",
             ErrorKind::StaticUndefined => "undefined name",
-            ErrorKind::StaticNamespace => "namespace axis not supported",
             ErrorKind::StaticBadFunction => "function call name and arity do not match",
             ErrorKind::MixedTypes => "result of path operator contains both nodes and non-nodes",
             ErrorKind::NotNodes => "path expression is not a sequence of nodes",
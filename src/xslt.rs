@@ -77,6 +77,7 @@ use crate::parser::avt::parse as parse_avt;
 use crate::parser::xpath::parse;
 use crate::pattern::{Branch, Pattern};
 use crate::transform::callable::{ActualParameters, Callable, FormalParameters};
+use crate::transform::collation::collation_uri;
 use crate::transform::context::{Context, ContextBuilder};
 use crate::transform::numbers::{Level, Numbering};
 use crate::transform::template::Template;
@@ -237,6 +238,10 @@ static ATTRORDER: LazyLock<QName> =
     LazyLock::new(|| QName::new_from_parts(NcName::try_from("order").unwrap(), None));
 static ATTRELEMENTS: LazyLock<QName> =
     LazyLock::new(|| QName::new_from_parts(NcName::try_from("elements").unwrap(), None));
+static ATTRCOLLATION: LazyLock<QName> =
+    LazyLock::new(|| QName::new_from_parts(NcName::try_from("collation").unwrap(), None));
+static ATTRLANG: LazyLock<QName> =
+    LazyLock::new(|| QName::new_from_parts(NcName::try_from("lang").unwrap(), None));
 
 /// The XSLT trait allows an object to use an XSL Stylesheet to transform a document into a [Sequence].
 pub trait XSLT: Node {
@@ -515,7 +520,7 @@ where
                             let (t, nt, q) = s.terminal_node_test();
                             // If "/" then -0.5
                             match (t, nt) {
-                                (Axis::SelfAttribute, _) => -0.5,
+                                (Axis::SelfAttribute, _) | (Axis::SelfNamespace, _) => -0.5,
                                 (Axis::SelfAxis, Axis::Parent)
                                 | (Axis::SelfAxis, Axis::Ancestor)
                                 | (Axis::SelfAxis, Axis::AncestorOrSelf) => match q {
@@ -566,10 +571,12 @@ where
             let m = c.get_attribute(&ATTRMATCH);
             let pat = Pattern::try_from(m.to_string())?;
             let u = c.get_attribute(&ATTRUSE);
+            let collation = c.get_attribute(&ATTRCOLLATION);
             keys.push((
                 name,
                 pat,
                 parse::<N>(&u.to_string(), Some(c.clone()), None)?,
+                collation_uri(&collation.to_string(), ""),
             ));
             Ok(())
         })?;
@@ -626,8 +633,12 @@ where
         .output_definition(od)
         .build();
 
-    keys.iter()
-        .for_each(|(name, m, u)| newctxt.declare_key(name.to_string(), m.clone(), u.clone()));
+    keys.iter().for_each(|(name, m, u, c)| {
+        newctxt.declare_key(name.to_string(), m.clone(), u.clone());
+        if let Some(uri) = c {
+            newctxt.key_collation(name.to_string(), uri.clone())
+        }
+    });
 
     // Add named templates
     stylenode
@@ -1034,6 +1045,7 @@ fn to_transform<N: Node>(
                 }
             } else if qn == *XSLFOREACHGROUP {
                 let ord = get_sort_keys(&n)?;
+                let collation = collation_uri(&n.get_attribute(&ATTRCOLLATION).to_string(), "");
                 let s = n.get_attribute(&ATTRSELECT);
                 if !s.to_string().is_empty() {
                     match (
@@ -1043,7 +1055,10 @@ fn to_transform<N: Node>(
                         n.get_attribute(&ATTRGROUPENDINGWITH).to_string().as_str(),
                     ) {
                         (by, "", "", "") => Ok(Transform::ForEach(
-                            Some(Grouping::By(vec![parse::<N>(by, Some(n.clone()), None)?])),
+                            Some(Grouping::By(
+                                vec![parse::<N>(by, Some(n.clone()), None)?],
+                                collation,
+                            )),
                             Box::new(parse::<N>(&s.to_string(), Some(n.clone()), None)?),
                             Box::new(Transform::SequenceItems(n.child_iter().try_fold(
                                 vec![],
@@ -1055,11 +1070,10 @@ fn to_transform<N: Node>(
                             ord,
                         )),
                         ("", adj, "", "") => Ok(Transform::ForEach(
-                            Some(Grouping::Adjacent(vec![parse::<N>(
-                                adj,
-                                Some(n.clone()),
-                                None,
-                            )?])),
+                            Some(Grouping::Adjacent(
+                                vec![parse::<N>(adj, Some(n.clone()), None)?],
+                                collation,
+                            )),
                             Box::new(parse::<N>(&s.to_string(), Some(n.clone()), None)?),
                             Box::new(Transform::SequenceItems(n.child_iter().try_fold(
                                 vec![],
@@ -1446,7 +1460,7 @@ fn to_transform<N: Node>(
     }
 }
 
-fn get_sort_keys<N: Node>(n: &N) -> Result<Vec<(Order, Transform<N>)>, Error> {
+fn get_sort_keys<N: Node>(n: &N) -> Result<Vec<(Order, Transform<N>, Option<String>)>, Error> {
     let mut result = vec![];
     let mut nit = n.child_iter();
     loop {
@@ -1464,6 +1478,10 @@ fn get_sort_keys<N: Node>(n: &N) -> Result<Vec<(Order, Transform<N>)>, Error> {
                         result.push((
                             ord,
                             parse::<N>(&sortsel.to_string(), Some(n.clone()), None)?,
+                            collation_uri(
+                                &c.get_attribute(&ATTRCOLLATION).to_string(),
+                                &c.get_attribute(&ATTRLANG).to_string(),
+                            ),
                         ));
                     } else {
                         break;
@@ -1,4 +1,4 @@
-//mod relaxng;
+mod relaxng;
 
 //mod xml_id;
 /*
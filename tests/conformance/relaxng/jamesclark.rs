@@ -1,8 +1,5 @@
+use super::parse;
 use std::fs;
-use std::rc::Rc;
-//use xrust::item::Node;
-use xrust::parser::xml;
-use xrust::trees::smite::{Node as SmiteNode};
 use xrust::validators::relaxng::validate_relaxng;
 
 
@@ -16,12 +13,10 @@ fn relaxng_incorrect_001_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/001/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -38,12 +33,10 @@ fn relaxng_incorrect_002_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/002/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -60,12 +53,10 @@ fn relaxng_incorrect_003_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/003/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -82,12 +73,10 @@ fn relaxng_incorrect_004_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/004/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -104,12 +93,10 @@ fn relaxng_incorrect_005_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/005/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -126,12 +113,10 @@ fn relaxng_incorrect_006_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/006/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -148,12 +133,10 @@ fn relaxng_incorrect_007_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/007/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -170,12 +153,10 @@ fn relaxng_incorrect_008_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/008/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -192,12 +173,10 @@ fn relaxng_incorrect_009_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/009/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -214,12 +193,10 @@ fn relaxng_incorrect_010_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/010/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -236,12 +213,10 @@ fn relaxng_incorrect_011_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/011/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -258,12 +233,10 @@ fn relaxng_incorrect_012_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/012/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -280,12 +253,10 @@ fn relaxng_incorrect_013_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/013/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -302,12 +273,10 @@ fn relaxng_incorrect_014_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/014/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -324,12 +293,10 @@ fn relaxng_incorrect_015_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/015/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -346,12 +313,10 @@ fn relaxng_incorrect_016_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/016/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -368,12 +333,10 @@ fn relaxng_incorrect_017_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/017/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -390,12 +353,10 @@ fn relaxng_incorrect_018_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/018/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -412,12 +373,10 @@ fn relaxng_incorrect_019_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/019/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -434,12 +393,10 @@ fn relaxng_incorrect_020_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/020/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -456,12 +413,10 @@ fn relaxng_incorrect_021_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/021/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -478,12 +433,10 @@ fn relaxng_incorrect_022_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/022/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -500,12 +453,10 @@ fn relaxng_incorrect_023_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/023/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -522,12 +473,10 @@ fn relaxng_incorrect_024_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/024/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -544,12 +493,10 @@ fn relaxng_incorrect_025_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/025/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -566,12 +513,10 @@ fn relaxng_incorrect_026_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/026/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -588,12 +533,10 @@ fn relaxng_incorrect_027_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/027/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -610,12 +553,10 @@ fn relaxng_incorrect_028_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/028/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -632,12 +573,10 @@ fn relaxng_incorrect_029_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/029/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -654,12 +593,10 @@ fn relaxng_incorrect_030_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/030/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -676,12 +613,10 @@ fn relaxng_incorrect_031_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/031/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -698,12 +633,10 @@ fn relaxng_incorrect_032_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/032/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -720,12 +653,10 @@ fn relaxng_incorrect_033_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/033/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -742,12 +673,10 @@ fn relaxng_incorrect_034_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/034/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -764,12 +693,10 @@ fn relaxng_incorrect_035_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/035/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -786,12 +713,10 @@ fn relaxng_incorrect_036_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/036/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -808,12 +733,10 @@ fn relaxng_incorrect_037_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/037/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -830,12 +753,10 @@ fn relaxng_incorrect_038_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/038/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -852,12 +773,10 @@ fn relaxng_incorrect_039_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/039/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -874,12 +793,10 @@ fn relaxng_incorrect_040_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/040/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -896,12 +813,10 @@ fn relaxng_incorrect_041_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/041/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -918,12 +833,10 @@ fn relaxng_incorrect_042_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/042/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -940,12 +853,10 @@ fn relaxng_incorrect_043_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/043/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -962,12 +873,10 @@ fn relaxng_incorrect_044_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/044/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -984,12 +893,10 @@ fn relaxng_incorrect_045_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/045/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1006,12 +913,10 @@ fn relaxng_incorrect_046_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/046/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1028,12 +933,10 @@ fn relaxng_incorrect_047_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/047/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1050,12 +953,10 @@ fn relaxng_incorrect_048_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/048/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1072,12 +973,10 @@ fn relaxng_valid_049_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/049/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/049/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -1095,12 +994,10 @@ fn relaxng_valid_050_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/050/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/050/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -1118,12 +1015,10 @@ fn relaxng_incorrect_053_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/053/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1141,12 +1036,10 @@ fn relaxng_valid_054_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/054/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/054/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -1164,12 +1057,10 @@ fn relaxng_valid_055_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/055/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/055/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -1187,12 +1078,10 @@ fn relaxng_incorrect_056_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/056/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1210,12 +1099,10 @@ fn relaxng_incorrect_057_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/057/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1233,12 +1120,10 @@ fn relaxng_incorrect_058_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/058/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1256,12 +1141,10 @@ fn relaxng_valid_059_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/059/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/059/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -1279,12 +1162,10 @@ fn relaxng_incorrect_060_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/060/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1302,12 +1183,10 @@ fn relaxng_incorrect_061_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/061/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1325,12 +1204,10 @@ fn relaxng_incorrect_062_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/062/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1348,12 +1225,10 @@ fn relaxng_incorrect_063_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/063/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1370,12 +1245,10 @@ fn relaxng_valid_064_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/064/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/064/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -1392,12 +1265,10 @@ fn relaxng_valid_065_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/065/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/065/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -1414,12 +1285,10 @@ fn relaxng_valid_066_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/066/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/066/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -1437,12 +1306,10 @@ fn relaxng_incorrect_067_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/067/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1460,12 +1327,10 @@ fn relaxng_incorrect_068_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/068/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1482,12 +1347,10 @@ fn relaxng_valid_069_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/069/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/069/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -1504,12 +1367,10 @@ fn relaxng_incorrect_070_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/070/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1526,12 +1387,10 @@ fn relaxng_incorrect_071_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/071/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1548,12 +1407,10 @@ fn relaxng_incorrect_072_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/072/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1570,12 +1427,10 @@ fn relaxng_incorrect_073_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/073/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1592,12 +1447,10 @@ fn relaxng_incorrect_074_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/074/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1614,12 +1467,10 @@ fn relaxng_valid_075_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/075/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/075/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -1636,12 +1487,10 @@ fn relaxng_incorrect_076_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/076/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1658,12 +1507,10 @@ fn relaxng_incorrect_077_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/077/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1680,12 +1527,10 @@ fn relaxng_incorrect_078_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/078/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1702,12 +1547,10 @@ fn relaxng_incorrect_079_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/079/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1724,12 +1567,10 @@ fn relaxng_incorrect_080_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/080/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1746,12 +1587,10 @@ fn relaxng_incorrect_081_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/081/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1768,12 +1607,10 @@ fn relaxng_incorrect_082_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/082/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1790,12 +1627,10 @@ fn relaxng_incorrect_083_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/083/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1812,12 +1647,10 @@ fn relaxng_incorrect_084_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/084/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1834,12 +1667,10 @@ fn relaxng_incorrect_085_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/085/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1856,12 +1687,10 @@ fn relaxng_incorrect_086_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/086/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1878,12 +1707,10 @@ fn relaxng_incorrect_087_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/087/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -1900,12 +1727,10 @@ fn relaxng_valid_088_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/088/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/088/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -1922,12 +1747,10 @@ fn relaxng_valid_089_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/089/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/089/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -1944,12 +1767,10 @@ fn relaxng_valid_090_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/090/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/090/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -1966,12 +1787,10 @@ fn relaxng_valid_091_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/091/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/091/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -1988,12 +1807,10 @@ fn relaxng_valid_092_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/092/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/092/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2010,12 +1827,10 @@ fn relaxng_valid_093_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/093/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/093/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2031,12 +1846,10 @@ fn relaxng_valid_094_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/094/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/094/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2052,12 +1865,10 @@ fn relaxng_valid_095_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/095/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/095/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2073,12 +1884,10 @@ fn relaxng_invalid_095_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/095/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/095/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2094,12 +1903,10 @@ fn relaxng_valid_096_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/096/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/096/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2115,12 +1922,10 @@ fn relaxng_valid_097_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/097/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/097/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2136,12 +1941,10 @@ fn relaxng_valid_098_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/098/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/098/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2157,12 +1960,10 @@ fn relaxng_valid_099_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/099/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/099/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2178,12 +1979,10 @@ fn relaxng_valid_099_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/099/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/099/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2199,12 +1998,10 @@ fn relaxng_valid_099_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/099/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/099/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2220,12 +2017,10 @@ fn relaxng_invalid_099_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/099/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/099/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2241,12 +2036,10 @@ fn relaxng_invalid_099_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/099/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/099/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2262,12 +2055,10 @@ fn relaxng_valid_100_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/100/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/100/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2283,12 +2074,10 @@ fn relaxng_invalid_100_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/100/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/100/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2304,12 +2093,10 @@ fn relaxng_valid_101_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/101/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/101/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2325,12 +2112,10 @@ fn relaxng_invalid_101_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/101/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/101/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2346,12 +2131,10 @@ fn relaxng_incorrect_102_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/102/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2367,12 +2150,10 @@ fn relaxng_valid_103_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/103/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/103/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2388,12 +2169,10 @@ fn relaxng_invalid_103_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/103/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/103/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2409,12 +2188,10 @@ fn relaxng_valid_104_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/104/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/104/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2430,12 +2207,10 @@ fn relaxng_invalid_104_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/104/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/104/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2451,12 +2226,10 @@ fn relaxng_incorrect_105_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/105/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2472,12 +2245,10 @@ fn relaxng_incorrect_106_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/106/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2493,12 +2264,10 @@ fn relaxng_incorrect_107_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/107/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2515,12 +2284,10 @@ fn relaxng_valid_108_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/108/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/108/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2537,12 +2304,10 @@ fn relaxng_invalid_108_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/108/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/108/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2558,12 +2323,10 @@ fn relaxng_valid_109_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/109/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/109/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2579,12 +2342,10 @@ fn relaxng_invalid_109_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/109/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/109/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2600,12 +2361,10 @@ fn relaxng_valid_110_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/110/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/110/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2621,12 +2380,10 @@ fn relaxng_invalid_110_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/110/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/110/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2642,12 +2399,10 @@ fn relaxng_valid_111_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/111/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/111/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2663,12 +2418,10 @@ fn relaxng_invalid_111_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/111/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/111/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2684,12 +2437,10 @@ fn relaxng_incorrect_112_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/112/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2705,12 +2456,10 @@ fn relaxng_incorrect_113_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/113/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2726,12 +2475,10 @@ fn relaxng_incorrect_114_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/114/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2747,12 +2494,10 @@ fn relaxng_valid_115_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/115/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/115/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2768,12 +2513,10 @@ fn relaxng_invalid_115_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/115/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/115/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2789,12 +2532,10 @@ fn relaxng_incorrect_116_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/116/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2810,12 +2551,10 @@ fn relaxng_valid_117_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/117/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/117/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2831,12 +2570,10 @@ fn relaxng_invalid_117_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/117/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/117/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2852,12 +2589,10 @@ fn relaxng_incorrect_118_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/118/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2873,12 +2608,10 @@ fn relaxng_valid_119_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/119/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/119/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2894,12 +2627,10 @@ fn relaxng_invalid_119_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/119/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/119/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2915,12 +2646,10 @@ fn relaxng_valid_120_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/120/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/120/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2936,12 +2665,10 @@ fn relaxng_valid_120_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/120/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/120/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -2957,12 +2684,10 @@ fn relaxng_invalid_120_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/120/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/120/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2978,12 +2703,10 @@ fn relaxng_incorrect_121_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/121/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -2999,12 +2722,10 @@ fn relaxng_valid_122_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/122/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/122/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -3020,12 +2741,10 @@ fn relaxng_invalid_122_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/122/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/122/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3041,12 +2760,10 @@ fn relaxng_invalid_123_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/123/1.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/123/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3062,12 +2779,10 @@ fn relaxng_valid_123_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/123/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/123/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -3083,12 +2798,10 @@ fn relaxng_valid_124_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/124/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/124/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -3104,12 +2817,10 @@ fn relaxng_invalid_124_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/124/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/124/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3125,12 +2836,10 @@ fn relaxng_valid_125_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/125/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/125/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -3146,12 +2855,10 @@ fn relaxng_invalid_125_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/125/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/125/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3166,12 +2873,10 @@ fn relaxng_valid_126_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/126/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/126/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -3186,12 +2891,10 @@ fn relaxng_invalid_126_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/126/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/126/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3206,12 +2909,10 @@ fn relaxng_valid_127_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/127/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/127/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -3226,12 +2927,10 @@ fn relaxng_invalid_127_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/127/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/127/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3246,12 +2945,10 @@ fn relaxng_valid_128_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/128/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/128/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -3266,12 +2963,10 @@ fn relaxng_invalid_128_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/128/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/128/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3287,12 +2982,10 @@ fn relaxng_incorrect_129_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/129/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3308,12 +3001,10 @@ fn relaxng_valid_130_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/130/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/130/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -3329,12 +3020,10 @@ fn relaxng_invalid_130_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/130/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/130/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3350,12 +3039,10 @@ fn relaxng_valid_131_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/131/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/131/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -3371,12 +3058,10 @@ fn relaxng_invalid_131_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/131/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/131/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3392,12 +3077,10 @@ fn relaxng_valid_132_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/132/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/132/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -3413,12 +3096,10 @@ fn relaxng_invalid_132_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/132/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/132/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3434,12 +3115,10 @@ fn relaxng_valid_133_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/133/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/133/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -3455,12 +3134,10 @@ fn relaxng_invalid_133_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/133/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/133/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3476,12 +3153,10 @@ fn relaxng_valid_134_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/134/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/134/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -3497,12 +3172,10 @@ fn relaxng_invalid_134_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/134/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/134/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3518,12 +3191,10 @@ fn relaxng_invalid_134_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/134/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/134/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3539,12 +3210,10 @@ fn relaxng_invalid_134_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/134/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/134/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3560,12 +3229,10 @@ fn relaxng_invalid_134_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/134/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/134/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3581,12 +3248,10 @@ fn relaxng_invalid_134_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/134/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/134/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3602,12 +3267,10 @@ fn relaxng_invalid_134_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/134/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/134/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3623,12 +3286,10 @@ fn relaxng_invalid_134_8(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/134/8.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/134/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3644,12 +3305,10 @@ fn relaxng_valid_135_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/135/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/135/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -3665,12 +3324,10 @@ fn relaxng_valid_135_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/135/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/135/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -3686,12 +3343,10 @@ fn relaxng_invalid_135_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/135/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/135/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3707,12 +3362,10 @@ fn relaxng_invalid_135_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/135/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/135/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3728,12 +3381,10 @@ fn relaxng_invalid_135_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/135/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/135/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3749,12 +3400,10 @@ fn relaxng_invalid_135_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/135/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/135/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3770,12 +3419,10 @@ fn relaxng_invalid_135_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/135/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/135/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3791,12 +3438,10 @@ fn relaxng_invalid_135_8(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/135/8.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/135/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3812,12 +3457,10 @@ fn relaxng_valid_136_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/136/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/136/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -3833,12 +3476,10 @@ fn relaxng_valid_136_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/136/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/136/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -3854,12 +3495,10 @@ fn relaxng_invalid_136_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/136/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/136/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3875,12 +3514,10 @@ fn relaxng_invalid_136_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/136/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/136/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3896,12 +3533,10 @@ fn relaxng_valid_136_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/136/5.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/136/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -3917,12 +3552,10 @@ fn relaxng_invalid_136_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/136/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/136/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3938,12 +3571,10 @@ fn relaxng_invalid_136_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/136/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/136/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3959,12 +3590,10 @@ fn relaxng_invalid_136_8(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/136/8.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/136/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -3980,12 +3609,10 @@ fn relaxng_valid_137_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/137/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/137/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -4001,12 +3628,10 @@ fn relaxng_invalid_137_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/137/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/137/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4022,12 +3647,10 @@ fn relaxng_invalid_137_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/137/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/137/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4043,12 +3666,10 @@ fn relaxng_invalid_137_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/137/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/137/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4064,12 +3685,10 @@ fn relaxng_valid_137_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/137/5.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/137/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -4085,12 +3704,10 @@ fn relaxng_invalid_137_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/137/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/137/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4106,12 +3723,10 @@ fn relaxng_invalid_137_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/137/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/137/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4127,12 +3742,10 @@ fn relaxng_invalid_137_8(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/137/8.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/137/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4148,12 +3761,10 @@ fn relaxng_valid_138_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/138/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/138/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -4169,12 +3780,10 @@ fn relaxng_invalid_138_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/138/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/138/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4190,12 +3799,10 @@ fn relaxng_valid_139_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/139/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/139/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -4211,12 +3818,10 @@ fn relaxng_invalid_139_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/139/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/139/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4232,12 +3837,10 @@ fn relaxng_invalid_139_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/139/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/139/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4253,12 +3856,10 @@ fn relaxng_invalid_139_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/139/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/139/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4274,12 +3875,10 @@ fn relaxng_invalid_139_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/139/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/139/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4295,12 +3894,10 @@ fn relaxng_valid_139_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/139/6.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/139/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -4316,12 +3913,10 @@ fn relaxng_invalid_139_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/139/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/139/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4337,12 +3932,10 @@ fn relaxng_invalid_139_8(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/139/8.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/139/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4358,12 +3951,10 @@ fn relaxng_valid_140_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/140/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/140/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -4379,12 +3970,10 @@ fn relaxng_invalid_140_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/140/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/140/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4400,12 +3989,10 @@ fn relaxng_invalid_140_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/140/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/140/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4421,12 +4008,10 @@ fn relaxng_invalid_140_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/140/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/140/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4442,12 +4027,10 @@ fn relaxng_invalid_140_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/140/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/140/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4463,12 +4046,10 @@ fn relaxng_invalid_140_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/140/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/140/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4484,12 +4065,10 @@ fn relaxng_invalid_140_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/140/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/140/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4505,12 +4084,10 @@ fn relaxng_invalid_140_8(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/140/8.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/140/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4526,12 +4103,10 @@ fn relaxng_valid_141_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/141/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/141/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -4547,12 +4122,10 @@ fn relaxng_invalid_141_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/141/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/141/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4568,12 +4141,10 @@ fn relaxng_invalid_141_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/141/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/141/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4589,12 +4160,10 @@ fn relaxng_invalid_141_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/141/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/141/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4610,12 +4179,10 @@ fn relaxng_valid_142_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/142/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/142/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -4631,12 +4198,10 @@ fn relaxng_valid_142_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/142/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/142/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -4652,12 +4217,10 @@ fn relaxng_invalid_142_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/142/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/142/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4673,12 +4236,10 @@ fn relaxng_invalid_142_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/142/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/142/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4694,12 +4255,10 @@ fn relaxng_invalid_142_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/142/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/142/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4715,12 +4274,10 @@ fn relaxng_valid_143_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/143/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/143/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -4736,12 +4293,10 @@ fn relaxng_valid_143_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/143/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/143/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -4757,12 +4312,10 @@ fn relaxng_invalid_143_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/143/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/143/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4778,12 +4331,10 @@ fn relaxng_valid_144_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/144/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/144/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -4799,12 +4350,10 @@ fn relaxng_valid_144_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/144/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/144/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -4820,12 +4369,10 @@ fn relaxng_invalid_144_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/144/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/144/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4841,12 +4388,10 @@ fn relaxng_valid_145_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/145/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/145/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -4862,12 +4407,10 @@ fn relaxng_invalid_145_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/145/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/145/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4883,12 +4426,10 @@ fn relaxng_invalid_145_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/145/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/145/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4904,12 +4445,10 @@ fn relaxng_invalid_145_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/145/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/145/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4925,12 +4464,10 @@ fn relaxng_invalid_145_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/145/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/145/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4946,12 +4483,10 @@ fn relaxng_invalid_145_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/145/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/145/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4967,12 +4502,10 @@ fn relaxng_invalid_145_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/145/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/145/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -4988,12 +4521,10 @@ fn relaxng_invalid_145_8(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/145/8.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/145/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5009,12 +4540,10 @@ fn relaxng_valid_146_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/146/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/146/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5030,12 +4559,10 @@ fn relaxng_valid_146_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/146/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/146/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5051,12 +4578,10 @@ fn relaxng_valid_146_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/146/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/146/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5072,12 +4597,10 @@ fn relaxng_invalid_146_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/146/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/146/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5093,12 +4616,10 @@ fn relaxng_valid_147_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/147/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/147/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5114,12 +4635,10 @@ fn relaxng_valid_147_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/147/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/147/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5135,12 +4654,10 @@ fn relaxng_valid_147_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/147/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/147/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5156,12 +4673,10 @@ fn relaxng_valid_147_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/147/4.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/147/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5177,12 +4692,10 @@ fn relaxng_valid_147_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/147/5.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/147/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5198,12 +4711,10 @@ fn relaxng_valid_147_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/147/6.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/147/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5219,12 +4730,10 @@ fn relaxng_invalid_147_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/147/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/147/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5240,12 +4749,10 @@ fn relaxng_invalid_147_8(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/147/8.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/147/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5261,12 +4768,10 @@ fn relaxng_valid_148_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/148/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/148/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5282,12 +4787,10 @@ fn relaxng_invalid_148_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/148/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/148/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5303,12 +4806,10 @@ fn relaxng_valid_149_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/149/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/149/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5324,12 +4825,10 @@ fn relaxng_invalid_149_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/149/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/149/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5345,12 +4844,10 @@ fn relaxng_valid_150_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/150/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/150/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5366,12 +4863,10 @@ fn relaxng_invalid_150_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/150/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/150/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5387,12 +4882,10 @@ fn relaxng_valid_151_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/151/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/151/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5408,12 +4901,10 @@ fn relaxng_valid_151_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/151/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/151/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5429,12 +4920,10 @@ fn relaxng_valid_151_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/151/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/151/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5450,12 +4939,10 @@ fn relaxng_valid_151_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/151/4.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/151/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5471,12 +4958,10 @@ fn relaxng_invalid_151_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/151/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/151/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5492,12 +4977,10 @@ fn relaxng_invalid_151_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/151/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/151/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5513,12 +4996,10 @@ fn relaxng_valid_152_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/152/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/152/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5534,12 +5015,10 @@ fn relaxng_valid_152_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/152/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/152/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5555,12 +5034,10 @@ fn relaxng_invalid_152_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/152/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/152/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5576,12 +5053,10 @@ fn relaxng_invalid_152_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/152/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/152/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5597,12 +5072,10 @@ fn relaxng_valid_153_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/153/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/153/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5618,12 +5091,10 @@ fn relaxng_valid_153_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/153/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/153/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5639,12 +5110,10 @@ fn relaxng_invalid_153_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/153/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/153/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5660,12 +5129,10 @@ fn relaxng_invalid_153_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/153/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/153/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5681,12 +5148,10 @@ fn relaxng_valid_153_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/153/5.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/153/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5702,12 +5167,10 @@ fn relaxng_incorrect_154_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/154/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5723,12 +5186,10 @@ fn relaxng_incorrect_155_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/155/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5744,12 +5205,10 @@ fn relaxng_incorrect_156_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/156/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5765,12 +5224,10 @@ fn relaxng_incorrect_157_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/157/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5786,12 +5243,10 @@ fn relaxng_incorrect_158_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/158/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5807,12 +5262,10 @@ fn relaxng_incorrect_159_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/159/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5829,12 +5282,10 @@ fn relaxng_incorrect_160_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/160/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5851,12 +5302,10 @@ fn relaxng_incorrect_161_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/161/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5872,12 +5321,10 @@ fn relaxng_incorrect_162_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/162/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5893,12 +5340,10 @@ fn relaxng_valid_163_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/163/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/163/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -5914,12 +5359,10 @@ fn relaxng_incorrect_164_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/164/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5935,12 +5378,10 @@ fn relaxng_incorrect_165_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/165/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5956,12 +5397,10 @@ fn relaxng_incorrect_166_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/166/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5977,12 +5416,10 @@ fn relaxng_incorrect_167_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/167/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -5998,12 +5435,10 @@ fn relaxng_incorrect_168_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/168/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6019,12 +5454,10 @@ fn relaxng_incorrect_169_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/169/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6040,12 +5473,10 @@ fn relaxng_incorrect_170_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/170/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6061,12 +5492,10 @@ fn relaxng_incorrect_171_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/171/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6082,12 +5511,10 @@ fn relaxng_incorrect_172_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/172/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6103,12 +5530,10 @@ fn relaxng_incorrect_173_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/173/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6124,12 +5549,10 @@ fn relaxng_incorrect_174_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/174/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6145,12 +5568,10 @@ fn relaxng_incorrect_175_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/175/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6166,12 +5587,10 @@ fn relaxng_valid_176_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/176/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/176/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -6187,12 +5606,10 @@ fn relaxng_incorrect_177_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/177/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6208,12 +5625,10 @@ fn relaxng_incorrect_178_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/178/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6229,12 +5644,10 @@ fn relaxng_incorrect_179_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/179/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6250,12 +5663,10 @@ fn relaxng_incorrect_180_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/180/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6271,12 +5682,10 @@ fn relaxng_incorrect_181_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/181/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6292,12 +5701,10 @@ fn relaxng_incorrect_182_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/182/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6313,12 +5720,10 @@ fn relaxng_incorrect_183_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/183/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6334,12 +5739,10 @@ fn relaxng_incorrect_184_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/184/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6355,12 +5758,10 @@ fn relaxng_incorrect_185_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/185/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6376,12 +5777,10 @@ fn relaxng_incorrect_186_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/186/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6397,12 +5796,10 @@ fn relaxng_incorrect_187_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/187/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6418,12 +5815,10 @@ fn relaxng_incorrect_188_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/188/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6439,12 +5834,10 @@ fn relaxng_incorrect_189_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/189/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6460,12 +5853,10 @@ fn relaxng_valid_190_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/190/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/190/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -6481,12 +5872,10 @@ fn relaxng_valid_190_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/190/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/190/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -6502,12 +5891,10 @@ fn relaxng_valid_190_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/190/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/190/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -6523,12 +5910,10 @@ fn relaxng_invalid_190_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/190/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/190/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6544,12 +5929,10 @@ fn relaxng_valid_191_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/191/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/191/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -6565,12 +5948,10 @@ fn relaxng_valid_191_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/191/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/191/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -6586,12 +5967,10 @@ fn relaxng_valid_191_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/191/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/191/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -6607,12 +5986,10 @@ fn relaxng_invalid_191_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/191/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/191/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6628,12 +6005,10 @@ fn relaxng_incorrect_192_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/192/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6649,12 +6024,10 @@ fn relaxng_incorrect_193_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/193/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6670,12 +6043,10 @@ fn relaxng_valid_194_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/194/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/194/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -6691,12 +6062,10 @@ fn relaxng_valid_194_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/194/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/194/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -6712,12 +6081,10 @@ fn relaxng_valid_194_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/194/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/194/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -6733,12 +6100,10 @@ fn relaxng_invalid_194_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/194/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/194/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6754,12 +6119,10 @@ fn relaxng_valid_195_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/195/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/195/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -6775,12 +6138,10 @@ fn relaxng_valid_195_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/195/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/195/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -6796,12 +6157,10 @@ fn relaxng_valid_195_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/195/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/195/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -6817,12 +6176,10 @@ fn relaxng_invalid_195_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/195/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/195/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6838,12 +6195,10 @@ fn relaxng_incorrect_196_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/196/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6859,12 +6214,10 @@ fn relaxng_incorrect_197_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/197/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6881,12 +6234,10 @@ fn relaxng_incorrect_198_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/198/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6903,12 +6254,10 @@ fn relaxng_incorrect_199_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/199/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6925,12 +6274,10 @@ fn relaxng_incorrect_200_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/200/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6947,12 +6294,10 @@ fn relaxng_incorrect_201_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/201/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6969,12 +6314,10 @@ fn relaxng_incorrect_202_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/202/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -6991,12 +6334,10 @@ fn relaxng_incorrect_203_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/203/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7013,12 +6354,10 @@ fn relaxng_incorrect_204_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/204/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7035,12 +6374,10 @@ fn relaxng_incorrect_205_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/205/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7057,12 +6394,10 @@ fn relaxng_incorrect_206_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/206/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7078,12 +6413,10 @@ fn relaxng_incorrect_207_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/207/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7099,12 +6432,10 @@ fn relaxng_valid_208_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/208/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/208/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7120,12 +6451,10 @@ fn relaxng_valid_209_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/209/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/209/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7141,12 +6470,10 @@ fn relaxng_invalid_209_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/209/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/209/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7162,12 +6489,10 @@ fn relaxng_valid_210_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/210/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/210/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7183,12 +6508,10 @@ fn relaxng_invalid_210_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/210/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/210/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7204,12 +6527,10 @@ fn relaxng_incorrect_211_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/211/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7225,12 +6546,10 @@ fn relaxng_valid_212_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/212/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/212/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7246,12 +6565,10 @@ fn relaxng_valid_212_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/212/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/212/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7267,12 +6584,10 @@ fn relaxng_invalid_212_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/212/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/212/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7288,12 +6603,10 @@ fn relaxng_valid_213_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/213/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/213/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7311,12 +6624,10 @@ normalization of notAllowed.
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/214/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7332,12 +6643,10 @@ fn relaxng_valid_215_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/215/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/215/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7353,12 +6662,10 @@ fn relaxng_valid_215_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/215/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/215/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7374,12 +6681,10 @@ fn relaxng_invalid_216_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/216/1.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/216/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7395,12 +6700,10 @@ fn relaxng_valid_216_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/216/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/216/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7416,12 +6719,10 @@ fn relaxng_valid_216_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/216/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/216/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7437,12 +6738,10 @@ fn relaxng_invalid_217_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/217/1.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/217/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7458,12 +6757,10 @@ fn relaxng_valid_217_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/217/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/217/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7479,12 +6776,10 @@ fn relaxng_valid_218_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/218/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/218/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7500,12 +6795,10 @@ fn relaxng_invalid_218_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/218/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/218/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7521,12 +6814,10 @@ fn relaxng_invalid_219_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/219/1.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/219/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7542,12 +6833,10 @@ fn relaxng_invalid_219_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/219/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/219/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7563,12 +6852,10 @@ fn relaxng_invalid_219_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/219/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/219/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7584,12 +6871,10 @@ fn relaxng_valid_219_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/219/4.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/219/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7605,12 +6890,10 @@ fn relaxng_invalid_220_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/220/1.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/220/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7626,12 +6909,10 @@ fn relaxng_invalid_220_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/220/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/220/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7647,12 +6928,10 @@ fn relaxng_valid_220_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/220/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/220/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7668,12 +6947,10 @@ fn relaxng_invalid_221_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/221/1.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/221/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7689,12 +6966,10 @@ fn relaxng_invalid_221_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/221/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/221/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7710,12 +6985,10 @@ fn relaxng_valid_221_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/221/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/221/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7731,12 +7004,10 @@ fn relaxng_valid_221_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/221/4.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/221/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7752,12 +7023,10 @@ fn relaxng_valid_222_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/222/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/222/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7773,12 +7042,10 @@ fn relaxng_invalid_222_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/222/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/222/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7794,12 +7061,10 @@ fn relaxng_invalid_222_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/222/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/222/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7815,12 +7080,10 @@ fn relaxng_invalid_222_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/222/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/222/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7836,12 +7099,10 @@ fn relaxng_invalid_223_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/223/1.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/223/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7857,12 +7118,10 @@ fn relaxng_valid_223_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/223/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/223/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7878,12 +7137,10 @@ fn relaxng_invalid_223_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/223/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/223/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7899,12 +7156,10 @@ fn relaxng_invalid_223_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/223/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/223/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7920,12 +7175,10 @@ fn relaxng_invalid_224_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/224/1.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/224/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -7941,12 +7194,10 @@ fn relaxng_valid_224_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/224/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/224/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7962,12 +7213,10 @@ fn relaxng_valid_224_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/224/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/224/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -7983,12 +7232,10 @@ fn relaxng_valid_225_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/225/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/225/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -8004,12 +7251,10 @@ fn relaxng_valid_225_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/225/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/225/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -8025,12 +7270,10 @@ fn relaxng_invalid_225_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/225/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/225/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8046,12 +7289,10 @@ fn relaxng_valid_226_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/226/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/226/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -8067,12 +7308,10 @@ fn relaxng_valid_226_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/226/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/226/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -8088,12 +7327,10 @@ fn relaxng_invalid_226_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/226/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/226/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8109,12 +7346,10 @@ fn relaxng_invalid_226_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/226/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/226/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8130,12 +7365,10 @@ fn relaxng_invalid_226_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/226/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/226/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8151,12 +7384,10 @@ fn relaxng_invalid_226_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/226/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/226/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8172,12 +7403,10 @@ fn relaxng_invalid_226_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/226/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/226/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8193,12 +7422,10 @@ fn relaxng_valid_227_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/227/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/227/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -8214,12 +7441,10 @@ fn relaxng_valid_227_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/227/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/227/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -8235,12 +7460,10 @@ fn relaxng_invalid_227_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/227/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/227/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8256,12 +7479,10 @@ fn relaxng_invalid_227_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/227/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/227/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8277,12 +7498,10 @@ fn relaxng_invalid_227_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/227/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/227/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8298,12 +7517,10 @@ fn relaxng_invalid_227_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/227/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/227/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8319,12 +7536,10 @@ fn relaxng_invalid_227_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/227/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/227/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8340,12 +7555,10 @@ fn relaxng_valid_228_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/228/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/228/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -8361,12 +7574,10 @@ fn relaxng_valid_228_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/228/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/228/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -8382,12 +7593,10 @@ fn relaxng_invalid_228_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/228/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/228/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8403,12 +7612,10 @@ fn relaxng_invalid_228_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/228/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/228/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8424,12 +7631,10 @@ fn relaxng_invalid_228_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/228/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/228/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8445,12 +7650,10 @@ fn relaxng_invalid_228_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/228/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/228/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8466,12 +7669,10 @@ fn relaxng_invalid_228_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/228/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/228/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8487,12 +7688,10 @@ fn relaxng_valid_229_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/229/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/229/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -8508,12 +7707,10 @@ fn relaxng_invalid_229_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/229/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/229/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8529,12 +7726,10 @@ fn relaxng_invalid_229_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/229/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/229/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8550,12 +7745,10 @@ fn relaxng_invalid_229_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/229/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/229/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8571,12 +7764,10 @@ fn relaxng_invalid_229_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/229/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/229/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8592,12 +7783,10 @@ fn relaxng_invalid_229_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/229/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/229/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8613,12 +7802,10 @@ fn relaxng_valid_230_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/230/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/230/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -8634,12 +7821,10 @@ fn relaxng_invalid_230_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/230/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/230/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8655,12 +7840,10 @@ fn relaxng_valid_231_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/231/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/231/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -8676,12 +7859,10 @@ fn relaxng_invalid_231_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/231/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/231/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8697,12 +7878,10 @@ fn relaxng_invalid_231_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/231/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/231/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8718,12 +7897,10 @@ fn relaxng_invalid_231_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/231/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/231/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8739,12 +7916,10 @@ fn relaxng_valid_232_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/232/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/232/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -8760,12 +7935,10 @@ fn relaxng_invalid_232_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/232/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/232/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8781,12 +7954,10 @@ fn relaxng_invalid_232_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/232/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/232/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8802,12 +7973,10 @@ fn relaxng_invalid_232_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/232/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/232/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8823,12 +7992,10 @@ fn relaxng_valid_233_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/233/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/233/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -8844,12 +8011,10 @@ fn relaxng_invalid_233_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/233/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/233/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8865,12 +8030,10 @@ fn relaxng_invalid_233_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/233/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/233/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8886,12 +8049,10 @@ fn relaxng_invalid_233_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/233/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/233/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8907,12 +8068,10 @@ fn relaxng_valid_234_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/234/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/234/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -8928,12 +8087,10 @@ fn relaxng_invalid_234_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/234/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/234/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8949,12 +8106,10 @@ fn relaxng_invalid_234_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/234/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/234/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8970,12 +8125,10 @@ fn relaxng_invalid_234_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/234/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/234/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -8991,12 +8144,10 @@ fn relaxng_valid_235_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/235/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/235/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9012,12 +8163,10 @@ fn relaxng_valid_235_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/235/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/235/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9033,12 +8182,10 @@ fn relaxng_valid_235_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/235/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/235/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9054,12 +8201,10 @@ fn relaxng_valid_235_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/235/4.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/235/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9075,12 +8220,10 @@ fn relaxng_invalid_235_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/235/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/235/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -9096,12 +8239,10 @@ fn relaxng_invalid_235_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/235/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/235/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -9117,12 +8258,10 @@ fn relaxng_invalid_235_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/235/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/235/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -9138,12 +8277,10 @@ fn relaxng_valid_236_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/236/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/236/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9159,12 +8296,10 @@ fn relaxng_valid_236_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/236/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/236/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9180,12 +8315,10 @@ fn relaxng_invalid_236_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/236/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/236/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -9201,12 +8334,10 @@ fn relaxng_valid_237_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/237/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/237/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9222,12 +8353,10 @@ fn relaxng_valid_237_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/237/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/237/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9243,12 +8372,10 @@ fn relaxng_invalid_237_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/237/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/237/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -9264,12 +8391,10 @@ fn relaxng_invalid_237_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/237/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/237/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -9285,12 +8410,10 @@ fn relaxng_invalid_237_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/237/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/237/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -9306,12 +8429,10 @@ fn relaxng_valid_238_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/238/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/238/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9327,12 +8448,10 @@ fn relaxng_valid_238_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/238/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/238/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9348,12 +8467,10 @@ fn relaxng_invalid_238_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/238/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/238/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -9369,12 +8486,10 @@ fn relaxng_valid_239_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/239/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/239/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9390,12 +8505,10 @@ fn relaxng_valid_239_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/239/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/239/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9411,12 +8524,10 @@ fn relaxng_invalid_239_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/239/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/239/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -9432,12 +8543,10 @@ fn relaxng_invalid_239_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/239/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/239/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -9452,12 +8561,10 @@ fn relaxng_valid_240_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/240/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/240/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9472,12 +8579,10 @@ fn relaxng_valid_240_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/240/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/240/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9492,12 +8597,10 @@ fn relaxng_invalid_240_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/240/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/240/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -9512,12 +8615,10 @@ fn relaxng_invalid_240_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/240/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/240/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -9533,12 +8634,10 @@ fn relaxng_valid_241_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/241/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/241/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9554,12 +8653,10 @@ fn relaxng_valid_241_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/241/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/241/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9575,12 +8672,10 @@ fn relaxng_valid_241_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/241/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/241/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9596,12 +8691,10 @@ fn relaxng_valid_241_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/241/4.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/241/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9617,12 +8710,10 @@ fn relaxng_invalid_241_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/241/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/241/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -9638,12 +8729,10 @@ fn relaxng_valid_242_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/242/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/242/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9659,12 +8748,10 @@ fn relaxng_valid_242_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/242/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/242/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9680,12 +8767,10 @@ fn relaxng_valid_242_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/242/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/242/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9701,12 +8786,10 @@ fn relaxng_valid_242_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/242/4.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/242/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9722,12 +8805,10 @@ fn relaxng_invalid_242_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/242/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/242/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -9743,12 +8824,10 @@ fn relaxng_invalid_242_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/242/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/242/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -9764,12 +8843,10 @@ fn relaxng_valid_243_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/243/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/243/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9785,12 +8862,10 @@ fn relaxng_valid_243_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/243/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/243/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9806,12 +8881,10 @@ fn relaxng_invalid_243_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/243/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/243/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -9827,12 +8900,10 @@ fn relaxng_valid_243_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/243/4.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/243/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9848,12 +8919,10 @@ fn relaxng_invalid_243_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/243/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/243/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -9869,12 +8938,10 @@ fn relaxng_valid_243_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/243/6.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/243/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9890,12 +8957,10 @@ fn relaxng_valid_244_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/244/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/244/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9911,12 +8976,10 @@ fn relaxng_valid_244_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/244/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/244/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9932,12 +8995,10 @@ fn relaxng_valid_244_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/244/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/244/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9953,12 +9014,10 @@ fn relaxng_valid_244_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/244/4.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/244/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -9974,12 +9033,10 @@ fn relaxng_invalid_244_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/244/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/244/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -9995,12 +9052,10 @@ fn relaxng_valid_244_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/244/6.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/244/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10016,12 +9071,10 @@ fn relaxng_valid_244_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/244/7.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/244/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10037,12 +9090,10 @@ fn relaxng_invalid_244_8(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/244/8.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/244/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10058,12 +9109,10 @@ fn relaxng_valid_245_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/245/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/245/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10079,12 +9128,10 @@ fn relaxng_valid_245_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/245/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/245/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10100,12 +9147,10 @@ fn relaxng_valid_245_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/245/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/245/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10121,12 +9166,10 @@ fn relaxng_invalid_245_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/245/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/245/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10142,12 +9185,10 @@ fn relaxng_invalid_245_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/245/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/245/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10163,12 +9204,10 @@ fn relaxng_valid_246_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/246/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/246/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10184,12 +9223,10 @@ fn relaxng_valid_246_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/246/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/246/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10205,12 +9242,10 @@ fn relaxng_valid_246_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/246/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/246/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10226,12 +9261,10 @@ fn relaxng_invalid_246_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/246/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/246/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10247,12 +9280,10 @@ fn relaxng_valid_247_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/247/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/247/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10268,12 +9299,10 @@ fn relaxng_valid_247_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/247/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/247/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10289,12 +9318,10 @@ fn relaxng_valid_247_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/247/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/247/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10310,12 +9337,10 @@ fn relaxng_invalid_247_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/247/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/247/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10331,12 +9356,10 @@ fn relaxng_invalid_247_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/247/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/247/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10352,12 +9375,10 @@ fn relaxng_invalid_247_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/247/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/247/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10373,12 +9394,10 @@ fn relaxng_valid_248_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/248/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/248/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10394,12 +9413,10 @@ fn relaxng_valid_248_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/248/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/248/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10415,12 +9432,10 @@ fn relaxng_valid_248_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/248/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/248/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10436,12 +9451,10 @@ fn relaxng_invalid_248_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/248/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/248/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10457,12 +9470,10 @@ fn relaxng_invalid_248_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/248/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/248/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10478,12 +9489,10 @@ fn relaxng_invalid_248_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/248/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/248/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10499,12 +9508,10 @@ fn relaxng_valid_249_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/249/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/249/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10520,12 +9527,10 @@ fn relaxng_valid_249_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/249/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/249/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10541,12 +9546,10 @@ fn relaxng_valid_249_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/249/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/249/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10562,12 +9565,10 @@ fn relaxng_invalid_249_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/249/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/249/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10583,12 +9584,10 @@ fn relaxng_valid_250_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/250/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/250/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10604,12 +9603,10 @@ fn relaxng_valid_250_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/250/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/250/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10625,12 +9622,10 @@ fn relaxng_invalid_250_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/250/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/250/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10646,12 +9641,10 @@ fn relaxng_invalid_250_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/250/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/250/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10667,12 +9660,10 @@ fn relaxng_invalid_250_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/250/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/250/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10688,12 +9679,10 @@ fn relaxng_invalid_250_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/250/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/250/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10709,12 +9698,10 @@ fn relaxng_valid_251_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/251/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/251/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10730,12 +9717,10 @@ fn relaxng_valid_251_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/251/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/251/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10751,12 +9736,10 @@ fn relaxng_valid_251_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/251/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/251/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10772,12 +9755,10 @@ fn relaxng_invalid_251_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/251/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/251/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10793,12 +9774,10 @@ fn relaxng_invalid_251_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/251/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/251/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10814,12 +9793,10 @@ fn relaxng_invalid_251_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/251/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/251/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10835,12 +9812,10 @@ fn relaxng_invalid_251_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/251/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/251/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10856,12 +9831,10 @@ fn relaxng_invalid_251_8(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/251/8.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/251/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10877,12 +9850,10 @@ fn relaxng_valid_252_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/252/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/252/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -10898,12 +9869,10 @@ fn relaxng_invalid_252_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/252/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/252/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10919,12 +9888,10 @@ fn relaxng_invalid_252_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/252/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/252/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10940,12 +9907,10 @@ fn relaxng_invalid_252_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/252/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/252/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10961,12 +9926,10 @@ fn relaxng_invalid_252_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/252/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/252/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -10982,12 +9945,10 @@ fn relaxng_valid_253_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/253/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/253/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11003,12 +9964,10 @@ fn relaxng_invalid_253_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/253/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/253/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11024,12 +9983,10 @@ fn relaxng_invalid_253_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/253/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/253/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11045,12 +10002,10 @@ fn relaxng_invalid_253_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/253/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/253/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11066,12 +10021,10 @@ fn relaxng_invalid_253_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/253/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/253/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11087,12 +10040,10 @@ fn relaxng_valid_254_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/254/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/254/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11108,12 +10059,10 @@ fn relaxng_invalid_254_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/254/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/254/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11129,12 +10078,10 @@ fn relaxng_invalid_254_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/254/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/254/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11150,12 +10097,10 @@ fn relaxng_invalid_254_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/254/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/254/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11171,12 +10116,10 @@ fn relaxng_invalid_254_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/254/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/254/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11192,12 +10135,10 @@ fn relaxng_invalid_254_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/254/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/254/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11213,12 +10154,10 @@ fn relaxng_valid_255_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/255/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/255/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11234,12 +10173,10 @@ fn relaxng_valid_255_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/255/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/255/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11255,12 +10192,10 @@ fn relaxng_invalid_255_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/255/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/255/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11276,12 +10211,10 @@ fn relaxng_invalid_255_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/255/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/255/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11297,12 +10230,10 @@ fn relaxng_invalid_255_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/255/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/255/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11318,12 +10249,10 @@ fn relaxng_invalid_255_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/255/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/255/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11339,12 +10268,10 @@ fn relaxng_invalid_255_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/255/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/255/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11360,12 +10287,10 @@ fn relaxng_valid_256_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/256/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/256/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11381,12 +10306,10 @@ fn relaxng_invalid_256_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/256/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/256/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11402,12 +10325,10 @@ fn relaxng_invalid_256_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/256/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/256/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11423,12 +10344,10 @@ fn relaxng_valid_257_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/257/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/257/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11444,12 +10363,10 @@ fn relaxng_valid_257_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/257/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/257/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11465,12 +10382,10 @@ fn relaxng_valid_257_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/257/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/257/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11486,12 +10401,10 @@ fn relaxng_invalid_257_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/257/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/257/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11507,12 +10420,10 @@ fn relaxng_invalid_257_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/257/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/257/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11528,12 +10439,10 @@ fn relaxng_valid_258_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/258/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/258/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11549,12 +10458,10 @@ fn relaxng_invalid_258_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/258/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/258/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11570,12 +10477,10 @@ fn relaxng_invalid_258_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/258/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/258/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11591,12 +10496,10 @@ fn relaxng_valid_259_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/259/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/259/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11612,12 +10515,10 @@ fn relaxng_valid_259_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/259/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/259/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11633,12 +10534,10 @@ fn relaxng_invalid_259_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/259/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/259/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11654,12 +10553,10 @@ fn relaxng_invalid_260_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/260/1.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/260/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11675,12 +10572,10 @@ fn relaxng_invalid_260_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/260/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/260/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11696,12 +10591,10 @@ fn relaxng_valid_260_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/260/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/260/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11717,12 +10610,10 @@ fn relaxng_valid_260_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/260/4.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/260/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11738,12 +10629,10 @@ fn relaxng_invalid_260_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/260/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/260/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11759,12 +10648,10 @@ fn relaxng_valid_261_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/261/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/261/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11780,12 +10667,10 @@ fn relaxng_valid_261_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/261/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/261/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11801,12 +10686,10 @@ fn relaxng_valid_261_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/261/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/261/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11822,12 +10705,10 @@ fn relaxng_valid_261_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/261/4.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/261/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11843,12 +10724,10 @@ fn relaxng_invalid_261_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/261/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/261/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11864,12 +10743,10 @@ fn relaxng_invalid_261_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/261/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/261/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11885,12 +10762,10 @@ fn relaxng_invalid_261_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/261/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/261/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11906,12 +10781,10 @@ fn relaxng_valid_262_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/262/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/262/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11927,12 +10800,10 @@ fn relaxng_valid_262_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/262/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/262/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11948,12 +10819,10 @@ fn relaxng_invalid_262_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/262/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/262/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -11969,12 +10838,10 @@ fn relaxng_valid_262_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/262/4.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/262/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -11990,12 +10857,10 @@ fn relaxng_valid_262_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/262/5.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/262/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12011,12 +10876,10 @@ fn relaxng_invalid_262_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/262/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/262/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12032,12 +10895,10 @@ fn relaxng_invalid_262_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/262/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/262/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12053,12 +10914,10 @@ fn relaxng_invalid_262_8(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/262/8.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/262/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12074,12 +10933,10 @@ fn relaxng_valid_263_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/263/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/263/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12095,12 +10952,10 @@ fn relaxng_valid_263_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/263/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/263/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12116,12 +10971,10 @@ fn relaxng_invalid_263_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/263/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/263/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12137,12 +10990,10 @@ fn relaxng_valid_263_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/263/4.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/263/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12158,12 +11009,10 @@ fn relaxng_valid_263_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/263/5.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/263/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12179,12 +11028,10 @@ fn relaxng_valid_263_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/263/6.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/263/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12200,12 +11047,10 @@ fn relaxng_invalid_263_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/263/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/263/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12221,12 +11066,10 @@ fn relaxng_valid_263_8(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/263/8.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/263/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12242,12 +11085,10 @@ fn relaxng_valid_264_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/264/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/264/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12263,12 +11104,10 @@ fn relaxng_invalid_264_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/264/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/264/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12284,12 +11123,10 @@ fn relaxng_invalid_264_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/264/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/264/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12305,12 +11142,10 @@ fn relaxng_invalid_264_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/264/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/264/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12326,12 +11161,10 @@ fn relaxng_valid_265_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/265/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/265/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12347,12 +11180,10 @@ fn relaxng_valid_266_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/266/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/266/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12368,12 +11199,10 @@ fn relaxng_invalid_267_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/267/1.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/267/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12389,12 +11218,10 @@ fn relaxng_valid_267_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/267/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/267/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12410,12 +11237,10 @@ fn relaxng_valid_268_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/268/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/268/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12431,12 +11256,10 @@ fn relaxng_valid_268_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/268/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/268/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12452,12 +11275,10 @@ fn relaxng_valid_268_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/268/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/268/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12473,12 +11294,10 @@ fn relaxng_valid_268_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/268/4.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/268/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12494,12 +11313,10 @@ fn relaxng_invalid_268_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/268/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/268/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12515,12 +11332,10 @@ fn relaxng_invalid_268_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/268/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/268/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12536,12 +11351,10 @@ fn relaxng_valid_269_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/269/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/269/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12557,12 +11370,10 @@ fn relaxng_valid_269_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/269/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/269/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12578,12 +11389,10 @@ fn relaxng_valid_269_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/269/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/269/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12599,12 +11408,10 @@ fn relaxng_valid_269_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/269/4.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/269/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12620,12 +11427,10 @@ fn relaxng_invalid_269_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/269/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/269/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12641,12 +11446,10 @@ fn relaxng_invalid_269_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/269/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/269/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12662,12 +11465,10 @@ fn relaxng_valid_270_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/270/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/270/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12683,12 +11484,10 @@ fn relaxng_invalid_270_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/270/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/270/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12704,12 +11503,10 @@ fn relaxng_invalid_270_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/270/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/270/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12725,12 +11522,10 @@ fn relaxng_invalid_270_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/270/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/270/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12746,12 +11541,10 @@ fn relaxng_valid_271_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/271/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/271/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12767,12 +11560,10 @@ fn relaxng_invalid_271_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/271/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/271/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12788,12 +11579,10 @@ fn relaxng_invalid_271_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/271/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/271/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12809,12 +11598,10 @@ fn relaxng_invalid_271_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/271/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/271/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12830,12 +11617,10 @@ fn relaxng_valid_272_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/272/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/272/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12851,12 +11636,10 @@ fn relaxng_valid_272_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/272/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/272/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12872,12 +11655,10 @@ fn relaxng_valid_272_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/272/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/272/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12893,12 +11674,10 @@ fn relaxng_valid_272_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/272/4.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/272/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12914,12 +11693,10 @@ fn relaxng_invalid_272_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/272/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/272/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12935,12 +11712,10 @@ fn relaxng_invalid_272_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/272/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/272/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -12956,12 +11731,10 @@ fn relaxng_valid_273_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/273/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/273/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12977,12 +11750,10 @@ fn relaxng_valid_273_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/273/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/273/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -12998,12 +11769,10 @@ fn relaxng_valid_273_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/273/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/273/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -13019,12 +11788,10 @@ fn relaxng_invalid_273_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/273/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/273/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13040,12 +11807,10 @@ fn relaxng_valid_274_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/274/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/274/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -13061,12 +11826,10 @@ fn relaxng_valid_274_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/274/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/274/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -13082,12 +11845,10 @@ fn relaxng_valid_274_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/274/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/274/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -13103,12 +11864,10 @@ fn relaxng_valid_274_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/274/4.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/274/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -13124,12 +11883,10 @@ fn relaxng_invalid_274_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/274/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/274/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13145,12 +11902,10 @@ fn relaxng_invalid_274_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/274/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/274/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13166,12 +11921,10 @@ fn relaxng_valid_275_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/275/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/275/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -13187,12 +11940,10 @@ fn relaxng_valid_275_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/275/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/275/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -13208,12 +11959,10 @@ fn relaxng_valid_275_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/275/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/275/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -13229,12 +11978,10 @@ fn relaxng_valid_275_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/275/4.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/275/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -13250,12 +11997,10 @@ fn relaxng_valid_275_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/275/5.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/275/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -13271,12 +12016,10 @@ fn relaxng_invalid_275_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/275/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/275/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13292,12 +12035,10 @@ fn relaxng_invalid_275_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/275/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/275/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13313,12 +12054,10 @@ fn relaxng_incorrect_276_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/276/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13334,12 +12073,10 @@ fn relaxng_incorrect_277_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/277/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13355,12 +12092,10 @@ fn relaxng_incorrect_278_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/278/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13376,12 +12111,10 @@ fn relaxng_incorrect_279_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/279/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13397,12 +12130,10 @@ fn relaxng_valid_280_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/280/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/280/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -13418,12 +12149,10 @@ fn relaxng_valid_280_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/280/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/280/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -13439,12 +12168,10 @@ fn relaxng_invalid_280_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/280/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/280/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13460,12 +12187,10 @@ fn relaxng_valid_281_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/281/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/281/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -13481,12 +12206,10 @@ fn relaxng_valid_281_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/281/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/281/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -13502,12 +12225,10 @@ fn relaxng_invalid_281_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/281/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/281/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13523,12 +12244,10 @@ fn relaxng_invalid_281_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/281/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/281/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13544,12 +12263,10 @@ fn relaxng_valid_282_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/282/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/282/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -13565,12 +12282,10 @@ fn relaxng_valid_282_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/282/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/282/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -13586,12 +12301,10 @@ fn relaxng_valid_282_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/282/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/282/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -13607,12 +12320,10 @@ fn relaxng_invalid_282_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/282/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/282/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13628,12 +12339,10 @@ fn relaxng_valid_283_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/283/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/283/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -13649,12 +12358,10 @@ fn relaxng_invalid_283_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/283/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/283/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13670,12 +12377,10 @@ fn relaxng_invalid_283_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/283/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/283/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13691,12 +12396,10 @@ fn relaxng_invalid_284_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/284/1.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/284/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13712,12 +12415,10 @@ fn relaxng_incorrect_285_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/285/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13733,12 +12434,10 @@ fn relaxng_incorrect_286_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/286/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13754,12 +12453,10 @@ fn relaxng_incorrect_287_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/287/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13775,12 +12472,10 @@ fn relaxng_incorrect_288_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/288/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13796,12 +12491,10 @@ fn relaxng_incorrect_289_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/289/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13817,12 +12510,10 @@ fn relaxng_incorrect_290_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/290/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13838,12 +12529,10 @@ fn relaxng_incorrect_291_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/291/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13859,12 +12548,10 @@ fn relaxng_incorrect_292_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/292/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13880,12 +12567,10 @@ fn relaxng_incorrect_293_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/293/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13901,12 +12586,10 @@ fn relaxng_incorrect_294_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/294/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13922,12 +12605,10 @@ fn relaxng_incorrect_295_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/295/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13943,12 +12624,10 @@ fn relaxng_incorrect_296_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/296/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13964,12 +12643,10 @@ fn relaxng_incorrect_297_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/297/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -13985,12 +12662,10 @@ fn relaxng_incorrect_298_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/298/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14006,12 +12681,10 @@ fn relaxng_incorrect_299_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/299/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14027,12 +12700,10 @@ fn relaxng_incorrect_300_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/300/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14048,12 +12719,10 @@ fn relaxng_incorrect_301_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/301/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14069,12 +12738,10 @@ fn relaxng_incorrect_302_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/302/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14090,12 +12757,10 @@ fn relaxng_incorrect_303_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/303/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14111,12 +12776,10 @@ fn relaxng_incorrect_304_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/304/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14132,12 +12795,10 @@ fn relaxng_incorrect_305_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/305/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14153,12 +12814,10 @@ fn relaxng_incorrect_306_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/306/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14174,12 +12833,10 @@ fn relaxng_incorrect_307_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/307/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14195,12 +12852,10 @@ fn relaxng_incorrect_308_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/308/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14216,12 +12871,10 @@ fn relaxng_incorrect_309_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/309/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14237,12 +12890,10 @@ fn relaxng_incorrect_310_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/310/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14258,12 +12909,10 @@ fn relaxng_incorrect_311_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/311/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14279,12 +12928,10 @@ fn relaxng_incorrect_312_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/312/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14300,12 +12947,10 @@ fn relaxng_incorrect_313_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/313/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14321,12 +12966,10 @@ fn relaxng_incorrect_314_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/314/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14342,12 +12985,10 @@ fn relaxng_incorrect_315_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/315/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14363,12 +13004,10 @@ fn relaxng_incorrect_316_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/316/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14384,12 +13023,10 @@ fn relaxng_incorrect_317_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/317/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14405,12 +13042,10 @@ fn relaxng_incorrect_318_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/318/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14426,12 +13061,10 @@ fn relaxng_incorrect_319_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/319/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14447,12 +13080,10 @@ fn relaxng_incorrect_320_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/320/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14468,12 +13099,10 @@ fn relaxng_incorrect_321_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/321/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14489,12 +13118,10 @@ fn relaxng_incorrect_322_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/322/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14510,12 +13137,10 @@ fn relaxng_incorrect_323_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/323/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14531,12 +13156,10 @@ fn relaxng_incorrect_324_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/324/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14552,12 +13175,10 @@ fn relaxng_incorrect_325_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/325/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14573,12 +13194,10 @@ fn relaxng_incorrect_326_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/326/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14594,12 +13213,10 @@ fn relaxng_incorrect_327_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/327/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14616,12 +13233,10 @@ fn relaxng_valid_328_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/328/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/328/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -14637,12 +13252,10 @@ fn relaxng_incorrect_329_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/329/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14658,12 +13271,10 @@ fn relaxng_valid_330_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/330/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/330/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -14681,12 +13292,10 @@ of the not allowed.
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/331/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/331/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -14703,12 +13312,10 @@ fn relaxng_valid_332_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/332/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/332/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -14725,12 +13332,10 @@ fn relaxng_valid_333_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/333/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/333/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -14747,12 +13352,10 @@ fn relaxng_valid_334_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/334/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/334/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -14768,12 +13371,10 @@ fn relaxng_incorrect_335_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/335/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14791,12 +13392,10 @@ before string sequence checking.
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/336/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/336/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -14813,12 +13412,10 @@ fn relaxng_incorrect_337_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/337/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14834,12 +13431,10 @@ fn relaxng_incorrect_338_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/338/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14855,12 +13450,10 @@ fn relaxng_incorrect_339_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/339/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14876,12 +13469,10 @@ fn relaxng_valid_340_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/340/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/340/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -14897,12 +13488,10 @@ fn relaxng_incorrect_341_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/341/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14918,12 +13507,10 @@ fn relaxng_incorrect_342_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/342/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14939,12 +13526,10 @@ fn relaxng_incorrect_343_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/343/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14960,12 +13545,10 @@ fn relaxng_incorrect_344_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/344/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -14981,12 +13564,10 @@ fn relaxng_valid_345_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/345/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/345/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -15002,12 +13583,10 @@ fn relaxng_valid_345_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/345/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/345/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -15023,12 +13602,10 @@ fn relaxng_invalid_345_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/345/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/345/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15044,12 +13621,10 @@ fn relaxng_invalid_345_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/345/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/345/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15065,12 +13640,10 @@ fn relaxng_invalid_345_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/345/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/345/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15086,12 +13659,10 @@ fn relaxng_incorrect_346_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/346/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15107,12 +13678,10 @@ fn relaxng_incorrect_347_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/347/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15128,12 +13697,10 @@ fn relaxng_incorrect_348_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/348/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15149,12 +13716,10 @@ fn relaxng_incorrect_349_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/349/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15170,12 +13735,10 @@ fn relaxng_incorrect_350_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/350/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15191,12 +13754,10 @@ fn relaxng_incorrect_351_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/351/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15212,12 +13773,10 @@ fn relaxng_incorrect_352_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/352/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15233,12 +13792,10 @@ fn relaxng_valid_353_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/353/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/353/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -15254,12 +13811,10 @@ fn relaxng_invalid_353_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/353/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/353/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15275,12 +13830,10 @@ fn relaxng_valid_353_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/353/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/353/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -15296,12 +13849,10 @@ fn relaxng_valid_354_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/354/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/354/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -15317,12 +13868,10 @@ fn relaxng_valid_355_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/355/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/355/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -15338,12 +13887,10 @@ fn relaxng_incorrect_356_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/356/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15359,12 +13906,10 @@ fn relaxng_incorrect_357_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/357/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15380,12 +13925,10 @@ fn relaxng_incorrect_358_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/358/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15401,12 +13944,10 @@ fn relaxng_incorrect_359_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/359/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15422,12 +13963,10 @@ fn relaxng_incorrect_360_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/360/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15443,12 +13982,10 @@ fn relaxng_incorrect_361_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/361/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15464,12 +14001,10 @@ fn relaxng_incorrect_362_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/362/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15485,12 +14020,10 @@ fn relaxng_incorrect_363_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/363/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15506,12 +14039,10 @@ fn relaxng_incorrect_364_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/364/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15527,12 +14058,10 @@ fn relaxng_incorrect_365_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/365/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15548,12 +14077,10 @@ fn relaxng_incorrect_366_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/366/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15569,12 +14096,10 @@ fn relaxng_incorrect_367_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/367/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15590,12 +14115,10 @@ fn relaxng_valid_368_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/368/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/368/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -15611,12 +14134,10 @@ fn relaxng_valid_369_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/369/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/369/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -15632,12 +14153,10 @@ fn relaxng_incorrect_370_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/370/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15653,12 +14172,10 @@ fn relaxng_incorrect_371_1(){
     */
 
     let docfile = "<doc/>".to_string();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/371/i.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15674,12 +14191,10 @@ fn relaxng_valid_372_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/372/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/372/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -15695,12 +14210,10 @@ fn relaxng_invalid_373_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/373/1.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/373/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15716,12 +14229,10 @@ fn relaxng_valid_374_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/374/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/374/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -15737,12 +14248,10 @@ fn relaxng_invalid_374_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/374/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/374/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15758,12 +14267,10 @@ fn relaxng_invalid_374_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/374/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/374/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15779,12 +14286,10 @@ fn relaxng_invalid_374_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/374/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/374/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15800,12 +14305,10 @@ fn relaxng_invalid_374_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/374/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/374/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15821,12 +14324,10 @@ fn relaxng_invalid_374_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/374/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/374/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15842,12 +14343,10 @@ fn relaxng_invalid_374_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/374/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/374/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15863,12 +14362,10 @@ fn relaxng_invalid_374_8(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/374/8.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/374/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15884,12 +14381,10 @@ fn relaxng_invalid_374_9(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/374/9.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/374/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15905,12 +14400,10 @@ fn relaxng_valid_375_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/375/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/375/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -15926,12 +14419,10 @@ fn relaxng_invalid_375_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/375/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/375/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -15947,12 +14438,10 @@ fn relaxng_valid_376_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/376/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/376/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -15968,12 +14457,10 @@ fn relaxng_valid_376_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/376/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/376/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -15989,12 +14476,10 @@ fn relaxng_invalid_376_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/376/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/376/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16010,12 +14495,10 @@ fn relaxng_valid_377_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/377/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/377/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -16031,12 +14514,10 @@ fn relaxng_invalid_377_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/377/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/377/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16052,12 +14533,10 @@ fn relaxng_valid_378_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/378/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/378/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -16073,12 +14552,10 @@ fn relaxng_invalid_378_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/378/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/378/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16094,12 +14571,10 @@ fn relaxng_invalid_378_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/378/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/378/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16115,12 +14590,10 @@ fn relaxng_invalid_378_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/378/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/378/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16136,12 +14609,10 @@ fn relaxng_valid_379_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/379/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/379/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -16157,12 +14628,10 @@ fn relaxng_valid_379_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/379/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/379/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -16178,12 +14647,10 @@ fn relaxng_invalid_379_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/379/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/379/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16199,12 +14666,10 @@ fn relaxng_invalid_379_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/379/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/379/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16220,12 +14685,10 @@ fn relaxng_invalid_379_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/379/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/379/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16241,12 +14704,10 @@ fn relaxng_valid_380_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/380/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/380/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -16262,12 +14723,10 @@ fn relaxng_valid_380_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/380/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/380/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -16283,12 +14742,10 @@ fn relaxng_invalid_380_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/380/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/380/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16304,12 +14761,10 @@ fn relaxng_invalid_380_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/380/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/380/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16325,12 +14780,10 @@ fn relaxng_invalid_380_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/380/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/380/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16346,12 +14799,10 @@ fn relaxng_valid_381_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/381/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/381/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -16367,12 +14818,10 @@ fn relaxng_invalid_381_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/381/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/381/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16388,12 +14837,10 @@ fn relaxng_invalid_381_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/381/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/381/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16409,12 +14856,10 @@ fn relaxng_valid_382_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/382/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/382/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -16430,12 +14875,10 @@ fn relaxng_invalid_382_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/382/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/382/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16451,12 +14894,10 @@ fn relaxng_invalid_382_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/382/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/382/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16472,12 +14913,10 @@ fn relaxng_invalid_382_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/382/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/382/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16493,12 +14932,10 @@ fn relaxng_valid_383_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/383/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/383/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -16514,12 +14951,10 @@ fn relaxng_valid_383_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/383/2.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/383/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -16535,12 +14970,10 @@ fn relaxng_invalid_383_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/383/3.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/383/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16556,12 +14989,10 @@ fn relaxng_invalid_383_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/383/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/383/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16577,12 +15008,10 @@ fn relaxng_invalid_383_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/383/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/383/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16598,12 +15027,10 @@ fn relaxng_valid_384_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/384/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/384/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -16619,12 +15046,10 @@ fn relaxng_invalid_384_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/384/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/384/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16640,12 +15065,10 @@ fn relaxng_valid_384_3(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/384/3.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/384/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -16661,12 +15084,10 @@ fn relaxng_invalid_384_4(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/384/4.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/384/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16682,12 +15103,10 @@ fn relaxng_invalid_384_5(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/384/5.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/384/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16703,12 +15122,10 @@ fn relaxng_invalid_384_6(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/384/6.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/384/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16724,12 +15141,10 @@ fn relaxng_invalid_384_7(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/384/7.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/384/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -16745,12 +15160,10 @@ fn relaxng_valid_385_1(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/385/1.v.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/385/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_ok());
@@ -16766,12 +15179,10 @@ fn relaxng_invalid_385_2(){
     */
 
     let docfile = fs::read_to_string("tests/conformance/relaxng/jamesclark/385/2.i.xml").unwrap();
-    let doc = Rc::new(SmiteNode::new());
-    let _ = xml::parse(doc.clone(), docfile.as_str(), None);
+    let doc = parse(docfile.as_str());
 
     let schemafile = fs::read_to_string("tests/conformance/relaxng/jamesclark/385/c.rng").unwrap();
-    let sch = Rc::new(SmiteNode::new());
-    let _ = xml::parse(sch.clone(), schemafile.as_str(), None);
+    let sch = parse(schemafile.as_str());
 
     let result = validate_relaxng(&doc, &sch);
     assert!(result.is_err());
@@ -899,3 +899,81 @@ where
     );
     Ok(())
 }
+
+pub fn pattern_intersect_1<N: Node, G>(make_empty_doc: G) -> Result<(), Error>
+where
+    G: Fn() -> N,
+{
+    let p: Pattern<N> =
+        Pattern::try_from("* intersect b").expect("unable to parse \"* intersect b\"");
+
+    // Setup a source document
+    let mut sd = make_empty_doc();
+    let mut t = sd
+        .new_element(QName::from_local_name(NcName::try_from("Test").unwrap()))
+        .expect("unable to create element");
+    sd.push(t.clone()).expect("unable to append child");
+    let a = sd
+        .new_element(QName::from_local_name(NcName::try_from("a").unwrap()))
+        .expect("unable to create element");
+    t.push(a.clone()).expect("unable to append child");
+    let b = sd
+        .new_element(QName::from_local_name(NcName::try_from("b").unwrap()))
+        .expect("unable to create element");
+    t.push(b.clone()).expect("unable to append child");
+
+    let mut stctxt = StaticContextBuilder::new()
+        .message(|_| Ok(()))
+        .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .build();
+
+    assert_eq!(
+        p.matches(&Context::new(), &mut stctxt, &Rc::new(Item::Node(a))),
+        false
+    );
+    assert_eq!(
+        p.matches(&Context::new(), &mut stctxt, &Rc::new(Item::Node(b))),
+        true
+    );
+    Ok(())
+}
+
+pub fn pattern_except_1<N: Node, G>(make_empty_doc: G) -> Result<(), Error>
+where
+    G: Fn() -> N,
+{
+    let p: Pattern<N> =
+        Pattern::try_from("Test/* except b").expect("unable to parse \"Test/* except b\"");
+
+    // Setup a source document
+    let mut sd = make_empty_doc();
+    let mut t = sd
+        .new_element(QName::from_local_name(NcName::try_from("Test").unwrap()))
+        .expect("unable to create element");
+    sd.push(t.clone()).expect("unable to append child");
+    let a = sd
+        .new_element(QName::from_local_name(NcName::try_from("a").unwrap()))
+        .expect("unable to create element");
+    t.push(a.clone()).expect("unable to append child");
+    let b = sd
+        .new_element(QName::from_local_name(NcName::try_from("b").unwrap()))
+        .expect("unable to create element");
+    t.push(b.clone()).expect("unable to append child");
+
+    let mut stctxt = StaticContextBuilder::new()
+        .message(|_| Ok(()))
+        .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .build();
+
+    assert_eq!(
+        p.matches(&Context::new(), &mut stctxt, &Rc::new(Item::Node(a))),
+        true
+    );
+    assert_eq!(
+        p.matches(&Context::new(), &mut stctxt, &Rc::new(Item::Node(b))),
+        false
+    );
+    Ok(())
+}
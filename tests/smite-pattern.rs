@@ -95,3 +95,11 @@ fn pattern_union_4() {
 fn pattern_attr_1() {
     patterngeneric::pattern_attr_1::<RNode, _>(smite::make_empty_doc).expect("test failed")
 }
+#[test]
+fn pattern_intersect_1() {
+    patterngeneric::pattern_intersect_1::<RNode, _>(smite::make_empty_doc).expect("test failed")
+}
+#[test]
+fn pattern_except_1() {
+    patterngeneric::pattern_except_1::<RNode, _>(smite::make_empty_doc).expect("test failed")
+}
@@ -812,6 +812,22 @@ fn tr_format_time() {
         .expect("test failed")
 }
 #[test]
+fn tr_current_date_time_fixed() {
+    transformgeneric::generic_tr_current_date_time_fixed::<RNode, _, _>(
+        smite::make_empty_doc,
+        smite::make_sd,
+    )
+    .expect("test failed")
+}
+#[test]
+fn tr_implicit_timezone() {
+    transformgeneric::generic_tr_implicit_timezone::<RNode, _, _>(
+        smite::make_empty_doc,
+        smite::make_sd,
+    )
+    .expect("test failed")
+}
+#[test]
 fn tr_format_number_1() {
     transformgeneric::generic_tr_format_number_1::<RNode, _, _>(
         smite::make_empty_doc,
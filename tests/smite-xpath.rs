@@ -502,6 +502,238 @@ fn xpath_for_2() {
         .expect("test failed")
 }
 #[test]
+fn xpath_some_1() {
+    xpathgeneric::generic_some_1::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_some_2() {
+    xpathgeneric::generic_some_2::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_every_1() {
+    xpathgeneric::generic_every_1::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_every_2() {
+    xpathgeneric::generic_every_2::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_arrow_1() {
+    xpathgeneric::generic_arrow_1::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_arrow_2() {
+    xpathgeneric::generic_arrow_2::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_simple_map_1() {
+    xpathgeneric::generic_simple_map_1::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_simple_map_2() {
+    xpathgeneric::generic_simple_map_2::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_map_constructor() {
+    xpathgeneric::generic_map_constructor::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_array_constructor() {
+    xpathgeneric::generic_array_constructor::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_lookup() {
+    xpathgeneric::generic_lookup::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_map_functions() {
+    xpathgeneric::generic_map_functions::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_array_functions() {
+    xpathgeneric::generic_array_functions::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_map_array_types() {
+    xpathgeneric::generic_map_array_types::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_function_ref() {
+    xpathgeneric::generic_function_ref::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_inline_function() {
+    xpathgeneric::generic_inline_function::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_partial_application() {
+    xpathgeneric::generic_partial_application::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_dynamic_call() {
+    xpathgeneric::generic_dynamic_call::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_higher_order() {
+    xpathgeneric::generic_higher_order::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_higher_order_map_array() {
+    xpathgeneric::generic_higher_order_map_array::<RNode, _, _>(
+        smite::make_empty_doc,
+        smite::make_sd,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xpath_instance_of_1() {
+    xpathgeneric::generic_instance_of_1::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_instance_of_2() {
+    xpathgeneric::generic_instance_of_2::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_instance_of_3() {
+    xpathgeneric::generic_instance_of_3::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_treat_1() {
+    xpathgeneric::generic_treat_1::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_cast_1() {
+    xpathgeneric::generic_cast_1::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_durations_1() {
+    xpathgeneric::generic_durations_1::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_date_arithmetic_1() {
+    xpathgeneric::generic_date_arithmetic_1::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_timezones_1() {
+    xpathgeneric::generic_timezones_1::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_timezones_2() {
+    xpathgeneric::generic_timezones_2::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_gregorian_1() {
+    xpathgeneric::generic_gregorian_1::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_numeric_promotion_1() {
+    xpathgeneric::generic_numeric_promotion_1::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_binary_1() {
+    xpathgeneric::generic_binary_1::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_castable_1() {
+    xpathgeneric::generic_castable_1::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_matches() {
+    xpathgeneric::generic_matches::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_replace() {
+    xpathgeneric::generic_replace::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_tokenize() {
+    xpathgeneric::generic_tokenize::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_analyze_string() {
+    xpathgeneric::generic_analyze_string::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_sequence_functions_1() {
+    xpathgeneric::generic_sequence_functions_1::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_sequence_functions_2() {
+    xpathgeneric::generic_sequence_functions_2::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_collations() {
+    xpathgeneric::generic_collations::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_string_functions_1() {
+    xpathgeneric::generic_string_functions_1::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_string_functions_2() {
+    xpathgeneric::generic_string_functions_2::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_unary_1() {
+    xpathgeneric::generic_unary_1::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_unary_2() {
+    xpathgeneric::generic_unary_2::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
+        .expect("test failed")
+}
+#[test]
+fn xpath_unary_3() {
+    xpathgeneric::generic_unary_3::<RNode, _, _, _>(
+        smite::make_empty_doc,
+        smite::make_sd,
+        smite::make_from_str,
+    )
+    .expect("test failed")
+}
+#[test]
 fn xpath_if_1() {
     xpathgeneric::generic_if_1::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
         .expect("test failed")
@@ -549,6 +781,51 @@ fn xpath_document_1() {
     .expect("test failed")
 }
 #[test]
+fn xpath_namespace_axis_1() {
+    xpathgeneric::generic_namespace_axis_1::<RNode, _, _, _>(
+        smite::make_empty_doc,
+        smite::make_sd,
+        smite::make_from_str,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xpath_qname_1() {
+    xpathgeneric::generic_qname_1::<RNode, _, _, _>(
+        smite::make_empty_doc,
+        smite::make_sd,
+        smite::make_from_str,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xpath_json_1() {
+    xpathgeneric::generic_json_1::<RNode, _, _, _>(
+        smite::make_empty_doc,
+        smite::make_sd,
+        smite::make_from_str,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xpath_intersect_except() {
+    xpathgeneric::generic_intersect_except::<RNode, _, _, _>(
+        smite::make_empty_doc,
+        smite::make_sd,
+        smite::make_from_str,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xpath_node_comparison() {
+    xpathgeneric::generic_node_comparison::<RNode, _, _, _>(
+        smite::make_empty_doc,
+        smite::make_sd,
+        smite::make_from_str,
+    )
+    .expect("test failed")
+}
+#[test]
 fn xpath_issue138_1() {
     xpathgeneric::issue138_1::<RNode, _, _>(smite::make_empty_doc, smite::make_sd)
         .expect("test failed")
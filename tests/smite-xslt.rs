@@ -103,6 +103,24 @@ fn xslt_apply_templates_sort() {
     .expect("test failed")
 }
 #[test]
+fn xslt_sort_collation() {
+    xsltgeneric::generic_sort_collation(
+        smite::make_from_str,
+        smite::make_from_str_with_ns,
+        smite::make_sd_cooked,
+    )
+    .expect("test failed")
+}
+#[test]
+fn xslt_group_key_collation() {
+    xsltgeneric::generic_group_key_collation(
+        smite::make_from_str,
+        smite::make_from_str_with_ns,
+        smite::make_sd_cooked,
+    )
+    .expect("test failed")
+}
+#[test]
 fn xslt_comment() {
     xsltgeneric::generic_comment(
         smite::make_from_str,
@@ -175,6 +193,15 @@ fn xslt_callable_posn_1() {
     .expect("test failed")
 }
 #[test]
+fn xslt_callable_shadow_1() {
+    xsltgeneric::generic_callable_shadow_1(
+        smite::make_from_str,
+        smite::make_from_str_with_ns,
+        smite::make_sd_cooked,
+    )
+    .expect("test failed")
+}
+#[test]
 #[should_panic]
 fn xslt_include() {
     xsltgeneric::generic_include(
@@ -436,3 +463,12 @@ fn xslt_conform_8() {
     )
     .expect("test failed")
 }
+#[test]
+fn xslt_namespace_axis() {
+    xsltgeneric::generic_namespace_axis(
+        smite::make_from_str,
+        smite::make_from_str_with_ns,
+        smite::make_empty_doc_cooked,
+    )
+    .expect("test failed")
+}
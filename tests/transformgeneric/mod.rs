@@ -1,6 +1,6 @@
 //! Tests for transform module defined generically
 
-use chrono::{Datelike, Local, Timelike};
+use chrono::{Datelike, FixedOffset, Local, NaiveDate, TimeZone, Timelike};
 use qualname::{NamespaceDeclaration, NamespaceMap, NamespacePrefix, NamespaceUri, NcName, QName};
 use std::rc::Rc;
 use xrust::item::{Item, Node, SequenceTrait};
@@ -14,7 +14,7 @@ use xrust::transform::{
     ArithmeticOperand, ArithmeticOperator, Axis, Grouping, KindTest, NameTest, NodeMatch, NodeTest,
     Order, Transform, WildcardOrName, WildcardOrNamespaceUri,
 };
-use xrust::value::{Operator, Value, ValueData};
+use xrust::value::{Operator, Value, ValueBuilder, ValueData};
 use xrust::xdmerror::{Error, ErrorKind};
 
 pub fn generic_tr_empty<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
@@ -2636,7 +2636,7 @@ where
             }),
         ])),
         Box::new(Transform::ContextItem),
-        vec![(Order::Ascending, Transform::ContextItem)],
+        vec![(Order::Ascending, Transform::ContextItem, None)],
     );
 
     let mut stctxt = StaticContextBuilder::new()
@@ -2661,13 +2661,16 @@ where
 {
     // xsl:for-each-group select="1 to 50" group-by=". mod 10" body == xsl:text "group current-grouping-key size count(current-group)"
     let x = Transform::ForEach(
-        Some(Grouping::By(vec![Transform::Arithmetic(vec![
-            ArithmeticOperand::new(ArithmeticOperator::Noop, Transform::ContextItem),
-            ArithmeticOperand::new(
-                ArithmeticOperator::Modulo,
-                Transform::Literal(Item::<N>::Value(Rc::new(Value::from(10)))),
-            ),
-        ])])),
+        Some(Grouping::By(
+            vec![Transform::Arithmetic(vec![
+                ArithmeticOperand::new(ArithmeticOperator::Noop, Transform::ContextItem),
+                ArithmeticOperand::new(
+                    ArithmeticOperator::Modulo,
+                    Transform::Literal(Item::<N>::Value(Rc::new(Value::from(10)))),
+                ),
+            ])],
+            None,
+        )),
         Box::new(Transform::Range(
             Box::new(Transform::Literal(Item::<N>::Value(Rc::new(Value::from(
                 1,
@@ -2713,13 +2716,16 @@ where
     // body == xsl:text "group current-grouping-key size count(current-group)"
     // sort == current-grouping-key()
     let x = Transform::ForEach(
-        Some(Grouping::By(vec![Transform::Arithmetic(vec![
-            ArithmeticOperand::new(ArithmeticOperator::Noop, Transform::ContextItem),
-            ArithmeticOperand::new(
-                ArithmeticOperator::Modulo,
-                Transform::Literal(Item::<N>::Value(Rc::new(Value::from(10)))),
-            ),
-        ])])),
+        Some(Grouping::By(
+            vec![Transform::Arithmetic(vec![
+                ArithmeticOperand::new(ArithmeticOperator::Noop, Transform::ContextItem),
+                ArithmeticOperand::new(
+                    ArithmeticOperator::Modulo,
+                    Transform::Literal(Item::<N>::Value(Rc::new(Value::from(10)))),
+                ),
+            ])],
+            None,
+        )),
         Box::new(Transform::Range(
             Box::new(Transform::Literal(Item::<N>::Value(Rc::new(Value::from(
                 1,
@@ -2737,7 +2743,7 @@ where
                 Transform::Count(Box::new(Transform::CurrentGroup)),
             ])),
         )),
-        vec![(Order::Ascending, Transform::CurrentGroupingKey)],
+        vec![(Order::Ascending, Transform::CurrentGroupingKey, None)],
     );
 
     let resdoc = make_empty_doc();
@@ -2772,7 +2778,7 @@ where
 {
     // xsl:for-each-group select="(a, a, b, c, c, c)" group-adjacent="." body == xsl:text "group current-grouping-key size count(current-group)"
     let x = Transform::ForEach(
-        Some(Grouping::Adjacent(vec![Transform::ContextItem])),
+        Some(Grouping::Adjacent(vec![Transform::ContextItem], None)),
         Box::new(Transform::SequenceItems(vec![
             Transform::Literal(Item::<N>::Value(Rc::new(Value::from("a")))),
             Transform::Literal(Item::<N>::Value(Rc::new(Value::from("a")))),
@@ -2816,7 +2822,7 @@ where
 {
     // xsl:for-each-group select="(a, a, b, c, c, c)" group-adjacent="." body == xsl:text "group current-grouping-key size count(current-group)"
     let x = Transform::ForEach(
-        Some(Grouping::Adjacent(vec![Transform::ContextItem])),
+        Some(Grouping::Adjacent(vec![Transform::ContextItem], None)),
         Box::new(Transform::SequenceItems(vec![
             Transform::Literal(Item::<N>::Value(Rc::new(Value::from("a")))),
             Transform::Literal(Item::<N>::Value(Rc::new(Value::from("a")))),
@@ -2834,7 +2840,7 @@ where
                 Transform::Count(Box::new(Transform::CurrentGroup)),
             ])),
         )),
-        vec![(Order::Ascending, Transform::CurrentGroupingKey)],
+        vec![(Order::Ascending, Transform::CurrentGroupingKey, None)],
     );
 
     let resdoc = make_empty_doc();
@@ -3680,7 +3686,7 @@ where
                     nodetest: NodeTest::Kind(KindTest::Any),
                 })),
                 None,
-                vec![(Order::Ascending, Transform::ContextItem)],
+                vec![(Order::Ascending, Transform::ContextItem, None)],
             ), // body "apply-templates select=node() sort",
             Some(1.0), // priority
             vec![0],   // import
@@ -4085,6 +4091,7 @@ where
         Box::new(Transform::Literal(Item::<N>::Value(Rc::new(Value::from(
             "ab",
         ))))),
+        None,
     );
     let mut stctxt = StaticContextBuilder::new()
         .message(|_| Ok(()))
@@ -4112,6 +4119,7 @@ where
         Box::new(Transform::Literal(Item::<N>::Value(Rc::new(Value::from(
             "x",
         ))))),
+        None,
     );
     let mut stctxt = StaticContextBuilder::new()
         .message(|_| Ok(()))
@@ -4139,6 +4147,7 @@ where
         Box::new(Transform::Literal(Item::<N>::Value(Rc::new(Value::from(
             "bc",
         ))))),
+        None,
     );
     let mut stctxt = StaticContextBuilder::new()
         .message(|_| Ok(()))
@@ -4166,6 +4175,7 @@ where
         Box::new(Transform::Literal(Item::<N>::Value(Rc::new(Value::from(
             "xyz",
         ))))),
+        None,
     );
     let mut stctxt = StaticContextBuilder::new()
         .message(|_| Ok(()))
@@ -4251,6 +4261,7 @@ where
         Box::new(Transform::Literal(Item::<N>::Value(Rc::new(Value::from(
             "bc",
         ))))),
+        None,
     );
     let mut stctxt = StaticContextBuilder::new()
         .message(|_| Ok(()))
@@ -4278,6 +4289,7 @@ where
         Box::new(Transform::Literal(Item::<N>::Value(Rc::new(Value::from(
             "bc",
         ))))),
+        None,
     );
     let mut stctxt = StaticContextBuilder::new()
         .message(|_| Ok(()))
@@ -4751,7 +4763,7 @@ where
     assert_eq!(seq.len(), 1);
     match &seq[0] {
         Item::Value(v) => match v.value {
-            ValueData::DateTime(dt) => {
+            ValueData::DateTime(dt, _) => {
                 assert_eq!(dt.year(), Local::now().year());
                 assert_eq!(dt.month(), Local::now().month());
                 assert_eq!(dt.day(), Local::now().day());
@@ -4784,7 +4796,7 @@ where
     assert_eq!(seq.len(), 1);
     match &seq[0] {
         Item::Value(v) => match v.value {
-            ValueData::Date(dt) => {
+            ValueData::Date(dt, _) => {
                 assert_eq!(dt.year(), Local::now().year());
                 assert_eq!(dt.month(), Local::now().month());
                 assert_eq!(dt.day(), Local::now().day());
@@ -4814,7 +4826,7 @@ where
     assert_eq!(seq.len(), 1);
     match &seq[0] {
         Item::Value(v) => match v.value {
-            ValueData::Time(dt) => {
+            ValueData::Time(dt, _) => {
                 assert_eq!(dt.hour(), Local::now().hour());
                 assert_eq!(dt.minute(), Local::now().minute());
                 assert_eq!(dt.second(), Local::now().second()); // It is possible for this to fail if the elapsed time to execute the function call and the test falls across a second quantum
@@ -4916,6 +4928,79 @@ where
     Ok(())
 }
 
+pub fn generic_tr_current_date_time_fixed<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    // XPath == current-dateTime(), current-date(), current-time()
+    let tz = FixedOffset::east_opt(9 * 3600).unwrap();
+    let ctxt = ContextBuilder::new()
+        .implicit_timezone(tz)
+        .current_date_time(tz.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap())
+        .build();
+    let mut stctxt = StaticContextBuilder::new()
+        .message(|_| Ok(()))
+        .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .build();
+    for (x, r) in [
+        (Transform::<N>::CurrentDateTime, "2024-01-01T10:00:00+09:00"),
+        (Transform::<N>::CurrentDate, "2024-01-01+09:00"),
+        (Transform::<N>::CurrentTime, "10:00:00+09:00"),
+    ] {
+        let seq = ctxt.dispatch(&mut stctxt, &x).expect("evaluation failed");
+        assert_eq!(seq.to_string(), r);
+    }
+    Ok(())
+}
+
+pub fn generic_tr_implicit_timezone<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    // XPath == xs:dateTime("2024-01-01T10:00:00") eq xs:dateTime("2024-01-01T01:00:00Z")
+    let day = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let x = Transform::ValueComparison(
+        Operator::Equal,
+        Box::new(Transform::Literal(Item::<N>::Value(Rc::new(
+            ValueBuilder::new()
+                .value(ValueData::DateTime(
+                    day.and_hms_opt(10, 0, 0).unwrap(),
+                    None,
+                ))
+                .build(),
+        )))),
+        Box::new(Transform::Literal(Item::<N>::Value(Rc::new(
+            ValueBuilder::new()
+                .value(ValueData::DateTime(
+                    day.and_hms_opt(1, 0, 0).unwrap(),
+                    FixedOffset::east_opt(0),
+                ))
+                .build(),
+        )))),
+    );
+    let mut stctxt = StaticContextBuilder::new()
+        .message(|_| Ok(()))
+        .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .build();
+    let seq = ContextBuilder::new()
+        .implicit_timezone(FixedOffset::east_opt(9 * 3600).unwrap())
+        .build()
+        .dispatch(&mut stctxt, &x)
+        .expect("evaluation failed");
+    assert_eq!(seq.to_string(), "true");
+    let seq = ContextBuilder::new()
+        .implicit_timezone(FixedOffset::east_opt(0).unwrap())
+        .build()
+        .dispatch(&mut stctxt, &x)
+        .expect("evaluation failed");
+    assert_eq!(seq.to_string(), "false");
+    Ok(())
+}
+
 pub fn generic_tr_format_number_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
 where
     G: Fn() -> N,
@@ -1,5 +1,6 @@
 //! Tests for XPath defined generically
 
+use num_bigint::BigInt;
 use pkg_version::{pkg_version_major, pkg_version_minor, pkg_version_patch};
 use qualname::{NamespaceDeclaration, NamespaceMap, NamespacePrefix, NamespaceUri, NcName, QName};
 use std::ops::Deref;
@@ -22,6 +23,31 @@ fn no_src_no_result<N: Node>(e: impl AsRef<str>) -> Result<Sequence<N>, Error> {
     Context::new().dispatch(&mut stctxt, &parse(e.as_ref(), None, None)?)
 }
 
+// Evaluate an expression with the "xs", "map" and "array" prefixes bound to their usual namespaces
+fn ns_no_src_no_result<N: Node>(e: impl AsRef<str>) -> Result<Sequence<N>, Error> {
+    let mut stctxt = StaticContextBuilder::new()
+        .message(|_| Ok(()))
+        .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .build();
+    let mut nsmap = NamespaceMap::new();
+    for (p, ns) in [
+        ("xs", "http://www.w3.org/2001/XMLSchema"),
+        ("map", "http://www.w3.org/2005/xpath-functions/map"),
+        ("array", "http://www.w3.org/2005/xpath-functions/array"),
+        ("bin", "http://expath.org/ns/binary"),
+    ] {
+        nsmap.push(
+            NamespaceDeclaration::new(
+                Some(NamespacePrefix::try_from(p).unwrap()),
+                NamespaceUri::try_from(ns).unwrap(),
+            )
+            .expect("unable to add namespace"),
+        );
+    }
+    Context::new().dispatch(&mut stctxt, &parse(e.as_ref(), None, Some(nsmap))?)
+}
+
 fn dispatch_rig<N: Node, G, H>(
     e: impl AsRef<str>,
     make_empty_doc: G,
@@ -1326,8 +1352,8 @@ where
     let s: Sequence<N> = no_src_no_result("number('123')")?;
     assert_eq!(s.len(), 1);
     match &s[0] {
-        Item::Value(v) => match v.value {
-            ValueData::Integer(i) => assert_eq!(i, 123),
+        Item::Value(v) => match &v.value {
+            ValueData::Integer(i) => assert_eq!(*i, BigInt::from(123)),
             _ => panic!("not a singleton integer value, got \"{}\"", s.to_string()),
         },
         _ => panic!("not a value"),
@@ -1494,8 +1520,8 @@ where
     let s: Sequence<N> = no_src_no_result("count((1, 2, 3, 4))")?;
     assert_eq!(s.len(), 1);
     match &s[0] {
-        Item::Value(v) => match v.value {
-            ValueData::Integer(d) => assert_eq!(d, 4),
+        Item::Value(v) => match &v.value {
+            ValueData::Integer(d) => assert_eq!(*d, BigInt::from(4)),
             _ => panic!("not a singleton integer value"),
         },
         _ => panic!("not a value"),
@@ -1645,292 +1671,1511 @@ where
     Ok(())
 }
 
-// Conditionals
+// Quantified expressions
 
-pub fn generic_if_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+pub fn generic_some_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
 where
     G: Fn() -> N,
     H: Fn() -> Item<N>,
 {
-    let s: Sequence<N> = no_src_no_result("if (1) then 'one' else 'not one'")?;
+    let s: Sequence<N> = no_src_no_result("some $x in (1, 2, 3) satisfies $x eq 2")?;
     assert_eq!(s.len(), 1);
-    assert_eq!(s.to_string(), "one");
+    assert_eq!(s.to_bool(), true);
     Ok(())
 }
-pub fn generic_if_2<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+pub fn generic_some_2<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
 where
     G: Fn() -> N,
     H: Fn() -> Item<N>,
 {
-    let s: Sequence<N> = no_src_no_result("if (0) then 'one' else 'not one'")?;
+    let s: Sequence<N> =
+        no_src_no_result("some $x in (1, 2), $y in ($x + 1, 5) satisfies $x + $y eq 4")?;
     assert_eq!(s.len(), 1);
-    assert_eq!(s.to_string(), "not one");
+    assert_eq!(s.to_bool(), false);
     Ok(())
 }
-pub fn generic_issue_95<N: Node, G, H>(make_empty_doc: G, make_doc: H) -> Result<(), Error>
+pub fn generic_every_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
 where
     G: Fn() -> N,
     H: Fn() -> Item<N>,
 {
-    let result: Sequence<N> = dispatch_rig("@*|node()", make_empty_doc, make_doc)?;
-    if result.len() == 1 {
-        match &result[0] {
-            Item::Node(n) => match (
-                n.node_type(),
-                n.name().map_or("".to_string(), |m| m.to_string()).deref(),
-            ) {
-                (NodeType::Element, "a") => Ok(()),
-                (NodeType::Element, _) => Err(Error::new(
-                    ErrorKind::Unknown,
-                    format!(
-                        "got element named \"{}\", expected \"a\"",
-                        result[0].name().map_or("".to_string(), |m| m.to_string())
-                    ),
-                )),
-                _ => Err(Error::new(ErrorKind::Unknown, "not an element type node")),
-            },
-            _ => Err(Error::new(ErrorKind::Unknown, "not a node")),
-        }
-    } else {
-        Err(Error::new(
-            ErrorKind::Unknown,
-            format!("got {} results, expected 0", result.len()),
-        ))
-    }
+    let s: Sequence<N> = no_src_no_result("every $x in (1, 2), $y in (3, 4) satisfies $x lt $y")?;
+    assert_eq!(s.len(), 1);
+    assert_eq!(s.to_bool(), true);
+    Ok(())
 }
-
-pub fn generic_navigate_predicate_1<N: Node, G, H>(
-    make_empty_doc: G,
-    make_doc: H,
-) -> Result<(), Error>
+pub fn generic_every_2<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
 where
     G: Fn() -> N,
     H: Fn() -> Item<N>,
 {
-    let rd = make_empty_doc();
-    let sd = make_doc();
-    if let Item::Node(d) = sd {
-        let xform = parse("../*[@id eq 'b6']", None, None).expect("parsing failed");
-        let mut stctxt = StaticContextBuilder::new()
-            .message(|_| Ok(()))
-            .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
-            .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
-            .build();
-        let s = ContextBuilder::new()
-            .context(vec![Item::Node(
-                d.first_child().unwrap().first_child().unwrap(),
-            )])
-            .result_document(rd)
-            .build()
-            .dispatch(&mut stctxt, &xform)
-            .expect("transform failed");
-        assert_eq!(s.len(), 1);
-        assert_eq!(s[0].name().map_or("".to_string(), |m| m.to_string()), "b");
-        Ok(())
-    } else {
-        panic!("unable to unpack node")
-    }
+    let s: Sequence<N> = no_src_no_result("every $x in () satisfies $x eq 1")?;
+    assert_eq!(s.len(), 1);
+    assert_eq!(s.to_bool(), true);
+    Ok(())
 }
 
-pub fn generic_predicate_1<N: Node, G, H>(make_empty_doc: G, make_doc: H) -> Result<(), Error>
+// Arrow and simple map operators
+
+pub fn generic_arrow_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
 where
     G: Fn() -> N,
     H: Fn() -> Item<N>,
 {
-    let rd = make_empty_doc();
-    let sd = make_doc();
-    if let Item::Node(d) = sd.clone() {
-        let xform = parse("$v[position() eq 1]", None, None).expect("parsing failed");
-        let mut stctxt = StaticContextBuilder::new()
-            .message(|_| Ok(()))
-            .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
-            .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
-            .build();
-        let mut ctxt = ContextBuilder::new()
-            .context(vec![sd])
-            .result_document(rd)
-            .build();
-        let a = d.first_child().unwrap();
-        let bs = a.child_iter().map(|c| Item::Node(c)).collect();
-        ctxt.var_push(String::from("v"), bs);
-        let s = ctxt
-            .dispatch(&mut stctxt, &xform)
-            .expect("transform failed");
-        assert_eq!(s.len(), 1);
-        assert_eq!(s[0].name().unwrap().to_string(), "b");
-        if let Item::Node(r) = &s[0] {
-            assert_eq!(
-                r.get_attribute(&QName::from_local_name(NcName::try_from("id").unwrap()))
-                    .to_string(),
-                "b1"
-            );
-        } else {
-            panic!("result is not a node")
-        }
-        Ok(())
-    } else {
-        panic!("unable to unpack node")
-    }
+    let s: Sequence<N> = no_src_no_result("'abcd' => substring(2) => concat('-', 'x')")?;
+    assert_eq!(s.to_string(), "bcd-x");
+    Ok(())
 }
-
-pub fn issue138_1<N: Node, G, H>(make_empty_doc: G, _: H) -> Result<(), Error>
+pub fn generic_arrow_2<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
 where
     G: Fn() -> N,
     H: Fn() -> Item<N>,
 {
-    let mut sd = make_empty_doc();
-    //let rd = make_empty_doc();
-    let mut top = sd
-        .new_element(QName::from_local_name(NcName::try_from("root").unwrap()))
-        .expect("unable to create root element");
-    sd.push(top.clone()).expect("unable to add root element");
-    let e_name = QName::from_local_name(NcName::try_from("element").unwrap());
-    let at_name = QName::from_local_name(NcName::try_from("attr").unwrap());
-    // <element attr="val1">text1</element>
-    let mut e1 = sd
-        .new_element(e_name.clone())
-        .expect("unable to create element 1");
-    let a1 = sd
-        .new_attribute(at_name.clone(), Rc::new(Value::from("val1")))
-        .expect("unable to create attribute 1");
-    e1.add_attribute(a1).expect("unable to add attribute 1");
-    let t1 = sd
-        .new_text(Rc::new(Value::from("text1")))
-        .expect("unable to create text 1");
-    e1.push(t1).expect("unable to add text 1");
-    top.push(e1).expect("unable to add element 1");
-    // <element attr="val2">text2</element>
-    let mut e2 = sd
-        .new_element(e_name.clone())
-        .expect("unable to create element 2");
-    let a2 = sd
-        .new_attribute(at_name.clone(), Rc::new(Value::from("val2")))
-        .expect("unable to create attribute 2");
-    e2.add_attribute(a2).expect("unable to add attribute 2");
-    let t2 = sd
-        .new_text(Rc::new(Value::from("text2")))
-        .expect("unable to create text 2");
-    e2.push(t2).expect("unable to add text 2");
-    top.push(e2).expect("unable to add element 2");
-
-    let xform = parse("/root/element[position() = 1]", None, None).expect("parsing failed");
-    let mut stctxt = StaticContextBuilder::new()
-        .message(|_| Ok(()))
-        .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
-        .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
-        .build();
-    let s = ContextBuilder::new()
-        .context(vec![Item::Node(sd.clone())])
-        //        .result_document(rd)
-        .result_document(sd)
-        .build()
-        .dispatch(&mut stctxt, &xform)
-        .expect("transform failed");
-    assert_eq!(s.len(), 1);
-    assert_eq!(s.to_xml(), "<element attr='val1'>text1</element>");
+    // The target is not a function item
+    assert!(no_src_no_result::<N>("let $f := 1 return 'a' => $f()").is_err());
     Ok(())
 }
-
-pub fn issue138_2<N: Node, G, H>(make_empty_doc: G, _: H) -> Result<(), Error>
+pub fn generic_simple_map_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result("(1, 2, 3) ! (. * 2) ! (. + 1)")?;
+    assert_eq!(s.len(), 3);
+    assert_eq!(s.to_string(), "357");
+    Ok(())
+}
+pub fn generic_simple_map_2<N: Node, G, H>(_: G, make_doc: H) -> Result<(), Error>
 where
     G: Fn() -> N,
     H: Fn() -> Item<N>,
 {
-    let rd = make_empty_doc();
-    let mut sd = make_empty_doc();
-    let mut top = sd
-        .new_element(QName::from_local_name(NcName::try_from("root").unwrap()))
-        .expect("unable to create root element");
-    sd.push(top.clone()).expect("unable to add root element");
-    let e_name = QName::from_local_name(NcName::try_from("element").unwrap());
-    let at_name = QName::from_local_name(NcName::try_from("attr").unwrap());
-    // <element attr="val1">text1</element>
-    let mut e1 = sd
-        .new_element(e_name.clone())
-        .expect("unable to create element 1");
-    let a1 = sd
-        .new_attribute(at_name.clone(), Rc::new(Value::from("val1")))
-        .expect("unable to create attribute 1");
-    e1.add_attribute(a1).expect("unable to add attribute 1");
-    let t1 = sd
-        .new_text(Rc::new(Value::from("text1")))
-        .expect("unable to create text 1");
-    e1.push(t1).expect("unable to add text 1");
-    top.push(e1).expect("unable to add element 1");
-    // <element attr="val2">text2</element>
-    let mut e2 = sd
-        .new_element(e_name.clone())
-        .expect("unable to create element 2");
-    let a2 = sd
-        .new_attribute(at_name.clone(), Rc::new(Value::from("val2")))
-        .expect("unable to create attribute 2");
-    e2.add_attribute(a2).expect("unable to add attribute 2");
-    let t2 = sd
-        .new_text(Rc::new(Value::from("text2")))
-        .expect("unable to create text 2");
-    e2.push(t2).expect("unable to add text 2");
-    top.push(e2).expect("unable to add element 2");
-
-    let xform = parse("/root/element[position() = 2]", None, None).expect("parsing failed");
     let mut stctxt = StaticContextBuilder::new()
         .message(|_| Ok(()))
         .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
         .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
         .build();
+    // Unlike a path, the result is not deduplicated
     let s = ContextBuilder::new()
-        .context(vec![Item::Node(sd)])
-        .result_document(rd)
+        .context(vec![make_doc()])
         .build()
-        .dispatch(&mut stctxt, &xform)
-        .expect("transform failed");
-    assert_eq!(s.len(), 1);
-    assert_eq!(s.to_xml(), "<element attr='val2'>text2</element>");
+        .dispatch(&mut stctxt, &parse("a/b ! .. ! name()", None, None)?)?;
+    assert_eq!(s.len(), 2);
+    assert_eq!(s.to_string(), "aa");
     Ok(())
 }
 
-// System properties
+// Maps and arrays
 
-pub fn generic_sys_prop_vers_qual<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+pub fn generic_map_constructor<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
 where
     G: Fn() -> N,
     H: Fn() -> Item<N>,
 {
-    let s: Sequence<N> =
-        no_src_no_result("system-property('Q{http://www.w3.org/1999/XSL/Transform}version')")?;
-    assert_eq!(s.len(), 1);
-    assert_eq!(s.to_string(), "0.9");
+    let s: Sequence<N> = no_src_no_result("map{'a': 1, 'b': (2, 3)}?b")?;
+    assert_eq!(s.len(), 2);
+    assert_eq!(s.to_string(), "23");
+    // Keys are compared using op:same-key
+    let s: Sequence<N> = no_src_no_result("map{1: 'one'}?(1.0)")?;
+    assert_eq!(s.to_string(), "one");
+    assert!(no_src_no_result::<N>("map{'a': 1, 'a': 2}").is_err());
     Ok(())
 }
-pub fn generic_sys_prop_product_vers<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+
+pub fn generic_array_constructor<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
 where
     G: Fn() -> N,
     H: Fn() -> Item<N>,
 {
-    let s: Sequence<N> = no_src_no_result(
-        "system-property('Q{http://www.w3.org/1999/XSL/Transform}product-version')",
-    )?;
-    assert_eq!(s.len(), 1);
-    assert_eq!(
-        s.to_string(),
-        format!(
-            "{}.{}.{}",
-            pkg_version_major!(),
-            pkg_version_minor!(),
-            pkg_version_patch!()
-        )
-    );
+    let s: Sequence<N> = no_src_no_result("[1, (2, 3), ()]?2")?;
+    assert_eq!(s.to_string(), "23");
+    let s: Sequence<N> = no_src_no_result("array{1, (2, 3), ()}?*")?;
+    assert_eq!(s.len(), 3);
+    let s: Sequence<N> = no_src_no_result("[1, 2, 3]?(3, 1)")?;
+    assert_eq!(s.to_string(), "31");
+    assert!(no_src_no_result::<N>("[1, 2]?3").is_err());
     Ok(())
 }
 
-pub fn generic_document_1<N: Node, G, H, J>(
-    make_empty_doc: G,
-    make_doc: H,
-    make_from_str: J,
-) -> Result<(), Error>
+pub fn generic_lookup<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
 where
     G: Fn() -> N,
     H: Fn() -> Item<N>,
-    J: Fn(&str) -> Result<N, Error>,
+{
+    let s: Sequence<N> = no_src_no_result("(map{'a': 1}, map{'a': 2})?a")?;
+    assert_eq!(s.to_string(), "12");
+    let s: Sequence<N> = no_src_no_result("[map{'a': [4, 5]}]?1?a?2")?;
+    assert_eq!(s.to_string(), "5");
+    let s: Sequence<N> = no_src_no_result("([1, 2], [3, 4])[2]?1")?;
+    assert_eq!(s.to_string(), "3");
+    // Unary lookup applies to the context item
+    let s: Sequence<N> = no_src_no_result("(map{'x': 'y'}) ! ?x")?;
+    assert_eq!(s.to_string(), "y");
+    Ok(())
+}
+
+pub fn generic_map_functions<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = ns_no_src_no_result("map:size(map:put(map{'a': 1}, 'b', 2))")?;
+    assert_eq!(s.to_string(), "2");
+    let s: Sequence<N> =
+        ns_no_src_no_result("map:keys(map:remove(map{'a': 1, 'b': 2, 'c': 3}, 'b'))")?;
+    assert_eq!(s.to_string(), "ac");
+    let s: Sequence<N> = ns_no_src_no_result(
+        "map:merge((map{'a': 1}, map{'a': 2}), map{'duplicates': 'combine'})?a",
+    )?;
+    assert_eq!(s.to_string(), "12");
+    let s: Sequence<N> = ns_no_src_no_result("map:contains(map:entry('k', ()), 'k')")?;
+    assert_eq!(s.to_string(), "true");
+    let s: Sequence<N> = ns_no_src_no_result("map:get(map{'a': 1}, 'z')")?;
+    assert!(s.is_empty());
+    let s: Sequence<N> =
+        ns_no_src_no_result("array:size(map:find([map{'k': 1}, map{'k': map{'k': 2}}], 'k'))")?;
+    assert_eq!(s.to_string(), "3");
+    Ok(())
+}
+
+pub fn generic_array_functions<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = ns_no_src_no_result("array:size(array:append([1, 2], (3, 4)))")?;
+    assert_eq!(s.to_string(), "3");
+    let s: Sequence<N> = ns_no_src_no_result("array:get(array:reverse([1, 2, 3]), 1)")?;
+    assert_eq!(s.to_string(), "3");
+    let s: Sequence<N> = ns_no_src_no_result("array:subarray([1, 2, 3, 4], 2, 2)?*")?;
+    assert_eq!(s.to_string(), "23");
+    let s: Sequence<N> =
+        ns_no_src_no_result("array:insert-before(array:remove([1, 2, 3], 2), 1, 0)?*")?;
+    assert_eq!(s.to_string(), "013");
+    let s: Sequence<N> =
+        ns_no_src_no_result("array:flatten(([1, [2, 3]], 4, array:join(([5], [6]))))")?;
+    assert_eq!(s.to_string(), "123456");
+    let s: Sequence<N> = ns_no_src_no_result("array:head(array:tail(array:put([1, 2], 2, 'x')))")?;
+    assert_eq!(s.to_string(), "x");
+    assert!(ns_no_src_no_result::<N>("array:head([])").is_err());
+    Ok(())
+}
+
+pub fn generic_map_array_types<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result("map{} instance of map(*)")?;
+    assert_eq!(s.to_string(), "true");
+    let s: Sequence<N> = no_src_no_result("[] instance of map(*)")?;
+    assert_eq!(s.to_string(), "false");
+    let s: Sequence<N> = no_src_no_result("[] instance of function(*)")?;
+    assert_eq!(s.to_string(), "true");
+    Ok(())
+}
+
+// Function items
+
+pub fn generic_function_ref<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result("count#1((1, 2, 3))")?;
+    assert_eq!(s.to_string(), "3");
+    let s: Sequence<N> = no_src_no_result("let $f := concat#3 return $f('a', 'b', 'c')")?;
+    assert_eq!(s.to_string(), "abc");
+    let s: Sequence<N> = no_src_no_result("count#1 instance of function(*)")?;
+    assert_eq!(s.to_string(), "true");
+    // Wrong number of arguments
+    assert!(no_src_no_result::<N>("count#1(1, 2)").is_err());
+    Ok(())
+}
+
+pub fn generic_inline_function<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result("function($a, $b) { $a + $b }(1, 2)")?;
+    assert_eq!(s.to_string(), "3");
+    let s: Sequence<N> = ns_no_src_no_result(
+        "let $f := function($x as xs:integer) as xs:integer { $x * 2 } return $f(21)",
+    )?;
+    assert_eq!(s.to_string(), "42");
+    // Variables in scope are captured by the function
+    let s: Sequence<N> = no_src_no_result("let $y := 10 return (function($x) { $x + $y })(1)")?;
+    assert_eq!(s.to_string(), "11");
+    let s: Sequence<N> = no_src_no_result("function() { }()")?;
+    assert!(s.is_empty());
+    assert!(ns_no_src_no_result::<N>("function($x as xs:integer) { $x }('a')").is_err());
+    Ok(())
+}
+
+pub fn generic_partial_application<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result("let $f := concat('a', ?, 'c') return $f('b')")?;
+    assert_eq!(s.to_string(), "abc");
+    let s: Sequence<N> = no_src_no_result("substring-before('a-b', ?)('-')")?;
+    assert_eq!(s.to_string(), "a");
+    let s: Sequence<N> =
+        no_src_no_result("let $f := function($a, $b) { ($a, $b) } return $f(?, 2)(1)")?;
+    assert_eq!(s.to_string(), "12");
+    Ok(())
+}
+
+pub fn generic_dynamic_call<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result("let $m := map{'a': 1} return $m('a')")?;
+    assert_eq!(s.to_string(), "1");
+    let s: Sequence<N> = no_src_no_result("[4, 5, 6](2)")?;
+    assert_eq!(s.to_string(), "5");
+    let s: Sequence<N> = no_src_no_result("let $f := count#1 return (1, 2, 3) => $f()")?;
+    assert_eq!(s.to_string(), "3");
+    assert!(no_src_no_result::<N>("let $f := 1 return $f(2)").is_err());
+    Ok(())
+}
+
+pub fn generic_higher_order<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result("for-each((1, 2, 3), function($x) { $x * 2 })")?;
+    assert_eq!(s.to_string(), "246");
+    let s: Sequence<N> = no_src_no_result("filter((1, 2, 3, 4), function($x) { $x > 2 })")?;
+    assert_eq!(s.to_string(), "34");
+    let s: Sequence<N> = no_src_no_result("fold-left((1, 2, 3), 0, function($a, $b) { $a + $b })")?;
+    assert_eq!(s.to_string(), "6");
+    let s: Sequence<N> =
+        no_src_no_result("fold-right((1, 2, 3), (), function($a, $b) { ($b, $a) })")?;
+    assert_eq!(s.to_string(), "321");
+    let s: Sequence<N> =
+        no_src_no_result("for-each-pair((1, 2), (3, 4), function($a, $b) { $a * $b })")?;
+    assert_eq!(s.to_string(), "38");
+    let s: Sequence<N> = no_src_no_result("sort((3, 1, 2))")?;
+    assert_eq!(s.to_string(), "123");
+    let s: Sequence<N> = no_src_no_result("sort((3, 1, 2), (), function($x) { 0 - $x })")?;
+    assert_eq!(s.to_string(), "321");
+    let s: Sequence<N> = no_src_no_result("apply(concat#2, ['a', 'b'])")?;
+    assert_eq!(s.to_string(), "ab");
+    // The predicate must return a boolean
+    assert!(no_src_no_result::<N>("filter((1, 2), function($x) { $x })").is_err());
+    Ok(())
+}
+
+pub fn generic_higher_order_map_array<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = ns_no_src_no_result(
+        "map:for-each(map{'a': 1, 'b': 2}, function($k, $v) { concat($k, $v) })",
+    )?;
+    assert_eq!(s.to_string(), "a1b2");
+    let s: Sequence<N> = ns_no_src_no_result("array:for-each([1, 2], function($x) { $x + 1 })?*")?;
+    assert_eq!(s.to_string(), "23");
+    let s: Sequence<N> =
+        ns_no_src_no_result("array:size(array:filter([1, 2, 3], function($x) { $x > 1 }))")?;
+    assert_eq!(s.to_string(), "2");
+    let s: Sequence<N> =
+        ns_no_src_no_result("array:fold-left([1, 2, 3], 0, function($a, $b) { $a + $b })")?;
+    assert_eq!(s.to_string(), "6");
+    let s: Sequence<N> = ns_no_src_no_result("array:sort([3, 1, 2])?*")?;
+    assert_eq!(s.to_string(), "123");
+    Ok(())
+}
+
+// Sequence types
+
+pub fn generic_instance_of_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = ns_no_src_no_result("(1, 2) instance of xs:decimal+")?;
+    assert_eq!(s.len(), 1);
+    assert_eq!(s.to_bool(), true);
+    Ok(())
+}
+pub fn generic_instance_of_2<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = ns_no_src_no_result("(1, 'two') instance of xs:integer*")?;
+    assert_eq!(s.len(), 1);
+    assert_eq!(s.to_bool(), false);
+    Ok(())
+}
+pub fn generic_instance_of_3<N: Node, G, H>(_: G, make_doc: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let mut stctxt = StaticContextBuilder::new()
+        .message(|_| Ok(()))
+        .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .build();
+    let s = ContextBuilder::new()
+        .context(vec![make_doc()])
+        .build()
+        .dispatch(
+            &mut stctxt,
+            &parse("(a, ()) instance of element()?", None, None)?,
+        )?;
+    assert_eq!(s.to_bool(), true);
+    Ok(())
+}
+pub fn generic_treat_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = ns_no_src_no_result("'a' treat as xs:string")?;
+    assert_eq!(s.to_string(), "a");
+    assert!(ns_no_src_no_result::<N>("'a' treat as xs:integer").is_err());
+    Ok(())
+}
+pub fn generic_cast_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = ns_no_src_no_result("('41' cast as xs:integer) + 1")?;
+    assert_eq!(s.to_string(), "42");
+    let s: Sequence<N> = ns_no_src_no_result("() cast as xs:integer?")?;
+    assert_eq!(s.len(), 0);
+    assert!(ns_no_src_no_result::<N>("'x' cast as xs:integer").is_err());
+    Ok(())
+}
+pub fn generic_durations_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    for (e, r) in [
+        ("xs:dayTimeDuration('PT36H')", "P1DT12H"),
+        ("xs:duration('P1Y14M')", "P2Y2M"),
+        ("xs:yearMonthDuration('P0Y')", "P0M"),
+        ("xs:dayTimeDuration('-PT0.50S')", "-PT0.5S"),
+        (
+            "xs:yearMonthDuration('P1Y') + xs:yearMonthDuration('P3M')",
+            "P1Y3M",
+        ),
+        ("xs:dayTimeDuration('P1D') * 1.5", "P1DT12H"),
+        ("2 * xs:dayTimeDuration('PT10M')", "PT20M"),
+        ("xs:yearMonthDuration('P1Y') div 2", "P6M"),
+        (
+            "xs:dayTimeDuration('P1D') div xs:dayTimeDuration('PT6H')",
+            "4",
+        ),
+        (
+            "xs:duration('P1Y2DT3H') cast as xs:yearMonthDuration",
+            "P1Y",
+        ),
+        ("years-from-duration(xs:yearMonthDuration('-P2Y3M'))", "-2"),
+        ("months-from-duration(xs:yearMonthDuration('-P2Y3M'))", "-3"),
+        ("days-from-duration(xs:dayTimeDuration('PT50H'))", "2"),
+        ("hours-from-duration(xs:dayTimeDuration('P1DT5H30M'))", "5"),
+        (
+            "minutes-from-duration(xs:dayTimeDuration('P1DT5H30M'))",
+            "30",
+        ),
+        (
+            "seconds-from-duration(xs:dayTimeDuration('PT1M2.5S'))",
+            "2.5",
+        ),
+        (
+            "xs:dayTimeDuration('PT24H') eq xs:dayTimeDuration('P1D')",
+            "true",
+        ),
+        (
+            "xs:yearMonthDuration('P1Y') gt xs:yearMonthDuration('P11M')",
+            "true",
+        ),
+        ("xs:duration('P1Y') eq xs:yearMonthDuration('P12M')", "true"),
+    ] {
+        let s: Sequence<N> = ns_no_src_no_result(e)?;
+        assert_eq!(s.to_string(), r, "{}", e);
+    }
+    assert!(ns_no_src_no_result::<N>("xs:dayTimeDuration('P1M')").is_err());
+    assert!(ns_no_src_no_result::<N>("xs:duration('P1Y') lt xs:duration('P2Y')").is_err());
+    assert!(ns_no_src_no_result::<N>("xs:duration('P1Y') + xs:duration('P2Y')").is_err());
+    assert!(ns_no_src_no_result::<N>("xs:yearMonthDuration('P1Y') div 0").is_err());
+    Ok(())
+}
+pub fn generic_date_arithmetic_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    for (e, r) in [
+        (
+            "xs:date('2024-01-31') + xs:yearMonthDuration('P1M')",
+            "2024-02-29",
+        ),
+        (
+            "xs:date('2024-03-31') - xs:yearMonthDuration('P1Y1M')",
+            "2023-02-28",
+        ),
+        (
+            "xs:dayTimeDuration('P2D') + xs:date('2024-02-28')",
+            "2024-03-01",
+        ),
+        (
+            "xs:date('2024-01-01') + xs:dayTimeDuration('-PT1H')",
+            "2023-12-31",
+        ),
+        ("xs:date('2024-03-01') - xs:date('2024-02-01')", "P29D"),
+        (
+            "days-from-duration(xs:date('2024-03-01') - xs:date('2000-03-01'))",
+            "8766",
+        ),
+        ("xs:date('2024-01-01') lt xs:date('2024-02-01')", "true"),
+        ("year-from-date(xs:date('2024-02-29'))", "2024"),
+        ("month-from-date(xs:date('2024-02-29'))", "2"),
+        ("day-from-date(xs:date('2024-02-29'))", "29"),
+        (
+            "hours-from-dateTime(xs:dateTime('2024-01-01T10:30:00'))",
+            "10",
+        ),
+        (
+            "seconds-from-dateTime(xs:dateTime('2024-01-01T10:30:05.25'))",
+            "5.25",
+        ),
+        (
+            "day-from-dateTime(xs:dateTime('2024-01-31T22:00:00') + xs:dayTimeDuration('PT3H'))",
+            "1",
+        ),
+        (
+            "month-from-dateTime(xs:dateTime('2024-01-31T22:00:00') + xs:yearMonthDuration('P1M'))",
+            "2",
+        ),
+        (
+            "xs:dateTime('2024-01-02T12:00:00') - xs:dateTime('2024-01-01T00:00:00')",
+            "P1DT12H",
+        ),
+        (
+            "minutes-from-time(xs:time('23:30:00') + xs:dayTimeDuration('PT45M'))",
+            "15",
+        ),
+        (
+            "hours-from-time(xs:time('23:30:00') + xs:dayTimeDuration('PT45M'))",
+            "0",
+        ),
+        (
+            "hours-from-dateTime(adjust-dateTime-to-timezone(xs:dateTime('2024-01-01T10:00:00')))",
+            "10",
+        ),
+        ("year-from-date(())", ""),
+    ] {
+        let s: Sequence<N> = ns_no_src_no_result(e)?;
+        assert_eq!(s.to_string(), r, "{}", e);
+    }
+    assert!(ns_no_src_no_result::<N>("xs:date('2024-01-01') + 1").is_err());
+    assert!(ns_no_src_no_result::<N>("year-from-date(xs:time('10:00:00'))").is_err());
+    assert!(
+        ns_no_src_no_result::<N>(
+            "adjust-dateTime-to-timezone(xs:dateTime('2024-01-01T10:00:00'), xs:dayTimeDuration('PT15H'))"
+        )
+        .is_err()
+    );
+    Ok(())
+}
+pub fn generic_timezones_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    for (e, r) in [
+        (
+            "xs:dateTime('2024-01-01T10:00:00+09:00')",
+            "2024-01-01T10:00:00+09:00",
+        ),
+        ("xs:time('10:00:00.50Z')", "10:00:00.5Z"),
+        ("xs:date('2024-01-01-05:00')", "2024-01-01-05:00"),
+        (
+            "xs:dateTime('2024-01-01T10:00:00+09:00') eq xs:dateTime('2024-01-01T01:00:00Z')",
+            "true",
+        ),
+        ("xs:time('10:00:00-05:00') gt xs:time('14:00:00Z')", "true"),
+        (
+            "xs:dateTime('2024-01-01T10:00:00+09:00') - xs:dateTime('2024-01-01T00:00:00Z')",
+            "PT1H",
+        ),
+        (
+            "xs:dateTime('2024-01-01T23:30:00-01:00') + xs:dayTimeDuration('PT1H')",
+            "2024-01-02T00:30:00-01:00",
+        ),
+        (
+            "hours-from-dateTime(xs:dateTime('2024-01-01T10:00:00+09:00'))",
+            "10",
+        ),
+        (
+            "timezone-from-dateTime(xs:dateTime('2024-01-01T10:00:00+09:00'))",
+            "PT9H",
+        ),
+        ("timezone-from-time(xs:time('10:00:00-05:30'))", "-PT5H30M"),
+        ("timezone-from-date(xs:date('2024-01-01'))", ""),
+        (
+            "adjust-dateTime-to-timezone(xs:dateTime('2024-01-01T10:00:00+09:00'), xs:dayTimeDuration('PT0S'))",
+            "2024-01-01T01:00:00Z",
+        ),
+        (
+            "adjust-dateTime-to-timezone(xs:dateTime('2024-01-01T10:00:00+09:00'), ())",
+            "2024-01-01T10:00:00",
+        ),
+        (
+            "adjust-dateTime-to-timezone(xs:dateTime('2024-01-01T10:00:00'), xs:dayTimeDuration('-PT5H'))",
+            "2024-01-01T10:00:00-05:00",
+        ),
+        (
+            "format-dateTime(xs:dateTime('2024-01-01T10:00:00+09:00'), '[H]:[m]')",
+            "10:00",
+        ),
+        // A value without a timezone is a different map key to a value with a timezone
+        (
+            "map:size(map{xs:dateTime('2024-01-01T10:00:00'):1, xs:dateTime('2024-01-01T10:00:00Z'):2})",
+            "2",
+        ),
+        (
+            "map:size(map{xs:time('10:00:00'):1, xs:time('10:00:00+00:00'):2})",
+            "2",
+        ),
+    ] {
+        let s: Sequence<N> = ns_no_src_no_result(e)?;
+        assert_eq!(s.to_string(), r, "{}", e);
+    }
+    assert!(ns_no_src_no_result::<N>("xs:dateTime('2024-01-01T10:00:00+15:00')").is_err());
+    assert!(
+        ns_no_src_no_result::<N>(
+            "map{xs:dateTime('2024-01-01T10:00:00+09:00'):1, xs:dateTime('2024-01-01T01:00:00Z'):2}"
+        )
+        .is_err()
+    );
+    Ok(())
+}
+pub fn generic_timezones_2<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    // Sorting untimezoned values uses the implicit timezone of the context, not the local timezone
+    let mut stctxt = StaticContextBuilder::new()
+        .message(|_| Ok(()))
+        .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .build();
+    let mut nsmap = NamespaceMap::new();
+    nsmap.push(
+        NamespaceDeclaration::new(
+            Some(NamespacePrefix::try_from("xs").unwrap()),
+            NamespaceUri::try_from("http://www.w3.org/2001/XMLSchema").unwrap(),
+        )
+        .expect("unable to add namespace"),
+    );
+    let e = parse(
+        "sort((xs:dateTime('2024-01-01T00:00:00Z'), xs:dateTime('2024-01-01T10:00:00'), xs:dateTime('2024-01-01T01:00:00+09:00')))",
+        None,
+        Some(nsmap),
+    )?;
+    for (tz, r) in [
+        (
+            14,
+            "2024-01-01T01:00:00+09:00 2024-01-01T10:00:00 2024-01-01T00:00:00Z",
+        ),
+        (
+            -12,
+            "2024-01-01T01:00:00+09:00 2024-01-01T00:00:00Z 2024-01-01T10:00:00",
+        ),
+    ] {
+        let s: Sequence<N> = ContextBuilder::new()
+            .implicit_timezone(chrono::FixedOffset::east_opt(tz * 3600).unwrap())
+            .build()
+            .dispatch(&mut stctxt, &e)?;
+        assert_eq!(
+            s.iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(" "),
+            r,
+            "implicit timezone {}",
+            tz
+        );
+    }
+    Ok(())
+}
+pub fn generic_gregorian_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    for (e, r) in [
+        ("xs:gYear('2019')", "2019"),
+        ("xs:gYearMonth('2019-05')", "2019-05"),
+        ("xs:gMonthDay('--05-01Z')", "--05-01Z"),
+        ("xs:gMonth('--05')", "--05"),
+        ("xs:gDay('---01+02:00')", "---01+02:00"),
+        ("xs:gYear('2019') instance of xs:gYear", "true"),
+        (
+            "xs:gYearMonth('2019-05') eq xs:gYearMonth('2019-05')",
+            "true",
+        ),
+        ("xs:gYear('2019Z') eq xs:gYear('2019+00:00')", "true"),
+        ("xs:gDay('---01Z') ne xs:gDay('---02Z')", "true"),
+        ("xs:gYear(xs:date('2019-05-01'))", "2019"),
+        (
+            "xs:gYearMonth(xs:dateTime('2019-05-01T10:00:00Z'))",
+            "2019-05Z",
+        ),
+        ("xs:gMonthDay(xs:date('2019-05-01'))", "--05-01"),
+        ("string(xs:gMonth(xs:date('2019-05-01')))", "--05"),
+        ("xs:gDay(xs:date('2019-05-01-01:00'))", "---01-01:00"),
+        (
+            "xs:dateTimeStamp('2019-05-01T10:00:00+09:00') eq xs:dateTime('2019-05-01T01:00:00Z')",
+            "true",
+        ),
+        (
+            "xs:dateTimeStamp('2019-05-01T10:00:00Z') instance of xs:dateTime",
+            "true",
+        ),
+        ("xs:gYear(())", ""),
+        (
+            "map:size(map{xs:gYear('2019'):1, xs:gYear('2019Z'):2})",
+            "2",
+        ),
+    ] {
+        let s: Sequence<N> = ns_no_src_no_result(e)?;
+        assert_eq!(s.to_string(), r, "{}", e);
+    }
+    assert!(ns_no_src_no_result::<N>("xs:gYearMonth('2019-13')").is_err());
+    assert!(ns_no_src_no_result::<N>("xs:gMonthDay('--02-30')").is_err());
+    assert!(ns_no_src_no_result::<N>("xs:dateTimeStamp('2019-05-01T10:00:00')").is_err());
+    assert!(ns_no_src_no_result::<N>("xs:gYear('2019') lt xs:gYear('2020')").is_err());
+    assert!(ns_no_src_no_result::<N>("xs:gYear(xs:gYearMonth('2019-05'))").is_err());
+    Ok(())
+}
+pub fn generic_unary_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = ns_no_src_no_result("-1")?;
+    assert_eq!(s.to_string(), "-1");
+    let s: Sequence<N> = ns_no_src_no_result("- -3")?;
+    assert_eq!(s.to_string(), "3");
+    let s: Sequence<N> = ns_no_src_no_result("+-2")?;
+    assert_eq!(s.to_string(), "-2");
+    let s: Sequence<N> = ns_no_src_no_result("-()")?;
+    assert_eq!(s.len(), 0);
+    assert!(ns_no_src_no_result::<N>("-'a'").is_err());
+    assert!(ns_no_src_no_result::<N>("-(1, 2)").is_err());
+    Ok(())
+}
+pub fn generic_unary_2<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    // The numeric type of the operand is preserved
+    let s: Sequence<N> = ns_no_src_no_result(
+        "(-1 instance of xs:integer, -('1.5' cast as xs:decimal) instance of xs:decimal, -1.5e0 instance of xs:double, -(2 + 3))",
+    )?;
+    assert_eq!(s.len(), 4);
+    assert_eq!(s[0].to_bool(), true);
+    assert_eq!(s[1].to_bool(), true);
+    assert_eq!(s[2].to_bool(), true);
+    assert_eq!(s[3].to_string(), "-5");
+    Ok(())
+}
+pub fn generic_unary_3<N: Node, G, H, J>(
+    make_empty_doc: G,
+    _: H,
+    make_from_str: J,
+) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+    J: Fn(&str) -> Result<N, Error>,
+{
+    let sd = make_from_str("<a><b>inf</b><c> 2 </c><d>INF</d></a>")?;
+    let mut stctxt = StaticContextBuilder::new()
+        .message(|_| Ok(()))
+        .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .build();
+    let mut eval = |e: &str| {
+        ContextBuilder::new()
+            .context(vec![Item::Node(sd.clone())])
+            .result_document(make_empty_doc())
+            .build()
+            .dispatch(&mut stctxt, &parse(e, None, None)?)
+    };
+    // Untyped operands are cast using the xs:double lexical form
+    assert_eq!(eval("-/a/c")?.to_string(), "-2");
+    assert_eq!(eval("/a/c + 1")?.to_string(), "3");
+    assert_eq!(eval("-/a/d")?[0].to_double(), f64::NEG_INFINITY);
+    assert!(eval("-/a/b").is_err());
+    assert!(eval("/a/b + 1").is_err());
+    assert!(eval("number('inf')")?[0].to_double().is_nan());
+    assert!(eval("number(/a/b)")?[0].to_double().is_nan());
+    assert_eq!(eval("number('-INF')")?[0].to_double(), f64::NEG_INFINITY);
+    Ok(())
+}
+pub fn generic_matches<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result(
+        r#"(matches('abracadabra', 'bra'), matches('abracadabra', '^a.*a$'), matches('abracadabra', '^bra'), matches('Abc', 'aBC', 'i'), matches('ab', '[a-z-[b]]+$'))"#,
+    )?;
+    assert_eq!(s.len(), 5);
+    assert_eq!(s[0].to_bool(), true);
+    assert_eq!(s[1].to_bool(), true);
+    assert_eq!(s[2].to_bool(), false);
+    assert_eq!(s[3].to_bool(), true);
+    assert_eq!(s[4].to_bool(), false);
+    assert!(no_src_no_result::<N>("matches('a', 'a', 'z')").is_err());
+    assert!(no_src_no_result::<N>("matches('a', '(a')").is_err());
+    Ok(())
+}
+pub fn generic_replace<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result("replace('abracadabra', 'bra', '*')")?;
+    assert_eq!(s.to_string(), "a*cada*");
+    let s: Sequence<N> = no_src_no_result("replace('abracadabra', 'a(.)', 'a$1$1')")?;
+    assert_eq!(s.to_string(), "abbraccaddabbra");
+    let s: Sequence<N> = no_src_no_result(r#"replace('darted', '^(.*?)d(.*)$', '$1c$2')"#)?;
+    assert_eq!(s.to_string(), "carted");
+    let s: Sequence<N> = no_src_no_result(r#"replace('a.b', '.', '\$', 'q')"#)?;
+    assert_eq!(s.to_string(), r"a\$b");
+    // A pattern that matches a zero-length string is an error
+    assert!(no_src_no_result::<N>("replace('abc', '.?', 'x')").is_err());
+    // An invalid replacement string is an error
+    assert!(no_src_no_result::<N>("replace('abc', 'b', '$')").is_err());
+    Ok(())
+}
+pub fn generic_tokenize<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result("tokenize(' red  green blue ')")?;
+    assert_eq!(s.len(), 3);
+    assert_eq!(s[1].to_string(), "green");
+    let s: Sequence<N> = no_src_no_result(r#"tokenize('1, 15, 24, 50', ',\s*')"#)?;
+    assert_eq!(s.len(), 4);
+    assert_eq!(s[3].to_string(), "50");
+    let s: Sequence<N> = no_src_no_result("tokenize(',a,,b', ',')")?;
+    assert_eq!(s.len(), 4);
+    assert_eq!(s[0].to_string(), "");
+    let s: Sequence<N> = no_src_no_result("tokenize('', ',')")?;
+    assert_eq!(s.len(), 0);
+    assert!(no_src_no_result::<N>("tokenize('abc', 'x*')").is_err());
+    Ok(())
+}
+pub fn generic_sequence_functions_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result("empty(())")?;
+    assert_eq!(s.to_bool(), true);
+    let s: Sequence<N> = no_src_no_result("exists(())")?;
+    assert_eq!(s.to_bool(), false);
+    let s: Sequence<N> = no_src_no_result("head(('a', 'b', 'c'))")?;
+    assert_eq!(s.to_string(), "a");
+    let s: Sequence<N> = no_src_no_result("tail(('a', 'b', 'c'))")?;
+    assert_eq!(s.to_string(), "bc");
+    let s: Sequence<N> = no_src_no_result("insert-before(('a', 'b', 'c'), 2, 'z')")?;
+    assert_eq!(s.to_string(), "azbc");
+    let s: Sequence<N> = no_src_no_result("insert-before(('a', 'b', 'c'), 0, 'z')")?;
+    assert_eq!(s.to_string(), "zabc");
+    let s: Sequence<N> = no_src_no_result("insert-before(('a', 'b', 'c'), 4, 'z')")?;
+    assert_eq!(s.to_string(), "abcz");
+    let s: Sequence<N> = no_src_no_result("remove(('a', 'b', 'c'), 2)")?;
+    assert_eq!(s.to_string(), "ac");
+    let s: Sequence<N> = no_src_no_result("remove(('a', 'b', 'c'), 6)")?;
+    assert_eq!(s.to_string(), "abc");
+    let s: Sequence<N> = no_src_no_result("reverse(('a', 'b', 'c'))")?;
+    assert_eq!(s.to_string(), "cba");
+    let s: Sequence<N> = no_src_no_result("subsequence((1, 2, 3, 4, 5), 3)")?;
+    assert_eq!(s.to_string(), "345");
+    let s: Sequence<N> = no_src_no_result("subsequence((1, 2, 3, 4, 5), 1.5, 2)")?;
+    assert_eq!(s.to_string(), "23");
+    let s: Sequence<N> = no_src_no_result("subsequence((1, 2, 3, 4, 5), -3, 5)")?;
+    assert_eq!(s.to_string(), "1");
+    let s: Sequence<N> = no_src_no_result("unordered((1, 2))")?;
+    assert_eq!(s.len(), 2);
+    let s: Sequence<N> = no_src_no_result("zero-or-one(())")?;
+    assert_eq!(s.len(), 0);
+    assert!(no_src_no_result::<N>("zero-or-one((1, 2))").is_err());
+    assert!(no_src_no_result::<N>("one-or-more(())").is_err());
+    let s: Sequence<N> = no_src_no_result("exactly-one('a')")?;
+    assert_eq!(s.to_string(), "a");
+    assert!(no_src_no_result::<N>("exactly-one(())").is_err());
+    Ok(())
+}
+pub fn generic_sequence_functions_2<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result("distinct-values((1, 2.0, 3, 2, 'a', 'a', '1'))")?;
+    assert_eq!(s.len(), 5);
+    assert_eq!(s[1].to_string(), "2");
+    let s: Sequence<N> =
+        no_src_no_result("count(distinct-values((number('NaN'), number('NaN'))))")?;
+    assert_eq!(s.to_string(), "1");
+    let s: Sequence<N> = no_src_no_result("index-of((10, 20, 30, 20), 20)")?;
+    assert_eq!(s.len(), 2);
+    assert_eq!(s[1].to_string(), "4");
+    let s: Sequence<N> = no_src_no_result("index-of(('a', 'b'), 1)")?;
+    assert_eq!(s.len(), 0);
+    let s: Sequence<N> = no_src_no_result("index-of((number('NaN')), number('NaN'))")?;
+    assert_eq!(s.len(), 0);
+    let s: Sequence<N> = no_src_no_result("deep-equal((1, 'a'), (1.0, 'a'))")?;
+    assert_eq!(s.to_bool(), true);
+    let s: Sequence<N> = no_src_no_result("deep-equal((1, 'a'), ('a', 1))")?;
+    assert_eq!(s.to_bool(), false);
+    let s: Sequence<N> = no_src_no_result("deep-equal([1, [2, 3]], [1, [2, 3]])")?;
+    assert_eq!(s.to_bool(), true);
+    let s: Sequence<N> = no_src_no_result("deep-equal(map{'a': 1, 'b': 2}, map{'b': 2, 'a': 1})")?;
+    assert_eq!(s.to_bool(), true);
+    let s: Sequence<N> = no_src_no_result("deep-equal(map{'a': 1}, map{'a': 2})")?;
+    assert_eq!(s.to_bool(), false);
+    Ok(())
+}
+pub fn generic_collations<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result(
+        "compare('Strasse', 'strasse', 'http://www.w3.org/2013/collation/UCA?lang=de;strength=secondary')",
+    )?;
+    assert_eq!(s.to_string(), "0");
+    let s: Sequence<N> = no_src_no_result(
+        "compare('abc', 'ABC', 'http://www.w3.org/2005/xpath-functions/collation/html-ascii-case-insensitive')",
+    )?;
+    assert_eq!(s.to_string(), "0");
+    let s: Sequence<N> = no_src_no_result(
+        "count(distinct-values(('a', 'A', 'b'), 'http://www.w3.org/2013/collation/UCA?strength=primary'))",
+    )?;
+    assert_eq!(s.to_string(), "2");
+    let s: Sequence<N> = no_src_no_result(
+        "string-join(sort(('Zebra', 'öl', 'apfel'), 'http://www.w3.org/2013/collation/UCA?lang=sv'), ' ')",
+    )?;
+    assert_eq!(s.to_string(), "apfel Zebra öl");
+    let s: Sequence<N> = no_src_no_result(
+        "string-join(sort(('Zebra', 'öl', 'apfel'), 'http://www.w3.org/2013/collation/UCA?lang=de'), ' ')",
+    )?;
+    assert_eq!(s.to_string(), "apfel öl Zebra");
+    assert!(
+        no_src_no_result::<N>("compare('a', 'b', 'http://example.org/no-such-collation')").is_err()
+    );
+    assert!(
+        no_src_no_result::<N>(
+            "contains('abc', 'B', 'http://www.w3.org/2013/collation/UCA?strength=primary')"
+        )
+        .is_err()
+    );
+    Ok(())
+}
+pub fn generic_string_functions_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result("upper-case('abCd0')")?;
+    assert_eq!(s.to_string(), "ABCD0");
+    let s: Sequence<N> = no_src_no_result("lower-case('ABc!D')")?;
+    assert_eq!(s.to_string(), "abc!d");
+    let s: Sequence<N> = no_src_no_result("string-join(('Now', 'is', 'the', 'time'), ' ')")?;
+    assert_eq!(s.to_string(), "Now is the time");
+    let s: Sequence<N> = no_src_no_result("string-join(1 to 3)")?;
+    assert_eq!(s.to_string(), "123");
+    let s: Sequence<N> = no_src_no_result("string-length('Harp not on that string')")?;
+    assert_eq!(s.to_string(), "23");
+    let s: Sequence<N> = no_src_no_result("string-length(())")?;
+    assert_eq!(s.to_string(), "0");
+    let s: Sequence<N> = no_src_no_result("string-to-codepoints('Thérèse')")?;
+    assert_eq!(s.len(), 7);
+    assert_eq!(s[2].to_string(), "233");
+    let s: Sequence<N> = no_src_no_result("codepoints-to-string((2309, 2358, 2378, 2325))")?;
+    assert_eq!(s.to_string(), "अशॊक");
+    assert!(no_src_no_result::<N>("codepoints-to-string(0)").is_err());
+    let s: Sequence<N> = no_src_no_result("compare('abc', 'abd')")?;
+    assert_eq!(s.to_string(), "-1");
+    let s: Sequence<N> = no_src_no_result("compare('abc', ())")?;
+    assert_eq!(s.len(), 0);
+    let s: Sequence<N> = no_src_no_result("codepoint-equal('abcd', 'abcd')")?;
+    assert_eq!(s.to_bool(), true);
+    let s: Sequence<N> = no_src_no_result(
+        "ends-with('tattoo', 'too', 'http://www.w3.org/2005/xpath-functions/collation/codepoint')",
+    )?;
+    assert_eq!(s.to_bool(), true);
+    assert!(
+        no_src_no_result::<N>("contains('abc', 'b', 'http://example.org/no-such-collation')")
+            .is_err()
+    );
+    Ok(())
+}
+pub fn generic_string_functions_2<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result("encode-for-uri('100% organic')")?;
+    assert_eq!(s.to_string(), "100%25%20organic");
+    let s: Sequence<N> = no_src_no_result("iri-to-uri('http://www.example.com/~bébé')")?;
+    assert_eq!(s.to_string(), "http://www.example.com/~b%C3%A9b%C3%A9");
+    let s: Sequence<N> = no_src_no_result(
+        "escape-html-uri('javascript:if (navigator.browserLanguage == \"fr\") window.open(\"http://www.example.com/~bébé\");')",
+    )?;
+    assert_eq!(
+        s.to_string(),
+        "javascript:if (navigator.browserLanguage == \"fr\") window.open(\"http://www.example.com/~b%C3%A9b%C3%A9\");"
+    );
+    let s: Sequence<N> = no_src_no_result("string-length(normalize-unicode('e\u{301}'))")?;
+    assert_eq!(s.to_string(), "1");
+    let s: Sequence<N> = no_src_no_result("string-length(normalize-unicode('\u{e9}', 'NFD'))")?;
+    assert_eq!(s.to_string(), "2");
+    assert!(no_src_no_result::<N>("normalize-unicode('abc', 'NFX')").is_err());
+    let s: Sequence<N> = no_src_no_result("contains-token('red green blue ', 'green')")?;
+    assert_eq!(s.to_bool(), true);
+    let s: Sequence<N> = no_src_no_result("contains-token(('red', 'green'), 'gre')")?;
+    assert_eq!(s.to_bool(), false);
+    Ok(())
+}
+pub fn generic_analyze_string<N: Node, G, H>(make_empty_doc: G, make_doc: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = dispatch_rig(
+        "count(analyze-string('a1b22', '[0-9]+')/*)",
+        &make_empty_doc,
+        &make_doc,
+    )?;
+    assert_eq!(s.to_string(), "4");
+    let s: Sequence<N> = dispatch_rig(
+        "analyze-string('a1b22', '[0-9]+')/*[local-name() = 'match'][2]",
+        &make_empty_doc,
+        &make_doc,
+    )?;
+    assert_eq!(s.to_string(), "22");
+    let s: Sequence<N> = dispatch_rig(
+        r#"string(analyze-string('on 2024-10-19', '(\d+)-((\d+)-(\d+))')/descendant::*[@nr = 3])"#,
+        &make_empty_doc,
+        &make_doc,
+    )?;
+    assert_eq!(s.to_string(), "10");
+    let s: Sequence<N> = dispatch_rig(
+        r#"string(analyze-string('on 2024-10-19', '(\d+)-((\d+)-(\d+))')/*[2]/*[2])"#,
+        &make_empty_doc,
+        &make_doc,
+    )?;
+    assert_eq!(s.to_string(), "10-19");
+    Ok(())
+}
+pub fn generic_numeric_promotion_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    for (e, r) in [
+        ("5 + 5", "10"),
+        ("(5 + 5) instance of xs:integer", "true"),
+        ("(7 idiv 2) instance of xs:integer", "true"),
+        ("7 idiv 2", "3"),
+        ("-7 idiv 2", "-3"),
+        ("7 mod -2", "1"),
+        ("-7 mod 2", "-1"),
+        ("(1 div 4) instance of xs:decimal", "true"),
+        ("1 div 4", "0.25"),
+        ("xs:decimal('1.10') + 1", "2.1"),
+        ("(xs:decimal('1.5') * 2) instance of xs:decimal", "true"),
+        ("(xs:float('1.5') + 1) instance of xs:float", "true"),
+        (
+            "(xs:float('1.5') + xs:decimal('1')) instance of xs:float",
+            "true",
+        ),
+        ("(xs:float('1.5') + 1e0) instance of xs:double", "true"),
+        ("(xs:decimal('1') + 1e0) instance of xs:double", "true"),
+        ("7.5e0 idiv 2", "3"),
+        ("(1e0 div 0) gt 1e308", "true"),
+        ("() + 1", ""),
+        ("9223372036854775806 + 1", "9223372036854775807"),
+        ("9223372036854775807 + 1", "9223372036854775808"),
+        ("9223372036854775807 * 2", "18446744073709551614"),
+        ("99999999999999999999 idiv 3", "33333333333333333333"),
+        ("-99999999999999999999 mod 7", "-1"),
+        ("(99999999999999999999 + 1) instance of xs:integer", "true"),
+        ("99999999999999999999 gt 9223372036854775807", "true"),
+        (
+            "xs:integer('123456789012345678901234567890')",
+            "123456789012345678901234567890",
+        ),
+        ("1.5 instance of xs:decimal", "true"),
+        ("1.50", "1.5"),
+        ("0.1 + 0.2", "0.3"),
+        ("(0.1 + 0.2) eq 0.3", "true"),
+        ("1.0 eq 1", "true"),
+        ("1 lt 1.5", "true"),
+        ("2.5 * 2", "5"),
+    ] {
+        let s: Sequence<N> = ns_no_src_no_result(e)?;
+        assert_eq!(s.to_string(), r, "{}", e);
+    }
+    for (e, c) in [
+        ("1 div 0", "FOAR0001"),
+        ("1 idiv 0", "FOAR0001"),
+        ("1 mod 0", "FOAR0001"),
+        ("xs:decimal('1') div xs:decimal('0')", "FOAR0001"),
+        ("1e0 idiv 0e0", "FOAR0001"),
+        (
+            "xs:decimal('79228162514264337593543950335') + 1",
+            "FOAR0002",
+        ),
+        ("number('NaN') idiv 1e0", "FOAR0002"),
+        ("'1' + 1", "XPTY0004"),
+    ] {
+        match ns_no_src_no_result::<N>(e) {
+            Err(err) => assert_eq!(
+                err.code.map(|q| q.local_name().to_string()).as_deref(),
+                Some(c),
+                "{}",
+                e
+            ),
+            Ok(s) => panic!("{} should fail, got \"{}\"", e, s.to_string()),
+        }
+    }
+    assert!(ns_no_src_no_result::<N>("(1, 2) + 1").is_err());
+    Ok(())
+}
+
+pub fn generic_binary_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    for (e, r) in [
+        ("xs:base64Binary('SGVsbG8=')", "SGVsbG8="),
+        ("xs:base64Binary('SGVs bG8=')", "SGVsbG8="),
+        ("xs:hexBinary('48656c6c6f')", "48656C6C6F"),
+        ("xs:hexBinary(xs:base64Binary('SGVsbG8='))", "48656C6C6F"),
+        ("xs:base64Binary(xs:hexBinary('48656c6c6f'))", "SGVsbG8="),
+        ("xs:hexBinary('0fa0') eq xs:hexBinary('0FA0')", "true"),
+        (
+            "xs:base64Binary('SGVsbG8=') ne xs:base64Binary('SGVsbA==')",
+            "true",
+        ),
+        (
+            "xs:base64Binary('SGVsbG8=') instance of xs:base64Binary",
+            "true",
+        ),
+        ("string(xs:hexBinary(''))", ""),
+        ("bin:length(xs:base64Binary('SGVsbG8='))", "5"),
+        ("bin:length(xs:hexBinary('0fa0'))", "2"),
+        ("bin:length('SGVsbG8=')", "5"),
+        ("bin:part(xs:base64Binary('SGVsbG8='), 1, 3)", "ZWxs"),
+        ("bin:part(xs:base64Binary('SGVsbG8='), 3)", "bG8="),
+        ("bin:part(xs:base64Binary('SGVsbG8='), 5)", ""),
+        ("bin:decode-string(xs:base64Binary('SGVsbG8='))", "Hello"),
+        (
+            "bin:decode-string(xs:base64Binary('SGVsbG8='), 'utf-8', 1, 3)",
+            "ell",
+        ),
+        ("bin:decode-string(xs:hexBinary('e282ac'))", "\u{20ac}"),
+        (
+            "bin:decode-string(xs:hexBinary('e9'), 'iso-8859-1')",
+            "\u{e9}",
+        ),
+        (
+            "bin:decode-string(xs:hexBinary('48e9'), 'Latin1')",
+            "H\u{e9}",
+        ),
+        ("bin:decode-string(xs:hexBinary('4869'), 'US-ASCII')", "Hi"),
+        (
+            "bin:decode-string(xs:hexBinary('48e282ac'), 'UTF-8', 1)",
+            "\u{20ac}",
+        ),
+        ("bin:decode-string(xs:hexBinary('4869'), (), 1, 1)", "i"),
+        ("bin:part(xs:hexBinary('0fa0'), 2, 0)", ""),
+        ("bin:length(())", ""),
+    ] {
+        let s: Sequence<N> = ns_no_src_no_result(e)?;
+        assert_eq!(s.to_string(), r, "{}", e);
+    }
+    for (e, c) in [
+        ("xs:base64Binary('SGVsbG8')", "FORG0001"),
+        ("xs:hexBinary('0fa')", "FORG0001"),
+        ("xs:hexBinary('0g')", "FORG0001"),
+        ("xs:base64Binary('SGVsbG8==')", "FORG0001"),
+        ("bin:length('SGVs*G8=')", "FORG0001"),
+        ("bin:part(xs:hexBinary('0fa0'), 3)", "index-out-of-range"),
+        (
+            "bin:decode-string(xs:hexBinary('48e282ac'), 'utf-8', 2)",
+            "conversion-error",
+        ),
+        (
+            "bin:decode-string(xs:hexBinary('4869'), 'us-ascii', 1, 2)",
+            "index-out-of-range",
+        ),
+        (
+            "bin:part(xs:base64Binary('SGVsbG8='), 4, 2)",
+            "index-out-of-range",
+        ),
+        (
+            "bin:part(xs:base64Binary('SGVsbG8='), -1)",
+            "index-out-of-range",
+        ),
+        (
+            "bin:part(xs:base64Binary('SGVsbG8='), 1, -1)",
+            "negative-size",
+        ),
+        ("bin:decode-string(xs:hexBinary('ff'))", "conversion-error"),
+        (
+            "bin:decode-string(xs:hexBinary('e9'), 'us-ascii')",
+            "conversion-error",
+        ),
+        (
+            "bin:decode-string(xs:hexBinary('41'), 'ebcdic')",
+            "unknown-encoding",
+        ),
+    ] {
+        match ns_no_src_no_result::<N>(e) {
+            Err(err) => assert_eq!(
+                err.code.map(|q| q.local_name().to_string()).as_deref(),
+                Some(c),
+                "{}",
+                e
+            ),
+            Ok(s) => panic!("{} should fail, got \"{}\"", e, s.to_string()),
+        }
+    }
+    assert!(
+        ns_no_src_no_result::<N>("xs:base64Binary('SGVsbG8=') eq xs:hexBinary('48656c6c6f')")
+            .is_err()
+    );
+    Ok(())
+}
+
+pub fn generic_castable_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = ns_no_src_no_result(
+        "('12' castable as xs:integer, 'x' castable as xs:integer, 300 castable as xs:byte)",
+    )?;
+    assert_eq!(s.len(), 3);
+    assert_eq!(s[0].to_bool(), true);
+    assert_eq!(s[1].to_bool(), false);
+    assert_eq!(s[2].to_bool(), false);
+    Ok(())
+}
+
+// Conditionals
+
+pub fn generic_if_1<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result("if (1) then 'one' else 'not one'")?;
+    assert_eq!(s.len(), 1);
+    assert_eq!(s.to_string(), "one");
+    Ok(())
+}
+pub fn generic_if_2<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result("if (0) then 'one' else 'not one'")?;
+    assert_eq!(s.len(), 1);
+    assert_eq!(s.to_string(), "not one");
+    Ok(())
+}
+pub fn generic_issue_95<N: Node, G, H>(make_empty_doc: G, make_doc: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let result: Sequence<N> = dispatch_rig("@*|node()", make_empty_doc, make_doc)?;
+    if result.len() == 1 {
+        match &result[0] {
+            Item::Node(n) => match (
+                n.node_type(),
+                n.name().map_or("".to_string(), |m| m.to_string()).deref(),
+            ) {
+                (NodeType::Element, "a") => Ok(()),
+                (NodeType::Element, _) => Err(Error::new(
+                    ErrorKind::Unknown,
+                    format!(
+                        "got element named \"{}\", expected \"a\"",
+                        result[0].name().map_or("".to_string(), |m| m.to_string())
+                    ),
+                )),
+                _ => Err(Error::new(ErrorKind::Unknown, "not an element type node")),
+            },
+            _ => Err(Error::new(ErrorKind::Unknown, "not a node")),
+        }
+    } else {
+        Err(Error::new(
+            ErrorKind::Unknown,
+            format!("got {} results, expected 0", result.len()),
+        ))
+    }
+}
+
+pub fn generic_navigate_predicate_1<N: Node, G, H>(
+    make_empty_doc: G,
+    make_doc: H,
+) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let rd = make_empty_doc();
+    let sd = make_doc();
+    if let Item::Node(d) = sd {
+        let xform = parse("../*[@id eq 'b6']", None, None).expect("parsing failed");
+        let mut stctxt = StaticContextBuilder::new()
+            .message(|_| Ok(()))
+            .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+            .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+            .build();
+        let s = ContextBuilder::new()
+            .context(vec![Item::Node(
+                d.first_child().unwrap().first_child().unwrap(),
+            )])
+            .result_document(rd)
+            .build()
+            .dispatch(&mut stctxt, &xform)
+            .expect("transform failed");
+        assert_eq!(s.len(), 1);
+        assert_eq!(s[0].name().map_or("".to_string(), |m| m.to_string()), "b");
+        Ok(())
+    } else {
+        panic!("unable to unpack node")
+    }
+}
+
+pub fn generic_predicate_1<N: Node, G, H>(make_empty_doc: G, make_doc: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let rd = make_empty_doc();
+    let sd = make_doc();
+    if let Item::Node(d) = sd.clone() {
+        let xform = parse("$v[position() eq 1]", None, None).expect("parsing failed");
+        let mut stctxt = StaticContextBuilder::new()
+            .message(|_| Ok(()))
+            .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+            .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+            .build();
+        let mut ctxt = ContextBuilder::new()
+            .context(vec![sd])
+            .result_document(rd)
+            .build();
+        let a = d.first_child().unwrap();
+        let bs = a.child_iter().map(|c| Item::Node(c)).collect();
+        ctxt.var_push(String::from("v"), bs);
+        let s = ctxt
+            .dispatch(&mut stctxt, &xform)
+            .expect("transform failed");
+        assert_eq!(s.len(), 1);
+        assert_eq!(s[0].name().unwrap().to_string(), "b");
+        if let Item::Node(r) = &s[0] {
+            assert_eq!(
+                r.get_attribute(&QName::from_local_name(NcName::try_from("id").unwrap()))
+                    .to_string(),
+                "b1"
+            );
+        } else {
+            panic!("result is not a node")
+        }
+        Ok(())
+    } else {
+        panic!("unable to unpack node")
+    }
+}
+
+pub fn issue138_1<N: Node, G, H>(make_empty_doc: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let mut sd = make_empty_doc();
+    //let rd = make_empty_doc();
+    let mut top = sd
+        .new_element(QName::from_local_name(NcName::try_from("root").unwrap()))
+        .expect("unable to create root element");
+    sd.push(top.clone()).expect("unable to add root element");
+    let e_name = QName::from_local_name(NcName::try_from("element").unwrap());
+    let at_name = QName::from_local_name(NcName::try_from("attr").unwrap());
+    // <element attr="val1">text1</element>
+    let mut e1 = sd
+        .new_element(e_name.clone())
+        .expect("unable to create element 1");
+    let a1 = sd
+        .new_attribute(at_name.clone(), Rc::new(Value::from("val1")))
+        .expect("unable to create attribute 1");
+    e1.add_attribute(a1).expect("unable to add attribute 1");
+    let t1 = sd
+        .new_text(Rc::new(Value::from("text1")))
+        .expect("unable to create text 1");
+    e1.push(t1).expect("unable to add text 1");
+    top.push(e1).expect("unable to add element 1");
+    // <element attr="val2">text2</element>
+    let mut e2 = sd
+        .new_element(e_name.clone())
+        .expect("unable to create element 2");
+    let a2 = sd
+        .new_attribute(at_name.clone(), Rc::new(Value::from("val2")))
+        .expect("unable to create attribute 2");
+    e2.add_attribute(a2).expect("unable to add attribute 2");
+    let t2 = sd
+        .new_text(Rc::new(Value::from("text2")))
+        .expect("unable to create text 2");
+    e2.push(t2).expect("unable to add text 2");
+    top.push(e2).expect("unable to add element 2");
+
+    let xform = parse("/root/element[position() = 1]", None, None).expect("parsing failed");
+    let mut stctxt = StaticContextBuilder::new()
+        .message(|_| Ok(()))
+        .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .build();
+    let s = ContextBuilder::new()
+        .context(vec![Item::Node(sd.clone())])
+        //        .result_document(rd)
+        .result_document(sd)
+        .build()
+        .dispatch(&mut stctxt, &xform)
+        .expect("transform failed");
+    assert_eq!(s.len(), 1);
+    assert_eq!(s.to_xml(), "<element attr='val1'>text1</element>");
+    Ok(())
+}
+
+pub fn issue138_2<N: Node, G, H>(make_empty_doc: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let rd = make_empty_doc();
+    let mut sd = make_empty_doc();
+    let mut top = sd
+        .new_element(QName::from_local_name(NcName::try_from("root").unwrap()))
+        .expect("unable to create root element");
+    sd.push(top.clone()).expect("unable to add root element");
+    let e_name = QName::from_local_name(NcName::try_from("element").unwrap());
+    let at_name = QName::from_local_name(NcName::try_from("attr").unwrap());
+    // <element attr="val1">text1</element>
+    let mut e1 = sd
+        .new_element(e_name.clone())
+        .expect("unable to create element 1");
+    let a1 = sd
+        .new_attribute(at_name.clone(), Rc::new(Value::from("val1")))
+        .expect("unable to create attribute 1");
+    e1.add_attribute(a1).expect("unable to add attribute 1");
+    let t1 = sd
+        .new_text(Rc::new(Value::from("text1")))
+        .expect("unable to create text 1");
+    e1.push(t1).expect("unable to add text 1");
+    top.push(e1).expect("unable to add element 1");
+    // <element attr="val2">text2</element>
+    let mut e2 = sd
+        .new_element(e_name.clone())
+        .expect("unable to create element 2");
+    let a2 = sd
+        .new_attribute(at_name.clone(), Rc::new(Value::from("val2")))
+        .expect("unable to create attribute 2");
+    e2.add_attribute(a2).expect("unable to add attribute 2");
+    let t2 = sd
+        .new_text(Rc::new(Value::from("text2")))
+        .expect("unable to create text 2");
+    e2.push(t2).expect("unable to add text 2");
+    top.push(e2).expect("unable to add element 2");
+
+    let xform = parse("/root/element[position() = 2]", None, None).expect("parsing failed");
+    let mut stctxt = StaticContextBuilder::new()
+        .message(|_| Ok(()))
+        .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .build();
+    let s = ContextBuilder::new()
+        .context(vec![Item::Node(sd)])
+        .result_document(rd)
+        .build()
+        .dispatch(&mut stctxt, &xform)
+        .expect("transform failed");
+    assert_eq!(s.len(), 1);
+    assert_eq!(s.to_xml(), "<element attr='val2'>text2</element>");
+    Ok(())
+}
+
+// System properties
+
+pub fn generic_sys_prop_vers_qual<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> =
+        no_src_no_result("system-property('Q{http://www.w3.org/1999/XSL/Transform}version')")?;
+    assert_eq!(s.len(), 1);
+    assert_eq!(s.to_string(), "0.9");
+    Ok(())
+}
+pub fn generic_sys_prop_product_vers<N: Node, G, H>(_: G, _: H) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+{
+    let s: Sequence<N> = no_src_no_result(
+        "system-property('Q{http://www.w3.org/1999/XSL/Transform}product-version')",
+    )?;
+    assert_eq!(s.len(), 1);
+    assert_eq!(
+        s.to_string(),
+        format!(
+            "{}.{}.{}",
+            pkg_version_major!(),
+            pkg_version_minor!(),
+            pkg_version_patch!()
+        )
+    );
+    Ok(())
+}
+
+pub fn generic_document_1<N: Node, G, H, J>(
+    make_empty_doc: G,
+    make_doc: H,
+    make_from_str: J,
+) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+    J: Fn(&str) -> Result<N, Error>,
 {
     let mut msgs: Vec<String> = vec![];
     let mut stctxt = StaticContextBuilder::new()
@@ -1957,6 +3202,412 @@ where
     Ok(())
 }
 
+pub fn generic_namespace_axis_1<N: Node, G, H, J>(
+    make_empty_doc: G,
+    _: H,
+    make_from_str: J,
+) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+    J: Fn(&str) -> Result<N, Error>,
+{
+    let sd = make_from_str("<Test xmlns:a='urn:a'><Level1 xmlns:b='urn:b'>one</Level1></Test>")?;
+    let mut stctxt = StaticContextBuilder::new()
+        .message(|_| Ok(()))
+        .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .build();
+    let mut eval = |e: &str| {
+        ContextBuilder::new()
+            .context(vec![Item::Node(sd.clone())])
+            .result_document(make_empty_doc())
+            .build()
+            .dispatch(&mut stctxt, &parse(e, None, None)?)
+    };
+    // The "xml" namespace is always in scope
+    assert_eq!(eval("count(/Test/Level1/namespace::*)")?.to_string(), "3");
+    assert_eq!(eval("count(/Test/namespace::*)")?.to_string(), "2");
+    assert_eq!(
+        eval("count(/Test/Level1/namespace::namespace-node())")?.to_string(),
+        "3"
+    );
+    assert_eq!(eval("name(/Test/Level1/namespace::a)")?.to_string(), "a");
+    assert_eq!(
+        eval("string(/Test/Level1/namespace::b)")?.to_string(),
+        "urn:b"
+    );
+    // Only elements have namespace nodes
+    assert_eq!(
+        eval("count(/Test/Level1/text()/namespace::*)")?.to_string(),
+        "0"
+    );
+    Ok(())
+}
+
+pub fn generic_qname_1<N: Node, G, H, J>(
+    make_empty_doc: G,
+    _: H,
+    make_from_str: J,
+) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+    J: Fn(&str) -> Result<N, Error>,
+{
+    let sd = make_from_str(
+        "<t:Test xmlns:t='urn:t' xmlns='urn:d'><Level1 t:att='x'>one</Level1></t:Test>",
+    )?;
+    let mut stctxt = StaticContextBuilder::new()
+        .message(|_| Ok(()))
+        .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .build();
+    let mut eval = |e: &str| {
+        ContextBuilder::new()
+            .context(vec![Item::Node(sd.clone())])
+            .result_document(make_empty_doc())
+            .build()
+            .dispatch(&mut stctxt, &parse(e, None, None)?)
+    };
+    for (e, r) in [
+        ("local-name-from-QName(QName('urn:a', 'a:b'))", "b"),
+        ("prefix-from-QName(QName('urn:a', 'a:b'))", "a"),
+        ("namespace-uri-from-QName(QName('urn:a', 'a:b'))", "urn:a"),
+        ("string(QName('urn:a', 'a:b'))", "a:b"),
+        ("string(QName('urn:a', 'b'))", "Q{urn:a}b"),
+        ("string(QName('', 'b'))", "b"),
+        ("QName('urn:a', 'a:b') eq QName('urn:a', 'c:b')", "true"),
+        ("QName('', 'b') eq QName((), 'b')", "true"),
+        ("QName('urn:a', 'b') eq QName('urn:c', 'b')", "false"),
+        ("prefix-from-QName(QName('', 'b'))", ""),
+        ("namespace-uri-from-QName(QName('', 'b'))", ""),
+        (
+            "count(distinct-values((QName('urn:a', 'a:b'), QName('urn:a', 'c:b'))))",
+            "1",
+        ),
+        (
+            "namespace-uri-from-QName(resolve-QName('t:x', /*))",
+            "urn:t",
+        ),
+        ("namespace-uri-from-QName(resolve-QName('x', /*))", "urn:d"),
+        ("prefix-from-QName(resolve-QName('t:x', /*))", "t"),
+        ("resolve-QName((), /*)", ""),
+        ("namespace-uri-for-prefix('t', /*/*)", "urn:t"),
+        ("namespace-uri-for-prefix('', /*)", "urn:d"),
+        (
+            "namespace-uri-for-prefix('xml', /*)",
+            "http://www.w3.org/XML/1998/namespace",
+        ),
+        ("namespace-uri-for-prefix('z', /*)", ""),
+        ("string-join(sort(in-scope-prefixes(/*)), ',')", ",t,xml"),
+        ("string(node-name(/*))", "t:Test"),
+        ("string(node-name(/*/*/@*))", "t:att"),
+        ("local-name-from-QName(node-name(/*/*))", "Level1"),
+        ("namespace-uri-from-QName(node-name(/*/*))", "urn:d"),
+        ("node-name(/*/*/text())", ""),
+        ("node-name(())", ""),
+        ("namespace-uri(/*/*)", "urn:d"),
+        ("namespace-uri(/*/*/@*)", "urn:t"),
+        ("namespace-uri(/*/*/text())", ""),
+        ("namespace-uri()", ""),
+        ("local-name(/*)", "Test"),
+        ("local-name(/*/*/@*)", "att"),
+        ("local-name(())", ""),
+        ("name(/*)", "t:Test"),
+        ("name(/*/*)", "Level1"),
+        ("name(/*/*/@*)", "t:att"),
+        ("name(/*/*/text())", ""),
+    ] {
+        assert_eq!(eval(e)?.to_string(), r, "{}", e);
+    }
+    for (e, c) in [
+        ("QName('', 'a:b')", "FOCA0002"),
+        ("QName('urn:a', '1b')", "FOCA0002"),
+        ("resolve-QName('z:x', /*)", "FONS0004"),
+        ("resolve-QName('t:x', /*/*/text())", "XPTY0004"),
+        ("prefix-from-QName('a:b')", "XPTY0004"),
+        ("node-name((/*, /*/*))", "XPTY0004"),
+        ("local-name('a')", "XPTY0004"),
+        ("name((/*, /*/*))", "XPTY0004"),
+    ] {
+        match eval(e) {
+            Err(err) => assert_eq!(
+                err.code.map(|q| q.local_name().to_string()).as_deref(),
+                Some(c),
+                "{}",
+                e
+            ),
+            Ok(s) => panic!("{} should fail, got \"{}\"", e, s.to_string()),
+        }
+    }
+    Ok(())
+}
+pub fn generic_json_1<N: Node, G, H, J>(
+    make_empty_doc: G,
+    _: H,
+    make_from_str: J,
+) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+    J: Fn(&str) -> Result<N, Error>,
+{
+    let sd = make_from_str(
+        "<doc><map xmlns='http://www.w3.org/2005/xpath-functions'><number key='n'>1.5</number><string key='s'>a\"b</string><null key='z'/></map><boolean xmlns='http://www.w3.org/2005/xpath-functions'>maybe</boolean><other/></doc>",
+    )?;
+    let mut stctxt = StaticContextBuilder::new()
+        .message(|_| Ok(()))
+        .fetcher(|u| match u.as_str() {
+            "http://example.org/a.json" => Ok(String::from("{\"b\": [2, 3]}")),
+            "http://example.org/data/c.json" => Ok(String::from("{\"c\": 1, \"c\": 2}")),
+            "http://example.org/data/bad.json" => Ok(String::from("{\"c\": ")),
+            _ => Err(Error::new(ErrorKind::Unknown, "not found")),
+        })
+        .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .build();
+    let mut eval = |e: &str, base: Option<&str>| {
+        let mut b = ContextBuilder::new()
+            .context(vec![Item::Node(sd.clone())])
+            .result_document(make_empty_doc());
+        if let Some(u) = base {
+            b = b.base_url(url::Url::parse(u).unwrap())
+        }
+        b.build().dispatch(&mut stctxt, &parse(e, None, None)?)
+    };
+    for (e, r) in [
+        ("parse-json('{\"a\": [1, true, null, \"x\"]}')?a?1", "1"),
+        ("parse-json('{\"a\": [1, true, null, \"x\"]}')?a?2", "true"),
+        (
+            "count(parse-json('{\"a\": [1, true, null, \"x\"]}')?a?3)",
+            "0",
+        ),
+        ("parse-json('{\"a\": [1, true, null, \"x\"]}')?a?4", "x"),
+        ("parse-json('1.5e1')", "15"),
+        ("parse-json(' -0.5 ')", "-0.5"),
+        (
+            "parse-json('\"a\\u0041\\n\"') eq concat('aA', codepoints-to-string(10))",
+            "true",
+        ),
+        ("parse-json('\"\\ud83d\\ude00\"')", "\u{1F600}"),
+        ("parse-json('{\"a\": 1, \"a\": 2}')?a", "1"),
+        (
+            "parse-json('{\"a\": 1, \"a\": 2}', map{'duplicates': 'use-last'})?a",
+            "2",
+        ),
+        ("parse-json('[1, 2, ]', map{'liberal': true()})?2", "2"),
+        ("parse-json('\"\\u0000\"')", "\u{FFFD}"),
+        (
+            "parse-json('\"\\u0000\"', map{'escape': true()})",
+            "\\u0000",
+        ),
+        ("parse-json('\"a\\\\b\"', map{'escape': true()})", "a\\\\b"),
+        ("parse-json('\"a\\\\b\"')", "a\\b"),
+        ("parse-json(())", ""),
+        ("json-doc('http://example.org/a.json')?b?2", "3"),
+        ("local-name(json-to-xml('[1]')/*)", "array"),
+        (
+            "namespace-uri(json-to-xml('[1]')/*)",
+            "http://www.w3.org/2005/xpath-functions",
+        ),
+        ("string(json-to-xml('{\"a\": 1}')/*/*/@key)", "a"),
+        ("string(json-to-xml('{\"a\": 1}')/*/*)", "1"),
+        (
+            "string(json-to-xml('[\"\\u0000\"]', map{'escape': true()})/*/*/@escaped)",
+            "true",
+        ),
+        ("count(json-to-xml('{\"a\": 1, \"a\": 2}')/*/*)", "2"),
+        (
+            "xml-to-json(json-to-xml('{\"a\": [1, \"x\", null, true], \"b\": {}}'))",
+            "{\"a\":[1,\"x\",null,true],\"b\":{}}",
+        ),
+        (
+            "xml-to-json(json-to-xml('[\"a\\\\b\"]', map{'escape': true()}))",
+            "[\"a\\\\b\"]",
+        ),
+        ("xml-to-json(json-to-xml('[\"a\\\\b\"]'))", "[\"a\\\\b\"]"),
+        (
+            "xml-to-json(/*/*[1])",
+            "{\"n\":1.5,\"s\":\"a\\\"b\",\"z\":null}",
+        ),
+        ("xml-to-json(())", ""),
+        (
+            "xml-to-json(json-to-xml('[-1.5e2, \"x\", [], {\"k\": {\"l\": false}}]'))",
+            "[-150,\"x\",[],{\"k\":{\"l\":false}}]",
+        ),
+        (
+            "xml-to-json(json-to-xml(xml-to-json(/*/*[1])))",
+            "{\"n\":1.5,\"s\":\"a\\\"b\",\"z\":null}",
+        ),
+        (
+            "string(json-to-xml(xml-to-json(/*/*[1]))/*/*[@key = 's'])",
+            "a\"b",
+        ),
+    ] {
+        assert_eq!(eval(e, None)?.to_string(), r, "{}", e);
+    }
+    // Relative URIs are resolved against the base URI
+    for (e, r) in [
+        ("json-doc('../a.json')?b?2", "3"),
+        ("json-doc('c.json')?c", "1"),
+        ("json-doc('c.json', map{'duplicates': 'use-last'})?c", "2"),
+        ("json-doc('http://example.org/a.json')?b?1", "2"),
+    ] {
+        assert_eq!(
+            eval(e, Some("http://example.org/data/index.xml"))?.to_string(),
+            r,
+            "{}",
+            e
+        );
+    }
+    for (e, c) in [
+        ("parse-json('{')", "FOJS0001"),
+        ("parse-json('[1, ]')", "FOJS0001"),
+        ("parse-json('01')", "FOJS0001"),
+        ("parse-json('[1] 2')", "FOJS0001"),
+        ("parse-json('\"\\x\"')", "FOJS0001"),
+        (
+            "parse-json('{\"a\": 1, \"a\": 2}', map{'duplicates': 'reject'})",
+            "FOJS0003",
+        ),
+        ("parse-json('1', map{'duplicates': 'retain'})", "FOJS0005"),
+        ("parse-json('1', map{'liberal': 'yes'})", "XPTY0004"),
+        (
+            "json-to-xml('{\"a\": 1, \"a\": 2}', map{'duplicates': 'reject'})",
+            "FOJS0003",
+        ),
+        ("json-doc('http://example.org/b.json')", "FOUT1170"),
+        ("json-doc('a.json')", "FOUT1170"),
+        ("json-doc('http://example.org/data/bad.json')", "FOJS0001"),
+        (
+            "json-doc('http://example.org/data/c.json', map{'duplicates': 'reject'})",
+            "FOJS0003",
+        ),
+        (
+            "json-doc('http://example.org/data/c.json', map{'duplicates': 'first'})",
+            "FOJS0005",
+        ),
+        ("json-to-xml('[1, ]')", "FOJS0001"),
+        ("json-to-xml('{\"a\" 1}')", "FOJS0001"),
+        (
+            "json-to-xml('1', map{'duplicates': 'use-last'})",
+            "FOJS0005",
+        ),
+        (
+            "json-to-xml('{\"a\": {\"b\": 1, \"b\": 1}}', map{'duplicates': 'reject'})",
+            "FOJS0003",
+        ),
+        ("xml-to-json(/*/*[2])", "FOJS0006"),
+        ("xml-to-json(/*/*[3])", "FOJS0006"),
+        ("xml-to-json(/*/*[1]/@*)", "FOJS0006"),
+    ] {
+        match eval(e, None) {
+            Err(err) => assert_eq!(
+                err.code.map(|q| q.local_name().to_string()).as_deref(),
+                Some(c),
+                "{}",
+                e
+            ),
+            Ok(s) => panic!("{} should fail, got \"{}\"", e, s.to_string()),
+        }
+    }
+    Ok(())
+}
+
+pub fn generic_intersect_except<N: Node, G, H, J>(
+    make_empty_doc: G,
+    _: H,
+    make_from_str: J,
+) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+    J: Fn(&str) -> Result<N, Error>,
+{
+    let sd = make_from_str(
+        "<Test><Level1>one</Level1><Level1>two</Level1><Level2>three</Level2></Test>",
+    )?;
+    let mut stctxt = StaticContextBuilder::new()
+        .message(|_| Ok(()))
+        .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .build();
+    let mut eval = |e: &str| {
+        ContextBuilder::new()
+            .context(vec![Item::Node(sd.clone())])
+            .result_document(make_empty_doc())
+            .build()
+            .dispatch(&mut stctxt, &parse(e, None, None)?)
+    };
+    assert_eq!(
+        eval("count(/Test/* intersect /Test/Level1)")?.to_string(),
+        "2"
+    );
+    assert_eq!(eval("count(/Test/* except /Test/Level1)")?.to_string(), "1");
+    assert_eq!(
+        eval("string(/Test/* except /Test/Level1)")?.to_string(),
+        "three"
+    );
+    // Results are in document order, with duplicates removed
+    assert_eq!(
+        eval("(/Test/Level2, /Test/Level1, /Test/Level1) intersect /Test/*")?.to_string(),
+        "onetwothree"
+    );
+    // Left-associative
+    assert_eq!(
+        eval("count(/Test/* except /Test/Level2 intersect /Test/Level1[1])")?.to_string(),
+        "1"
+    );
+    // Operands must be nodes
+    assert!(eval("(1, 2) intersect /Test/*").is_err());
+    Ok(())
+}
+
+pub fn generic_node_comparison<N: Node, G, H, J>(
+    make_empty_doc: G,
+    _: H,
+    make_from_str: J,
+) -> Result<(), Error>
+where
+    G: Fn() -> N,
+    H: Fn() -> Item<N>,
+    J: Fn(&str) -> Result<N, Error>,
+{
+    let sd = make_from_str("<Test><Level1>one</Level1><Level1>two</Level1></Test>")?;
+    let mut stctxt = StaticContextBuilder::new()
+        .message(|_| Ok(()))
+        .fetcher(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .parser(|_| Err(Error::new(ErrorKind::NotImplemented, "not implemented")))
+        .build();
+    let mut eval = |e: &str| {
+        ContextBuilder::new()
+            .context(vec![Item::Node(sd.clone())])
+            .result_document(make_empty_doc())
+            .build()
+            .dispatch(&mut stctxt, &parse(e, None, None)?)
+    };
+    assert_eq!(eval("/Test/Level1[1] is /Test/*[1]")?.to_string(), "true");
+    assert_eq!(
+        eval("/Test/Level1[1] is /Test/Level1[2]")?.to_string(),
+        "false"
+    );
+    assert_eq!(
+        eval("/Test/Level1[1] << /Test/Level1[2]")?.to_string(),
+        "true"
+    );
+    assert_eq!(
+        eval("/Test/Level1[1] >> /Test/Level1[2]")?.to_string(),
+        "false"
+    );
+    assert_eq!(eval("/Test >> /Test/Level1[2]")?.to_string(), "false");
+    // An empty operand gives an empty result
+    assert_eq!(eval("count(/Test/Level2 is /Test)")?.to_string(), "0");
+    // Operands must be single nodes
+    assert!(eval("/Test/Level1 is /Test").is_err());
+    assert!(eval("1 is /Test").is_err());
+    Ok(())
+}
+
 // Keys
 
 pub fn generic_key_1<N: Node, G, H>(make_empty_doc: G, _: H) -> Result<(), Error>
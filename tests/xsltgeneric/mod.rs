@@ -457,6 +457,65 @@ where
     }
 }
 
+pub fn generic_sort_collation<N: Node, G, H, J>(
+    parse_from_str: G,
+    parse_from_str_with_ns: J,
+    make_doc: H,
+) -> Result<(), Error>
+where
+    G: Fn(&str) -> Result<N, Error>,
+    H: Fn() -> Result<N, Error>,
+    J: Fn(&str) -> Result<(N, Option<NamespaceMap>), Error>,
+{
+    let result = test_rig(
+        "<Test><w>Zucker</w><w>Öl</w><w>Apfel</w><w>Äpfel</w><w>apfel</w><w>Ofen</w></Test>",
+        r#"<xsl:stylesheet xmlns:xsl='http://www.w3.org/1999/XSL/Transform'>
+  <xsl:template match='/'><xsl:apply-templates select='Test'/></xsl:template>
+  <xsl:template match='Test'>
+    <de><xsl:for-each select='w'><xsl:sort select='.' lang='de'/><xsl:value-of select='concat(., " ")'/></xsl:for-each></de>
+    <sv><xsl:for-each select='w'><xsl:sort select='.' collation='http://www.w3.org/2013/collation/UCA?lang=sv'/><xsl:value-of select='concat(., " ")'/></xsl:for-each></sv>
+    <cp><xsl:for-each select='w'><xsl:sort select='.'/><xsl:value-of select='concat(., " ")'/></xsl:for-each></cp>
+  </xsl:template>
+</xsl:stylesheet>"#,
+        parse_from_str,
+        parse_from_str_with_ns,
+        make_doc,
+    )?;
+    assert_eq!(
+        result.0.to_xml(),
+        "<de>apfel Apfel Äpfel Ofen Öl Zucker </de><sv>apfel Apfel Ofen Zucker Äpfel Öl </sv><cp>Apfel Ofen Zucker apfel Äpfel Öl </cp>"
+    );
+    Ok(())
+}
+
+pub fn generic_group_key_collation<N: Node, G, H, J>(
+    parse_from_str: G,
+    parse_from_str_with_ns: J,
+    make_doc: H,
+) -> Result<(), Error>
+where
+    G: Fn(&str) -> Result<N, Error>,
+    H: Fn() -> Result<N, Error>,
+    J: Fn(&str) -> Result<(N, Option<NamespaceMap>), Error>,
+{
+    let result = test_rig(
+        "<Test><w>Strasse</w><w>strasse</w><w>Weg</w><w>STRASSE</w></Test>",
+        r#"<xsl:stylesheet xmlns:xsl='http://www.w3.org/1999/XSL/Transform'>
+  <xsl:key name='words' match='w' use='.' collation='http://www.w3.org/2013/collation/UCA?lang=de;strength=secondary'/>
+  <xsl:template match='/'><xsl:apply-templates select='Test'/></xsl:template>
+  <xsl:template match='Test'>
+    <groups><xsl:for-each-group select='w' group-by='.' collation='http://www.w3.org/2013/collation/UCA?lang=de;strength=secondary'><xsl:sort select='current-grouping-key()'/><xsl:value-of select='concat(count(current-group()), " ")'/></xsl:for-each-group></groups>
+    <key><xsl:value-of select='count(key("words", "strasse"))'/></key>
+  </xsl:template>
+</xsl:stylesheet>"#,
+        parse_from_str,
+        parse_from_str_with_ns,
+        make_doc,
+    )?;
+    assert_eq!(result.0.to_xml(), "<groups>3 1 </groups><key>3</key>");
+    Ok(())
+}
+
 pub fn generic_comment<N: Node, G, H, J>(
     parse_from_str: G,
     parse_from_str_with_ns: J,
@@ -872,6 +931,51 @@ where
     }
 }
 
+pub fn generic_callable_shadow_1<N: Node, G, H, J>(
+    parse_from_str: G,
+    parse_from_str_with_ns: J,
+    make_doc: H,
+) -> Result<(), Error>
+where
+    G: Fn(&str) -> Result<N, Error>,
+    H: Fn() -> Result<N, Error>,
+    J: Fn(&str) -> Result<(N, Option<NamespaceMap>), Error>,
+{
+    // A user function with the same local name as a built-in function does not override it
+    let result = test_rig(
+        "<Test><one>blue</one><two>yellow</two><three>green</three><four>blue</four></Test>",
+        r#"<xsl:stylesheet xmlns:xsl='http://www.w3.org/1999/XSL/Transform' xmlns:eg='http://example.org/' xmlns:fn='http://www.w3.org/2005/xpath-functions'>
+  <xsl:template match='/'><xsl:apply-templates/></xsl:template>
+  <xsl:template match='child::Test'>
+    <xsl:sequence select='eg:reverse(count(child::*))'/>
+    <xsl:text>|</xsl:text>
+    <xsl:sequence select='count(reverse(child::*))'/>
+    <xsl:text>|</xsl:text>
+    <xsl:sequence select='fn:count(child::*)'/>
+  </xsl:template>
+  <xsl:function name='eg:reverse'>
+    <xsl:param name='my_param'/>
+    <xsl:text>user function </xsl:text>
+    <xsl:sequence select='$my_param'/>
+  </xsl:function>
+</xsl:stylesheet>"#,
+        parse_from_str,
+        parse_from_str_with_ns,
+        make_doc,
+    )?;
+    if result.0.to_string() == "user function 4|4|4" {
+        Ok(())
+    } else {
+        Err(Error::new(
+            ErrorKind::Unknown,
+            format!(
+                "got result \"{}\", expected \"user function 4|4|4\"",
+                result.0.to_string()
+            ),
+        ))
+    }
+}
+
 pub fn generic_include<N: Node, G, H, J>(
     parse_from_str: G,
     _parse_from_str_with_ns: J,
@@ -2420,3 +2524,39 @@ where
     assert!(result.is_ok());
     Ok(())
 }
+
+pub fn generic_namespace_axis<N: Node, G, H, J>(
+    parse_from_str: G,
+    parse_from_str_with_ns: J,
+    make_doc: H,
+) -> Result<(), Error>
+where
+    G: Fn(&str) -> Result<N, Error>,
+    H: Fn() -> Result<N, Error>,
+    J: Fn(&str) -> Result<(N, Option<NamespaceMap>), Error>,
+{
+    let result = test_rig(
+        "<Test xmlns:a='urn:a'><Level1 xmlns:b='urn:b'>one</Level1></Test>",
+        r#"<xsl:stylesheet xmlns:xsl='http://www.w3.org/1999/XSL/Transform'>
+  <xsl:template match='/'><xsl:apply-templates select='Test/Level1'/></xsl:template>
+  <xsl:template match='Level1'>
+    <out>
+      <xsl:for-each select='namespace::b'><xsl:copy/></xsl:for-each>
+      <xsl:for-each select='namespace::*'>
+        <xsl:sort select='name()'/>
+        <xsl:value-of select='concat(name(), "=", ., ";")'/>
+      </xsl:for-each>
+    </out>
+  </xsl:template>
+</xsl:stylesheet>"#,
+        parse_from_str,
+        parse_from_str_with_ns,
+        make_doc,
+    )?;
+    assert_eq!(
+        result.0.to_string(),
+        "a=urn:a;b=urn:b;xml=http://www.w3.org/XML/1998/namespace;"
+    );
+    assert!(result.0.to_xml().contains("xmlns:b="));
+    Ok(())
+}